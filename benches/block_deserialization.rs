@@ -0,0 +1,58 @@
+//! Block deserialization throughput.
+//!
+//! Run with `cargo bench --bench block_deserialization`. Parses the captured
+//! `nextBlock` results under `fixtures/chain-sync`, one block per era, and
+//! reports blocks per second for each block type.
+
+use ogmios_client::schema::Block;
+use serde_json::Value;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ITERATIONS: usize = 20_000;
+
+const EBB: &[&str] = &[include_str!("../fixtures/chain-sync/nextBlock-byron-ebb.json")];
+
+const BFT: &[&str] = &[include_str!("../fixtures/chain-sync/nextBlock-byron-bft.json")];
+
+const PRAOS: &[&str] = &[
+    include_str!("../fixtures/chain-sync/nextBlock-shelley.json"),
+    include_str!("../fixtures/chain-sync/nextBlock-allegra.json"),
+    include_str!("../fixtures/chain-sync/nextBlock-mary.json"),
+    include_str!("../fixtures/chain-sync/nextBlock-alonzo.json"),
+    include_str!("../fixtures/chain-sync/nextBlock-babbage.json"),
+    include_str!("../fixtures/chain-sync/nextBlock-conway.json"),
+];
+
+/// The blocks of `nextBlock` responses, as sent on the wire.
+fn corpus(responses: &[&str]) -> Vec<String> {
+    responses
+        .iter()
+        .map(|response| {
+            let response: Value = serde_json::from_str(response).unwrap();
+            response["result"]["block"].to_string()
+        })
+        .collect()
+}
+
+fn bench(name: &str, corpus: &[String]) {
+    let started = Instant::now();
+    for _ in 0..ITERATIONS {
+        for block in corpus {
+            black_box(serde_json::from_str::<Block>(black_box(block)).unwrap());
+        }
+    }
+    let elapsed = started.elapsed().max(Duration::from_nanos(1));
+    let blocks = (corpus.len() * ITERATIONS) as f64;
+    println!(
+        "{name:>6}: {:>12.0} blocks/s ({:.2} µs/block)",
+        blocks / elapsed.as_secs_f64(),
+        elapsed.as_secs_f64() * 1e6 / blocks
+    );
+}
+
+fn main() {
+    bench("ebb", &corpus(EBB));
+    bench("bft", &corpus(BFT));
+    bench("praos", &corpus(PRAOS));
+}
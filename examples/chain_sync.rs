@@ -48,19 +48,6 @@ impl BlockHandler {
             max_blocks,
         }
     }
-
-    fn block_count(&self) -> usize {
-        self.block_count.load(Ordering::SeqCst)
-    }
-
-    fn rollback_count(&self) -> usize {
-        self.rollback_count.load(Ordering::SeqCst)
-    }
-
-    fn should_continue(&self) -> bool {
-        self.max_blocks
-            .map_or(true, |max| self.block_count() < max)
-    }
 }
 
 impl ChainSynchronizationMessageHandlers for BlockHandler {
@@ -73,13 +60,13 @@ impl ChainSynchronizationMessageHandlers for BlockHandler {
         println!("  Hash: {}", block.id());
 
         // Display block type and era
-        let (block_type, era, tx_count) = match &block {
-            Block::EBB(b) => (&b.block_type, &b.era, 0),
-            Block::BFT(b) => (&b.block_type, &b.era, b.transactions.len()),
-            Block::Praos(b) => (&b.block_type, &b.era, b.transactions.len()),
+        let tx_count = match &block {
+            Block::EBB(_) => 0,
+            Block::BFT(b) => b.transactions.len(),
+            Block::Praos(b) => b.transactions.len(),
         };
-        println!("  Type: {}", block_type);
-        println!("  Era: {}", era);
+        println!("  Type: {}", block.block_type());
+        println!("  Era: {}", block.era());
         println!("  Transactions: {}", tx_count);
 
         // Display tip information
@@ -94,11 +81,14 @@ impl ChainSynchronizationMessageHandlers for BlockHandler {
         }
 
         // Check if we should stop
-        if let Some(max) = self.max_blocks {
-            if count >= max {
-                println!("\nReached maximum block count ({}), stopping...", max);
-                return Err(ogmios_client::error::OgmiosError::ConnectionClosed);
-            }
+        if let Some(max) = self.max_blocks
+            && count >= max
+        {
+            println!("\nReached maximum block count ({}), stopping...", max);
+            return Err(ogmios_client::error::OgmiosError::ConnectionClosed {
+                method: None,
+                id: None,
+            });
         }
 
         Ok(())
@@ -117,6 +107,9 @@ impl ChainSynchronizationMessageHandlers for BlockHandler {
                 println!("  Point ID: {}", id);
             }
         }
+        if let Some(distance) = point.distance_to(&tip) {
+            println!("  Slots behind tip: {}", distance);
+        }
 
         match &tip {
             Tip::Origin(_) => {
@@ -161,6 +154,7 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         port,
         tls,
         max_payload: 65536,
+        ..Default::default()
     };
 
     println!("Connection: {}://{}:{}", if tls { "wss" } else { "ws" }, host, port);
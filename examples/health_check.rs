@@ -10,6 +10,7 @@
 
 use ogmios_client::{
     connection::ConnectionConfig,
+    error::OgmiosError,
     schema::Tip,
     server_health::{get_server_health, ensure_server_health, EnsureServerHealthOptions},
 };
@@ -37,6 +38,7 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         port,
         tls,
         max_payload: 65536, // 64KB default
+        ..Default::default()
     };
 
     // Method 1: Simple health check
@@ -83,8 +85,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             port,
             tls,
             max_payload: 65536,
+            ..Default::default()
         }),
         min_synchronization: 0.90, // Require at least 90% sync
+        ..Default::default()
     };
 
     match ensure_server_health(options).await {
@@ -93,9 +97,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("  Network sync: {:.2}%", health.network_synchronization * 100.0);
             println!();
         }
+        Err(OgmiosError::ServerNotReady(not_ready)) => {
+            eprintln!("{}", not_ready);
+            eprintln!("  Era: {:?}", not_ready.health.current_era);
+            eprintln!("  Last known tip: {:?}", not_ready.health.last_known_tip);
+            eprintln!("  Last tip update: {:?}", not_ready.health.last_tip_update);
+            return Err(OgmiosError::ServerNotReady(not_ready).into());
+        }
         Err(e) => {
             eprintln!("Server health check failed: {}", e);
-            eprintln!("The server may not be sufficiently synchronized.");
             return Err(e.into());
         }
     }
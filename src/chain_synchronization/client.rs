@@ -88,9 +88,23 @@ impl<H: ChainSynchronizationMessageHandlers + 'static> ChainSynchronizationClien
         context: InteractionContext,
         handlers: H,
         options: ChainSynchronizationClientOptions,
+    ) -> Result<Self> {
+        Self::with_shared(Arc::new(context), handlers, options).await
+    }
+
+    /// Create a chain synchronization client on a context shared with other
+    /// clients.
+    ///
+    /// The chain sync state belongs to the connection, so share it with at
+    /// most one chain synchronization client. [`shutdown`](Self::shutdown)
+    /// closes the connection for all users.
+    pub async fn with_shared(
+        context: Arc<InteractionContext>,
+        handlers: H,
+        options: ChainSynchronizationClientOptions,
     ) -> Result<Self> {
         Ok(Self {
-            context: Arc::new(context),
+            context,
             handlers: Arc::new(Mutex::new(handlers)),
             options,
             running: Arc::new(std::sync::atomic::AtomicBool::new(false)),
@@ -115,7 +129,7 @@ impl<H: ChainSynchronizationMessageHandlers + 'static> ChainSynchronizationClien
     /// # Arguments
     ///
     /// * `points` - Optional list of points to try to intersect with. If not provided,
-    ///              starts from the origin.
+    ///   starts from the origin.
     /// * `in_flight` - Optional number of blocks to request in parallel.
     ///
     /// # Returns
@@ -263,7 +277,7 @@ impl CollectingHandler {
     /// Check if the handler has reached the maximum block count.
     pub fn is_complete(&self) -> bool {
         self.max_blocks
-            .map_or(false, |max| self.blocks.len() >= max)
+            .is_some_and(|max| self.blocks.len() >= max)
     }
 }
 
@@ -0,0 +1,231 @@
+//! One client for all Ogmios mini-protocols.
+//!
+//! [`OgmiosClient`] opens one connection and hands out the ledger state query,
+//! mempool monitoring and transaction submission clients over it, creating
+//! each on first use. Requests are correlated by id, so these clients can be
+//! used concurrently. Chain synchronization gets a connection of its own.
+
+use crate::chain_synchronization::{
+    ChainSynchronizationClient, ChainSynchronizationClientOptions,
+    ChainSynchronizationMessageHandlers,
+};
+use crate::connection::{
+    ConnectionConfig, InteractionContext, InteractionContextOptions, InteractionType,
+    create_interaction_context,
+};
+use crate::error::Result;
+use crate::ledger_state_query::{LedgerStateQueryClient, LedgerStateQueryClientOptions};
+use crate::mempool_monitoring::MempoolMonitoringClient;
+use crate::schema::{ServerHealth, TransactionId};
+use crate::server_health::HealthClient;
+use crate::transaction_submission::TransactionSubmissionClient;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
+/// A client for all Ogmios mini-protocols.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::OgmiosClient;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = OgmiosClient::connect_url("ws://localhost:1337").await?;
+///
+/// let epoch = client.ledger().epoch().await?;
+/// let mempool = client.mempool().size_of_mempool().await?;
+/// println!("Epoch {epoch}, {} pending transactions", mempool.transactions);
+///
+/// let tx_id = client.submit("84a400...").await?;
+/// println!("Submitted: {tx_id}");
+///
+/// client.shutdown().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct OgmiosClient {
+    /// Configuration of every connection opened by the client.
+    config: ConnectionConfig,
+    /// Connection shared by the ledger, mempool and submission clients.
+    context: Arc<InteractionContext>,
+    ledger: OnceLock<LedgerStateQueryClient>,
+    mempool: OnceLock<MempoolMonitoringClient>,
+    submission: OnceLock<TransactionSubmissionClient>,
+    /// Health checker, keeping its HTTP connection between checks.
+    health: HealthClient,
+    /// Connections of the chain synchronization clients, closed on shutdown.
+    chain_sync: Mutex<Vec<Weak<InteractionContext>>>,
+}
+
+impl OgmiosClient {
+    /// Connect to Ogmios.
+    pub async fn connect(config: ConnectionConfig) -> Result<Self> {
+        let context = open(&config).await?;
+        let health = HealthClient::new(Some(config.clone()))?;
+        Ok(Self {
+            config,
+            context: Arc::new(context),
+            ledger: OnceLock::new(),
+            mempool: OnceLock::new(),
+            submission: OnceLock::new(),
+            health,
+            chain_sync: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Connect to Ogmios at a URL such as `wss://ogmios.example.com`.
+    ///
+    /// See [`ConnectionConfig::from_url`] for the URLs accepted.
+    pub async fn connect_url(url: &str) -> Result<Self> {
+        Self::connect(ConnectionConfig::from_url(url)?).await
+    }
+
+    /// Configuration of the connections.
+    pub fn config(&self) -> &ConnectionConfig {
+        &self.config
+    }
+
+    /// The shared connection, e.g. to build a
+    /// [`LedgerStateQueryClient::with_shared`] with other options.
+    pub fn context(&self) -> &Arc<InteractionContext> {
+        &self.context
+    }
+
+    /// Ledger state query client, with the default options.
+    pub fn ledger(&self) -> &LedgerStateQueryClient {
+        self.ledger.get_or_init(|| {
+            LedgerStateQueryClient::shared(
+                self.context.clone(),
+                &LedgerStateQueryClientOptions::default(),
+            )
+        })
+    }
+
+    /// Mempool monitoring client.
+    pub fn mempool(&self) -> &MempoolMonitoringClient {
+        self.mempool
+            .get_or_init(|| MempoolMonitoringClient::with_shared(self.context.clone()))
+    }
+
+    /// Transaction submission client.
+    pub fn submission(&self) -> &TransactionSubmissionClient {
+        self.submission
+            .get_or_init(|| TransactionSubmissionClient::with_shared(self.context.clone()))
+    }
+
+    /// Submit a signed transaction (CBOR hex string).
+    pub async fn submit(&self, cbor: &str) -> Result<TransactionId> {
+        self.submission().submit_transaction(cbor).await
+    }
+
+    /// Create a chain synchronization client on a new connection.
+    ///
+    /// Each call opens its own connection, as the chain sync state belongs
+    /// to the connection. [`shutdown`](Self::shutdown) closes it too.
+    pub async fn chain_sync<H: ChainSynchronizationMessageHandlers + 'static>(
+        &self,
+        handlers: H,
+        options: Option<ChainSynchronizationClientOptions>,
+    ) -> Result<ChainSynchronizationClient<H>> {
+        let context = Arc::new(open(&self.config).await?);
+        {
+            let mut chain_sync = self.chain_sync.lock().unwrap();
+            // Forget the connections of clients already dropped.
+            chain_sync.retain(|context| context.strong_count() > 0);
+            chain_sync.push(Arc::downgrade(&context));
+        }
+        ChainSynchronizationClient::with_shared(context, handlers, options.unwrap_or_default())
+            .await
+    }
+
+    /// Get the server health.
+    pub async fn health(&self) -> Result<ServerHealth> {
+        self.health.health().await
+    }
+
+    /// Close every connection opened by the client.
+    pub async fn shutdown(&self) -> Result<()> {
+        let chain_sync: Vec<_> = self.chain_sync.lock().unwrap().drain(..).collect();
+        for context in chain_sync.iter().filter_map(Weak::upgrade) {
+            context.shutdown().await?;
+        }
+        self.context.shutdown().await
+    }
+}
+
+async fn open(config: &ConnectionConfig) -> Result<InteractionContext> {
+    create_interaction_context(InteractionContextOptions {
+        connection: config.clone(),
+        interaction_type: InteractionType::LongRunning,
+        ..Default::default()
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+    use crate::schema::{Block, Point, Tip};
+    use serde_json::json;
+
+    struct Ignore;
+
+    impl ChainSynchronizationMessageHandlers for Ignore {
+        fn on_roll_forward(&mut self, _block: Block, _tip: Tip) -> Result<()> {
+            Ok(())
+        }
+
+        fn on_roll_backward(&mut self, _point: Point, _tip: Tip) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_clients_share_one_connection() {
+        let server = MockServer::start(|method, _| match method {
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            "hasTransaction" => MockReply::result(json!({ "hasTransaction": false })),
+            "submitTransaction" => MockReply::result(json!({ "transaction": { "id": "ab".repeat(32) } })),
+            "findIntersection" => MockReply::result(json!({
+                "intersection": "origin",
+                "tip": "origin",
+            })),
+            _ => MockReply::NoReply,
+        })
+        .await;
+        let client = OgmiosClient::connect(server.config()).await.unwrap();
+        let id: TransactionId = "ab".repeat(32).parse().unwrap();
+
+        let (epoch, pending, tx_id) = tokio::try_join!(
+            client.ledger().epoch(),
+            client.mempool().has_transaction(&id),
+            client.submit("84a400"),
+        )
+        .unwrap();
+        assert_eq!((epoch, pending, tx_id), (42, false, id));
+        assert_eq!(server.accepts(), 1);
+
+        let chain_sync = client.chain_sync(Ignore, None).await.unwrap();
+        chain_sync.resume(None, None).await.unwrap();
+        assert_eq!(server.accepts(), 2);
+
+        client.shutdown().await.unwrap();
+        assert!(!client.context().is_socket_open());
+        assert!(!chain_sync.context().is_socket_open());
+    }
+
+    #[tokio::test]
+    async fn test_forgets_dropped_chain_sync_connections() {
+        let server = MockServer::start(|_, _| MockReply::NoReply).await;
+        let client = OgmiosClient::connect(server.config()).await.unwrap();
+
+        for _ in 0..3 {
+            drop(client.chain_sync(Ignore, None).await.unwrap());
+        }
+        let kept = client.chain_sync(Ignore, None).await.unwrap();
+        assert_eq!(client.chain_sync.lock().unwrap().len(), 1);
+
+        client.shutdown().await.unwrap();
+        assert!(!kept.context().is_socket_open());
+    }
+}
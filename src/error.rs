@@ -1,17 +1,40 @@
 //! Error types for the Ogmios client.
 
+use crate::schema::{
+    CannotCreateEvaluationContext, EraMismatch, ExecutionUnitsTooLarge, IncompatibleEra,
+    InsufficientCollateral, JsonRpcError, MissingScripts, MissingSignatories,
+    OutsideOfValidityInterval, OverlappingAdditionalUtxo, Point, TransactionFeeTooSmall,
+    TransactionTooLarge, UnknownOutputReferences, ValidationFailure, ValidatorIndex,
+    ValueNotConserved, error_codes,
+};
+use crate::util::format_point;
+use serde::Deserialize;
+use serde::de::DeserializeOwned;
+use std::sync::Arc;
 use thiserror::Error;
+use tokio_tungstenite::tungstenite;
 
 /// Main error type for the Ogmios client.
 #[derive(Error, Debug)]
 pub enum OgmiosError {
     /// WebSocket connection error
+    ///
+    /// Migration: this used to carry the error message as a `String`; use
+    /// its `Display` instead.
     #[error("WebSocket error: {0}")]
-    WebSocket(String),
+    WebSocket(#[source] WebSocketError),
 
-    /// HTTP handshake error
+    /// HTTP handshake error, e.g. the server rejected the WebSocket upgrade
+    ///
+    /// The status of a rejected upgrade is given by
+    /// [`handshake_status`](OgmiosError::handshake_status). Migration: this
+    /// used to carry the error message as a `String`.
     #[error("HTTP handshake error: {0}")]
-    HttpHandshake(String),
+    HttpHandshake(#[source] WebSocketError),
+
+    /// Invalid TLS configuration
+    #[error("TLS configuration error: {0}")]
+    Tls(String),
 
     /// HTTP request error (for health checks)
     #[error("HTTP error: {0}")]
@@ -21,24 +44,46 @@ pub enum OgmiosError {
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
-    /// Server not ready error
-    #[error("Server not ready: network synchronization is {synchronization:.2}%, minimum required is {minimum:.2}%")]
-    ServerNotReady {
-        /// Server health network synchronization
-        synchronization: f64,
-        /// Synchronization minimum required
-        minimum: f64,
+    /// Server not ready error, with the health document it was judged on
+    #[error("{0}")]
+    ServerNotReady(Box<crate::server_health::ServerNotReady>),
+
+    /// Ogmios reported that it cannot reach its node
+    #[error("Server not ready: Ogmios is disconnected from its node")]
+    NodeDisconnected,
+
+    /// Server did not become ready before the timeout
+    #[error("Server not ready after {timeout_ms}ms: {reason}")]
+    ServerReadyTimeout {
+        /// Timed out after (ms) value
+        timeout_ms: u64,
+        /// State of the server at the last health check
+        reason: NotReadyReason,
+        /// Last health document observed, if any health check succeeded
+        last_health: Option<Box<crate::schema::ServerHealth>>,
     },
 
     /// Connection closed unexpectedly
-    #[error("Connection closed unexpectedly")]
-    ConnectionClosed,
+    ///
+    /// Migration: this used to be a unit variant. Match
+    /// `ConnectionClosed { .. }` instead.
+    #[error("Connection closed unexpectedly{}", for_request(method, id))]
+    ConnectionClosed {
+        /// JSON-RPC method of the request left without a response, if any
+        method: Option<String>,
+        /// Id of that request
+        id: Option<u64>,
+    },
 
     /// Socket not open
-    #[error("Socket is not open (state: {state})")]
+    #[error("Socket is not open (state: {state}){}", for_request(method, id))]
     SocketNotOpen {
         /// Socket state
         state: String,
+        /// JSON-RPC method of the request that could not be sent, if any
+        method: Option<String>,
+        /// Id of that request
+        id: Option<u64>,
     },
 
     /// Invalid response from server
@@ -48,11 +93,43 @@ pub enum OgmiosError {
         message: String,
     },
 
+    /// Error reported by the server in a JSON-RPC response
+    ///
+    /// Migration: these errors used to be `InvalidResponse`, with the code
+    /// and message formatted in `message`. Match on [`code`](Self::code)
+    /// instead of the text.
+    #[error("{method} failed with JSON-RPC error {code}: {message}")]
+    Rpc {
+        /// JSON-RPC error code
+        code: i32,
+        /// Error message
+        message: String,
+        /// Additional error data
+        data: Option<serde_json::Value>,
+        /// JSON-RPC method of the request
+        method: String,
+    },
+
+    /// Response carrying another id than the request it was matched to
+    #[error("Response id {got} does not match request id {expected}")]
+    ResponseIdMismatch {
+        /// Id of the request
+        expected: serde_json::Value,
+        /// Id of the response, `null` if missing
+        got: serde_json::Value,
+    },
+
     /// Request timeout
-    #[error("Request timed out after {timeout_ms}ms")]
+    ///
+    /// Migration: `id` was added. Patterns listing the fields need `..`.
+    #[error("Request timed out after {timeout_ms}ms{}", for_request(method, id))]
     Timeout {
         /// Timedout after (ms) value
         timeout_ms: u64,
+        /// JSON-RPC method of the request that timed out, if any
+        method: Option<String>,
+        /// Id of that request
+        id: Option<u64>,
     },
 
     /// Intersection not found during chain sync
@@ -63,25 +140,78 @@ pub enum OgmiosError {
     },
 
     /// Transaction submission error
+    #[deprecated(note = "the server's failures are reported as `TransactionSubmission`")]
     #[error("Transaction submission failed: {0}")]
     SubmissionError(String),
 
+    /// Transaction submission rejected by the server
+    ///
+    /// The failure can be matched in place, e.g.
+    /// `TransactionSubmission(SubmitTransactionError::TransactionFeeTooSmall(fee))`,
+    /// or borrowed with [`submission_failure`](OgmiosError::submission_failure).
+    #[error("Transaction submission failed: {0}")]
+    TransactionSubmission(SubmitTransactionError),
+
     /// Transaction evaluation error
+    #[deprecated(note = "the server's failures are reported as `TransactionEvaluation`")]
     #[error("Transaction evaluation failed: {0}")]
     EvaluationError(String),
 
-    /// Ledger state acquisition error
+    /// Transaction evaluation rejected by the server
+    ///
+    /// The failure can be matched in place, or borrowed with
+    /// [`evaluation_failure`](OgmiosError::evaluation_failure).
+    #[error("Transaction evaluation failed: {0}")]
+    TransactionEvaluation(EvaluateTransactionError),
+
+    /// Ledger state acquisition error raised by the client, e.g. with no
+    /// point to acquire
     #[error("Failed to acquire ledger state: {0}")]
     AcquisitionError(String),
 
+    /// Ledger state or mempool acquisition rejected by the server
+    ///
+    /// Migration: this used to be the tuple variant `Acquisition(AcquireFailure)`.
+    /// Match `Acquisition { failure, .. }` instead.
+    #[error("{}", describe_acquisition(failure, point.as_ref()))]
+    Acquisition {
+        /// Why the acquisition was rejected
+        failure: AcquireFailure,
+        /// Point of the ledger state requested, `None` for the ledger tip and
+        /// the mempool
+        point: Option<Point>,
+    },
+
     /// Query error
     #[error("Query failed: {0}")]
     QueryError(String),
 
+    /// Slot or time conversion beyond the era horizon
+    #[error("Cannot convert {value}: beyond the era horizon at slot {horizon}")]
+    PastHorizon {
+        /// The value that could not be converted (e.g. `slot 123`)
+        value: String,
+        /// First slot past the last known era summary
+        horizon: u64,
+    },
+
+    /// Malformed or out-of-range time value
+    #[error("Invalid time: {0}")]
+    InvalidTime(String),
+
     /// URL parsing error
     #[error("URL parsing error: {0}")]
     UrlParse(#[from] url::ParseError),
 
+    /// URL that cannot address an Ogmios server, e.g. with an `ftp` scheme
+    #[error("Invalid Ogmios URL {url}: {reason}")]
+    InvalidUrl {
+        /// The URL
+        url: String,
+        /// Why it was rejected
+        reason: String,
+    },
+
     /// Generic I/O error
     #[error("I/O error: {0}")]
     Io(#[from] std::io::Error),
@@ -93,7 +223,1491 @@ pub enum OgmiosError {
     /// Channel receive error
     #[error("Channel receive error: receiver dropped")]
     ChannelRecv,
+
+    /// Malformed output reference, rejected before reaching the server
+    #[error("{0}")]
+    InvalidOutputReference(#[from] crate::schema::InvalidOutputReference),
+
+    /// Malformed digest, e.g. a transaction ID, rejected before reaching the
+    /// server
+    #[error("{0}")]
+    InvalidDigest(#[from] crate::schema::InvalidDigest),
+
+    /// Script-based stake address given as a key credential, rejected before
+    /// reaching the server
+    #[error("Script stake address {0} given as a key; select it by its script hash")]
+    ScriptStakeAddress(String),
+
+    /// Malformed address, rejected before reaching the server
+    #[cfg(feature = "address")]
+    #[error("{0}")]
+    InvalidAddress(#[from] crate::schema::InvalidAddress),
+
+    /// Fetching or verifying off-chain metadata failed
+    #[cfg(feature = "fetch")]
+    #[error("Metadata fetch failed: {0}")]
+    Fetch(#[from] crate::fetch::FetchError),
+}
+
+/// Error of the WebSocket connection or of its opening handshake.
+///
+/// Its [`source`](std::error::Error::source) is the underlying I/O or TLS
+/// error, if any.
+#[derive(Debug, Clone)]
+pub struct WebSocketError(Arc<tungstenite::Error>);
+
+impl WebSocketError {
+    pub(crate) fn new(error: tungstenite::Error) -> Self {
+        WebSocketError(Arc::new(error))
+    }
+
+    /// HTTP status of the response rejecting the WebSocket upgrade, e.g. 401.
+    pub fn handshake_status(&self) -> Option<u16> {
+        match self.0.as_ref() {
+            tungstenite::Error::Http(response) => Some(response.status().as_u16()),
+            _ => None,
+        }
+    }
+
+    /// Whether the TLS connection failed, e.g. on an untrusted certificate.
+    pub fn is_tls(&self) -> bool {
+        matches!(self.0.as_ref(), tungstenite::Error::Tls(_))
+    }
+
+    /// Whether reading from or writing to the socket failed.
+    pub fn is_io(&self) -> bool {
+        matches!(self.0.as_ref(), tungstenite::Error::Io(_))
+    }
+
+    /// Whether the server violated the WebSocket protocol.
+    pub fn is_protocol(&self) -> bool {
+        matches!(self.0.as_ref(), tungstenite::Error::Protocol(_))
+    }
+
+    /// Whether the connection was already closed.
+    pub fn is_closed(&self) -> bool {
+        matches!(
+            self.0.as_ref(),
+            tungstenite::Error::ConnectionClosed | tungstenite::Error::AlreadyClosed
+        )
+    }
+}
+
+impl std::fmt::Display for WebSocketError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for WebSocketError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self.0.as_ref() {
+            tungstenite::Error::Io(e) => Some(e),
+            tungstenite::Error::Tls(e) => Some(e),
+            tungstenite::Error::Protocol(e) => Some(e),
+            tungstenite::Error::Capacity(e) => Some(e),
+            tungstenite::Error::Url(e) => Some(e),
+            tungstenite::Error::HttpFormat(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+/// Mini-protocol an Ogmios error code belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum OgmiosErrorCategory {
+    /// Chain synchronization, e.g. `findIntersection` (1000–1999).
+    ChainSync,
+    /// Ledger state acquisition and queries (2000–2999).
+    Acquisition,
+    /// Transaction submission (3005 and 3100–3999).
+    Submission,
+    /// Transaction evaluation (3000–3099, except 3005).
+    Evaluation,
+    /// Mempool monitoring (4000–4999).
+    Mempool,
+    /// Standard JSON-RPC codes and codes outside of the Ogmios ranges.
+    Generic,
+}
+
+/// Ranges of Ogmios error codes, in ascending order.
+///
+/// Evaluation may also fail with the codes of the ledger rules it checks,
+/// which are in the submission range.
+const CATEGORIES: &[(std::ops::RangeInclusive<i32>, OgmiosErrorCategory)] = &[
+    (1000..=1999, OgmiosErrorCategory::ChainSync),
+    (2000..=2999, OgmiosErrorCategory::Acquisition),
+    (3000..=3004, OgmiosErrorCategory::Evaluation),
+    (3005..=3005, OgmiosErrorCategory::Submission),
+    (3006..=3099, OgmiosErrorCategory::Evaluation),
+    (3100..=3999, OgmiosErrorCategory::Submission),
+    (4000..=4999, OgmiosErrorCategory::Mempool),
+];
+
+/// Category of a JSON-RPC error code, including codes without a constant in
+/// [`error_codes`].
+pub fn classify(code: i32) -> OgmiosErrorCategory {
+    CATEGORIES
+        .iter()
+        .find(|(range, _)| range.contains(&code))
+        .map_or(OgmiosErrorCategory::Generic, |(_, category)| *category)
+}
+
+/// State of the server when waiting for it to become ready timed out.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum NotReadyReason {
+    /// The last health check failed, e.g. the server is not listening yet.
+    #[error("server unreachable ({0})")]
+    Unreachable(String),
+    /// The server answered but was disconnected from its node.
+    #[error("Ogmios is disconnected from its node")]
+    NodeDisconnected,
+    /// The server answered but was not synchronized enough.
+    #[error(
+        "network synchronization is {:.2}%, minimum required is {:.2}%",
+        .synchronization * 100.0,
+        .minimum * 100.0
+    )]
+    Syncing {
+        /// Server health network synchronization (0.0 to 1.0), as sent by Ogmios.
+        synchronization: f64,
+        /// Synchronization minimum required (0.0 to 1.0).
+        minimum: f64,
+    },
+}
+
+/// Reason a ledger state acquisition was rejected.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum AcquireFailure {
+    /// The point is older than the node's volatile window; pick a newer point.
+    #[error("point too old")]
+    PointTooOld,
+    /// The point is not on the node's chain (unknown block or wrong hash).
+    #[error("point not on chain")]
+    PointNotOnChain,
+    /// A mempool request was sent before acquiring a mempool snapshot.
+    #[error("mempool not acquired")]
+    MempoolNotAcquired,
+    /// Any other failure.
+    ///
+    /// Migration: `data` was added. Patterns listing the fields need `..`.
+    #[error("error {code}: {message}")]
+    Unknown {
+        /// JSON-RPC error code.
+        code: i32,
+        /// Error message.
+        message: String,
+        /// Additional error data.
+        data: Option<serde_json::Value>,
+    },
+}
+
+impl From<JsonRpcError> for AcquireFailure {
+    fn from(error: JsonRpcError) -> Self {
+        match error.data.as_ref().and_then(|data| data.as_str()) {
+            Some("pointTooOld") => return AcquireFailure::PointTooOld,
+            Some("pointNotOnChain") => return AcquireFailure::PointNotOnChain,
+            _ => {}
+        }
+
+        match error.code {
+            error_codes::ACQUIRE_POINT_TOO_OLD => AcquireFailure::PointTooOld,
+            error_codes::ACQUIRE_POINT_NOT_ON_CHAIN => AcquireFailure::PointNotOnChain,
+            error_codes::MEMPOOL_MUST_ACQUIRE_FIRST => AcquireFailure::MempoolNotAcquired,
+            code => AcquireFailure::Unknown {
+                code,
+                message: error.message,
+                data: error.data,
+            },
+        }
+    }
+}
+
+impl AcquireFailure {
+    /// The JSON-RPC error code of the failure.
+    ///
+    /// Recognised failures give the code Ogmios documents for them.
+    pub fn code(&self) -> i32 {
+        match self {
+            AcquireFailure::PointTooOld => error_codes::ACQUIRE_POINT_TOO_OLD,
+            AcquireFailure::PointNotOnChain => error_codes::ACQUIRE_POINT_NOT_ON_CHAIN,
+            AcquireFailure::MempoolNotAcquired => error_codes::MEMPOOL_MUST_ACQUIRE_FIRST,
+            AcquireFailure::Unknown { code, .. } => *code,
+        }
+    }
+
+    /// The error data, as sent for [`Unknown`](AcquireFailure::Unknown)
+    /// failures. Recognised failures carry none.
+    pub fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            AcquireFailure::Unknown { data, .. } => data.clone(),
+            _ => None,
+        }
+    }
+}
+
+/// Reason a transaction submission was rejected, with the data Ogmios sent.
+///
+/// Failures without a dedicated variant, or whose data does not have the
+/// expected shape, are kept as [`Other`](SubmitTransactionError::Other).
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SubmitTransactionError {
+    /// The transaction was built for another era than the ledger's.
+    #[error("era mismatch: transaction is for {}, ledger is in {}", .0.query_era, .0.ledger_era)]
+    EraMismatch(EraMismatch),
+    /// Signatures required by the transaction are missing.
+    #[error("missing signatures from {}", .0.missing_signatories.join(", "))]
+    MissingSignatories(MissingSignatories),
+    /// Scripts required by the transaction are missing.
+    #[error("missing scripts {}", join(&.0.missing_scripts))]
+    MissingScripts(MissingScripts),
+    /// Inputs spend outputs that do not exist or were already spent.
+    #[error("unknown output references {}", join(&.0.unknown_output_references))]
+    UnknownOutputReferences(UnknownOutputReferences),
+    /// The transaction is not valid at the current slot.
+    #[error("outside of the validity interval at slot {}", .0.current_slot)]
+    OutsideOfValidityInterval(OutsideOfValidityInterval),
+    /// The transaction exceeds the maximum transaction size.
+    #[error(
+        "transaction too large: {} bytes, maximum is {}",
+        .0.measured_transaction_size.bytes,
+        .0.maximum_transaction_size.bytes
+    )]
+    TransactionTooLarge(TransactionTooLarge),
+    /// The fee is lower than the minimum fee.
+    #[error(
+        "fee too small: {} lovelace, minimum is {} ({} short)",
+        .0.provided_fee.lovelace,
+        .0.minimum_required_fee.lovelace,
+        .0.minimum_required_fee.lovelace.saturating_sub(.0.provided_fee.lovelace)
+    )]
+    TransactionFeeTooSmall(TransactionFeeTooSmall),
+    /// The consumed and produced values differ.
+    #[error(
+        "value not conserved: consumed {} lovelace, produced {}",
+        .0.value_consumed.lovelace(),
+        .0.value_produced.lovelace()
+    )]
+    ValueNotConserved(ValueNotConserved),
+    /// The collateral is insufficient.
+    #[error(
+        "insufficient collateral: {} lovelace, minimum is {}",
+        .0.provided_collateral.lovelace(),
+        .0.minimum_required_collateral.lovelace()
+    )]
+    InsufficientCollateral(InsufficientCollateral),
+    /// The execution units exceed the maximum per transaction.
+    #[error("{}", execution_units_too_large(.0))]
+    ExecutionUnitsTooLarge(ExecutionUnitsTooLarge),
+    /// Any other failure.
+    #[error("error {code}: {message}")]
+    Other {
+        /// JSON-RPC error code.
+        code: i32,
+        /// Error message.
+        message: String,
+        /// Additional error data.
+        data: Option<serde_json::Value>,
+    },
+}
+
+impl From<JsonRpcError> for SubmitTransactionError {
+    fn from(error: JsonRpcError) -> Self {
+        use SubmitTransactionError as E;
+
+        let parsed = match error.code {
+            error_codes::SUBMIT_ERA_MISMATCH => parse_data(&error).map(E::EraMismatch),
+            error_codes::SUBMIT_MISSING_SIGNATORIES => {
+                parse_data(&error).map(E::MissingSignatories)
+            }
+            error_codes::SUBMIT_MISSING_SCRIPTS => parse_data(&error).map(E::MissingScripts),
+            error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES => {
+                parse_data(&error).map(E::UnknownOutputReferences)
+            }
+            error_codes::SUBMIT_OUTSIDE_OF_VALIDITY_INTERVAL => {
+                parse_data(&error).map(E::OutsideOfValidityInterval)
+            }
+            error_codes::SUBMIT_TRANSACTION_TOO_LARGE => {
+                parse_data(&error).map(E::TransactionTooLarge)
+            }
+            error_codes::SUBMIT_TRANSACTION_FEE_TOO_SMALL => {
+                parse_data(&error).map(E::TransactionFeeTooSmall)
+            }
+            error_codes::SUBMIT_VALUE_NOT_CONSERVED => parse_data(&error).map(E::ValueNotConserved),
+            error_codes::SUBMIT_INSUFFICIENT_COLLATERAL => {
+                parse_data(&error).map(E::InsufficientCollateral)
+            }
+            error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE => {
+                parse_data(&error).map(E::ExecutionUnitsTooLarge)
+            }
+            _ => None,
+        };
+        parsed.unwrap_or(E::Other {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        })
+    }
+}
+
+impl SubmitTransactionError {
+    /// The JSON-RPC error code of the failure.
+    pub fn code(&self) -> i32 {
+        use SubmitTransactionError as E;
+
+        match self {
+            E::EraMismatch(_) => error_codes::SUBMIT_ERA_MISMATCH,
+            E::MissingSignatories(_) => error_codes::SUBMIT_MISSING_SIGNATORIES,
+            E::MissingScripts(_) => error_codes::SUBMIT_MISSING_SCRIPTS,
+            E::UnknownOutputReferences(_) => error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES,
+            E::OutsideOfValidityInterval(_) => error_codes::SUBMIT_OUTSIDE_OF_VALIDITY_INTERVAL,
+            E::TransactionTooLarge(_) => error_codes::SUBMIT_TRANSACTION_TOO_LARGE,
+            E::TransactionFeeTooSmall(_) => error_codes::SUBMIT_TRANSACTION_FEE_TOO_SMALL,
+            E::ValueNotConserved(_) => error_codes::SUBMIT_VALUE_NOT_CONSERVED,
+            E::InsufficientCollateral(_) => error_codes::SUBMIT_INSUFFICIENT_COLLATERAL,
+            E::ExecutionUnitsTooLarge(_) => error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE,
+            E::Other { code, .. } => *code,
+        }
+    }
+
+    /// The error data: as sent for [`Other`](SubmitTransactionError::Other),
+    /// encoded back from the parsed data otherwise.
+    pub fn data(&self) -> Option<serde_json::Value> {
+        use SubmitTransactionError as E;
+
+        match self {
+            E::EraMismatch(data) => encode_data(data),
+            E::MissingSignatories(data) => encode_data(data),
+            E::MissingScripts(data) => encode_data(data),
+            E::UnknownOutputReferences(data) => encode_data(data),
+            E::OutsideOfValidityInterval(data) => encode_data(data),
+            E::TransactionTooLarge(data) => encode_data(data),
+            E::TransactionFeeTooSmall(data) => encode_data(data),
+            E::ValueNotConserved(data) => encode_data(data),
+            E::InsufficientCollateral(data) => encode_data(data),
+            E::ExecutionUnitsTooLarge(data) => encode_data(data),
+            E::Other { data, .. } => data.clone(),
+        }
+    }
+}
+
+impl From<SubmitTransactionError> for OgmiosError {
+    fn from(error: SubmitTransactionError) -> Self {
+        OgmiosError::TransactionSubmission(error)
+    }
+}
+
+/// Reason a transaction evaluation was rejected, with the data Ogmios sent.
+///
+/// Failures without a dedicated variant, or whose data does not have the
+/// expected shape, are kept as [`Other`](EvaluateTransactionError::Other).
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum EvaluateTransactionError {
+    /// The transaction cannot be evaluated in the ledger's era.
+    #[error("cannot evaluate a transaction of the {} era", .0.incompatible_era)]
+    IncompatibleEra(IncompatibleEra),
+    /// Additional UTXOs overlap with outputs of the ledger.
+    #[error("additional UTXOs already in the ledger: {}", join(&.0.overlapping_output_references))]
+    OverlappingAdditionalUtxo(OverlappingAdditionalUtxo),
+    /// The evaluation context could not be created.
+    #[error("cannot create the evaluation context: {}", .0.reason)]
+    CannotCreateEvaluationContext(CannotCreateEvaluationContext),
+    /// Some scripts failed, in the order Ogmios reported them.
+    ///
+    /// Displayed with one line per script.
+    #[error("script execution failed:{}", script_failures(.0))]
+    ScriptExecutionFailure(Vec<ScriptFailure>),
+    /// Scripts required by the transaction are missing.
+    #[error("missing scripts {}", join(&.0.missing_scripts))]
+    MissingScripts(MissingScripts),
+    /// Inputs spend outputs that do not exist or were already spent.
+    #[error("unknown output references {}", join(&.0.unknown_output_references))]
+    UnknownOutputReferences(UnknownOutputReferences),
+    /// The execution units exceed the maximum per transaction.
+    #[error("{}", execution_units_too_large(.0))]
+    ExecutionUnitsTooLarge(ExecutionUnitsTooLarge),
+    /// Any other failure.
+    #[error("error {code}: {message}")]
+    Other {
+        /// JSON-RPC error code.
+        code: i32,
+        /// Error message.
+        message: String,
+        /// Additional error data.
+        data: Option<serde_json::Value>,
+    },
+}
+
+impl From<JsonRpcError> for EvaluateTransactionError {
+    fn from(error: JsonRpcError) -> Self {
+        use EvaluateTransactionError as E;
+
+        let parsed = match error.code {
+            error_codes::EVALUATE_INCOMPATIBLE_ERA => parse_data(&error).map(E::IncompatibleEra),
+            error_codes::EVALUATE_OVERLAPPING_ADDITIONAL_UTXO => {
+                parse_data(&error).map(E::OverlappingAdditionalUtxo)
+            }
+            error_codes::EVALUATE_CANNOT_CREATE_EVALUATION_CONTEXT => {
+                parse_data(&error).map(E::CannotCreateEvaluationContext)
+            }
+            error_codes::EVALUATE_SCRIPT_EXECUTION_FAILURE => {
+                parse_data::<Vec<RawScriptFailure>>(&error).map(|failures| {
+                    E::ScriptExecutionFailure(failures.into_iter().map(Into::into).collect())
+                })
+            }
+            error_codes::SUBMIT_MISSING_SCRIPTS => parse_data(&error).map(E::MissingScripts),
+            error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES => {
+                parse_data(&error).map(E::UnknownOutputReferences)
+            }
+            error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE => {
+                parse_data(&error).map(E::ExecutionUnitsTooLarge)
+            }
+            _ => None,
+        };
+        parsed.unwrap_or(E::Other {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        })
+    }
+}
+
+impl EvaluateTransactionError {
+    /// The JSON-RPC error code of the failure.
+    pub fn code(&self) -> i32 {
+        use EvaluateTransactionError as E;
+
+        match self {
+            E::IncompatibleEra(_) => error_codes::EVALUATE_INCOMPATIBLE_ERA,
+            E::OverlappingAdditionalUtxo(_) => error_codes::EVALUATE_OVERLAPPING_ADDITIONAL_UTXO,
+            E::CannotCreateEvaluationContext(_) => {
+                error_codes::EVALUATE_CANNOT_CREATE_EVALUATION_CONTEXT
+            }
+            E::ScriptExecutionFailure(_) => error_codes::EVALUATE_SCRIPT_EXECUTION_FAILURE,
+            E::MissingScripts(_) => error_codes::SUBMIT_MISSING_SCRIPTS,
+            E::UnknownOutputReferences(_) => error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES,
+            E::ExecutionUnitsTooLarge(_) => error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE,
+            E::Other { code, .. } => *code,
+        }
+    }
+
+    /// The error data: as sent for [`Other`](EvaluateTransactionError::Other),
+    /// encoded back from the parsed data otherwise.
+    ///
+    /// Script failures are encoded as `{ validator, error: { code, data } }`;
+    /// the messages of their errors are not kept.
+    pub fn data(&self) -> Option<serde_json::Value> {
+        use EvaluateTransactionError as E;
+
+        match self {
+            E::IncompatibleEra(data) => encode_data(data),
+            E::OverlappingAdditionalUtxo(data) => encode_data(data),
+            E::CannotCreateEvaluationContext(data) => encode_data(data),
+            E::ScriptExecutionFailure(failures) => Some(
+                failures
+                    .iter()
+                    .map(|failure| {
+                        serde_json::json!({
+                            "validator": failure.validator,
+                            "error": { "code": failure.error.code(), "data": failure.error.data() },
+                        })
+                    })
+                    .collect(),
+            ),
+            E::MissingScripts(data) => encode_data(data),
+            E::UnknownOutputReferences(data) => encode_data(data),
+            E::ExecutionUnitsTooLarge(data) => encode_data(data),
+            E::Other { data, .. } => data.clone(),
+        }
+    }
+}
+
+impl From<EvaluateTransactionError> for OgmiosError {
+    fn from(error: EvaluateTransactionError) -> Self {
+        OgmiosError::TransactionEvaluation(error)
+    }
+}
+
+/// Failure of one script of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScriptFailure {
+    /// The script that failed.
+    pub validator: ValidatorIndex,
+    /// Why it failed.
+    pub error: ScriptError,
+}
+
+impl ScriptFailure {
+    /// Messages traced by the script before it failed, in order.
+    ///
+    /// Empty unless the script itself failed to validate.
+    pub fn traces(&self) -> &[String] {
+        match &self.error {
+            ScriptError::ValidationFailure(failure) => &failure.traces,
+            _ => &[],
+        }
+    }
+}
+
+/// The validator and the reason, followed by the first trace, if any.
+impl std::fmt::Display for ScriptFailure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}]: {}", self.validator.purpose, self.validator.index, self.error)?;
+        match self.traces() {
+            [] => Ok(()),
+            [trace] => write!(f, " (trace: {trace})"),
+            [trace, rest @ ..] => write!(f, " (trace: {trace}, {} more)", rest.len()),
+        }
+    }
+}
+
+/// Reason a script failed during evaluation.
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum ScriptError {
+    /// The script failed to validate.
+    #[error("{}", .0.validation_error)]
+    ValidationFailure(ValidationFailure),
+    /// Scripts required to run the validator are missing.
+    #[error("missing scripts {}", join(&.0.missing_scripts))]
+    MissingScripts(MissingScripts),
+    /// The validator spends outputs that do not exist.
+    #[error("unknown output references {}", join(&.0.unknown_output_references))]
+    UnknownOutputReferences(UnknownOutputReferences),
+    /// The script exceeded the execution units it was given.
+    #[error("{}", execution_units_too_large(.0))]
+    ExecutionUnitsTooLarge(ExecutionUnitsTooLarge),
+    /// Any other failure.
+    #[error("error {code}: {message}")]
+    Other {
+        /// JSON-RPC error code.
+        code: i32,
+        /// Error message.
+        message: String,
+        /// Additional error data.
+        data: Option<serde_json::Value>,
+    },
+}
+
+impl From<JsonRpcError> for ScriptError {
+    fn from(error: JsonRpcError) -> Self {
+        use ScriptError as E;
+
+        let parsed = match error.code {
+            error_codes::EVALUATE_VALIDATION_FAILURE => {
+                parse_data(&error).map(E::ValidationFailure)
+            }
+            error_codes::SUBMIT_MISSING_SCRIPTS => parse_data(&error).map(E::MissingScripts),
+            error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES => {
+                parse_data(&error).map(E::UnknownOutputReferences)
+            }
+            error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE => {
+                parse_data(&error).map(E::ExecutionUnitsTooLarge)
+            }
+            _ => None,
+        };
+        parsed.unwrap_or(E::Other {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+        })
+    }
+}
+
+impl ScriptError {
+    /// The JSON-RPC error code of the failure.
+    pub fn code(&self) -> i32 {
+        match self {
+            ScriptError::ValidationFailure(_) => error_codes::EVALUATE_VALIDATION_FAILURE,
+            ScriptError::MissingScripts(_) => error_codes::SUBMIT_MISSING_SCRIPTS,
+            ScriptError::UnknownOutputReferences(_) => error_codes::SUBMIT_UNKNOWN_OUTPUT_REFERENCES,
+            ScriptError::ExecutionUnitsTooLarge(_) => error_codes::SUBMIT_EXECUTION_UNITS_TOO_LARGE,
+            ScriptError::Other { code, .. } => *code,
+        }
+    }
+
+    /// The error data: as sent for [`Other`](ScriptError::Other), encoded
+    /// back from the parsed data otherwise.
+    pub fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            ScriptError::ValidationFailure(data) => encode_data(data),
+            ScriptError::MissingScripts(data) => encode_data(data),
+            ScriptError::UnknownOutputReferences(data) => encode_data(data),
+            ScriptError::ExecutionUnitsTooLarge(data) => encode_data(data),
+            ScriptError::Other { data, .. } => data.clone(),
+        }
+    }
+}
+
+/// A script failure as sent by Ogmios, with its error still undecoded.
+#[derive(Deserialize)]
+struct RawScriptFailure {
+    validator: ValidatorIndex,
+    error: JsonRpcError,
+}
+
+impl From<RawScriptFailure> for ScriptFailure {
+    fn from(failure: RawScriptFailure) -> Self {
+        Self {
+            validator: failure.validator,
+            error: failure.error.into(),
+        }
+    }
+}
+
+/// Encode parsed error data back to JSON.
+fn encode_data(data: &impl serde::Serialize) -> Option<serde_json::Value> {
+    serde_json::to_value(data).ok()
+}
+
+fn execution_units_too_large(data: &ExecutionUnitsTooLarge) -> String {
+    let (provided, maximum) = (data.provided_execution_units, data.maximum_execution_units);
+    format!(
+        "execution units too large: {} memory and {} cpu, maximum is {} and {}",
+        provided.memory, provided.cpu, maximum.memory, maximum.cpu
+    )
+}
+
+/// Name the request an error was raised for, e.g. ` (nextBlock, id 3)`.
+fn for_request(method: &Option<String>, id: &Option<u64>) -> String {
+    match (method, id) {
+        (Some(method), Some(id)) => format!(" ({method}, id {id})"),
+        (Some(method), None) => format!(" ({method})"),
+        (None, Some(id)) => format!(" (id {id})"),
+        (None, None) => String::new(),
+    }
+}
+
+fn describe_acquisition(failure: &AcquireFailure, point: Option<&Point>) -> String {
+    match point {
+        Some(point) => {
+            format!("Failed to acquire ledger state at {}: {failure}", format_point(point))
+        }
+        None => format!("Acquisition failed: {failure}"),
+    }
+}
+
+fn script_failures(failures: &[ScriptFailure]) -> String {
+    failures.iter().map(|failure| format!("\n  {failure}")).collect()
+}
+
+/// Parse the data of a failure, if present and of the expected shape.
+fn parse_data<T: DeserializeOwned>(error: &JsonRpcError) -> Option<T> {
+    T::deserialize(error.data.as_ref()?).ok()
+}
+
+fn join<T: std::fmt::Display>(items: &[T]) -> String {
+    items.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+}
+
+impl OgmiosError {
+    /// HTTP status of the response rejecting the WebSocket upgrade, e.g. 401
+    /// for a missing API key.
+    pub fn handshake_status(&self) -> Option<u16> {
+        match self {
+            OgmiosError::HttpHandshake(e) => e.handshake_status(),
+            _ => None,
+        }
+    }
+
+    /// Attribute a connection error or timeout to the request `id` to `method`.
+    ///
+    /// Other errors, and errors already attributed, are returned as is.
+    pub(crate) fn for_request(mut self, request_method: &str, request_id: u64) -> Self {
+        if let OgmiosError::Timeout { method, id, .. }
+        | OgmiosError::ConnectionClosed { method, id }
+        | OgmiosError::SocketNotOpen { method, id, .. } = &mut self
+            && method.is_none()
+            && id.is_none()
+        {
+            *method = Some(request_method.to_string());
+            *id = Some(request_id);
+        }
+        self
+    }
+
+    /// Wrap an error the server reported for a request to `method`.
+    pub(crate) fn rpc(method: &str, error: JsonRpcError) -> Self {
+        OgmiosError::Rpc {
+            code: error.code,
+            message: error.message,
+            data: error.data,
+            method: method.to_string(),
+        }
+    }
+
+    /// The JSON-RPC error code, if this error was reported by the server:
+    /// `Rpc`, `TransactionSubmission`, `TransactionEvaluation` or
+    /// `Acquisition`.
+    ///
+    /// Codes are listed in [`error_codes`].
+    pub fn code(&self) -> Option<i32> {
+        match self {
+            OgmiosError::Rpc { code, .. } => Some(*code),
+            OgmiosError::TransactionSubmission(failure) => Some(failure.code()),
+            OgmiosError::TransactionEvaluation(failure) => Some(failure.code()),
+            OgmiosError::Acquisition { failure, .. } => Some(failure.code()),
+            _ => None,
+        }
+    }
+
+    /// The data of a JSON-RPC error reported by the server, if any.
+    ///
+    /// Failures parsed into a dedicated variant give their data encoded back
+    /// to JSON, see e.g. [`SubmitTransactionError::data`].
+    pub fn data(&self) -> Option<serde_json::Value> {
+        match self {
+            OgmiosError::Rpc { data, .. } => data.clone(),
+            OgmiosError::TransactionSubmission(failure) => failure.data(),
+            OgmiosError::TransactionEvaluation(failure) => failure.data(),
+            OgmiosError::Acquisition { failure, .. } => failure.data(),
+            _ => None,
+        }
+    }
+
+    /// The reason a transaction submission was rejected, if it was.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::{OgmiosError, SubmitTransactionError};
+    ///
+    /// fn minimum_fee(error: &OgmiosError) -> Option<u64> {
+    ///     match error.submission_failure()? {
+    ///         SubmitTransactionError::TransactionFeeTooSmall(failure) => {
+    ///             Some(failure.minimum_required_fee.lovelace)
+    ///         }
+    ///         _ => None,
+    ///     }
+    /// }
+    /// ```
+    pub fn submission_failure(&self) -> Option<&SubmitTransactionError> {
+        match self {
+            OgmiosError::TransactionSubmission(failure) => Some(failure),
+            _ => None,
+        }
+    }
+
+    /// The reason a transaction evaluation was rejected, if it was.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::{EvaluateTransactionError, OgmiosError};
+    ///
+    /// fn print_traces(error: &OgmiosError) {
+    ///     if let Some(EvaluateTransactionError::ScriptExecutionFailure(failures)) =
+    ///         error.evaluation_failure()
+    ///     {
+    ///         for failure in failures {
+    ///             println!("{failure}");
+    ///             for trace in failure.traces() {
+    ///                 println!("  {trace}");
+    ///             }
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn evaluation_failure(&self) -> Option<&EvaluateTransactionError> {
+        match self {
+            OgmiosError::TransactionEvaluation(failure) => Some(failure),
+            _ => None,
+        }
+    }
+
+    /// Category of the error, if it was reported by the server.
+    ///
+    /// Known failures are categorized by their variant, and others by their
+    /// code with [`classify`].
+    pub fn category(&self) -> Option<OgmiosErrorCategory> {
+        match self {
+            OgmiosError::Rpc { code, .. } => Some(classify(*code)),
+            OgmiosError::TransactionSubmission(_) => Some(OgmiosErrorCategory::Submission),
+            OgmiosError::TransactionEvaluation(_) => Some(OgmiosErrorCategory::Evaluation),
+            OgmiosError::Acquisition { failure, .. } => Some(match failure {
+                AcquireFailure::MempoolNotAcquired => OgmiosErrorCategory::Mempool,
+                AcquireFailure::Unknown { code, .. } => classify(*code),
+                _ => OgmiosErrorCategory::Acquisition,
+            }),
+            _ => None,
+        }
+    }
+
+    /// Whether the same request may succeed if sent again later, possibly on a
+    /// new connection.
+    ///
+    /// [`wait_for_server_ready`](crate::server_health::wait_for_server_ready)
+    /// and mempool tracking give up on errors that are not retryable. This
+    /// crate does not reconnect by itself.
+    ///
+    /// | Errors                                                   | Retryable |
+    /// |----------------------------------------------------------|-----------|
+    /// | [connection errors](Self::is_connection_error)           | yes       |
+    /// | upgrades rejected with 4xx statuses but 408 and 429      | no        |
+    /// | `Timeout`, `ServerReadyTimeout`                          | yes       |
+    /// | `ServerNotReady`, `NodeDisconnected`                     | yes       |
+    /// | `Http` timeouts and 5xx statuses, `Io` timeouts          | yes       |
+    /// | server errors with code `-32603` or `-32000` to `-32099` | yes       |
+    /// | other server errors, e.g. invalid params or ledger rules | no        |
+    /// | `Json`, `InvalidResponse`, `ResponseIdMismatch`          | no        |
+    /// | validation of arguments, `Tls`, `UrlParse` and the rest  | no        |
+    pub fn is_retryable(&self) -> bool {
+        if let Some(status) = self.handshake_status()
+            && (400..500).contains(&status)
+        {
+            return status == 408 || status == 429;
+        }
+        if self.is_connection_error() {
+            return true;
+        }
+        match self {
+            OgmiosError::Timeout { .. }
+            | OgmiosError::ServerReadyTimeout { .. }
+            | OgmiosError::ServerNotReady(_)
+            | OgmiosError::NodeDisconnected => true,
+            OgmiosError::Http(e) => {
+                e.is_timeout() || e.status().is_some_and(|status| status.is_server_error())
+            }
+            OgmiosError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::TimedOut | std::io::ErrorKind::Interrupted
+            ),
+            OgmiosError::Rpc { code, .. } => is_retryable_code(*code),
+            OgmiosError::TransactionSubmission(SubmitTransactionError::Other { code, .. })
+            | OgmiosError::TransactionEvaluation(EvaluateTransactionError::Other {
+                code, ..
+            }) => is_retryable_code(*code),
+            OgmiosError::Acquisition {
+                failure: AcquireFailure::Unknown { code, .. },
+                ..
+            } => is_retryable_code(*code),
+            _ => false,
+        }
+    }
+
+    /// Whether the connection to the server failed or was lost.
+    ///
+    /// These are `WebSocket`, `HttpHandshake`, `ConnectionClosed`,
+    /// `SocketNotOpen`, the channel errors raised when the connection task
+    /// stops, and `Http` and `Io` errors of connecting or of a dropped
+    /// connection. All of them are [retryable](Self::is_retryable).
+    pub fn is_connection_error(&self) -> bool {
+        match self {
+            OgmiosError::WebSocket(_)
+            | OgmiosError::HttpHandshake(_)
+            | OgmiosError::ConnectionClosed { .. }
+            | OgmiosError::SocketNotOpen { .. }
+            | OgmiosError::ChannelSend(_)
+            | OgmiosError::ChannelRecv => true,
+            OgmiosError::Http(e) => e.is_connect(),
+            OgmiosError::Io(e) => matches!(
+                e.kind(),
+                std::io::ErrorKind::ConnectionRefused
+                    | std::io::ErrorKind::ConnectionReset
+                    | std::io::ErrorKind::ConnectionAborted
+                    | std::io::ErrorKind::NotConnected
+                    | std::io::ErrorKind::BrokenPipe
+                    | std::io::ErrorKind::UnexpectedEof
+            ),
+            _ => false,
+        }
+    }
+
+    /// Whether the server answered the request with a JSON-RPC error: `Rpc`,
+    /// `TransactionSubmission`, `TransactionEvaluation` or `Acquisition`.
+    pub fn is_server_error(&self) -> bool {
+        matches!(
+            self,
+            OgmiosError::Rpc { .. }
+                | OgmiosError::TransactionSubmission(_)
+                | OgmiosError::TransactionEvaluation(_)
+                | OgmiosError::Acquisition { .. }
+        )
+    }
+}
+
+/// Whether a JSON-RPC error code reports a failure of the server rather than
+/// of the request.
+fn is_retryable_code(code: i32) -> bool {
+    code == error_codes::INTERNAL_ERROR
+        || (error_codes::SERVER_ERROR_END..=error_codes::SERVER_ERROR_START).contains(&code)
 }
 
 /// Result type alias for Ogmios operations.
 pub type Result<T> = std::result::Result<T, OgmiosError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::DigestBlake2b256;
+    use serde_json::json;
+
+    fn rpc_error(code: i32, data: Option<serde_json::Value>) -> JsonRpcError {
+        JsonRpcError {
+            code,
+            message: "Failed to acquire requested point.".to_string(),
+            data,
+        }
+    }
+
+    #[test]
+    fn test_acquire_failure_from_rpc_error() {
+        assert_eq!(AcquireFailure::from(rpc_error(2000, None)), AcquireFailure::PointTooOld);
+        assert_eq!(AcquireFailure::from(rpc_error(2001, None)), AcquireFailure::PointNotOnChain);
+        assert_eq!(
+            AcquireFailure::from(rpc_error(4000, None)),
+            AcquireFailure::MempoolNotAcquired
+        );
+        assert_eq!(
+            AcquireFailure::from(rpc_error(2000, Some(json!("pointNotOnChain")))),
+            AcquireFailure::PointNotOnChain
+        );
+        assert!(matches!(
+            AcquireFailure::from(rpc_error(-32602, None)),
+            AcquireFailure::Unknown { code: -32602, .. }
+        ));
+
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::PointTooOld,
+            point: Some(Point::at(100, DigestBlake2b256::filled("ab"))),
+        };
+        assert_eq!(
+            error.to_string(),
+            format!("Failed to acquire ledger state at 100.{}: point too old", "ab".repeat(32))
+        );
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::MempoolNotAcquired,
+            point: None,
+        };
+        assert_eq!(error.to_string(), "Acquisition failed: mempool not acquired");
+    }
+
+    #[test]
+    fn test_rpc_error() {
+        let error = OgmiosError::rpc(
+            "acquireLedgerState",
+            rpc_error(2003, Some(json!({ "currentSlot": 1 }))),
+        );
+        assert_eq!(error.code(), Some(2003));
+        assert_eq!(error.data(), Some(json!({ "currentSlot": 1 })));
+        assert_eq!(
+            error.to_string(),
+            "acquireLedgerState failed with JSON-RPC error 2003: Failed to acquire requested point."
+        );
+        assert_eq!(OgmiosError::NodeDisconnected.code(), None);
+        assert_eq!(OgmiosError::NodeDisconnected.data(), None);
+    }
+
+    #[test]
+    fn test_submission_failure_code_and_data() {
+        let response = crate::schema::testing::fixture("submission/submitTransaction-3122").error();
+        let (code, data) = (response.code, response.data.clone());
+        let error = OgmiosError::from(SubmitTransactionError::from(response));
+        assert!(matches!(
+            error,
+            OgmiosError::TransactionSubmission(SubmitTransactionError::TransactionFeeTooSmall(_))
+        ));
+        assert_eq!(error.code(), Some(code));
+        assert_eq!(error.data(), data);
+    }
+
+    #[test]
+    fn test_evaluation_failure_code_and_data() {
+        let response = crate::schema::testing::fixture("evaluation/evaluateTransaction-3010").error();
+        let sent = response.data.clone().unwrap();
+        let error = OgmiosError::from(EvaluateTransactionError::from(response));
+        assert_eq!(error.code(), Some(error_codes::EVALUATE_SCRIPT_EXECUTION_FAILURE));
+
+        // Script errors are encoded without their messages.
+        let data = error.data().unwrap();
+        assert_eq!(data[0]["validator"], sent[0]["validator"]);
+        assert_eq!(data[0]["error"]["code"], sent[0]["error"]["code"]);
+        assert_eq!(data[0]["error"]["data"], sent[0]["error"]["data"]);
+    }
+
+    #[test]
+    fn test_acquisition_failure_code_and_data() {
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::from(rpc_error(2001, None)),
+            point: None,
+        };
+        assert_eq!(error.code(), Some(error_codes::ACQUIRE_POINT_NOT_ON_CHAIN));
+        assert_eq!(error.data(), None);
+
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::from(rpc_error(2002, Some(json!({ "era": "babbage" })))),
+            point: None,
+        };
+        assert_eq!(error.code(), Some(2002));
+        assert_eq!(error.data(), Some(json!({ "era": "babbage" })));
+    }
+
+    #[test]
+    fn test_errors_name_their_request() {
+        let timeout = OgmiosError::Timeout {
+            timeout_ms: 5000,
+            method: None,
+            id: None,
+        };
+        assert_eq!(timeout.to_string(), "Request timed out after 5000ms");
+        let timeout = timeout.for_request("nextBlock", 3);
+        assert_eq!(timeout.to_string(), "Request timed out after 5000ms (nextBlock, id 3)");
+        // Already attributed errors are kept as is.
+        let timeout = timeout.for_request("findIntersection", 4);
+        assert!(matches!(timeout, OgmiosError::Timeout { id: Some(3), .. }));
+
+        let closed = OgmiosError::ConnectionClosed {
+            method: None,
+            id: None,
+        };
+        assert_eq!(
+            closed.for_request("queryLedgerState/tip", 7).to_string(),
+            "Connection closed unexpectedly (queryLedgerState/tip, id 7)"
+        );
+        let not_open = OgmiosError::SocketNotOpen {
+            state: "closed".to_string(),
+            method: None,
+            id: None,
+        };
+        assert_eq!(
+            not_open.for_request("nextBlock", 1).to_string(),
+            "Socket is not open (state: closed) (nextBlock, id 1)"
+        );
+        assert!(matches!(
+            OgmiosError::ChannelRecv.for_request("nextBlock", 1),
+            OgmiosError::ChannelRecv
+        ));
+    }
+
+    #[test]
+    fn test_classify() {
+        for pair in CATEGORIES.windows(2) {
+            assert!(pair[0].0.end() < pair[1].0.start(), "{pair:?}");
+        }
+
+        use OgmiosErrorCategory as C;
+        for (code, category) in [
+            (error_codes::INTERSECTION_NOT_FOUND, C::ChainSync),
+            (error_codes::ACQUIRED_EXPIRED, C::Acquisition),
+            (error_codes::EVALUATE_INCOMPATIBLE_ERA, C::Evaluation),
+            (error_codes::SUBMIT_ERA_MISMATCH, C::Submission),
+            (error_codes::EVALUATE_VALIDATION_FAILURE, C::Evaluation),
+            (error_codes::SUBMIT_INSUFFICIENTLY_FUNDED_OUTPUTS, C::Submission),
+            (3999, C::Submission),
+            (error_codes::MEMPOOL_MUST_ACQUIRE_FIRST, C::Mempool),
+            (error_codes::INVALID_PARAMS, C::Generic),
+            (999, C::Generic),
+            (5000, C::Generic),
+        ] {
+            assert_eq!(classify(code), category, "{code}");
+        }
+
+        let error = OgmiosError::rpc("hasTransaction", rpc_error(4000, None));
+        assert_eq!(error.category(), Some(C::Mempool));
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::PointTooOld,
+            point: None,
+        };
+        assert_eq!(error.category(), Some(C::Acquisition));
+        let error = OgmiosError::Acquisition {
+            failure: AcquireFailure::MempoolNotAcquired,
+            point: None,
+        };
+        assert_eq!(error.category(), Some(C::Mempool));
+        assert_eq!(OgmiosError::NodeDisconnected.category(), None);
+    }
+
+    fn handshake(status: u16) -> OgmiosError {
+        let response = tungstenite::http::Response::builder()
+            .status(status)
+            .body(None)
+            .unwrap();
+        OgmiosError::HttpHandshake(WebSocketError::new(tungstenite::Error::Http(response)))
+    }
+
+    #[test]
+    fn test_websocket_error_sources() {
+        use std::error::Error as _;
+        use std::io::{Error, ErrorKind};
+
+        let rejected = handshake(401);
+        assert_eq!(rejected.handshake_status(), Some(401));
+        assert_eq!(rejected.to_string(), "HTTP handshake error: HTTP error: 401 Unauthorized");
+        assert!(rejected.source().unwrap().source().is_none());
+
+        let reset = WebSocketError::new(Error::from(ErrorKind::ConnectionReset).into());
+        assert!(reset.is_io() && !reset.is_tls() && !reset.is_protocol());
+        assert_eq!(reset.handshake_status(), None);
+        let error = OgmiosError::WebSocket(reset);
+        assert_eq!(error.handshake_status(), None);
+        let io = error.source().unwrap().source().unwrap();
+        assert_eq!(io.downcast_ref::<Error>().unwrap().kind(), ErrorKind::ConnectionReset);
+
+        let closed = WebSocketError::new(tungstenite::Error::ConnectionClosed);
+        assert!(closed.is_closed());
+        assert!(closed.clone().source().is_none());
+    }
+
+    #[test]
+    fn test_error_classification() {
+        use std::io::{Error, ErrorKind};
+
+        let rpc = |code| OgmiosError::rpc("nextBlock", rpc_error(code, None));
+        let websocket = |e| OgmiosError::WebSocket(WebSocketError::new(e));
+        // (error, retryable, connection, server)
+        let cases = [
+            (websocket(Error::from(ErrorKind::ConnectionReset).into()), true, true, false),
+            (handshake(502), true, true, false),
+            (handshake(429), true, true, false),
+            (handshake(401), false, true, false),
+            (OgmiosError::ConnectionClosed { method: None, id: None }, true, true, false),
+            (
+                OgmiosError::SocketNotOpen { state: "closed".to_string(), method: None, id: None },
+                true,
+                true,
+                false,
+            ),
+            (OgmiosError::ChannelSend("closed".to_string()), true, true, false),
+            (OgmiosError::ChannelRecv, true, true, false),
+            (Error::from(ErrorKind::ConnectionReset).into(), true, true, false),
+            (Error::from(ErrorKind::TimedOut).into(), true, false, false),
+            (Error::from(ErrorKind::PermissionDenied).into(), false, false, false),
+            (OgmiosError::Timeout { timeout_ms: 1, method: None, id: None }, true, false, false),
+            (
+                OgmiosError::ServerReadyTimeout {
+                    timeout_ms: 1,
+                    reason: NotReadyReason::NodeDisconnected,
+                    last_health: None,
+                },
+                true,
+                false,
+                false,
+            ),
+            (OgmiosError::NodeDisconnected, true, false, false),
+            (rpc(error_codes::INTERNAL_ERROR), true, false, true),
+            (rpc(-32050), true, false, true),
+            (rpc(error_codes::INVALID_PARAMS), false, false, true),
+            (rpc(error_codes::ACQUIRED_EXPIRED), false, false, true),
+            (
+                OgmiosError::TransactionSubmission(rpc_error(-32000, None).into()),
+                true,
+                false,
+                true,
+            ),
+            (
+                OgmiosError::TransactionSubmission(rpc_error(3117, None).into()),
+                false,
+                false,
+                true,
+            ),
+            (
+                OgmiosError::TransactionEvaluation(rpc_error(3010, None).into()),
+                false,
+                false,
+                true,
+            ),
+            (
+                OgmiosError::Acquisition {
+                    failure: AcquireFailure::PointTooOld,
+                    point: Some(Point::at(100, DigestBlake2b256::filled("ab"))),
+                },
+                false,
+                false,
+                true,
+            ),
+            (
+                serde_json::from_str::<u8>("x").unwrap_err().into(),
+                false,
+                false,
+                false,
+            ),
+            (
+                OgmiosError::InvalidResponse { message: "bad".to_string() },
+                false,
+                false,
+                false,
+            ),
+            (OgmiosError::Tls("no roots".to_string()), false, false, false),
+            (OgmiosError::InvalidTime("NaN".to_string()), false, false, false),
+            (OgmiosError::QueryError("empty".to_string()), false, false, false),
+        ];
+        for (error, retryable, connection, server) in cases {
+            assert_eq!(error.is_retryable(), retryable, "{error:?}");
+            assert_eq!(error.is_connection_error(), connection, "{error:?}");
+            assert_eq!(error.is_server_error(), server, "{error:?}");
+        }
+
+        let builder = reqwest::Client::new().get("http://[::1").build().unwrap_err();
+        let builder = OgmiosError::from(builder);
+        assert!(!builder.is_retryable() && !builder.is_connection_error());
+    }
+
+    const TX_ID: &str = "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7";
+
+    fn submit_error(code: i32, message: &str, data: serde_json::Value) -> SubmitTransactionError {
+        SubmitTransactionError::from(JsonRpcError {
+            code,
+            message: message.to_string(),
+            data: Some(data),
+        })
+    }
+
+    #[test]
+    fn test_submit_error_payloads() {
+        let error = submit_error(
+            3117,
+            "The transaction contains unknown UTxO references as inputs.",
+            json!({ "unknownOutputReferences": [{ "transaction": { "id": TX_ID }, "index": 1 }] }),
+        );
+        let SubmitTransactionError::UnknownOutputReferences(data) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.unknown_output_references, [format!("{TX_ID}#1").parse().unwrap()]);
+        assert_eq!(error.to_string(), format!("unknown output references {TX_ID}#1"));
+
+        let error = submit_error(
+            3122,
+            "Insufficient fee! The transaction does not pay enough fee.",
+            json!({
+                "minimumRequiredFee": { "ada": { "lovelace": 171045 } },
+                "providedFee": { "ada": { "lovelace": 170000 } }
+            }),
+        );
+        let SubmitTransactionError::TransactionFeeTooSmall(data) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.minimum_required_fee.lovelace, 171045);
+        assert_eq!(
+            error.to_string(),
+            "fee too small: 170000 lovelace, minimum is 171045 (1045 short)"
+        );
+
+        let error = OgmiosError::from(error);
+        let Some(SubmitTransactionError::TransactionFeeTooSmall(data)) = error.submission_failure()
+        else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.minimum_required_fee.lovelace, 171_045);
+        assert_eq!(OgmiosError::NodeDisconnected.submission_failure(), None);
+
+        let error = submit_error(
+            3123,
+            "The transaction does not preserve value.",
+            json!({
+                "valueConsumed": { "ada": { "lovelace": 10000000 } },
+                "valueProduced": {
+                    "ada": { "lovelace": 9000000 },
+                    "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7": { "4e4654": 1 }
+                }
+            }),
+        );
+        let SubmitTransactionError::ValueNotConserved(data) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.value_consumed.lovelace(), 10_000_000);
+        assert!(matches!(data.value_produced, crate::schema::Value::WithAssets { .. }));
+
+        let key = "8fd2ea1e9a8ce6ae1b7dc3b5e96ea8c0a6a1a7a5cf0bcc2e1bb2c1e1";
+        let error = submit_error(
+            3101,
+            "Some signatures are missing.",
+            json!({ "missingSignatories": [key] }),
+        );
+        assert_eq!(
+            error,
+            SubmitTransactionError::MissingSignatories(MissingSignatories {
+                missing_signatories: vec![key.parse().unwrap()],
+            })
+        );
+
+        let script = "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656";
+        let error = submit_error(
+            3102,
+            "Some scripts are missing.",
+            json!({ "missingScripts": [script] }),
+        );
+        let SubmitTransactionError::MissingScripts(data) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.missing_scripts[0], script);
+
+        let error = submit_error(
+            3118,
+            "The transaction is outside of its validity interval.",
+            json!({ "validityInterval": { "invalidAfter": 1000 }, "currentSlot": 1200 }),
+        );
+        let SubmitTransactionError::OutsideOfValidityInterval(data) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(data.validity_interval.invalid_before, None);
+        assert_eq!(data.validity_interval.invalid_after, Some(1000));
+        assert_eq!(data.current_slot, 1200);
+
+        let error = submit_error(
+            3119,
+            "The transaction exceeds the maximum size.",
+            json!({
+                "measuredTransactionSize": { "bytes": 16500 },
+                "maximumTransactionSize": { "bytes": 16384 }
+            }),
+        );
+        assert_eq!(error.to_string(), "transaction too large: 16500 bytes, maximum is 16384");
+
+        let error = submit_error(
+            3128,
+            "The collateral is insufficient.",
+            json!({
+                "providedCollateral": { "ada": { "lovelace": 1000000 } },
+                "minimumRequiredCollateral": { "ada": { "lovelace": 1500000 } }
+            }),
+        );
+        assert!(matches!(error, SubmitTransactionError::InsufficientCollateral(_)));
+
+        let error = submit_error(
+            3005,
+            "Failed to submit the transaction in the current era.",
+            json!({ "queryEra": "babbage", "ledgerEra": "conway" }),
+        );
+        assert_eq!(
+            error.to_string(),
+            "era mismatch: transaction is for babbage, ledger is in conway"
+        );
+    }
+
+    #[test]
+    fn test_submit_error_falls_back_to_other() {
+        // Unknown code.
+        let error = submit_error(3999, "Something new.", json!({ "what": 1 }));
+        assert_eq!(
+            error,
+            SubmitTransactionError::Other {
+                code: 3999,
+                message: "Something new.".to_string(),
+                data: Some(json!({ "what": 1 })),
+            }
+        );
+        // Known code with data of an unexpected shape.
+        let error = submit_error(3122, "Insufficient fee!", json!({ "fee": 1 }));
+        assert!(matches!(error, SubmitTransactionError::Other { code: 3122, .. }));
+    }
+
+    fn evaluate_error(code: i32, data: serde_json::Value) -> EvaluateTransactionError {
+        EvaluateTransactionError::from(JsonRpcError {
+            code,
+            message: "Some scripts of the transactions terminated with error(s).".to_string(),
+            data: Some(data),
+        })
+    }
+
+    #[test]
+    fn test_script_execution_failure_keeps_traces_in_order() {
+        let script = "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656";
+        let validation_failure = json!({
+            "code": 3012,
+            "message": "Some of the scripts failed to evaluate to a positive outcome.",
+            "data": {
+                "validationError": "An error has occurred: The machine terminated because of an \
+                    error, either from a built-in function or from an explicit use of 'error'.",
+                "traces": ["Deadline not reached", "PT5"]
+            }
+        });
+        let units_too_large = json!({
+            "code": 3134,
+            "message": "The transaction exceeds the maximum execution units.",
+            "data": {
+                "providedExecutionUnits": { "memory": 14000001, "cpu": 10_000_000_000u64 },
+                "maximumExecutionUnits": { "memory": 14000000, "cpu": 10_000_000_000u64 }
+            }
+        });
+        let error = evaluate_error(
+            3010,
+            json!([
+                {
+                    "validator": { "purpose": "spend", "index": 0 },
+                    "error": validation_failure
+                },
+                {
+                    "validator": { "purpose": "mint", "index": 1 },
+                    "error": {
+                        "code": 3102,
+                        "message": "Some scripts are missing.",
+                        "data": { "missingScripts": [script] }
+                    }
+                },
+                {
+                    "validator": { "purpose": "withdraw", "index": 0 },
+                    "error": units_too_large
+                }
+            ]),
+        );
+
+        let EvaluateTransactionError::ScriptExecutionFailure(failures) = &error else {
+            panic!("{error:?}");
+        };
+        assert_eq!(failures.len(), 3);
+        assert_eq!(failures[0].validator.index, 0);
+        assert_eq!(failures[0].traces(), ["Deadline not reached", "PT5"]);
+        assert!(failures[1].traces().is_empty());
+        let ScriptError::MissingScripts(missing) = &failures[1].error else {
+            panic!("{:?}", failures[1]);
+        };
+        assert_eq!(missing.missing_scripts[0], script);
+        let ScriptError::ExecutionUnitsTooLarge(units) = &failures[2].error else {
+            panic!("{:?}", failures[2]);
+        };
+        assert_eq!(units.provided_execution_units.memory, 14_000_001);
+        let display = error.to_string();
+        let lines: Vec<_> = display.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[0], "script execution failed:");
+        assert!(lines[1].starts_with("  spend[0]: An error has occurred"));
+        assert!(lines[1].ends_with("(trace: Deadline not reached, 1 more)"));
+        assert_eq!(lines[2], format!("  mint[1]: missing scripts {script}"));
+        assert!(lines[3].starts_with("  withdraw[0]: execution units too large"));
+
+        let error = OgmiosError::from(error);
+        assert!(matches!(
+            error.evaluation_failure(),
+            Some(EvaluateTransactionError::ScriptExecutionFailure(failures)) if failures.len() == 3
+        ));
+        assert_eq!(OgmiosError::NodeDisconnected.evaluation_failure(), None);
+    }
+
+    #[test]
+    fn test_evaluate_error_payloads() {
+        let tx_id = "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7";
+        let error = evaluate_error(
+            3002,
+            json!({
+                "overlappingOutputReferences": [{ "transaction": { "id": tx_id }, "index": 2 }]
+            }),
+        );
+        assert_eq!(
+            error.to_string(),
+            format!("additional UTXOs already in the ledger: {tx_id}#2")
+        );
+
+        let error = evaluate_error(3000, json!({ "incompatibleEra": "mary" }));
+        assert!(matches!(
+            error,
+            EvaluateTransactionError::IncompatibleEra(IncompatibleEra {
+                incompatible_era: crate::schema::Era::Mary
+            })
+        ));
+
+        let error = evaluate_error(3004, json!({ "reason": "Missing cost model for plutus:v3" }));
+        assert_eq!(
+            error.to_string(),
+            "cannot create the evaluation context: Missing cost model for plutus:v3"
+        );
+
+        let error = evaluate_error(
+            3117,
+            json!({ "unknownOutputReferences": [{ "transaction": { "id": tx_id }, "index": 0 }] }),
+        );
+        assert!(matches!(error, EvaluateTransactionError::UnknownOutputReferences(_)));
+
+        // Malformed script failures are kept whole.
+        let error = evaluate_error(3010, json!([{ "validator": "spend:0" }]));
+        assert!(matches!(error, EvaluateTransactionError::Other { code: 3010, .. }));
+    }
+}
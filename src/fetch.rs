@@ -0,0 +1,698 @@
+//! Fetching and verification of off-chain metadata.
+//!
+//! On-chain certificates and governance anchors only carry a URL and a
+//! Blake2b-256 hash of off-chain metadata. This module downloads such
+//! documents with a size cap and a timeout, and checks the hash before anything
+//! is parsed. `ipfs://` URLs are downloaded through an HTTP gateway,
+//! [`DEFAULT_IPFS_GATEWAY`] unless configured otherwise.
+//!
+//! Requires the `fetch` feature.
+
+use crate::connection::shared_http_client;
+use crate::error::{OgmiosError, Result};
+use crate::schema::{Anchor, PoolMetadata, StakePoolId, StakePoolView};
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use futures_util::{StreamExt, stream};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default maximum size of a stake pool metadata document (16 KiB).
+pub const DEFAULT_POOL_METADATA_MAX_BYTES: usize = 16 * 1024;
+
+/// Smallest size cap accepted for stake pool metadata (1 KiB).
+pub const MIN_POOL_METADATA_MAX_BYTES: usize = 1024;
+
+/// Default maximum size of a governance anchor document (512 KiB).
+///
+/// CIP-108 documents carry free-form rationale, so they are allowed to be
+/// much larger than pool metadata.
+pub const DEFAULT_ANCHOR_MAX_BYTES: usize = 512 * 1024;
+
+/// Default timeout for a single metadata download.
+pub const DEFAULT_FETCH_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Default gateway through which `ipfs://` URLs are downloaded.
+pub const DEFAULT_IPFS_GATEWAY: &str = "https://ipfs.io/ipfs/";
+
+/// Default number of concurrent downloads in [`PoolMetadataFetcher::fetch_all`].
+pub const DEFAULT_FETCH_CONCURRENCY: usize = 8;
+
+/// Error fetching or verifying off-chain metadata.
+#[derive(Error, Debug)]
+pub enum FetchError {
+    /// Transport-level failure (DNS, TLS, connection, timeout).
+    #[error("request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    /// The server answered with a non-success status.
+    #[error("unexpected HTTP status {0}")]
+    Status(u16),
+
+    /// The document exceeds the configured size cap.
+    #[error("document exceeds {limit} bytes")]
+    TooLarge {
+        /// Configured maximum size in bytes.
+        limit: usize,
+    },
+
+    /// The document does not hash to the value committed on-chain.
+    #[error("hash mismatch: expected {expected}, got {actual}")]
+    HashMismatch {
+        /// Hash committed on-chain.
+        expected: String,
+        /// Hash of the downloaded document.
+        actual: String,
+    },
+
+    /// The document is not valid metadata JSON.
+    #[error("invalid metadata: {0}")]
+    InvalidMetadata(#[from] serde_json::Error),
+}
+
+/// Limits applied to each download.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FetchLimits {
+    /// Maximum document size in bytes.
+    pub max_bytes: usize,
+    /// Timeout for the whole download.
+    pub timeout: Duration,
+}
+
+impl Default for FetchLimits {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_POOL_METADATA_MAX_BYTES,
+            timeout: DEFAULT_FETCH_TIMEOUT,
+        }
+    }
+}
+
+impl FetchLimits {
+    /// Limits for governance anchor documents.
+    pub fn anchor() -> Self {
+        Self {
+            max_bytes: DEFAULT_ANCHOR_MAX_BYTES,
+            timeout: DEFAULT_FETCH_TIMEOUT,
+        }
+    }
+}
+
+/// Stake pool metadata whose hash matched the on-chain commitment.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifiedPoolMetadata {
+    /// Pool name.
+    pub name: String,
+    /// Pool ticker.
+    pub ticker: String,
+    /// Pool description.
+    #[serde(default)]
+    pub description: Option<String>,
+    /// Pool homepage.
+    #[serde(default)]
+    pub homepage: Option<String>,
+    /// URL of extended metadata, if any (not fetched or verified).
+    #[serde(default)]
+    pub extended: Option<String>,
+}
+
+/// Governance anchor content whose hash matched the on-chain commitment.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerifiedAnchorContent {
+    /// The document as downloaded.
+    pub bytes: Vec<u8>,
+    /// Fields of a CIP-100/CIP-108 document, if the content is one.
+    pub metadata: Option<GovernanceMetadata>,
+}
+
+impl VerifiedAnchorContent {
+    /// The document as text, if it is UTF-8.
+    pub fn text(&self) -> Option<&str> {
+        std::str::from_utf8(&self.bytes).ok()
+    }
+}
+
+/// Fields of a CIP-100 governance metadata document, as extended by CIP-108.
+///
+/// Fields are read from the document `body`, either as plain strings or as
+/// JSON-LD `{ "@value": ... }` objects. Missing fields are `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GovernanceMetadata {
+    /// Title.
+    pub title: Option<String>,
+    /// Short summary.
+    pub r#abstract: Option<String>,
+    /// Problem the proposal addresses.
+    pub motivation: Option<String>,
+    /// Reasoning behind the proposal.
+    pub rationale: Option<String>,
+    /// Names of the authors. Author witnesses are not checked.
+    pub authors: Vec<String>,
+}
+
+impl GovernanceMetadata {
+    /// Extract the fields of a CIP-100 document.
+    ///
+    /// `None` if the bytes are not a JSON object with a `body` object.
+    pub fn from_json_ld(bytes: &[u8]) -> Option<Self> {
+        let document: serde_json::Value = serde_json::from_slice(bytes).ok()?;
+        let body = document.get("body")?.as_object()?;
+        let field = |name: &str| body.get(name).and_then(json_ld_string);
+        let authors = document
+            .get("authors")
+            .and_then(serde_json::Value::as_array)
+            .map(|authors| {
+                authors
+                    .iter()
+                    .filter_map(|author| author.get("name").and_then(json_ld_string))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Some(Self {
+            title: field("title"),
+            r#abstract: field("abstract"),
+            motivation: field("motivation"),
+            rationale: field("rationale"),
+            authors,
+        })
+    }
+}
+
+/// A string given as is or as a JSON-LD value object.
+fn json_ld_string(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(string) => Some(string.clone()),
+        serde_json::Value::Object(object) => object.get("@value").and_then(json_ld_string),
+        _ => None,
+    }
+}
+
+/// HTTP URL of a document through an IPFS gateway.
+///
+/// `ipfs://<cid>/<path>` becomes `<gateway>/<cid>/<path>`; other URLs are
+/// returned as is.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::fetch::ipfs_gateway_url;
+///
+/// assert_eq!(
+///     ipfs_gateway_url("ipfs://QmHash/doc.json", "https://ipfs.io/ipfs/"),
+///     "https://ipfs.io/ipfs/QmHash/doc.json"
+/// );
+/// assert_eq!(
+///     ipfs_gateway_url("https://example.com", "https://ipfs.io/ipfs/"),
+///     "https://example.com"
+/// );
+/// ```
+pub fn ipfs_gateway_url(url: &str, gateway: &str) -> String {
+    match url.strip_prefix("ipfs://") {
+        // Some tools write `ipfs://ipfs/<cid>`.
+        Some(path) => {
+            let path = path.strip_prefix("ipfs/").unwrap_or(path);
+            format!("{}/{}", gateway.trim_end_matches('/'), path)
+        }
+        None => url.to_string(),
+    }
+}
+
+impl Anchor {
+    /// Download the anchored document and check it against `content_hash`.
+    ///
+    /// `ipfs://` URLs are downloaded through [`DEFAULT_IPFS_GATEWAY`]; use
+    /// [`fetch_and_verify_via`](Self::fetch_and_verify_via) for another one.
+    ///
+    /// A document that downloads but does not match fails with
+    /// [`FetchError::HashMismatch`], separate from transport errors: it means
+    /// the content changed since it was committed on-chain. Matching content
+    /// is returned as is, along with its CIP-100/CIP-108 fields when it is
+    /// such a document.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ogmios_client::fetch::FetchLimits;
+    /// use ogmios_client::schema::Anchor;
+    ///
+    /// # async fn example(anchor: Anchor) -> ogmios_client::Result<()> {
+    /// let content = anchor.fetch_and_verify(FetchLimits::anchor()).await?;
+    /// match content.metadata.and_then(|metadata| metadata.title) {
+    ///     Some(title) => println!("{title}"),
+    ///     None => println!("{} bytes", content.bytes.len()),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn fetch_and_verify(&self, limits: FetchLimits) -> Result<VerifiedAnchorContent> {
+        self.fetch_and_verify_via(DEFAULT_IPFS_GATEWAY, limits).await
+    }
+
+    /// Same as [`fetch_and_verify`](Self::fetch_and_verify), downloading
+    /// `ipfs://` URLs through `ipfs_gateway`, e.g. a local node at
+    /// `http://127.0.0.1:8080/ipfs/`.
+    pub async fn fetch_and_verify_via(
+        &self,
+        ipfs_gateway: &str,
+        limits: FetchLimits,
+    ) -> Result<VerifiedAnchorContent> {
+        self.fetch_and_verify_with_client(shared_http_client(), ipfs_gateway, limits)
+            .await
+    }
+
+    /// Same as [`fetch_and_verify_via`](Self::fetch_and_verify_via), reusing an
+    /// existing HTTP client, e.g. one with proxy settings or shared with a
+    /// [`PoolMetadataFetcher`].
+    pub async fn fetch_and_verify_with_client(
+        &self,
+        client: &reqwest::Client,
+        ipfs_gateway: &str,
+        limits: FetchLimits,
+    ) -> Result<VerifiedAnchorContent> {
+        let url = ipfs_gateway_url(&self.url, ipfs_gateway);
+        let bytes = fetch_verified(client, &url, self.content_hash.as_str(), limits).await?;
+        Ok(VerifiedAnchorContent {
+            metadata: GovernanceMetadata::from_json_ld(&bytes),
+            bytes,
+        })
+    }
+}
+
+/// Downloads and verifies stake pool metadata.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::fetch::PoolMetadataFetcher;
+/// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+/// use ogmios_client::connection::ConnectionConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+/// let pools = client.stake_pools(None, false).await?;
+///
+/// let fetcher = PoolMetadataFetcher::new();
+/// for (id, metadata) in fetcher.fetch_all(&pools).await {
+///     match metadata {
+///         Ok(metadata) => println!("{}: [{}] {}", id, metadata.ticker, metadata.name),
+///         Err(e) => println!("{}: {}", id, e),
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct PoolMetadataFetcher {
+    client: reqwest::Client,
+    limits: FetchLimits,
+    concurrency: usize,
+    ipfs_gateway: String,
+}
+
+impl Default for PoolMetadataFetcher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PoolMetadataFetcher {
+    /// Create a fetcher with default limits.
+    pub fn new() -> Self {
+        Self::with_client(reqwest::Client::new())
+    }
+
+    /// Create a fetcher reusing an existing HTTP client.
+    pub fn with_client(client: reqwest::Client) -> Self {
+        Self {
+            client,
+            limits: FetchLimits::default(),
+            concurrency: DEFAULT_FETCH_CONCURRENCY,
+            ipfs_gateway: DEFAULT_IPFS_GATEWAY.to_string(),
+        }
+    }
+
+    /// Set the download limits.
+    ///
+    /// The size cap is clamped to between 1 KiB and 16 KiB.
+    pub fn with_limits(mut self, limits: FetchLimits) -> Self {
+        self.limits = FetchLimits {
+            max_bytes: limits
+                .max_bytes
+                .clamp(MIN_POOL_METADATA_MAX_BYTES, DEFAULT_POOL_METADATA_MAX_BYTES),
+            ..limits
+        };
+        self
+    }
+
+    /// Set the number of concurrent downloads in [`fetch_all`](Self::fetch_all).
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// Set the gateway through which `ipfs://` URLs are downloaded.
+    pub fn with_ipfs_gateway(mut self, gateway: impl Into<String>) -> Self {
+        self.ipfs_gateway = gateway.into();
+        self
+    }
+
+    /// Download, verify, and parse a pool's metadata.
+    pub async fn fetch(&self, metadata: &PoolMetadata) -> Result<VerifiedPoolMetadata> {
+        let url = ipfs_gateway_url(&metadata.url, &self.ipfs_gateway);
+        let bytes = fetch_verified(&self.client, &url, metadata.hash.as_str(), self.limits).await?;
+        Ok(serde_json::from_slice(&bytes).map_err(FetchError::from)?)
+    }
+
+    /// Fetch the metadata of every pool that declares some.
+    ///
+    /// Pools without metadata are skipped. Each pool gets its own result, so one
+    /// unreachable or tampered document doesn't fail the batch.
+    pub async fn fetch_all(
+        &self,
+        pools: &HashMap<StakePoolId, StakePoolView>,
+    ) -> HashMap<StakePoolId, Result<VerifiedPoolMetadata>> {
+        stream::iter(pools.iter().filter_map(|(id, pool)| {
+            let metadata = pool.parameters.metadata.as_ref()?;
+            Some(async move { (id.clone(), self.fetch(metadata).await) })
+        }))
+        .buffer_unordered(self.concurrency)
+        .collect()
+        .await
+    }
+}
+
+/// Download, verify, and parse stake pool metadata with default limits.
+pub async fn fetch_pool_metadata(metadata: &PoolMetadata) -> Result<VerifiedPoolMetadata> {
+    PoolMetadataFetcher::new().fetch(metadata).await
+}
+
+/// Download a document and check it against a hex Blake2b-256 hash.
+pub(crate) async fn fetch_verified(
+    client: &reqwest::Client,
+    url: &str,
+    expected_hash: &str,
+    limits: FetchLimits,
+) -> Result<Vec<u8>> {
+    let download = async {
+        let mut response = client.get(url).send().await.map_err(FetchError::from)?;
+        if !response.status().is_success() {
+            return Err(FetchError::Status(response.status().as_u16()));
+        }
+        if response
+            .content_length()
+            .is_some_and(|length| length > limits.max_bytes as u64)
+        {
+            return Err(FetchError::TooLarge {
+                limit: limits.max_bytes,
+            });
+        }
+
+        let mut bytes = Vec::new();
+        while let Some(chunk) = response.chunk().await? {
+            if bytes.len() + chunk.len() > limits.max_bytes {
+                return Err(FetchError::TooLarge {
+                    limit: limits.max_bytes,
+                });
+            }
+            bytes.extend_from_slice(&chunk);
+        }
+        Ok(bytes)
+    };
+
+    let bytes = tokio::time::timeout(limits.timeout, download)
+        .await
+        .map_err(|_| OgmiosError::Timeout {
+            timeout_ms: limits.timeout.as_millis() as u64,
+            method: None,
+            id: None,
+        })??;
+
+    let actual = blake2b_256_hex(&bytes);
+    if !actual.eq_ignore_ascii_case(expected_hash) {
+        return Err(FetchError::HashMismatch {
+            expected: expected_hash.to_string(),
+            actual,
+        }
+        .into());
+    }
+    Ok(bytes)
+}
+
+fn blake2b_256_hex(bytes: &[u8]) -> String {
+    crate::util::hex_encode(&Blake2b::<U32>::digest(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    const METADATA: &str = r#"{"name":"Test Pool","ticker":"TEST","description":"A pool","homepage":"https://example.com"}"#;
+
+    /// Serve `body` with status 200 to every HTTP request; returns the base URL.
+    async fn serve(body: String) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let _ = stream.read(&mut request).await;
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+        url
+    }
+
+    fn metadata(url: String, hash: String) -> PoolMetadata {
+        PoolMetadata {
+            url,
+            hash: hash.parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_verified_metadata() {
+        let url = serve(METADATA.to_string()).await;
+        let verified = fetch_pool_metadata(&metadata(url, blake2b_256_hex(METADATA.as_bytes())))
+            .await
+            .unwrap();
+        assert_eq!(verified.ticker, "TEST");
+        assert_eq!(verified.name, "Test Pool");
+        assert_eq!(verified.homepage.as_deref(), Some("https://example.com"));
+    }
+
+    #[tokio::test]
+    async fn test_hash_mismatch_is_typed() {
+        let url = serve(METADATA.to_string()).await;
+        let error = fetch_pool_metadata(&metadata(url, "00".repeat(32)))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::Fetch(FetchError::HashMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_oversized_payload_is_typed() {
+        let body = format!(
+            r#"{{"name":"Big","ticker":"BIG","description":"{}"}}"#,
+            "x".repeat(2048)
+        );
+        let hash = blake2b_256_hex(body.as_bytes());
+        let url = serve(body).await;
+        let fetcher = PoolMetadataFetcher::new().with_limits(FetchLimits {
+            max_bytes: 16,
+            ..Default::default()
+        });
+        let error = fetcher.fetch(&metadata(url, hash)).await.unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::Fetch(FetchError::TooLarge {
+                limit: MIN_POOL_METADATA_MAX_BYTES
+            })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fetch_all_skips_pools_without_metadata() {
+        let url = serve(METADATA.to_string()).await;
+        let pool = |id: &str, metadata: Option<PoolMetadata>| {
+            let view: StakePoolView = serde_json::from_value(serde_json::json!({
+                "id": id,
+                "vrf": "ab",
+                "pledge": { "lovelace": 0 },
+                "cost": { "lovelace": 0 },
+                "margin": "1/40",
+                "rewardAccount": "stake1u9",
+                "owners": [],
+                "metadata": metadata,
+            }))
+            .unwrap();
+            (id.to_string(), view)
+        };
+        let pools = HashMap::from([
+            pool(
+                "pool1a",
+                Some(metadata(url.clone(), blake2b_256_hex(METADATA.as_bytes()))),
+            ),
+            pool("pool1b", Some(metadata(url, "00".repeat(32)))),
+            pool("pool1c", None),
+        ]);
+
+        let results = PoolMetadataFetcher::new()
+            .with_concurrency(2)
+            .fetch_all(&pools)
+            .await;
+        assert_eq!(results.len(), 2);
+        assert_eq!(results["pool1a"].as_ref().unwrap().ticker, "TEST");
+        assert!(results["pool1b"].is_err());
+    }
+
+    /// An abridged CIP-108 document, with JSON-LD value objects as some tools
+    /// emit them.
+    const GOVERNANCE_METADATA: &str = r#"{
+        "@context": { "@language": "en-us" },
+        "hashAlgorithm": "blake2b-256",
+        "body": {
+            "title": "Treasury withdrawal for tooling",
+            "abstract": { "@value": "Fund open-source tooling." },
+            "motivation": "Tooling is underfunded.",
+            "references": []
+        },
+        "authors": [{ "name": "Alice", "witness": {} }, { "witness": {} }]
+    }"#;
+
+    fn anchor(url: String, body: &str) -> Anchor {
+        Anchor {
+            url,
+            content_hash: blake2b_256_hex(body.as_bytes()).parse().unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_anchor_fetch_and_verify() {
+        let url = serve(GOVERNANCE_METADATA.to_string()).await;
+        let content = anchor(url, GOVERNANCE_METADATA)
+            .fetch_and_verify(FetchLimits::anchor())
+            .await
+            .unwrap();
+        assert_eq!(content.text(), Some(GOVERNANCE_METADATA));
+        assert_eq!(
+            content.metadata,
+            Some(GovernanceMetadata {
+                title: Some("Treasury withdrawal for tooling".to_string()),
+                r#abstract: Some("Fund open-source tooling.".to_string()),
+                motivation: Some("Tooling is underfunded.".to_string()),
+                rationale: None,
+                authors: vec!["Alice".to_string()],
+            })
+        );
+
+        // Anything else is kept as raw bytes.
+        let url = serve("plain text".to_string()).await;
+        let content = anchor(url, "plain text")
+            .fetch_and_verify(FetchLimits::anchor())
+            .await
+            .unwrap();
+        assert_eq!(content.bytes, b"plain text");
+        assert_eq!(content.metadata, None);
+    }
+
+    #[test]
+    fn test_ipfs_gateway_url() {
+        let cid = "bafkreifnwj6zpu3ixa4siz2lndqybyc5wnnt3jkwyutci4e2tmbnj3xrdm";
+        assert_eq!(
+            ipfs_gateway_url(&format!("ipfs://{cid}"), DEFAULT_IPFS_GATEWAY),
+            format!("https://ipfs.io/ipfs/{cid}")
+        );
+        assert_eq!(
+            ipfs_gateway_url(&format!("ipfs://ipfs/{cid}/doc.json"), "http://127.0.0.1:8080/ipfs"),
+            format!("http://127.0.0.1:8080/ipfs/{cid}/doc.json")
+        );
+        assert_eq!(
+            ipfs_gateway_url("https://example.com/doc.json", DEFAULT_IPFS_GATEWAY),
+            "https://example.com/doc.json"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_anchor_fetch_through_ipfs_gateway() {
+        let gateway = format!("{}/ipfs/", serve(GOVERNANCE_METADATA.to_string()).await);
+        let content = anchor("ipfs://QmHash".to_string(), GOVERNANCE_METADATA)
+            .fetch_and_verify_via(&gateway, FetchLimits::anchor())
+            .await
+            .unwrap();
+        assert_eq!(content.text(), Some(GOVERNANCE_METADATA));
+
+        let gateway = format!("{}/ipfs/", serve(METADATA.to_string()).await);
+        let url = "ipfs://QmHash".to_string();
+        let verified = PoolMetadataFetcher::new()
+            .with_ipfs_gateway(gateway)
+            .fetch(&metadata(url, blake2b_256_hex(METADATA.as_bytes())))
+            .await
+            .unwrap();
+        assert_eq!(verified.ticker, "TEST");
+    }
+
+    #[tokio::test]
+    async fn test_anchor_fetch_with_client() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let (requests, mut received) = tokio::sync::mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let mut request = [0u8; 1024];
+                let n = stream.read(&mut request).await.unwrap_or(0);
+                let _ = requests.send(String::from_utf8_lossy(&request[..n]).to_lowercase());
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                    GOVERNANCE_METADATA.len(),
+                    GOVERNANCE_METADATA
+                );
+                let _ = stream.write_all(response.as_bytes()).await;
+            }
+        });
+
+        let client = reqwest::Client::builder()
+            .user_agent("anchor-test")
+            .build()
+            .unwrap();
+        let content = anchor(url, GOVERNANCE_METADATA)
+            .fetch_and_verify_with_client(&client, DEFAULT_IPFS_GATEWAY, FetchLimits::anchor())
+            .await
+            .unwrap();
+        assert_eq!(content.text(), Some(GOVERNANCE_METADATA));
+        assert!(received.recv().await.unwrap().contains("user-agent: anchor-test"));
+    }
+
+    #[tokio::test]
+    async fn test_anchor_hash_mismatch_is_typed() {
+        let url = serve(GOVERNANCE_METADATA.to_string()).await;
+        let error = anchor(url, "tampered")
+            .fetch_and_verify(FetchLimits::anchor())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::Fetch(FetchError::HashMismatch { .. })
+        ));
+    }
+
+    #[test]
+    fn test_blake2b_256() {
+        assert_eq!(
+            blake2b_256_hex(b""),
+            "0e5751c026e543b2e8ab2eb06099daa1d1e5df47778f7787faab45cdf12fe3a8"
+        );
+    }
+}
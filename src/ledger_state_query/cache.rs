@@ -0,0 +1,65 @@
+//! Protocol parameters cache with epoch-based invalidation.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{Epoch, ProtocolParameters};
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, Instant};
+
+use super::query;
+
+/// Caches protocol parameters for the epoch they were fetched in.
+///
+/// Protocol parameters only change at epoch boundaries, so a cached value is
+/// served as long as the current epoch matches the one it was fetched in. The
+/// epoch itself may be cached for `epoch_ttl`, trading up to that much
+/// staleness after a boundary for fewer round trips.
+#[derive(Debug)]
+pub(crate) struct ProtocolParametersCache {
+    epoch_ttl: Duration,
+    epoch: Mutex<Option<(Instant, Epoch)>>,
+    parameters: RwLock<Option<(Epoch, ProtocolParameters)>>,
+}
+
+impl ProtocolParametersCache {
+    pub(crate) fn new(epoch_ttl: Duration) -> Self {
+        Self {
+            epoch_ttl,
+            epoch: Mutex::new(None),
+            parameters: RwLock::new(None),
+        }
+    }
+
+    /// Get the protocol parameters, refreshing them if the epoch changed.
+    pub(crate) async fn get(&self, context: &InteractionContext) -> Result<ProtocolParameters> {
+        let epoch = self.epoch(context).await?;
+
+        if let Some((cached_epoch, parameters)) = self.parameters.read().unwrap().as_ref()
+            && *cached_epoch == epoch
+        {
+            return Ok(parameters.clone());
+        }
+
+        let parameters = query::protocol_parameters(context).await?;
+        *self.parameters.write().unwrap() = Some((epoch, parameters.clone()));
+        Ok(parameters)
+    }
+
+    /// Drop all cached values.
+    pub(crate) fn invalidate(&self) {
+        *self.epoch.lock().unwrap() = None;
+        *self.parameters.write().unwrap() = None;
+    }
+
+    async fn epoch(&self, context: &InteractionContext) -> Result<Epoch> {
+        if let Some((fetched_at, epoch)) = *self.epoch.lock().unwrap()
+            && fetched_at.elapsed() < self.epoch_ttl
+        {
+            return Ok(epoch);
+        }
+
+        let epoch = query::epoch(context).await?;
+        *self.epoch.lock().unwrap() = Some((Instant::now(), epoch));
+        Ok(epoch)
+    }
+}
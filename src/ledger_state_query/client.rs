@@ -1,27 +1,52 @@
 //! Ledger State Query client implementation.
 
 use crate::connection::{
-    create_interaction_context, ConnectionConfig, InteractionContext, InteractionContextOptions,
-    InteractionType,
+    create_interaction_context, with_request_timeout, ConnectionConfig, InteractionContext,
+    InteractionContextOptions, InteractionType,
 };
 use crate::error::Result;
 use crate::schema::{
     Address, BlockHeight, Epoch, EraStart, EraSummary, EraWithGenesis,
-    Constitution, GenesisConfiguration, GovernanceProposalState,
-    LiveStakeDistributionEntry, Point, ProjectedRewards, ProtocolParameters,
-    RewardAccount, RewardAccountSummary, Slot, StakeAddress, StakePoolId,
-    StakePoolPerformance, StakePoolView, Tip, TransactionOutputReference, UtcTime, Utxo,
+    Constitution, ConstitutionalCommittee, DelegateRepresentativeSummary, GenesisConfiguration,
+    GovernanceProposalState, LiveStakeDistributionEntry, Point, ProjectedRewards,
+    ProtocolParameters, RewardAccount, RewardAccountSummary, Slot, StakeAddress,
+    StakePoolId, StakePoolPerformance, StakePoolView, Tip,
+    TreasuryAndReserves, UtcTime, Utxo, error_codes,
 };
+use futures_util::Stream;
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
+use super::cache::ProtocolParametersCache;
+use super::drep::{self, DRepStakeDistribution};
+use super::governance::{self, GovernanceSnapshot, GovernanceSnapshotOptions};
+use super::pinned::PinnedLedgerState;
 use super::query::{self, *};
+use super::stake_pools::{self, StakePoolSummaries, StakePoolSummary, StakePoolsQuery};
+use super::summary::{self, ChainSummary};
+use super::time::SlotTimeInterpreter;
+use super::totals::{self, NetworkTotals};
+use super::utxo_map::{self, UtxoMap};
+use super::utxo_stream::{self, DEFAULT_UTXO_STREAM_BUFFER};
 
 /// Options for creating a ledger state query client.
 #[derive(Debug, Clone, Default)]
 pub struct LedgerStateQueryClientOptions {
     /// Automatically acquire ledger state at this point.
     pub point: Option<Point>,
+    /// Cache protocol parameters for the current epoch.
+    ///
+    /// See [`LedgerStateQueryClient::protocol_parameters_cached`].
+    pub cache_protocol_parameters: bool,
+    /// How long the current epoch is cached when checking protocol parameters
+    /// freshness. Zero (the default) queries the epoch on every call.
+    pub epoch_cache_ttl: Duration,
+    /// Retry [`genesis_configuration`](LedgerStateQueryClient::genesis_configuration)
+    /// with the pre-v6 `queryLedgerState/genesisConfiguration` method when the
+    /// server does not know `queryNetwork/genesisConfiguration`.
+    pub legacy_genesis_configuration: bool,
 }
 
 /// A ledger state query client for querying blockchain state.
@@ -51,16 +76,50 @@ pub struct LedgerStateQueryClientOptions {
 /// println!("Min fee coefficient: {}", params.min_fee_coefficient);
 ///
 /// // Query UTXOs for an address
-/// let utxos = client.utxo_by_addresses(vec!["addr_test1...".to_string()]).await?;
+/// let utxos = client.utxo_by_addresses(["addr_test1..."]).await?;
 /// println!("Found {} UTXOs", utxos.len());
 ///
 /// client.shutdown().await?;
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Concurrency
+///
+/// Responses are matched to requests by id, so queries on one client can run
+/// concurrently over the same connection and cost roughly one round trip in
+/// total. Use `tokio::try_join!` to stop at the first error (the remaining
+/// queries are dropped and their responses discarded), or `tokio::join!` to
+/// collect every result. Results may arrive in any order. Do not run queries
+/// concurrently with [`acquire_ledger_state`](Self::acquire_ledger_state) or
+/// [`release_ledger_state`](Self::release_ledger_state): which ledger state
+/// such a query is answered from is unspecified.
+///
+/// ```rust,no_run
+/// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+/// use ogmios_client::connection::ConnectionConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+/// let (epoch, eras, params, tip) = tokio::try_join!(
+///     client.epoch(),
+///     client.era_summaries(),
+///     client.protocol_parameters(),
+///     client.network_tip(),
+/// )?;
+/// # Ok(())
+/// # }
+/// ```
 pub struct LedgerStateQueryClient {
     /// The interaction context.
     context: Arc<InteractionContext>,
+    /// Protocol parameters cache, if enabled.
+    cache: Option<ProtocolParametersCache>,
+    /// Fall back to the legacy genesis configuration method.
+    legacy_genesis_configuration: bool,
+    /// Point acquired through this client, restored after helpers that
+    /// acquire and release a ledger state of their own.
+    pinned: Mutex<Option<Point>>,
 }
 
 impl LedgerStateQueryClient {
@@ -68,6 +127,9 @@ impl LedgerStateQueryClient {
     pub fn new(context: InteractionContext) -> Self {
         Self {
             context: Arc::new(context),
+            cache: None,
+            legacy_genesis_configuration: false,
+            pinned: Mutex::new(None),
         }
     }
 
@@ -88,31 +150,196 @@ impl LedgerStateQueryClient {
         })
         .await?;
 
-        let client = Self::new(context);
+        Self::with_shared(Arc::new(context), options).await
+    }
+
+    /// Create a ledger state query client on a context shared with other clients.
+    ///
+    /// Requests are correlated by id, so ledger state queries can interleave
+    /// with, say, transaction submissions on the same connection. Honors
+    /// `options.point` by acquiring the ledger state on the shared connection;
+    /// the acquired state then applies to every ledger state query made over
+    /// it. [`shutdown`](Self::shutdown) closes the connection for all users.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::sync::Arc;
+    /// use ogmios_client::connection::{create_interaction_context, InteractionContextOptions};
+    /// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+    /// use ogmios_client::transaction_submission::submit_transaction;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let context = Arc::new(create_interaction_context(InteractionContextOptions::default()).await?);
+    /// let client = LedgerStateQueryClient::with_shared(context.clone(), None).await?;
+    ///
+    /// let (tip, tx_id) = tokio::try_join!(
+    ///     client.network_tip(),
+    ///     submit_transaction(&context, "84a400..."),
+    /// )?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_shared(
+        context: Arc<InteractionContext>,
+        options: Option<LedgerStateQueryClientOptions>,
+    ) -> Result<Self> {
+        let options = options.unwrap_or_default();
+        let client = Self::shared(context, &options);
 
         // Optionally acquire ledger state at a specific point
-        if let Some(opts) = options {
-            if let Some(point) = opts.point {
-                client.acquire_ledger_state(Some(point)).await?;
-            }
+        if let Some(point) = options.point {
+            client.acquire_ledger_state(Some(point)).await?;
         }
 
         Ok(client)
     }
 
+    /// Create a client on a shared context, ignoring `options.point`.
+    pub(crate) fn shared(
+        context: Arc<InteractionContext>,
+        options: &LedgerStateQueryClientOptions,
+    ) -> Self {
+        Self {
+            context,
+            cache: options
+                .cache_protocol_parameters
+                .then(|| ProtocolParametersCache::new(options.epoch_cache_ttl)),
+            legacy_genesis_configuration: options.legacy_genesis_configuration,
+            pinned: Mutex::new(None),
+        }
+    }
+
+    /// Run queries with a timeout overriding the context default.
+    ///
+    /// Every request made by the closure's future is subject to `timeout`, so a
+    /// heavyweight query can be given more time (or a light one less) without
+    /// touching other calls on the client. Expiry yields
+    /// [`OgmiosError::Timeout`](crate::error::OgmiosError::Timeout) naming the
+    /// method. Streams, which run their requests in a background task, are not
+    /// affected.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use std::time::Duration;
+    /// use ogmios_client::ledger_state_query::{LedgerStateQueryClient, UtxoQuery};
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+    /// let utxos = client
+    ///     .with_timeout(Duration::from_secs(120), |c| c.utxo(UtxoQuery::WholeSet))
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn with_timeout<'a, F, Fut, T>(&'a self, timeout: Duration, f: F) -> Result<T>
+    where
+        F: FnOnce(&'a Self) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        with_request_timeout(timeout, f(self)).await
+    }
+
     /// Get a reference to the interaction context.
     pub fn context(&self) -> &InteractionContext {
         &self.context
     }
 
     /// Acquire a ledger state at a specific point.
+    ///
+    /// Invalidates the protocol parameters cache. A ledger state acquired at a
+    /// point is re-acquired after [`at_point`](Self::at_point) and the other
+    /// helpers that acquire their own; one acquired at the tip (`None`) is not.
     pub async fn acquire_ledger_state(&self, point: Option<Point>) -> Result<Slot> {
-        query::acquire_ledger_state(&self.context, point).await
+        self.invalidate_cache();
+        let slot = query::acquire_ledger_state(&self.context, point.clone()).await?;
+        *self.pinned.lock().unwrap() = point;
+        Ok(slot)
     }
 
     /// Release the acquired ledger state.
+    ///
+    /// Invalidates the protocol parameters cache.
     pub async fn release_ledger_state(&self) -> Result<()> {
-        query::release_ledger_state(&self.context).await
+        self.invalidate_cache();
+        query::release_ledger_state(&self.context).await?;
+        *self.pinned.lock().unwrap() = None;
+        Ok(())
+    }
+
+    /// Re-acquire the point pinned through this client, once a helper has
+    /// released the ledger state it acquired.
+    async fn restore_pinned_point(&self) -> Result<()> {
+        let point = self.pinned.lock().unwrap().clone();
+        if let Some(point) = point {
+            query::acquire_ledger_state(&self.context, Some(point)).await?;
+        }
+        Ok(())
+    }
+
+    /// Run several queries against the ledger state at a single point.
+    ///
+    /// Acquires the ledger state at `point`, hands a [`PinnedLedgerState`] to the
+    /// closure, and releases the ledger state afterward, whether the closure
+    /// succeeded or not. Every query made through the handle is answered from the
+    /// same snapshot. A point acquired earlier through this client, for instance
+    /// with `options.point`, is re-acquired once the closure is done.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+    /// let tip = client.ledger_tip().await?;
+    ///
+    /// let (epoch, params) = client
+    ///     .at_point(tip, |q| async move {
+    ///         Ok((q.epoch().await?, q.protocol_parameters().await?))
+    ///     })
+    ///     .await?;
+    /// println!("Epoch {}: min fee coefficient {}", epoch, params.min_fee_coefficient);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn at_point<F, Fut, T>(&self, point: Point, f: F) -> Result<T>
+    where
+        F: FnOnce(PinnedLedgerState) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        self.invalidate_cache();
+        let slot = query::acquire_ledger_state(&self.context, Some(point)).await?;
+
+        let result = f(PinnedLedgerState::new(self.context.clone(), slot)).await;
+        let released = match query::release_ledger_state(&self.context).await {
+            Ok(()) => self.restore_pinned_point().await,
+            err => err,
+        };
+
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+
+    /// Read the epoch, ledger tip and protocol parameters from one ledger state.
+    ///
+    /// The three values are mutually consistent even across an epoch boundary.
+    /// Acquires and releases the ledger state, so it invalidates the protocol
+    /// parameters cache and must not run concurrently with other queries on
+    /// this client. A point acquired earlier through this client is re-acquired
+    /// afterward.
+    pub async fn chain_summary(&self) -> Result<ChainSummary> {
+        self.invalidate_cache();
+        let summary = summary::chain_summary(&self.context).await;
+        let restored = self.restore_pinned_point().await;
+
+        let summary = summary?;
+        restored?;
+        Ok(summary)
     }
 
     /// Query the current constitution.
@@ -120,6 +347,22 @@ impl LedgerStateQueryClient {
         query::constitution(&self.context).await
     }
 
+    /// Query the current constitutional committee.
+    pub async fn constitutional_committee(&self) -> Result<ConstitutionalCommittee> {
+        query::constitutional_committee(&self.context).await
+    }
+
+    /// Query registered delegate representatives.
+    pub async fn delegate_representatives(&self) -> Result<Vec<DelegateRepresentativeSummary>> {
+        query::delegate_representatives(&self.context).await
+    }
+
+    /// Query the stake delegated to each DRep, including the always-abstain and
+    /// always-no-confidence options.
+    pub async fn drep_stake_distribution(&self) -> Result<DRepStakeDistribution> {
+        drep::drep_stake_distribution(&self.context).await
+    }
+
     /// Query the current epoch.
     pub async fn epoch(&self) -> Result<Epoch> {
         query::epoch(&self.context).await
@@ -136,8 +379,19 @@ impl LedgerStateQueryClient {
     }
 
     /// Query genesis configuration for a specific era.
+    ///
+    /// Uses `queryNetwork/genesisConfiguration`, falling back to the legacy
+    /// method if enabled in [`LedgerStateQueryClientOptions`].
     pub async fn genesis_configuration(&self, era: EraWithGenesis) -> Result<GenesisConfiguration> {
-        query::genesis_configuration(&self.context, era).await
+        match query::genesis_configuration(&self.context, era).await {
+            Err(e)
+                if self.legacy_genesis_configuration
+                    && e.code() == Some(error_codes::METHOD_NOT_FOUND) =>
+            {
+                query::genesis_configuration_legacy(&self.context, era).await
+            }
+            result => result,
+        }
     }
 
     /// Query governance proposals.
@@ -148,6 +402,52 @@ impl LedgerStateQueryClient {
         query::governance_proposals(&self.context, filter).await
     }
 
+    /// Take a consistent snapshot of the governance state at the volatile tip.
+    ///
+    /// Acquires the ledger state, queries proposals, DReps, the constitutional
+    /// committee and the constitution concurrently, then releases it. Fails if
+    /// any section fails; see
+    /// [`governance_snapshot_with_options`](Self::governance_snapshot_with_options)
+    /// to keep partial results. Invalidates the protocol parameters cache, and
+    /// must not run concurrently with other queries on this client. A point
+    /// acquired earlier through this client is re-acquired afterward.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+    /// let snapshot = client.governance_snapshot().await?;
+    /// println!(
+    ///     "{} proposals at slot {}",
+    ///     snapshot.proposals?.len(),
+    ///     snapshot.slot
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn governance_snapshot(&self) -> Result<GovernanceSnapshot> {
+        self.governance_snapshot_with_options(GovernanceSnapshotOptions::default())
+            .await
+    }
+
+    /// Take a governance snapshot with the given options.
+    pub async fn governance_snapshot_with_options(
+        &self,
+        options: GovernanceSnapshotOptions,
+    ) -> Result<GovernanceSnapshot> {
+        self.invalidate_cache();
+        let snapshot = governance::governance_snapshot(&self.context, options).await;
+        let restored = self.restore_pinned_point().await;
+
+        let snapshot = snapshot?;
+        restored?;
+        Ok(snapshot)
+    }
+
     /// Query the ledger tip.
     pub async fn ledger_tip(&self) -> Result<Point> {
         query::ledger_tip(&self.context).await
@@ -170,21 +470,52 @@ impl LedgerStateQueryClient {
         query::live_stake_distribution(&self.context).await
     }
 
+    /// Query the network-wide ada totals: live stake, circulating supply,
+    /// treasury and reserves.
+    ///
+    /// See [`NetworkTotals`] for the formulas.
+    pub async fn network_totals(&self) -> Result<NetworkTotals> {
+        totals::network_totals(&self.context).await
+    }
+
     /// Query the network start time.
     pub async fn network_start_time(&self) -> Result<UtcTime> {
         query::network_start_time(&self.context).await
     }
 
-    /// Query projected rewards.
+    /// Build a slot/time interpreter from the era summaries and network start time.
+    ///
+    /// Both are fetched once; the returned interpreter does no further I/O. Its
+    /// horizon only moves forward as the chain grows, so long-running services
+    /// should rebuild it periodically.
+    pub async fn time_interpreter(&self) -> Result<SlotTimeInterpreter> {
+        let eras = self.era_summaries().await?;
+        let start_time = self.network_start_time().await?;
+        SlotTimeInterpreter::new(eras, start_time)
+    }
+
+    /// Query projected rewards for stake key credentials or stake addresses.
+    ///
+    /// Script-based stake addresses are rejected, see
+    /// [`RewardAccountSummariesFilter::keys`].
     pub async fn projected_rewards(
         &self,
         stake_addresses: Vec<StakeAddress>,
-    ) -> Result<Vec<ProjectedRewards>> {
-        query::projected_rewards(
-            &self.context,
-            ProjectedRewardsFilter { stake_addresses },
-        )
-        .await
+    ) -> Result<ProjectedRewards> {
+        self.projected_rewards_by(ProjectedRewardsFilter::keys(stake_addresses))
+            .await
+    }
+
+    /// Query projected rewards for any combination of stake amounts and
+    /// credentials.
+    ///
+    /// Build the filter with [`ProjectedRewardsFilter::stake`] for amounts, or
+    /// [`ProjectedRewardsFilter::credentials`] for stake credentials.
+    pub async fn projected_rewards_by(
+        &self,
+        filter: ProjectedRewardsFilter,
+    ) -> Result<ProjectedRewards> {
+        query::projected_rewards(&self.context, filter).await
     }
 
     /// Query protocol parameters.
@@ -192,12 +523,49 @@ impl LedgerStateQueryClient {
         query::protocol_parameters(&self.context).await
     }
 
-    /// Query reward account summaries.
+    /// Query protocol parameters, served from the cache while the epoch is unchanged.
+    ///
+    /// Each call costs one `epoch` query (unless cached per
+    /// [`LedgerStateQueryClientOptions::epoch_cache_ttl`]); the parameters are
+    /// only re-fetched when the epoch differs from the cached one. Without
+    /// [`LedgerStateQueryClientOptions::cache_protocol_parameters`] this is the
+    /// same as [`protocol_parameters`](Self::protocol_parameters).
+    pub async fn protocol_parameters_cached(&self) -> Result<ProtocolParameters> {
+        match &self.cache {
+            Some(cache) => cache.get(&self.context).await,
+            None => self.protocol_parameters().await,
+        }
+    }
+
+    /// Drop cached protocol parameters and epoch, if caching is enabled.
+    pub fn invalidate_cache(&self) {
+        if let Some(cache) = &self.cache {
+            cache.invalidate();
+        }
+    }
+
+    /// Query reward account summaries by stake key credentials or stake addresses.
+    ///
+    /// Script-based stake addresses are rejected, see
+    /// [`RewardAccountSummariesFilter::keys`]. The result is keyed by
+    /// credential; see [`query::reward_account_summaries`].
     pub async fn reward_account_summaries(
         &self,
         keys: Vec<StakeAddress>,
     ) -> Result<HashMap<RewardAccount, RewardAccountSummary>> {
-        query::reward_account_summaries(&self.context, RewardAccountSummariesFilter { keys }).await
+        self.reward_account_summaries_by(RewardAccountSummariesFilter::keys(keys))
+            .await
+    }
+
+    /// Query reward account summaries by key and/or script credentials.
+    ///
+    /// Build the filter from stake credentials with
+    /// [`RewardAccountSummariesFilter::credentials`].
+    pub async fn reward_account_summaries_by(
+        &self,
+        filter: RewardAccountSummariesFilter,
+    ) -> Result<HashMap<RewardAccount, RewardAccountSummary>> {
+        query::reward_account_summaries(&self.context, filter).await
     }
 
     /// Query stake pools.
@@ -209,6 +577,18 @@ impl LedgerStateQueryClient {
         query::stake_pools(&self.context, filter, include_stake).await
     }
 
+    /// Query stake pools, returning summaries with convenience accessors.
+    pub async fn stake_pool_summaries(&self, query: StakePoolsQuery) -> Result<StakePoolSummaries> {
+        stake_pools::stake_pool_summaries(&self.context, query).await
+    }
+
+    /// Query a single stake pool, including its live stake.
+    ///
+    /// Returns `None` if the pool is not registered.
+    pub async fn stake_pool(&self, id: impl Into<StakePoolId>) -> Result<Option<StakePoolSummary>> {
+        stake_pools::stake_pool(&self.context, id).await
+    }
+
     /// Query stake pool performances.
     pub async fn stake_pools_performances(
         &self,
@@ -216,20 +596,89 @@ impl LedgerStateQueryClient {
         query::stake_pools_performances(&self.context).await
     }
 
-    /// Query UTXOs.
-    pub async fn utxo(&self, filter: Option<UtxoFilter>) -> Result<Vec<Utxo>> {
-        query::utxo(&self.context, filter).await
+    /// Query the ada held in the treasury and the reserves.
+    pub async fn treasury_and_reserves(&self) -> Result<TreasuryAndReserves> {
+        query::treasury_and_reserves(&self.context).await
+    }
+
+    /// Query the UTXOs selected by `query`.
+    ///
+    /// See [`UtxoQuery::WholeSet`] before querying the entire UTXO set. A
+    /// deprecated [`UtxoFilter`], or `Option<UtxoFilter>`, is still accepted;
+    /// one setting both criteria fails with
+    /// [`OgmiosError::QueryError`](crate::error::OgmiosError::QueryError)
+    /// before anything is sent.
+    pub async fn utxo(&self, query: impl UtxoQueryInput) -> Result<Vec<Utxo>> {
+        query::utxo(&self.context, query).await
+    }
+
+    /// Query UTXOs, keyed by output reference.
+    ///
+    /// Fails if the response lists an output reference twice.
+    pub async fn utxo_map(&self, query: UtxoQuery) -> Result<UtxoMap> {
+        utxo_map::utxo_map(&self.context, query).await
+    }
+
+    /// Query UTXOs, yielding them one by one as the response is decoded.
+    ///
+    /// Use this instead of [`utxo`](Self::utxo) for very large results, such as
+    /// the UTXOs of a popular script address: decoding happens on a blocking
+    /// thread and at most [`DEFAULT_UTXO_STREAM_BUFFER`] decoded UTXOs are held
+    /// ahead of the consumer. A JSON-RPC or decoding error ends the stream with
+    /// an `Err` item.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use ogmios_client::ledger_state_query::{LedgerStateQueryClient, UtxoQuery};
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+    /// let query = UtxoQuery::ByAddresses(vec!["addr1...".into()]);
+    /// let mut utxos = Box::pin(client.utxo_stream(query));
+    /// while let Some(utxo) = utxos.next().await {
+    ///     println!("{:?}", utxo?.transaction);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn utxo_stream(&self, query: UtxoQuery) -> impl Stream<Item = Result<Utxo>> + use<> {
+        self.utxo_stream_with_buffer(query, DEFAULT_UTXO_STREAM_BUFFER)
+    }
+
+    /// Query UTXOs as a stream, holding at most `buffer` decoded UTXOs.
+    ///
+    /// See [`utxo_stream`](Self::utxo_stream).
+    pub fn utxo_stream_with_buffer(
+        &self,
+        query: UtxoQuery,
+        buffer: usize,
+    ) -> impl Stream<Item = Result<Utxo>> + use<> {
+        utxo_stream::utxo_stream(self.context.clone(), query, buffer)
     }
 
     /// Query UTXOs by addresses.
-    pub async fn utxo_by_addresses(&self, addresses: Vec<Address>) -> Result<Vec<Utxo>> {
+    ///
+    /// Accepts strings as well as [`Address`]es. With the `address` feature,
+    /// malformed addresses fail client-side with
+    /// [`OgmiosError::InvalidAddress`](crate::error::OgmiosError::InvalidAddress).
+    pub async fn utxo_by_addresses(
+        &self,
+        addresses: impl IntoIterator<Item = impl Into<Address>>,
+    ) -> Result<Vec<Utxo>> {
         query::utxo_by_addresses(&self.context, addresses).await
     }
 
     /// Query UTXOs by output references.
+    ///
+    /// References can be given as
+    /// [`TransactionOutputReference`](crate::schema::TransactionOutputReference)s or in
+    /// the `<transaction id>#<index>` form.
     pub async fn utxo_by_output_references(
         &self,
-        output_references: Vec<TransactionOutputReference>,
+        output_references: impl IntoIterator<Item = impl OutputReferenceInput>,
     ) -> Result<Vec<Utxo>> {
         query::utxo_by_output_references(&self.context, output_references).await
     }
@@ -249,3 +698,483 @@ pub async fn create_ledger_state_query_client(
 ) -> Result<LedgerStateQueryClient> {
     LedgerStateQueryClient::connect(connection, options).await
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AcquireFailure, OgmiosError};
+    use crate::mock_server::{MockReply, MockServer};
+    use crate::schema::{DigestBlake2b256, TransactionId};
+    use serde_json::json;
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    async fn mock_server() -> MockServer {
+        MockServer::start(|method, _| match method {
+            "acquireLedgerState" => MockReply::result(json!({ "acquired": "ledgerState", "slot": 100 })),
+            "releaseLedgerState" => MockReply::result(json!({ "released": "ledgerState" })),
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_at_point_brackets_queries() {
+        let server = mock_server().await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        let (slot, epoch) = client
+            .at_point(Point::at(100, DigestBlake2b256::filled("ab")), |q| async move {
+                Ok((q.slot(), q.epoch().await?))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!((slot, epoch), (100, 42));
+        assert_eq!(
+            server.methods(),
+            vec![
+                "acquireLedgerState",
+                "queryLedgerState/epoch",
+                "releaseLedgerState"
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_at_point_releases_on_failure() {
+        let server = mock_server().await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        let result: Result<()> = client
+            .at_point(Point::at(100, DigestBlake2b256::filled("ab")), |q| async move {
+                q.constitution().await?;
+                Ok(())
+            })
+            .await;
+
+        assert!(matches!(result, Err(OgmiosError::Rpc { code: -32601, .. })));
+        assert_eq!(server.methods().last().unwrap(), "releaseLedgerState");
+    }
+
+    #[tokio::test]
+    async fn test_at_point_restores_pinned_point() {
+        let server = mock_server().await;
+        let pinned = Point::at(90, DigestBlake2b256::filled("cd"));
+        let options = LedgerStateQueryClientOptions {
+            point: Some(pinned.clone()),
+            ..Default::default()
+        };
+        let client = LedgerStateQueryClient::connect(server.config(), Some(options))
+            .await
+            .unwrap();
+
+        client
+            .at_point(Point::at(100, DigestBlake2b256::filled("ab")), |q| async move {
+                q.epoch().await
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            server.methods(),
+            vec![
+                "acquireLedgerState",
+                "acquireLedgerState",
+                "queryLedgerState/epoch",
+                "releaseLedgerState",
+                "acquireLedgerState"
+            ]
+        );
+        let requests = server.requests();
+        let restored = &requests.last().unwrap()["params"]["point"];
+        assert_eq!(restored["slot"], 90);
+
+        client.release_ledger_state().await.unwrap();
+        client
+            .at_point(Point::at(100, DigestBlake2b256::filled("ab")), |q| async move {
+                q.epoch().await
+            })
+            .await
+            .unwrap();
+        assert_eq!(server.methods().last().unwrap(), "releaseLedgerState");
+    }
+
+    fn protocol_parameters() -> serde_json::Value {
+        let ada = |lovelace: u64| json!({ "lovelace": lovelace });
+        let ratio = |numerator: u64, denominator: u64| {
+            json!({ "numerator": numerator, "denominator": denominator })
+        };
+        json!({
+            "minFeeCoefficient": 44,
+            "minFeeConstant": ada(155381),
+            "maxBlockBodySize": { "bytes": 90112 },
+            "maxBlockHeaderSize": { "bytes": 1100 },
+            "maxTransactionSize": { "bytes": 16384 },
+            "stakeCredentialDeposit": ada(2000000),
+            "stakePoolDeposit": ada(500000000),
+            "stakePoolRetirementEpochBound": 18,
+            "desiredNumberOfStakePools": 500,
+            "stakePoolPledgeInfluence": ratio(3, 10),
+            "monetaryExpansion": ratio(3, 1000),
+            "treasuryExpansion": ratio(1, 5),
+            "version": { "major": 9, "minor": 0 },
+            "minStakePoolCost": ada(170000000)
+        })
+    }
+
+    #[tokio::test]
+    async fn test_chain_summary_acquires_once() {
+        let server = MockServer::start(|method, _| match method {
+            "acquireLedgerState" => MockReply::result(json!({ "acquired": "ledgerState", "slot": 100 })),
+            "releaseLedgerState" => MockReply::result(json!({ "released": "ledgerState" })),
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            "queryLedgerState/tip" => MockReply::result(json!({ "slot": 100, "id": "ab".repeat(32) })),
+            "queryLedgerState/protocolParameters" => MockReply::result(protocol_parameters()),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        let summary = client.chain_summary().await.unwrap();
+        assert_eq!(summary.acquired_at, 100);
+        assert_eq!(summary.epoch, 42);
+        assert_eq!(summary.tip, Point::at(100, DigestBlake2b256::filled("ab")));
+        assert_eq!(summary.protocol_parameters.min_fee_coefficient, 44);
+        assert_eq!(server.count("acquireLedgerState"), 1);
+        assert_eq!(server.methods().last().unwrap(), "releaseLedgerState");
+    }
+
+    #[tokio::test]
+    async fn test_chain_summary_releases_on_failure() {
+        let server = mock_server().await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        assert!(client.chain_summary().await.is_err());
+        assert_eq!(server.count("acquireLedgerState"), 1);
+        assert_eq!(server.count("releaseLedgerState"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_chain_summary_restores_pinned_point() {
+        let server = mock_server().await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+        let pinned = Point::at(90, DigestBlake2b256::filled("cd"));
+        client.acquire_ledger_state(Some(pinned)).await.unwrap();
+
+        assert!(client.chain_summary().await.is_err());
+        assert_eq!(server.methods().last().unwrap(), "acquireLedgerState");
+        assert_eq!(server.requests().last().unwrap()["params"]["point"]["slot"], 90);
+    }
+
+    #[tokio::test]
+    async fn test_governance_snapshot_restores_pinned_point() {
+        let server = mock_server().await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+        let pinned = Point::at(90, DigestBlake2b256::filled("cd"));
+        client.acquire_ledger_state(Some(pinned)).await.unwrap();
+
+        assert!(client.governance_snapshot().await.is_err());
+        assert_eq!(server.count("releaseLedgerState"), 1);
+        assert_eq!(server.methods().last().unwrap(), "acquireLedgerState");
+        assert_eq!(server.requests().last().unwrap()["params"]["point"]["slot"], 90);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_parameters_cached_per_epoch() {
+        let epoch = Arc::new(AtomicU64::new(42));
+        let current = epoch.clone();
+        let server = MockServer::start(move |method, _| match method {
+            "queryLedgerState/epoch" => MockReply::result(json!(current.load(Ordering::SeqCst))),
+            "queryLedgerState/protocolParameters" => MockReply::result(protocol_parameters()),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let options = LedgerStateQueryClientOptions {
+            cache_protocol_parameters: true,
+            ..Default::default()
+        };
+        let client = LedgerStateQueryClient::connect(server.config(), Some(options))
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            let params = client.protocol_parameters_cached().await.unwrap();
+            assert_eq!(params.min_fee_coefficient, 44);
+        }
+        assert_eq!(server.count("queryLedgerState/protocolParameters"), 1);
+        assert_eq!(server.count("queryLedgerState/epoch"), 3);
+
+        epoch.store(43, Ordering::SeqCst);
+        client.protocol_parameters_cached().await.unwrap();
+        assert_eq!(server.count("queryLedgerState/protocolParameters"), 2);
+
+        client.invalidate_cache();
+        client.protocol_parameters_cached().await.unwrap();
+        assert_eq!(server.count("queryLedgerState/protocolParameters"), 3);
+    }
+
+    #[tokio::test]
+    async fn test_epoch_cache_ttl() {
+        let server = MockServer::start(|method, _| match method {
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            "queryLedgerState/protocolParameters" => MockReply::result(protocol_parameters()),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let options = LedgerStateQueryClientOptions {
+            cache_protocol_parameters: true,
+            epoch_cache_ttl: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let client = LedgerStateQueryClient::connect(server.config(), Some(options))
+            .await
+            .unwrap();
+
+        for _ in 0..3 {
+            client.protocol_parameters_cached().await.unwrap();
+        }
+        assert_eq!(server.count("queryLedgerState/epoch"), 1);
+        assert_eq!(server.count("queryLedgerState/protocolParameters"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_protocol_parameters_cached_disabled() {
+        let server = MockServer::start(|method, _| match method {
+            "queryLedgerState/protocolParameters" => MockReply::result(protocol_parameters()),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        client.protocol_parameters_cached().await.unwrap();
+        client.protocol_parameters_cached().await.unwrap();
+        assert_eq!(server.count("queryLedgerState/protocolParameters"), 2);
+        assert_eq!(server.count("queryLedgerState/epoch"), 0);
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_queries_take_one_round_trip() {
+        let delay = Duration::from_millis(200);
+        let server = MockServer::start(move |method, _| {
+            let reply = match method {
+                "queryLedgerState/epoch" => MockReply::result(json!(42)),
+                "queryLedgerState/eraSummaries" => MockReply::result(json!([])),
+                "queryLedgerState/protocolParameters" => MockReply::result(protocol_parameters()),
+                "queryNetwork/tip" => MockReply::result(
+                    json!({ "slot": 100, "id": "ab".repeat(32), "height": 7 }),
+                ),
+                _ => MockReply::error(-32601, "Method not found"),
+            };
+            reply.delayed(delay)
+        })
+        .await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        let started = std::time::Instant::now();
+        let (epoch, eras, params, tip) = tokio::try_join!(
+            client.epoch(),
+            client.era_summaries(),
+            client.protocol_parameters(),
+            client.network_tip(),
+        )
+        .unwrap();
+
+        assert!(started.elapsed() < delay * 2, "took {:?}", started.elapsed());
+        assert_eq!(epoch, 42);
+        assert!(eras.is_empty());
+        assert_eq!(params.min_fee_coefficient, 44);
+        assert!(matches!(tip, Tip::Tip { slot: 100, height: 7, .. }));
+    }
+
+    #[tokio::test]
+    async fn test_connect_reports_typed_acquire_failure() {
+        let server = MockServer::start(|method, _| match method {
+            "acquireLedgerState" => MockReply::Error {
+                code: 2000,
+                message: "Failed to acquire requested point.".to_string(),
+                data: Some(json!("pointTooOld")),
+            },
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let options = LedgerStateQueryClientOptions {
+            point: Some(Point::at(1, DigestBlake2b256::filled("ab"))),
+            ..Default::default()
+        };
+
+        let result = LedgerStateQueryClient::connect(server.config(), Some(options)).await;
+        assert!(matches!(
+            result,
+            Err(OgmiosError::Acquisition {
+                failure: AcquireFailure::PointTooOld,
+                point: Some(point),
+            }) if point == Point::at(1, DigestBlake2b256::filled("ab"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_ledger_tip_origin_and_point() {
+        let tips = [json!("origin"), json!({ "slot": 100, "id": "ab".repeat(32) })];
+        let calls = AtomicU64::new(0);
+        let server = MockServer::start(move |_, _| {
+            MockReply::result(tips[calls.fetch_add(1, Ordering::SeqCst) as usize].clone())
+        })
+        .await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        assert!(client.ledger_tip().await.unwrap().is_origin());
+        assert_eq!(client.ledger_tip().await.unwrap(), Point::at(100, DigestBlake2b256::filled("ab")));
+    }
+
+    #[tokio::test]
+    async fn test_with_timeout_overrides_context_default() {
+        let server = MockServer::start(|method, _| match method {
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            "queryLedgerState/utxo" => MockReply::result(json!([])).delayed(Duration::from_secs(5)),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+        let timeout = Duration::from_millis(100);
+
+        let epoch = client.with_timeout(timeout, |c| c.epoch()).await.unwrap();
+        assert_eq!(epoch, 42);
+
+        let error = client
+            .with_timeout(timeout, |c| c.utxo(UtxoQuery::WholeSet))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            &error,
+            OgmiosError::Timeout { timeout_ms: 100, method: Some(method), .. }
+                if method == "queryLedgerState/utxo"
+        ));
+        assert_eq!(
+            error.to_string(),
+            "Request timed out after 100ms (queryLedgerState/utxo, id 2)"
+        );
+
+        // The connection stays usable after a timed-out request.
+        assert_eq!(client.epoch().await.unwrap(), 42);
+    }
+
+    #[tokio::test]
+    async fn test_shared_context_with_submission() {
+        let server = MockServer::start(|method, _| match method {
+            "acquireLedgerState" => MockReply::result(json!({ "acquired": "ledgerState", "slot": 100 }))
+                .delayed(Duration::from_millis(50)),
+            "queryLedgerState/epoch" => MockReply::result(json!(42)).delayed(Duration::from_millis(200)),
+            "submitTransaction" => MockReply::result(json!({ "transaction": { "id": "ab".repeat(32) } })),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let context = Arc::new(server.context().await);
+        let options = LedgerStateQueryClientOptions {
+            point: Some(Point::at(100, DigestBlake2b256::filled("ab"))),
+            ..Default::default()
+        };
+        let client = LedgerStateQueryClient::with_shared(context.clone(), Some(options))
+            .await
+            .unwrap();
+        assert_eq!(server.count("acquireLedgerState"), 1);
+
+        // The submission is answered while the query is still pending.
+        let (epoch, tx_id) = tokio::try_join!(
+            client.epoch(),
+            crate::transaction_submission::submit_transaction(&context, "84a400"),
+        )
+        .unwrap();
+        assert_eq!((epoch, tx_id), (42, TransactionId::filled("ab")));
+        assert_eq!(server.accepts(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_genesis_configuration_method_and_fallback() {
+        let server = MockServer::start(|method, params| match method {
+            "queryLedgerState/genesisConfiguration" => MockReply::result(json!({
+                "era": params["era"],
+                "updatableParameters": {
+                    "collateralPercentage": 150,
+                    "plutusCostModels": {},
+                    "maxCollateralInputs": 3,
+                    "maxExecutionUnitsPerBlock": { "memory": 1, "cpu": 1 },
+                    "maxExecutionUnitsPerTransaction": { "memory": 1, "cpu": 1 },
+                    "maxValueSize": { "bytes": 5000 },
+                    "scriptExecutionPrices": { "memory": "1/2", "cpu": "1/2" }
+                }
+            })),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+
+        let client = LedgerStateQueryClient::new(server.context().await);
+        let error = client.genesis_configuration(EraWithGenesis::Alonzo).await.unwrap_err();
+        assert_eq!(error.code(), Some(error_codes::METHOD_NOT_FOUND));
+        assert_eq!(server.methods(), vec!["queryNetwork/genesisConfiguration"]);
+
+        let options = LedgerStateQueryClientOptions {
+            legacy_genesis_configuration: true,
+            ..Default::default()
+        };
+        let client = LedgerStateQueryClient::connect(server.config(), Some(options))
+            .await
+            .unwrap();
+        let genesis = client.genesis_configuration(EraWithGenesis::Alonzo).await.unwrap();
+        assert!(matches!(genesis, GenesisConfiguration::Alonzo(_)));
+        assert_eq!(server.count("queryLedgerState/genesisConfiguration"), 1);
+    }
+
+    #[tokio::test]
+    #[allow(deprecated)]
+    async fn test_utxo_accepts_legacy_filters() {
+        let server = MockServer::start(|_, _| MockReply::result(json!([]))).await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        assert!(client.utxo(None).await.unwrap().is_empty());
+        let reference = crate::schema::TransactionOutputReference::new(TransactionId::filled("ab"), 1);
+        let filter = UtxoFilter {
+            output_references: Some(vec![reference.clone()]),
+            ..Default::default()
+        };
+        client.utxo(Some(filter)).await.unwrap();
+        client
+            .utxo(UtxoQuery::ByOutputReferences(vec![reference.clone()]))
+            .await
+            .unwrap();
+
+        let both = UtxoFilter {
+            addresses: Some(vec!["addr_test1".into()]),
+            output_references: Some(vec![reference]),
+        };
+        let error = client.utxo(both).await.unwrap_err();
+        assert!(matches!(error, OgmiosError::QueryError(_)));
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 3);
+        assert!(requests[0].get("params").is_none());
+        assert_eq!(
+            requests[1]["params"],
+            json!({ "outputReferences": [{ "id": "ab".repeat(32), "index": 1 }] })
+        );
+        assert_eq!(requests[2]["params"], requests[1]["params"]);
+    }
+
+    #[cfg(feature = "address")]
+    #[tokio::test]
+    async fn test_utxo_by_addresses_rejects_malformed_address() {
+        let server = MockServer::start(|_, _| MockReply::result(json!([]))).await;
+        let client = LedgerStateQueryClient::new(server.context().await);
+
+        let error = client
+            .utxo_by_addresses(["addr_test1vz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer"])
+            .await
+            .unwrap_err();
+        assert!(matches!(error, OgmiosError::InvalidAddress(_)));
+        assert!(server.requests().is_empty());
+
+        let address = "addr_test1vz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerspjrlsz";
+        assert!(client.utxo_by_addresses([address]).await.unwrap().is_empty());
+        assert_eq!(server.requests()[0]["params"], json!({ "addresses": [address] }));
+    }
+}
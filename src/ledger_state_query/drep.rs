@@ -0,0 +1,177 @@
+//! Stake delegated to DReps.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{AdaValue, DRepId, DigestBlake2b224, Lovelace};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// Stake delegated to each DRep, including the pre-defined voting options.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DRepStakeDistribution(HashMap<DRepId, Lovelace>);
+
+impl DRepStakeDistribution {
+    /// Stake delegated to any DRep, pre-defined options included.
+    pub fn total_drep_stake(&self) -> Lovelace {
+        self.0.values().sum()
+    }
+
+    /// Stake delegated to registered DReps only.
+    pub fn total_registered_drep_stake(&self) -> Lovelace {
+        self.0
+            .iter()
+            .filter(|(id, _)| !id.is_predefined())
+            .map(|(_, stake)| stake)
+            .sum()
+    }
+
+    /// Share of the live stake delegated to any DRep (0.0 to 1.0).
+    ///
+    /// Returns `None` if `total_live_stake` is zero.
+    pub fn participation_rate(&self, total_live_stake: Lovelace) -> Option<f64> {
+        (total_live_stake != 0).then(|| self.total_drep_stake() as f64 / total_live_stake as f64)
+    }
+
+    /// Convert into the underlying map.
+    pub fn into_inner(self) -> HashMap<DRepId, Lovelace> {
+        self.0
+    }
+}
+
+impl Deref for DRepStakeDistribution {
+    type Target = HashMap<DRepId, Lovelace>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<HashMap<DRepId, Lovelace>> for DRepStakeDistribution {
+    fn from(stake: HashMap<DRepId, Lovelace>) -> Self {
+        Self(stake)
+    }
+}
+
+impl IntoIterator for DRepStakeDistribution {
+    type Item = (DRepId, Lovelace);
+    type IntoIter = std::collections::hash_map::IntoIter<DRepId, Lovelace>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// A `delegateRepresentatives` result entry, reduced to what the distribution needs.
+#[derive(Deserialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+enum DRepStakeEntry {
+    Registered {
+        from: CredentialKind,
+        id: DigestBlake2b224,
+        stake: AdaValue,
+    },
+    Abstain {
+        stake: AdaValue,
+    },
+    NoConfidence {
+        stake: AdaValue,
+    },
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+enum CredentialKind {
+    VerificationKey,
+    Script,
+}
+
+impl From<DRepStakeEntry> for (DRepId, Lovelace) {
+    fn from(entry: DRepStakeEntry) -> Self {
+        match entry {
+            DRepStakeEntry::Registered {
+                from: CredentialKind::VerificationKey,
+                id,
+                stake,
+            } => (DRepId::Key(id), stake.lovelace),
+            DRepStakeEntry::Registered {
+                from: CredentialKind::Script,
+                id,
+                stake,
+            } => (DRepId::Script(id.into()), stake.lovelace),
+            DRepStakeEntry::Abstain { stake } => (DRepId::AlwaysAbstain, stake.lovelace),
+            DRepStakeEntry::NoConfidence { stake } => (DRepId::AlwaysNoConfidence, stake.lovelace),
+        }
+    }
+}
+
+/// Query the stake delegated to each DRep.
+///
+/// Registered DReps are keyed by credential, split by key or script since the
+/// same hash may denote either. Stake delegated to the always-abstain and
+/// always-no-confidence options appears under [`DRepId::AlwaysAbstain`] and
+/// [`DRepId::AlwaysNoConfidence`].
+pub async fn drep_stake_distribution(
+    context: &InteractionContext,
+) -> Result<DRepStakeDistribution> {
+    let entries: Vec<DRepStakeEntry> = context
+        .request("queryLedgerState/delegateRepresentatives", None::<()>)
+        .await?;
+    Ok(DRepStakeDistribution(
+        entries.into_iter().map(Into::into).collect(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn distribution() -> DRepStakeDistribution {
+        // Shape of a `queryLedgerState/delegateRepresentatives` result.
+        let entries: Vec<DRepStakeEntry> = serde_json::from_value(json!([
+            {
+                "type": "registered",
+                "from": "verificationKey",
+                "id": "03ccae794affbe27a5f5f74da6266002db11daa6ae446aea783b972d",
+                "mandate": { "epoch": 500 },
+                "deposit": { "ada": { "lovelace": 500000000 } },
+                "stake": { "ada": { "lovelace": 600 } },
+                "delegators": []
+            },
+            {
+                "type": "registered",
+                "from": "script",
+                "id": "03ccae794affbe27a5f5f74da6266002db11daa6ae446aea783b972d",
+                "deposit": { "ada": { "lovelace": 500000000 } },
+                "stake": { "ada": { "lovelace": 100 } }
+            },
+            { "type": "abstain", "stake": { "ada": { "lovelace": 200 } } },
+            { "type": "noConfidence", "stake": { "ada": { "lovelace": 100 } } }
+        ]))
+        .unwrap();
+        DRepStakeDistribution(entries.into_iter().map(Into::into).collect())
+    }
+
+    #[test]
+    fn test_distribution_keys() {
+        let distribution = distribution();
+        let hash: DigestBlake2b224 = "03ccae794affbe27a5f5f74da6266002db11daa6ae446aea783b972d"
+            .parse()
+            .unwrap();
+        assert_eq!(distribution[&DRepId::Key(hash.clone())], 600);
+        assert_eq!(distribution[&DRepId::Script(hash.into())], 100);
+        assert_eq!(distribution[&DRepId::AlwaysAbstain], 200);
+        assert_eq!(distribution[&DRepId::AlwaysNoConfidence], 100);
+        assert_eq!(DRepId::AlwaysAbstain.credential(), None);
+    }
+
+    #[test]
+    fn test_summary_helpers() {
+        let distribution = distribution();
+        assert_eq!(distribution.total_drep_stake(), 1000);
+        assert_eq!(distribution.total_registered_drep_stake(), 700);
+        assert_eq!(distribution.participation_rate(4000), Some(0.25));
+        assert_eq!(distribution.participation_rate(0), None);
+    }
+}
@@ -0,0 +1,213 @@
+//! Consistent snapshot of the governance state.
+
+use crate::connection::InteractionContext;
+use crate::error::{OgmiosError, Result};
+use crate::schema::{
+    Constitution, ConstitutionalCommittee, DelegateRepresentativeSummary, GovernanceProposalState,
+    Point, Slot,
+};
+
+use super::query;
+
+/// Options for [`governance_snapshot`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct GovernanceSnapshotOptions {
+    /// Return a snapshot even if some sections failed, with the error in place
+    /// of each failed section. When `false` (the default), the first failure
+    /// fails the whole snapshot.
+    pub allow_partial: bool,
+}
+
+/// Governance state answered from a single ledger state.
+///
+/// Every section is queried at the same point, so proposals, DReps, committee
+/// and constitution are mutually consistent. Sections are `Err` only when the
+/// snapshot was taken with [`allow_partial`](GovernanceSnapshotOptions::allow_partial).
+#[derive(Debug)]
+pub struct GovernanceSnapshot {
+    /// Point the snapshot was taken at.
+    pub point: Point,
+    /// Slot the ledger state was acquired at.
+    pub slot: Slot,
+    /// Governance proposals.
+    pub proposals: Result<Vec<GovernanceProposalState>>,
+    /// Registered delegate representatives.
+    pub delegate_representatives: Result<Vec<DelegateRepresentativeSummary>>,
+    /// Constitutional committee.
+    pub constitutional_committee: Result<ConstitutionalCommittee>,
+    /// Constitution.
+    pub constitution: Result<Constitution>,
+}
+
+impl GovernanceSnapshot {
+    /// Whether every section was queried successfully.
+    pub fn is_complete(&self) -> bool {
+        self.errors().next().is_none()
+    }
+
+    /// Errors of the failed sections, with the section name.
+    pub fn errors(&self) -> impl Iterator<Item = (&'static str, &OgmiosError)> {
+        [
+            ("proposals", self.proposals.as_ref().err()),
+            (
+                "delegateRepresentatives",
+                self.delegate_representatives.as_ref().err(),
+            ),
+            (
+                "constitutionalCommittee",
+                self.constitutional_committee.as_ref().err(),
+            ),
+            ("constitution", self.constitution.as_ref().err()),
+        ]
+        .into_iter()
+        .filter_map(|(section, error)| error.map(|error| (section, error)))
+    }
+}
+
+/// Take a governance snapshot at the volatile tip.
+///
+/// Acquires the ledger state at the tip, runs the four governance queries
+/// concurrently, and releases the ledger state, whether the queries succeeded
+/// or not. The caller must not run other queries on the same connection in the
+/// meantime.
+pub async fn governance_snapshot(
+    context: &InteractionContext,
+    options: GovernanceSnapshotOptions,
+) -> Result<GovernanceSnapshot> {
+    let slot = query::acquire_ledger_state(context, None).await?;
+
+    let (point, proposals, delegate_representatives, constitutional_committee, constitution) = tokio::join!(
+        query::ledger_tip(context),
+        query::governance_proposals(context, None),
+        query::delegate_representatives(context),
+        query::constitutional_committee(context),
+        query::constitution(context),
+    );
+    let released = query::release_ledger_state(context).await;
+
+    let point = point?;
+    let snapshot = if options.allow_partial {
+        GovernanceSnapshot {
+            point,
+            slot,
+            proposals,
+            delegate_representatives,
+            constitutional_committee,
+            constitution,
+        }
+    } else {
+        GovernanceSnapshot {
+            point,
+            slot,
+            proposals: Ok(proposals?),
+            delegate_representatives: Ok(delegate_representatives?),
+            constitutional_committee: Ok(constitutional_committee?),
+            constitution: Ok(constitution?),
+        }
+    };
+    released?;
+    Ok(snapshot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+    use crate::schema::DRepId;
+    use crate::schema::testing::fixture;
+    use serde_json::json;
+
+    async fn mock_server(committee: MockReply) -> MockServer {
+        MockServer::start(move |method, _| match method {
+            "acquireLedgerState" => {
+                MockReply::result(json!({ "acquired": "ledgerState", "slot": 100 }))
+            }
+            "releaseLedgerState" => MockReply::result(json!({ "released": "ledgerState" })),
+            "queryLedgerState/tip" => MockReply::result(json!({ "slot": 100, "id": "ab".repeat(32) })),
+            "queryLedgerState/governanceProposals" => {
+                MockReply::result(fixture("ledger-state/governanceProposals").result())
+            }
+            "queryLedgerState/delegateRepresentatives" => {
+                MockReply::result(fixture("ledger-state/delegateRepresentatives").result())
+            }
+            "queryLedgerState/constitutionalCommittee" => committee.clone(),
+            "queryLedgerState/constitution" => MockReply::result(json!({
+                "metadata": { "url": "https://example.com", "hash": "ab".repeat(32) }
+            })),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_is_taken_at_one_point() {
+        let server = mock_server(MockReply::result(json!({
+            "members": [{ "id": "cd".repeat(28), "status": "active", "mandate": { "epoch": 500 } }],
+            "quorum": "2/3"
+        })))
+        .await;
+        let context = server.context().await;
+
+        let snapshot = governance_snapshot(&context, GovernanceSnapshotOptions::default())
+            .await
+            .unwrap();
+        assert_eq!(snapshot.slot, 100);
+        assert_eq!(snapshot.point.slot(), Some(100));
+        assert!(snapshot.is_complete());
+        assert_eq!(
+            snapshot.constitutional_committee.unwrap().members[0]
+                .mandate
+                .unwrap()
+                .epoch,
+            500
+        );
+        let dreps = snapshot.delegate_representatives.unwrap();
+        let ids: Vec<_> = dreps.iter().map(|drep| drep.id()).collect();
+        assert_eq!(
+            ids,
+            [
+                DRepId::Key("1ded5672637c4eb156011ff7c9da715d8860528bb10d563841c362b5".parse().unwrap()),
+                DRepId::AlwaysAbstain,
+                DRepId::AlwaysNoConfidence,
+            ]
+        );
+        assert_eq!(dreps[1].stake(), 4_112_941_774_032_118);
+        let proposals = snapshot.proposals.unwrap();
+        assert_eq!(proposals[0].expires_after, 559);
+
+        let methods = server.methods();
+        assert_eq!(
+            methods.first().map(String::as_str),
+            Some("acquireLedgerState")
+        );
+        assert_eq!(
+            methods.last().map(String::as_str),
+            Some("releaseLedgerState")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_partial_failures() {
+        let server = mock_server(MockReply::error(2002, "Era mismatch")).await;
+        let context = server.context().await;
+
+        assert!(
+            governance_snapshot(&context, GovernanceSnapshotOptions::default())
+                .await
+                .is_err()
+        );
+        assert_eq!(server.count("releaseLedgerState"), 1);
+
+        let snapshot = governance_snapshot(
+            &context,
+            GovernanceSnapshotOptions {
+                allow_partial: true,
+            },
+        )
+        .await
+        .unwrap();
+        let failed: Vec<_> = snapshot.errors().map(|(section, _)| section).collect();
+        assert_eq!(failed, ["constitutionalCommittee"]);
+        assert!(snapshot.proposals.is_ok());
+    }
+}
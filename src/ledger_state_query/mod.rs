@@ -3,8 +3,27 @@
 //! This module provides functionality for querying the current ledger state
 //! of the Cardano blockchain via Ogmios.
 
+mod cache;
 mod client;
+mod drep;
+mod governance;
+mod pinned;
 mod query;
+mod stake_pools;
+mod summary;
+pub(crate) mod time;
+mod totals;
+mod utxo_map;
+mod utxo_stream;
 
 pub use client::*;
+pub use drep::*;
+pub use governance::*;
+pub use pinned::*;
 pub use query::*;
+pub use stake_pools::*;
+pub use summary::*;
+pub use time::*;
+pub use totals::*;
+pub use utxo_map::*;
+pub use utxo_stream::DEFAULT_UTXO_STREAM_BUFFER;
@@ -0,0 +1,502 @@
+//! Query handle pinned to an acquired ledger state.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{
+    Address, Constitution, ConstitutionalCommittee, DelegateRepresentativeSummary, Epoch, EraStart,
+    EraSummary, GovernanceProposalState, LiveStakeDistributionEntry, Point,
+    ProjectedRewards, ProtocolParameters, RewardAccount, RewardAccountSummary, Slot,
+    StakeAddress, StakePoolId, StakePoolPerformance, StakePoolView,
+    TreasuryAndReserves, Utxo, error_codes,
+};
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tracing::warn;
+
+use super::drep::{self, DRepStakeDistribution};
+use super::query::{self, *};
+use super::stake_pools::{self, StakePoolSummaries, StakePoolSummary, StakePoolsQuery};
+use super::utxo_map::{self, UtxoMap};
+
+/// Where to re-acquire the ledger state when the pinned point is rolled back.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReacquireTarget {
+    /// The volatile tip of the node's ledger, acquired without a point.
+    ///
+    /// While the node is syncing, this lags behind the network tip, which it
+    /// cannot acquire.
+    VolatileTip,
+    /// The newest of these points that can still be acquired: a first point,
+    /// so that there is always one, and any others.
+    NewestOf(Point, Vec<Point>),
+}
+
+/// Callback invoked after a re-acquisition with the previous and new slot.
+pub type ReacquireCallback = Arc<dyn Fn(Slot, Slot) + Send + Sync>;
+
+/// Fallback used by [`PinnedLedgerState`] when its point is no longer available.
+#[derive(Clone)]
+pub struct ReacquireOptions {
+    /// Where to re-acquire.
+    pub target: ReacquireTarget,
+    /// Notified after each successful re-acquisition.
+    pub on_reacquire: Option<ReacquireCallback>,
+}
+
+impl ReacquireOptions {
+    /// Re-acquire at the given target, without notification.
+    pub fn new(target: ReacquireTarget) -> Self {
+        Self {
+            target,
+            on_reacquire: None,
+        }
+    }
+
+    /// Set the callback notified after each re-acquisition.
+    pub fn with_callback(mut self, callback: impl Fn(Slot, Slot) + Send + Sync + 'static) -> Self {
+        self.on_reacquire = Some(Arc::new(callback));
+        self
+    }
+}
+
+impl std::fmt::Debug for ReacquireOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReacquireOptions")
+            .field("target", &self.target)
+            .field("on_reacquire", &self.on_reacquire.is_some())
+            .finish()
+    }
+}
+
+/// A restricted query handle answering every query from one acquired ledger state.
+///
+/// Handed out by [`LedgerStateQueryClient::at_point`](super::LedgerStateQueryClient::at_point),
+/// which owns the acquire/release bracketing. The handle deliberately exposes no
+/// way to acquire or release, so the pinned point can't change under the queries.
+/// Network queries (tip, block height, start time, genesis) are not affected by
+/// acquisition and are only available on the client itself.
+///
+/// The one exception is [`with_reacquire`](Self::with_reacquire): near the tip,
+/// the acquired block may be rolled back, after which queries fail. With a
+/// fallback registered, such a failure re-acquires at the fallback target,
+/// notifies the callback, and retries the failed query once.
+pub struct PinnedLedgerState {
+    /// The interaction context.
+    context: Arc<InteractionContext>,
+    /// Slot at which the ledger state was acquired.
+    slot: AtomicU64,
+    /// Fallback for when the acquired point is rolled back.
+    reacquire: Option<ReacquireOptions>,
+}
+
+impl PinnedLedgerState {
+    pub(crate) fn new(context: Arc<InteractionContext>, slot: Slot) -> Self {
+        Self {
+            context,
+            slot: AtomicU64::new(slot),
+            reacquire: None,
+        }
+    }
+
+    /// Re-acquire automatically when the pinned point is rolled back.
+    ///
+    /// Queries made after a re-acquisition are answered from the new point, so
+    /// results gathered before and after it may come from different states;
+    /// register a callback to find out when that happens.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use ogmios_client::ledger_state_query::{
+    ///     LedgerStateQueryClient, ReacquireOptions, ReacquireTarget,
+    /// };
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+    /// let tip = client.ledger_tip().await?;
+    ///
+    /// let epoch = client
+    ///     .at_point(tip, |q| async move {
+    ///         let q = q.with_reacquire(
+    ///             ReacquireOptions::new(ReacquireTarget::VolatileTip)
+    ///                 .with_callback(|from, to| println!("re-acquired {from} -> {to}")),
+    ///         );
+    ///         q.epoch().await
+    ///     })
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_reacquire(mut self, options: ReacquireOptions) -> Self {
+        self.reacquire = Some(options);
+        self
+    }
+
+    /// Get the slot at which the ledger state is currently acquired.
+    pub fn slot(&self) -> Slot {
+        self.slot.load(Ordering::SeqCst)
+    }
+
+    /// Run a query, re-acquiring and retrying once if the acquired point expired.
+    async fn run<T, F, Fut>(&self, query: F) -> Result<T>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        match query().await {
+            Err(e) if e.code() == Some(error_codes::ACQUIRED_EXPIRED) => {
+                let Some(options) = &self.reacquire else {
+                    return Err(e);
+                };
+                warn!(
+                    "Acquired ledger state expired at slot {}, re-acquiring",
+                    self.slot()
+                );
+                self.reacquire(options).await?;
+                query().await
+            }
+            result => result,
+        }
+    }
+
+    async fn reacquire(&self, options: &ReacquireOptions) -> Result<()> {
+        let slot = match &options.target {
+            ReacquireTarget::VolatileTip => {
+                query::acquire_ledger_state(&self.context, None).await?
+            }
+            ReacquireTarget::NewestOf(first, rest) => self.acquire_newest(first, rest).await?,
+        };
+
+        let previous = self.slot.swap(slot, Ordering::SeqCst);
+        if let Some(callback) = &options.on_reacquire {
+            callback(previous, slot);
+        }
+        Ok(())
+    }
+
+    /// Acquire the newest point that can be, failing with the error of the
+    /// oldest one when none can.
+    async fn acquire_newest(&self, first: &Point, rest: &[Point]) -> Result<Slot> {
+        let mut candidates: Vec<_> = std::iter::once(first).chain(rest).collect();
+        candidates.sort_by_key(|point| std::cmp::Reverse(point.slot()));
+        let oldest = candidates.pop().unwrap_or(first);
+
+        for point in candidates {
+            if let Ok(slot) = query::acquire_ledger_state(&self.context, Some(point.clone())).await {
+                return Ok(slot);
+            }
+        }
+        query::acquire_ledger_state(&self.context, Some(oldest.clone())).await
+    }
+
+    /// Query the current constitution.
+    pub async fn constitution(&self) -> Result<Constitution> {
+        self.run(|| query::constitution(&self.context)).await
+    }
+
+    /// Query the current constitutional committee.
+    pub async fn constitutional_committee(&self) -> Result<ConstitutionalCommittee> {
+        self.run(|| query::constitutional_committee(&self.context))
+            .await
+    }
+
+    /// Query registered delegate representatives.
+    pub async fn delegate_representatives(&self) -> Result<Vec<DelegateRepresentativeSummary>> {
+        self.run(|| query::delegate_representatives(&self.context))
+            .await
+    }
+
+    /// Query the stake delegated to each DRep, including the always-abstain and
+    /// always-no-confidence options.
+    pub async fn drep_stake_distribution(&self) -> Result<DRepStakeDistribution> {
+        self.run(|| drep::drep_stake_distribution(&self.context))
+            .await
+    }
+
+    /// Query the current epoch.
+    pub async fn epoch(&self) -> Result<Epoch> {
+        self.run(|| query::epoch(&self.context)).await
+    }
+
+    /// Query the era start information.
+    pub async fn era_start(&self) -> Result<EraStart> {
+        self.run(|| query::era_start(&self.context)).await
+    }
+
+    /// Query era summaries.
+    pub async fn era_summaries(&self) -> Result<Vec<EraSummary>> {
+        self.run(|| query::era_summaries(&self.context)).await
+    }
+
+    /// Query governance proposals.
+    pub async fn governance_proposals(
+        &self,
+        filter: Option<GovernanceProposalFilter>,
+    ) -> Result<Vec<GovernanceProposalState>> {
+        self.run(|| query::governance_proposals(&self.context, filter.clone()))
+            .await
+    }
+
+    /// Query the ledger tip.
+    pub async fn ledger_tip(&self) -> Result<Point> {
+        self.run(|| query::ledger_tip(&self.context)).await
+    }
+
+    /// Query live stake distribution.
+    pub async fn live_stake_distribution(
+        &self,
+    ) -> Result<HashMap<StakePoolId, LiveStakeDistributionEntry>> {
+        self.run(|| query::live_stake_distribution(&self.context))
+            .await
+    }
+
+    /// Query projected rewards for stake key credentials or stake addresses.
+    ///
+    /// Script-based stake addresses are rejected, see
+    /// [`RewardAccountSummariesFilter::keys`].
+    pub async fn projected_rewards(
+        &self,
+        stake_addresses: Vec<StakeAddress>,
+    ) -> Result<ProjectedRewards> {
+        self.projected_rewards_by(ProjectedRewardsFilter::keys(stake_addresses))
+            .await
+    }
+
+    /// Query projected rewards for any combination of stake amounts and
+    /// credentials.
+    ///
+    /// Build the filter with [`ProjectedRewardsFilter::stake`] for amounts, or
+    /// [`ProjectedRewardsFilter::credentials`] for stake credentials.
+    pub async fn projected_rewards_by(
+        &self,
+        filter: ProjectedRewardsFilter,
+    ) -> Result<ProjectedRewards> {
+        self.run(|| query::projected_rewards(&self.context, filter.clone()))
+            .await
+    }
+
+    /// Query protocol parameters.
+    pub async fn protocol_parameters(&self) -> Result<ProtocolParameters> {
+        self.run(|| query::protocol_parameters(&self.context)).await
+    }
+
+    /// Query reward account summaries by stake key credentials or stake addresses.
+    ///
+    /// Script-based stake addresses are rejected, see
+    /// [`RewardAccountSummariesFilter::keys`]. The result is keyed by
+    /// credential; see [`query::reward_account_summaries`].
+    pub async fn reward_account_summaries(
+        &self,
+        keys: Vec<StakeAddress>,
+    ) -> Result<HashMap<RewardAccount, RewardAccountSummary>> {
+        self.reward_account_summaries_by(RewardAccountSummariesFilter::keys(keys))
+            .await
+    }
+
+    /// Query reward account summaries by key and/or script credentials.
+    ///
+    /// Build the filter from stake credentials with
+    /// [`RewardAccountSummariesFilter::credentials`].
+    pub async fn reward_account_summaries_by(
+        &self,
+        filter: RewardAccountSummariesFilter,
+    ) -> Result<HashMap<RewardAccount, RewardAccountSummary>> {
+        self.run(|| query::reward_account_summaries(&self.context, filter.clone()))
+            .await
+    }
+
+    /// Query stake pools.
+    pub async fn stake_pools(
+        &self,
+        filter: Option<StakePoolsFilter>,
+        include_stake: bool,
+    ) -> Result<HashMap<StakePoolId, StakePoolView>> {
+        self.run(|| query::stake_pools(&self.context, filter.clone(), include_stake))
+            .await
+    }
+
+    /// Query stake pools, returning summaries with convenience accessors.
+    pub async fn stake_pool_summaries(&self, query: StakePoolsQuery) -> Result<StakePoolSummaries> {
+        self.run(|| stake_pools::stake_pool_summaries(&self.context, query.clone()))
+            .await
+    }
+
+    /// Query a single stake pool, including its live stake.
+    ///
+    /// Returns `None` if the pool is not registered.
+    pub async fn stake_pool(&self, id: impl Into<StakePoolId>) -> Result<Option<StakePoolSummary>> {
+        let id = id.into();
+        self.run(|| stake_pools::stake_pool(&self.context, id.clone()))
+            .await
+    }
+
+    /// Query stake pool performances.
+    pub async fn stake_pools_performances(
+        &self,
+    ) -> Result<HashMap<StakePoolId, StakePoolPerformance>> {
+        self.run(|| query::stake_pools_performances(&self.context))
+            .await
+    }
+
+    /// Query the ada held in the treasury and the reserves.
+    pub async fn treasury_and_reserves(&self) -> Result<TreasuryAndReserves> {
+        self.run(|| query::treasury_and_reserves(&self.context))
+            .await
+    }
+
+    /// Query the UTXOs selected by `query`.
+    ///
+    /// See [`LedgerStateQueryClient::utxo`](super::LedgerStateQueryClient::utxo).
+    pub async fn utxo(&self, query: impl UtxoQueryInput) -> Result<Vec<Utxo>> {
+        let query = query.into_utxo_query()?;
+        self.run(|| query::utxo(&self.context, query.clone())).await
+    }
+
+    /// Query UTXOs, keyed by output reference.
+    ///
+    /// Fails if the response lists an output reference twice.
+    pub async fn utxo_map(&self, query: UtxoQuery) -> Result<UtxoMap> {
+        self.run(|| utxo_map::utxo_map(&self.context, query.clone()))
+            .await
+    }
+
+    /// Query UTXOs by addresses.
+    pub async fn utxo_by_addresses(
+        &self,
+        addresses: impl IntoIterator<Item = impl Into<Address>>,
+    ) -> Result<Vec<Utxo>> {
+        let addresses: Vec<Address> = addresses.into_iter().map(Into::into).collect();
+        self.run(|| query::utxo_by_addresses(&self.context, addresses.clone()))
+            .await
+    }
+
+    /// Query UTXOs by output references.
+    ///
+    /// References can be given as
+    /// [`TransactionOutputReference`](crate::schema::TransactionOutputReference)s or in
+    /// the `<transaction id>#<index>` form.
+    pub async fn utxo_by_output_references(
+        &self,
+        output_references: impl IntoIterator<Item = impl OutputReferenceInput>,
+    ) -> Result<Vec<Utxo>> {
+        let output_references = query::output_references_from(output_references)?;
+        self.run(|| query::utxo_by_output_references(&self.context, output_references.clone()))
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{AcquireFailure, OgmiosError};
+    use crate::mock_server::{MockReply, MockServer};
+    use crate::schema::DigestBlake2b256;
+    use serde_json::{Value, json};
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicUsize;
+
+    /// A server whose first `epoch` query fails with an expired acquisition.
+    ///
+    /// Its node lags behind: acquiring without a point gets its volatile tip at
+    /// slot 150, while the network tip is at slot 200.
+    async fn rolled_back_server(acquirable: fn(&Value) -> bool) -> MockServer {
+        let epochs = AtomicUsize::new(0);
+        MockServer::start(move |method, params| match method {
+            "acquireLedgerState" if params.get("point").is_none() => {
+                MockReply::result(json!({ "acquired": "ledgerState", "slot": 150 }))
+            }
+            "acquireLedgerState" if params["point"]["slot"] == 200 => {
+                MockReply::error(error_codes::ACQUIRE_POINT_NOT_ON_CHAIN, "Point not on chain")
+            }
+            "acquireLedgerState" if acquirable(params) => MockReply::result(
+                json!({ "acquired": "ledgerState", "slot": params["point"]["slot"] }),
+            ),
+            "acquireLedgerState" => MockReply::error(2000, "Point not on chain"),
+            "queryNetwork/tip" => MockReply::result(json!({ "slot": 200, "id": "cd".repeat(32) })),
+            "queryLedgerState/epoch" if epochs.fetch_add(1, Ordering::SeqCst) == 0 => {
+                MockReply::error(error_codes::ACQUIRED_EXPIRED, "Acquired point expired")
+            }
+            "queryLedgerState/epoch" => MockReply::result(json!(42)),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await
+    }
+
+    #[tokio::test]
+    async fn test_reacquires_at_volatile_tip_of_lagging_node() {
+        let server = rolled_back_server(|_| true).await;
+        let notified = Arc::new(Mutex::new(Vec::new()));
+        let log = notified.clone();
+        let pinned = PinnedLedgerState::new(Arc::new(server.context().await), 100).with_reacquire(
+            ReacquireOptions::new(ReacquireTarget::VolatileTip)
+                .with_callback(move |from, to| log.lock().unwrap().push((from, to))),
+        );
+
+        assert_eq!(pinned.epoch().await.unwrap(), 42);
+        assert_eq!(pinned.slot(), 150);
+        assert_eq!(*notified.lock().unwrap(), vec![(100, 150)]);
+        assert_eq!(
+            server.methods(),
+            vec!["queryLedgerState/epoch", "acquireLedgerState", "queryLedgerState/epoch"]
+        );
+        assert_eq!(server.requests()[1]["params"], json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_reacquires_at_newest_acquirable_point() {
+        let server = rolled_back_server(|params| params["point"]["slot"] != 150).await;
+        let pinned = PinnedLedgerState::new(Arc::new(server.context().await), 100).with_reacquire(
+            ReacquireOptions::new(ReacquireTarget::NewestOf(
+                Point::at(120, DigestBlake2b256::filled("a")),
+                vec![
+                    Point::at(150, DigestBlake2b256::filled("b")),
+                    Point::at(90, DigestBlake2b256::filled("c")),
+                ],
+            )),
+        );
+
+        assert_eq!(pinned.epoch().await.unwrap(), 42);
+        assert_eq!(pinned.slot(), 120);
+        let attempted: Vec<_> = server
+            .requests()
+            .iter()
+            .filter(|r| r["method"] == "acquireLedgerState")
+            .map(|r| r["params"]["point"]["slot"].clone())
+            .collect();
+        assert_eq!(attempted, vec![json!(150), json!(120)]);
+    }
+
+    #[tokio::test]
+    async fn test_reacquisition_fails_with_typed_error_of_oldest_point() {
+        let server = rolled_back_server(|_| false).await;
+        let pinned = PinnedLedgerState::new(Arc::new(server.context().await), 100).with_reacquire(
+            ReacquireOptions::new(ReacquireTarget::NewestOf(
+                Point::at(90, DigestBlake2b256::filled("c")),
+                vec![Point::at(120, DigestBlake2b256::filled("a"))],
+            )),
+        );
+
+        let error = pinned.epoch().await.unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::Acquisition {
+                failure: AcquireFailure::PointTooOld,
+                point: Some(ref point),
+            } if point.slot() == Some(90)
+        ));
+        assert_eq!(server.count("acquireLedgerState"), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_acquisition_without_fallback_fails() {
+        let server = rolled_back_server(|_| true).await;
+        let pinned = PinnedLedgerState::new(Arc::new(server.context().await), 100);
+
+        let error = pinned.epoch().await.unwrap_err();
+        assert_eq!(error.code(), Some(error_codes::ACQUIRED_EXPIRED));
+        assert_eq!(server.count("acquireLedgerState"), 0);
+    }
+}
@@ -0,0 +1,283 @@
+//! Stake pool queries with an ergonomic result type.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{
+    Lovelace, StakePool, StakePoolId, StakePoolStatus, StakePoolView, stake_pool_reference,
+};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::ops::Deref;
+
+/// Parameters of a stake pools query.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::ledger_state_query::StakePoolsQuery;
+///
+/// let query = StakePoolsQuery::builder()
+///     .with_pool("pool1abc")
+///     .with_stake();
+/// assert!(query.include_stake);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StakePoolsQuery {
+    /// Specific stake pool IDs to query (all pools if `None`).
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "stake_pool_reference::serialize_list"
+    )]
+    pub stake_pools: Option<Vec<StakePoolId>>,
+    /// Include each pool's live stake in the result.
+    pub include_stake: bool,
+}
+
+impl StakePoolsQuery {
+    /// Start a query for all stake pools, without stake.
+    pub fn builder() -> Self {
+        Self::default()
+    }
+
+    /// Restrict the query to a stake pool, in addition to any already selected.
+    pub fn with_pool(mut self, id: impl Into<StakePoolId>) -> Self {
+        self.stake_pools.get_or_insert_with(Vec::new).push(id.into());
+        self
+    }
+
+    /// Restrict the query to the given stake pools, in addition to any already selected.
+    pub fn with_pools(mut self, ids: impl IntoIterator<Item = StakePoolId>) -> Self {
+        self.stake_pools.get_or_insert_with(Vec::new).extend(ids);
+        self
+    }
+
+    /// Include each pool's live stake in the result.
+    pub fn with_stake(mut self) -> Self {
+        self.include_stake = true;
+        self
+    }
+}
+
+/// A stake pool returned by a stake pools query.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StakePoolSummary {
+    view: StakePoolView,
+}
+
+impl StakePoolSummary {
+    /// Get the pool ID.
+    pub fn id(&self) -> &StakePoolId {
+        self.view.id()
+    }
+
+    /// Get the registered pool parameters.
+    pub fn parameters(&self) -> &StakePool {
+        &self.view.parameters
+    }
+
+    /// Get the pool status, if reported.
+    pub fn status(&self) -> Option<StakePoolStatus> {
+        self.view.status.clone()
+    }
+
+    /// Get the declared pledge in lovelace.
+    pub fn pledge(&self) -> Lovelace {
+        self.view.parameters.pledge.lovelace
+    }
+
+    /// Get the fixed cost per epoch in lovelace.
+    pub fn cost(&self) -> Lovelace {
+        self.view.parameters.cost.lovelace
+    }
+
+    /// Get the margin as a fraction (0.0 to 1.0).
+    pub fn margin_f64(&self) -> f64 {
+        self.view.parameters.margin.to_f64()
+    }
+
+    /// Get the live stake in lovelace, if the query included it.
+    pub fn stake(&self) -> Option<Lovelace> {
+        self.view.stake.as_ref().map(|stake| stake.lovelace)
+    }
+
+    /// Compute the pool saturation (1.0 means exactly saturated).
+    ///
+    /// The saturation point is `total_stake / optimal_pool_count`, where
+    /// `optimal_pool_count` is the `k` protocol parameter (the
+    /// `stakePoolsLimit`). Returns `None` if the query did not include stake or
+    /// either argument is zero.
+    pub fn saturation(&self, total_stake: Lovelace, optimal_pool_count: u64) -> Option<f64> {
+        if total_stake == 0 || optimal_pool_count == 0 {
+            return None;
+        }
+        let saturation_point = total_stake as f64 / optimal_pool_count as f64;
+        self.stake().map(|stake| stake as f64 / saturation_point)
+    }
+
+    /// Get the underlying view.
+    pub fn view(&self) -> &StakePoolView {
+        &self.view
+    }
+
+    /// Convert into the underlying view.
+    pub fn into_view(self) -> StakePoolView {
+        self.view
+    }
+}
+
+impl From<StakePoolView> for StakePoolSummary {
+    fn from(view: StakePoolView) -> Self {
+        Self { view }
+    }
+}
+
+/// Stake pools returned by a stake pools query, keyed by pool ID.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StakePoolSummaries(HashMap<StakePoolId, StakePoolSummary>);
+
+impl StakePoolSummaries {
+    /// Pools sorted by live stake, largest first.
+    ///
+    /// Pools without stake (when the query did not include it) sort last; ties
+    /// are broken by pool ID for a stable order.
+    pub fn by_stake_desc(&self) -> Vec<&StakePoolSummary> {
+        let mut pools: Vec<_> = self.0.values().collect();
+        pools.sort_by(|a, b| b.stake().cmp(&a.stake()).then_with(|| a.id().cmp(b.id())));
+        pools
+    }
+
+    /// Convert into the underlying map.
+    pub fn into_inner(self) -> HashMap<StakePoolId, StakePoolSummary> {
+        self.0
+    }
+}
+
+impl Deref for StakePoolSummaries {
+    type Target = HashMap<StakePoolId, StakePoolSummary>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<HashMap<StakePoolId, StakePoolView>> for StakePoolSummaries {
+    fn from(views: HashMap<StakePoolId, StakePoolView>) -> Self {
+        Self(
+            views
+                .into_iter()
+                .map(|(id, view)| (id, StakePoolSummary::from(view)))
+                .collect(),
+        )
+    }
+}
+
+impl IntoIterator for StakePoolSummaries {
+    type Item = (StakePoolId, StakePoolSummary);
+    type IntoIter = std::collections::hash_map::IntoIter<StakePoolId, StakePoolSummary>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Query stake pools, returning summaries.
+pub async fn stake_pool_summaries(
+    context: &InteractionContext,
+    query: StakePoolsQuery,
+) -> Result<StakePoolSummaries> {
+    let views: HashMap<StakePoolId, StakePoolView> = context
+        .request("queryLedgerState/stakePools", Some(query))
+        .await?;
+    Ok(views.into())
+}
+
+/// Query a single stake pool, including its live stake.
+///
+/// Returns `None` if the pool is not registered.
+pub async fn stake_pool(
+    context: &InteractionContext,
+    id: impl Into<StakePoolId>,
+) -> Result<Option<StakePoolSummary>> {
+    let id = id.into();
+    let query = StakePoolsQuery::builder().with_pool(id.clone()).with_stake();
+    Ok(stake_pool_summaries(context, query).await?.into_inner().remove(&id))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn pools() -> StakePoolSummaries {
+        let pool = |id: &str, stake: Option<u64>| {
+            let mut value = json!({
+                "id": id,
+                "vrf": "ab",
+                "pledge": { "lovelace": 500_000_000_000u64 },
+                "cost": { "lovelace": 340_000_000 },
+                "margin": { "numerator": 1, "denominator": 40 },
+                "rewardAccount": "stake1u9",
+                "owners": [],
+                "status": "active"
+            });
+            if let Some(stake) = stake {
+                value["stake"] = json!({ "lovelace": stake });
+            }
+            (id.to_string(), value)
+        };
+        let views: HashMap<StakePoolId, StakePoolView> = serde_json::from_value(json!(
+            [pool("pool1a", Some(10)), pool("pool1b", None), pool("pool1c", Some(30))]
+                .into_iter()
+                .collect::<serde_json::Map<_, _>>()
+        ))
+        .unwrap();
+        views.into()
+    }
+
+    #[test]
+    fn test_query_serialization() {
+        assert_eq!(
+            serde_json::to_value(StakePoolsQuery::builder()).unwrap(),
+            json!({ "includeStake": false })
+        );
+        assert_eq!(
+            serde_json::to_value(
+                StakePoolsQuery::builder()
+                    .with_pool("pool1a")
+                    .with_pools(vec!["pool1b".to_string()])
+                    .with_stake()
+            )
+            .unwrap(),
+            json!({
+                "stakePools": [{ "id": "pool1a" }, { "id": "pool1b" }],
+                "includeStake": true
+            })
+        );
+    }
+
+    #[test]
+    fn test_summary_accessors() {
+        let pools = pools();
+        let pool = &pools["pool1c"];
+        assert_eq!(pool.id(), "pool1c");
+        assert_eq!(pool.parameters().id, "pool1c");
+        assert_eq!(pool.status(), Some(StakePoolStatus::Active));
+        assert_eq!(pool.pledge(), 500_000_000_000);
+        assert_eq!(pool.cost(), 340_000_000);
+        assert_eq!(pool.margin_f64(), 0.025);
+        assert_eq!(pool.stake(), Some(30));
+        assert_eq!(pool.saturation(600, 10), Some(0.5));
+        assert_eq!(pools["pool1b"].saturation(600, 10), None);
+    }
+
+    #[test]
+    fn test_by_stake_desc() {
+        let ids: Vec<_> = pools()
+            .by_stake_desc()
+            .into_iter()
+            .map(|pool| pool.id().clone())
+            .collect();
+        assert_eq!(ids, ["pool1c", "pool1a", "pool1b"]);
+    }
+}
@@ -0,0 +1,48 @@
+//! Consistent read of the chain position and protocol parameters.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{Epoch, Point, ProtocolParameters, Slot};
+
+use super::query;
+
+/// Epoch, tip and protocol parameters answered from a single ledger state.
+///
+/// Unlike three separate queries, the fields can never straddle an epoch
+/// boundary.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChainSummary {
+    /// Slot the ledger state was acquired at.
+    pub acquired_at: Slot,
+    /// Current epoch.
+    pub epoch: Epoch,
+    /// Ledger tip.
+    pub tip: Point,
+    /// Protocol parameters in effect.
+    pub protocol_parameters: ProtocolParameters,
+}
+
+/// Read the epoch, ledger tip and protocol parameters at the volatile tip.
+///
+/// Acquires the ledger state once, runs the three queries concurrently, and
+/// releases the ledger state, whether the queries succeeded or not. The caller
+/// must not run other queries on the same connection in the meantime.
+pub async fn chain_summary(context: &InteractionContext) -> Result<ChainSummary> {
+    let acquired_at = query::acquire_ledger_state(context, None).await?;
+
+    let queried = tokio::try_join!(
+        query::epoch(context),
+        query::ledger_tip(context),
+        query::protocol_parameters(context),
+    );
+    let released = query::release_ledger_state(context).await;
+
+    let (epoch, tip, protocol_parameters) = queried?;
+    released?;
+    Ok(ChainSummary {
+        acquired_at,
+        epoch,
+        tip,
+        protocol_parameters,
+    })
+}
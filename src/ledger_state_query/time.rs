@@ -0,0 +1,460 @@
+//! Slot and wall-clock time conversions.
+
+use crate::error::{OgmiosError, Result};
+use crate::schema::{Epoch, EraSummary, RelativeTime, Slot, UtcTime};
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Slot range of an epoch.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EpochBounds {
+    /// First slot of the epoch.
+    pub first_slot: Slot,
+    /// Last slot of the epoch.
+    pub last_slot: Slot,
+}
+
+/// A wall-clock time accepted by [`SlotTimeInterpreter`].
+///
+/// Implemented for RFC 3339 strings and, with the `chrono` feature, for
+/// `chrono::DateTime<Utc>`, so that either form of [`UtcTime`] can be passed.
+pub trait UtcTimeInput: fmt::Display {
+    /// Milliseconds since the Unix epoch.
+    fn to_utc_millis(&self) -> Result<i64>;
+}
+
+impl UtcTimeInput for str {
+    fn to_utc_millis(&self) -> Result<i64> {
+        parse_utc_millis(self)
+    }
+}
+
+impl UtcTimeInput for String {
+    fn to_utc_millis(&self) -> Result<i64> {
+        parse_utc_millis(self)
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl UtcTimeInput for chrono::DateTime<chrono::Utc> {
+    fn to_utc_millis(&self) -> Result<i64> {
+        Ok(self.timestamp_millis())
+    }
+}
+
+impl<T: UtcTimeInput + ?Sized> UtcTimeInput for &T {
+    fn to_utc_millis(&self) -> Result<i64> {
+        (**self).to_utc_millis()
+    }
+}
+
+/// Converts between slots, epochs, and wall-clock times.
+///
+/// Built from the era summaries and the network start time, both of which are
+/// fetched once by [`LedgerStateQueryClient::time_interpreter`](super::LedgerStateQueryClient::time_interpreter).
+/// Conversions are pure and account for the slot length of each era (e.g. the
+/// 20-second Byron slots). The end of the last era summary is the horizon that
+/// the node can safely forecast; conversions past it fail with
+/// [`OgmiosError::PastHorizon`] since a future hard fork may change slot length.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::ledger_state_query::LedgerStateQueryClient;
+/// use ogmios_client::connection::ConnectionConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = LedgerStateQueryClient::connect(ConnectionConfig::default(), None).await?;
+/// let interpreter = client.time_interpreter().await?;
+/// println!("Slot 4492800 started at {}", interpreter.slot_to_time(4492800)?);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct SlotTimeInterpreter {
+    /// Network start time, in milliseconds since the Unix epoch.
+    system_start: i64,
+    eras: Vec<EraSummary>,
+}
+
+impl SlotTimeInterpreter {
+    /// Create an interpreter from era summaries and the network start time.
+    pub fn new(eras: Vec<EraSummary>, system_start: impl UtcTimeInput) -> Result<Self> {
+        if eras.is_empty() {
+            return Err(OgmiosError::InvalidResponse {
+                message: "no era summaries".to_string(),
+            });
+        }
+
+        Ok(Self {
+            system_start: system_start.to_utc_millis()?,
+            eras,
+        })
+    }
+
+    /// Get the network start time.
+    pub fn system_start(&self) -> Result<UtcTime> {
+        utc_time_from_millis(self.system_start)
+    }
+
+    /// Get the first slot past the last era summary, if it is bounded.
+    pub fn horizon(&self) -> Option<Slot> {
+        horizon(&self.eras)
+    }
+
+    /// Convert a slot to the wall-clock time at which it starts.
+    pub fn slot_to_time(&self, slot: Slot) -> Result<UtcTime> {
+        utc_time_from_millis(self.system_start + slot_to_relative_millis(&self.eras, slot)?)
+    }
+
+    /// Convert a wall-clock time to the slot in progress at that time.
+    pub fn time_to_slot(&self, time: impl UtcTimeInput) -> Result<Slot> {
+        let relative = time.to_utc_millis()? - self.system_start;
+        if relative < 0 {
+            return Err(OgmiosError::InvalidTime(format!(
+                "{time} is before the network start time"
+            )));
+        }
+
+        relative_millis_to_slot(&self.eras, relative, || format!("time {time}"))
+    }
+
+    /// Get the epoch containing a slot.
+    pub fn slot_to_epoch(&self, slot: Slot) -> Result<Epoch> {
+        let era = find_era(&self.eras, |era| slot_in(era, slot), || format!("slot {slot}"))?;
+        Ok(era.start.epoch + (slot - era.start.slot) / era.parameters.epoch_length)
+    }
+
+    /// Get the first and last slot of an epoch.
+    pub fn epoch_bounds(&self, epoch: Epoch) -> Result<EpochBounds> {
+        let era = find_era(
+            &self.eras,
+            |era| epoch >= era.start.epoch && era.end.as_ref().is_none_or(|end| epoch < end.epoch),
+            || format!("epoch {epoch}"),
+        )?;
+        let first_slot = era.start.slot + (epoch - era.start.epoch) * era.parameters.epoch_length;
+        Ok(EpochBounds {
+            first_slot,
+            last_slot: first_slot + era.parameters.epoch_length - 1,
+        })
+    }
+}
+
+/// Milliseconds between the network start and the beginning of a slot.
+pub(crate) fn slot_to_relative_millis(eras: &[EraSummary], slot: Slot) -> Result<i64> {
+    let era = find_era(eras, |era| slot_in(era, slot), || format!("slot {slot}"))?;
+    let offset = (slot - era.start.slot) as i64 * seconds_to_millis(era.parameters.slot_length);
+    Ok(seconds_to_millis(era.start.time) + offset)
+}
+
+/// Slot in progress a number of milliseconds after the network start.
+pub(crate) fn relative_millis_to_slot(
+    eras: &[EraSummary],
+    relative: i64,
+    describe: impl FnOnce() -> String,
+) -> Result<Slot> {
+    let era = find_era(
+        eras,
+        |era| {
+            relative >= seconds_to_millis(era.start.time)
+                && era
+                    .end
+                    .as_ref()
+                    .is_none_or(|end| relative < seconds_to_millis(end.time))
+        },
+        describe,
+    )?;
+    let offset = (relative - seconds_to_millis(era.start.time))
+        / seconds_to_millis(era.parameters.slot_length);
+    Ok(era.start.slot + offset as u64)
+}
+
+fn horizon(eras: &[EraSummary]) -> Option<Slot> {
+    eras.last().and_then(|era| era.end.as_ref()).map(|end| end.slot)
+}
+
+/// Find the era matching a predicate, or fail with a horizon error.
+fn find_era(
+    eras: &[EraSummary],
+    matches: impl Fn(&EraSummary) -> bool,
+    describe: impl FnOnce() -> String,
+) -> Result<&EraSummary> {
+    eras.iter()
+        .find(|era| matches(era))
+        .ok_or_else(|| OgmiosError::PastHorizon {
+            value: describe(),
+            horizon: horizon(eras).unwrap_or_default(),
+        })
+}
+
+fn slot_in(era: &EraSummary, slot: Slot) -> bool {
+    slot >= era.start.slot && era.end.as_ref().is_none_or(|end| slot < end.slot)
+}
+
+fn seconds_to_millis(seconds: RelativeTime) -> i64 {
+    (seconds * 1000.0).round() as i64
+}
+
+/// Parse an ISO 8601 / RFC 3339 timestamp into milliseconds since the Unix epoch.
+fn parse_utc_millis(time: &str) -> Result<i64> {
+    let invalid =
+        || OgmiosError::InvalidTime(format!("expected an RFC 3339 timestamp, got {time}"));
+    let number = |range: std::ops::Range<usize>| -> Result<i64> {
+        time.get(range)
+            .filter(|s| s.bytes().all(|b| b.is_ascii_digit()))
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(invalid)
+    };
+
+    let bytes = time.as_bytes();
+    if bytes.len() < 20
+        || bytes[4] != b'-'
+        || bytes[7] != b'-'
+        || !matches!(bytes[10], b'T' | b't' | b' ')
+        || bytes[13] != b':'
+        || bytes[16] != b':'
+    {
+        return Err(invalid());
+    }
+
+    let (year, month, day) = (number(0..4)?, number(5..7)?, number(8..10)?);
+    let (hour, minute, second) = (number(11..13)?, number(14..16)?, number(17..19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return Err(invalid());
+    }
+
+    let mut rest = &time[19..];
+    let mut millis = 0;
+    if let Some(fraction) = rest.strip_prefix('.') {
+        let digits = fraction.bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(invalid());
+        }
+        let padded = format!("{:0<3}", &fraction[..digits.min(3)]);
+        millis = padded.parse::<i64>().map_err(|_| invalid())?;
+        rest = &fraction[digits..];
+    }
+
+    let offset_minutes = match rest {
+        "Z" | "z" => 0,
+        _ if rest.len() == 6 && rest.as_bytes()[3] == b':' => {
+            let sign = match rest.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return Err(invalid()),
+            };
+            let hours = rest.get(1..3).and_then(|h| h.parse::<i64>().ok());
+            let minutes = rest.get(4..6).and_then(|m| m.parse::<i64>().ok());
+            let (Some(hours), Some(minutes)) = (hours, minutes) else {
+                return Err(invalid());
+            };
+            sign * (hours * 60 + minutes)
+        }
+        _ => return Err(invalid()),
+    };
+
+    let seconds = days_from_civil(year, month, day) * 86_400 + hour * 3_600 + minute * 60 + second
+        - offset_minutes * 60;
+    Ok(seconds * 1000 + millis)
+}
+
+#[cfg(not(feature = "chrono"))]
+pub(crate) fn utc_time_from_millis(millis: i64) -> Result<UtcTime> {
+    Ok(format_utc_millis(millis))
+}
+
+#[cfg(feature = "chrono")]
+pub(crate) fn utc_time_from_millis(millis: i64) -> Result<UtcTime> {
+    chrono::DateTime::from_timestamp_millis(millis)
+        .ok_or_else(|| OgmiosError::InvalidTime(format!("{millis} ms is out of range")))
+}
+
+/// Format milliseconds since the Unix epoch as an RFC 3339 UTC timestamp.
+#[cfg(any(not(feature = "chrono"), test))]
+fn format_utc_millis(millis: i64) -> String {
+    let seconds = millis.div_euclid(1000);
+    let millis = millis.rem_euclid(1000);
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+    let (hour, minute, second) = (time / 3_600, time % 3_600 / 60, time % 60);
+
+    if millis == 0 {
+        format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}Z")
+    } else {
+        format!("{year:04}-{month:02}-{day:02}T{hour:02}:{minute:02}:{second:02}.{millis:03}Z")
+    }
+}
+
+/// Days since 1970-01-01 for a proleptic Gregorian date.
+fn days_from_civil(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Proleptic Gregorian date for a number of days since 1970-01-01.
+#[cfg(any(not(feature = "chrono"), test))]
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const MAINNET_START: &str = "2017-09-23T21:44:51Z";
+
+    fn utc(time: &str) -> UtcTime {
+        #[cfg(feature = "chrono")]
+        return time.parse().unwrap();
+        #[cfg(not(feature = "chrono"))]
+        return time.to_string();
+    }
+
+    /// Byron and Shelley mainnet summaries, with the horizon at the start of epoch 210.
+    fn mainnet() -> SlotTimeInterpreter {
+        let eras = serde_json::from_value(json!([
+            {
+                "start": { "slot": 0, "epoch": 0, "time": 0 },
+                "end": { "slot": 4492800, "epoch": 208, "time": 89856000 },
+                "parameters": { "epochLength": 21600, "slotLength": 20, "safeZone": 4320 }
+            },
+            {
+                "start": { "slot": 4492800, "epoch": 208, "time": 89856000 },
+                "end": { "slot": 5356800, "epoch": 210, "time": 90720000 },
+                "parameters": { "epochLength": 432000, "slotLength": 1, "safeZone": 129600 }
+            }
+        ]))
+        .unwrap();
+        SlotTimeInterpreter::new(eras, MAINNET_START).unwrap()
+    }
+
+    #[test]
+    fn test_slot_to_time() {
+        let interpreter = mainnet();
+        assert_eq!(interpreter.system_start().unwrap(), utc(MAINNET_START));
+        assert_eq!(interpreter.slot_to_time(0).unwrap(), utc(MAINNET_START));
+        assert_eq!(
+            interpreter.slot_to_time(1).unwrap(),
+            utc("2017-09-23T21:45:11Z")
+        );
+        // Shelley hard fork.
+        assert_eq!(
+            interpreter.slot_to_time(4492800).unwrap(),
+            utc("2020-07-29T21:44:51Z")
+        );
+        assert_eq!(
+            interpreter.slot_to_time(4924800).unwrap(),
+            utc("2020-08-03T21:44:51Z")
+        );
+    }
+
+    #[test]
+    fn test_time_to_slot() {
+        let interpreter = mainnet();
+        assert_eq!(interpreter.time_to_slot(MAINNET_START).unwrap(), 0);
+        assert_eq!(interpreter.time_to_slot("2017-09-23T21:45:10Z").unwrap(), 0);
+        assert_eq!(
+            interpreter.time_to_slot("2020-07-29T21:44:51Z").unwrap(),
+            4492800
+        );
+        assert_eq!(
+            interpreter
+                .time_to_slot("2020-07-29T23:44:52.5+02:00")
+                .unwrap(),
+            4492801
+        );
+        assert!(matches!(
+            interpreter.time_to_slot("2017-09-23T21:44:50Z"),
+            Err(OgmiosError::InvalidTime(_))
+        ));
+    }
+
+    #[test]
+    fn test_epochs() {
+        let interpreter = mainnet();
+        assert_eq!(interpreter.slot_to_epoch(21599).unwrap(), 0);
+        assert_eq!(interpreter.slot_to_epoch(4492800).unwrap(), 208);
+        assert_eq!(interpreter.slot_to_epoch(4924800).unwrap(), 209);
+        assert_eq!(
+            interpreter.epoch_bounds(207).unwrap(),
+            EpochBounds {
+                first_slot: 4471200,
+                last_slot: 4492799
+            }
+        );
+        assert_eq!(
+            interpreter.epoch_bounds(209).unwrap(),
+            EpochBounds {
+                first_slot: 4924800,
+                last_slot: 5356799
+            }
+        );
+    }
+
+    #[test]
+    fn test_past_horizon() {
+        let interpreter = mainnet();
+        assert_eq!(interpreter.horizon(), Some(5356800));
+        for result in [
+            interpreter.slot_to_time(5356800).map(|_| ()),
+            interpreter.slot_to_epoch(5356800).map(|_| ()),
+            interpreter.epoch_bounds(210).map(|_| ()),
+            interpreter.time_to_slot("2020-08-08T21:44:51Z").map(|_| ()),
+        ] {
+            assert!(matches!(
+                result,
+                Err(OgmiosError::PastHorizon {
+                    horizon: 5356800,
+                    ..
+                })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_utc_round_trip() {
+        for time in [
+            "1970-01-01T00:00:00Z",
+            "2000-02-29T12:34:56.789Z",
+            "2024-12-31T23:59:59Z",
+        ] {
+            assert_eq!(format_utc_millis(parse_utc_millis(time).unwrap()), time);
+        }
+        assert!(parse_utc_millis("2024-13-01T00:00:00Z").is_err());
+        assert!(parse_utc_millis("not a time").is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_chrono_inputs() {
+        let interpreter = mainnet();
+        let time: UtcTime = "2020-07-29T23:44:52.5+02:00".parse().unwrap();
+        assert_eq!(interpreter.time_to_slot(time).unwrap(), 4492801);
+        assert_eq!(interpreter.time_to_slot(interpreter.system_start().unwrap()).unwrap(), 0);
+        assert_eq!(
+            interpreter.slot_to_time(4492801).unwrap().to_rfc3339(),
+            "2020-07-29T21:44:52+00:00"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Network-wide ada totals.
+
+use crate::connection::InteractionContext;
+use crate::error::{OgmiosError, Result};
+use crate::schema::{EraWithGenesis, GenesisConfiguration, Lovelace, TreasuryAndReserves};
+
+use super::query;
+
+/// Network-wide ada totals, in lovelace.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkTotals {
+    /// Stake delegated to registered pools: the sum of every pool's live stake.
+    pub total_live_stake: Lovelace,
+    /// Ada in circulation: `max_lovelace_supply - reserves - treasury`.
+    pub circulating_supply: Lovelace,
+    /// Treasury balance.
+    pub treasury: Lovelace,
+    /// Reserves balance.
+    pub reserves: Lovelace,
+}
+
+impl NetworkTotals {
+    /// Compute the totals from their inputs.
+    ///
+    /// `max_lovelace_supply` is the Shelley genesis `maxLovelaceSupply`, and
+    /// `pool_stakes` the live stake of each pool. Subtractions saturate at zero.
+    pub fn compute(
+        max_lovelace_supply: Lovelace,
+        treasury_and_reserves: TreasuryAndReserves,
+        pool_stakes: impl IntoIterator<Item = Lovelace>,
+    ) -> Self {
+        let treasury = treasury_and_reserves.treasury.lovelace();
+        let reserves = treasury_and_reserves.reserves.lovelace();
+        Self {
+            total_live_stake: pool_stakes.into_iter().fold(0, Lovelace::saturating_add),
+            circulating_supply: max_lovelace_supply
+                .saturating_sub(reserves)
+                .saturating_sub(treasury),
+            treasury,
+            reserves,
+        }
+    }
+}
+
+/// Query the network-wide ada totals.
+///
+/// Combines the Shelley genesis, the treasury and reserves, and every pool's
+/// live stake. The live stake is summed from the absolute per-pool stake of
+/// `stakePools` rather than from `liveStakeDistribution`, whose ratios are
+/// relative to the very total being computed. The three queries run
+/// concurrently and are not pinned to one ledger state.
+pub async fn network_totals(context: &InteractionContext) -> Result<NetworkTotals> {
+    let (genesis, treasury_and_reserves, pools) = tokio::try_join!(
+        query::genesis_configuration(context, EraWithGenesis::Shelley),
+        query::treasury_and_reserves(context),
+        query::stake_pools(context, None, true),
+    )?;
+
+    let GenesisConfiguration::Shelley(genesis) = genesis else {
+        return Err(OgmiosError::InvalidResponse {
+            message: "expected a Shelley genesis configuration".to_string(),
+        });
+    };
+    let pool_stakes = pools
+        .values()
+        .filter_map(|pool| pool.stake.as_ref().map(|stake| stake.lovelace));
+
+    Ok(NetworkTotals::compute(
+        genesis.max_lovelace_supply,
+        treasury_and_reserves,
+        pool_stakes,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Value;
+
+    fn treasury_and_reserves(treasury: Lovelace, reserves: Lovelace) -> TreasuryAndReserves {
+        TreasuryAndReserves {
+            treasury: Value::ada_only(treasury),
+            reserves: Value::ada_only(reserves),
+        }
+    }
+
+    #[test]
+    fn test_compute_mainnet_like() {
+        let totals = NetworkTotals::compute(
+            45_000_000_000_000_000,
+            treasury_and_reserves(1_500_000_000_000_000, 7_000_000_000_000_000),
+            [10_000_000_000_000_000, 12_000_000_000_000_000, 500_000_000],
+        );
+        assert_eq!(
+            totals,
+            NetworkTotals {
+                total_live_stake: 22_000_000_500_000_000,
+                circulating_supply: 36_500_000_000_000_000,
+                treasury: 1_500_000_000_000_000,
+                reserves: 7_000_000_000_000_000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_compute_saturates() {
+        let totals = NetworkTotals::compute(100, treasury_and_reserves(60, 60), []);
+        assert_eq!(totals.circulating_supply, 0);
+        assert_eq!(totals.total_live_stake, 0);
+        assert_eq!(
+            NetworkTotals::compute(0, treasury_and_reserves(0, 0), [Lovelace::MAX, 1])
+                .total_live_stake,
+            Lovelace::MAX
+        );
+    }
+
+    #[test]
+    fn test_treasury_and_reserves_response() {
+        let value: TreasuryAndReserves = serde_json::from_value(serde_json::json!({
+            "treasury": { "ada": { "lovelace": 1 } },
+            "reserves": { "ada": { "lovelace": 2 } }
+        }))
+        .unwrap();
+        assert_eq!(value, treasury_and_reserves(1, 2));
+    }
+}
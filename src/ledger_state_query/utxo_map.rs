@@ -0,0 +1,205 @@
+//! UTXO query results keyed by output reference.
+
+use crate::connection::InteractionContext;
+use crate::error::{OgmiosError, Result};
+use crate::schema::{Lovelace, TransactionOutput, TransactionOutputReference, Utxo, Value};
+use std::collections::HashMap;
+use std::ops::Deref;
+
+use super::query::{self, UtxoQuery};
+
+/// UTXOs keyed by output reference, for lookups while building transactions.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct UtxoMap(HashMap<TransactionOutputReference, TransactionOutput>);
+
+impl UtxoMap {
+    /// Sum of the values of all outputs, ada and native assets.
+    ///
+    /// Assets whose quantities sum to zero are left out. Fails with
+    /// [`OgmiosError::InvalidResponse`] if the sum overflows.
+    pub fn total_value(&self) -> Result<Value> {
+        self.0
+            .values()
+            .try_fold(Value::ada_only(0), |total, output| total.checked_add(&output.value))
+            .ok_or_else(|| OgmiosError::InvalidResponse {
+                message: "total value of the UTXOs overflows".to_string(),
+            })
+    }
+
+    /// Naive coin selection: pick outputs, largest ada amount first, until
+    /// they cover `target` lovelace.
+    ///
+    /// Returns `None` if all outputs together hold less than `target`. Ties
+    /// are broken by output reference, so the selection is deterministic.
+    /// Outputs carrying native assets are candidates like any other.
+    pub fn select_ada(
+        &self,
+        target: Lovelace,
+    ) -> Option<Vec<(&TransactionOutputReference, &TransactionOutput)>> {
+        let mut candidates: Vec<_> = self.0.iter().collect();
+        candidates.sort_by(|(a_ref, a), (b_ref, b)| {
+            b.value
+                .lovelace()
+                .cmp(&a.value.lovelace())
+                .then_with(|| (&a_ref.id, a_ref.index).cmp(&(&b_ref.id, b_ref.index)))
+        });
+
+        let mut selected = Vec::new();
+        let mut total: Lovelace = 0;
+        for candidate in candidates {
+            if total >= target {
+                break;
+            }
+            total = total.saturating_add(candidate.1.value.lovelace());
+            selected.push(candidate);
+        }
+        (total >= target).then_some(selected)
+    }
+
+    /// Convert into the underlying map.
+    pub fn into_inner(self) -> HashMap<TransactionOutputReference, TransactionOutput> {
+        self.0
+    }
+}
+
+impl Deref for UtxoMap {
+    type Target = HashMap<TransactionOutputReference, TransactionOutput>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for UtxoMap {
+    type Item = (TransactionOutputReference, TransactionOutput);
+    type IntoIter =
+        std::collections::hash_map::IntoIter<TransactionOutputReference, TransactionOutput>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl TryFrom<Vec<Utxo>> for UtxoMap {
+    type Error = OgmiosError;
+
+    /// Key the UTXOs by reference, failing on a duplicate reference.
+    fn try_from(utxos: Vec<Utxo>) -> Result<Self> {
+        let mut map = HashMap::with_capacity(utxos.len());
+        for utxo in utxos {
+            if map.contains_key(&utxo.transaction) {
+                return Err(OgmiosError::InvalidResponse {
+                    message: format!(
+                        "duplicate output reference {}#{}",
+                        utxo.transaction.id, utxo.transaction.index
+                    ),
+                });
+            }
+            map.insert(utxo.transaction, utxo.output);
+        }
+        Ok(Self(map))
+    }
+}
+
+/// Query UTXOs, keyed by output reference.
+///
+/// Fails with [`OgmiosError::InvalidResponse`] if the response lists an output
+/// reference twice.
+pub async fn utxo_map(
+    context: &InteractionContext,
+    query: UtxoQuery,
+) -> Result<UtxoMap> {
+    query::utxo(context, query).await?.try_into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::TransactionId;
+    use serde_json::json;
+
+    const POLICY: &str = "0029cb7c88c7567b63d1a512c0ed626aa169688ec980730c0473b913";
+
+    fn utxo(id: &str, index: u32, value: serde_json::Value) -> Utxo {
+        serde_json::from_value(json!({
+            "transaction": { "id": TransactionId::filled(id), "index": index },
+            "address": "addr_test1",
+            "value": value
+        }))
+        .unwrap()
+    }
+
+    fn utxos() -> UtxoMap {
+        vec![
+            utxo("aa", 0, json!({ "ada": { "lovelace": 5_000_000 } })),
+            utxo(
+                "aa",
+                1,
+                json!({ "ada": { "lovelace": 2_000_000 }, POLICY: { "746f6b656e": 10 } }),
+            ),
+            utxo(
+                "bb",
+                0,
+                json!({ "ada": { "lovelace": 2_000_000 }, POLICY: { "746f6b656e": 5 } }),
+            ),
+        ]
+        .try_into()
+        .unwrap()
+    }
+
+    #[test]
+    fn test_duplicate_reference_is_an_error() {
+        let duplicated = vec![
+            utxo("aa", 0, json!({ "ada": { "lovelace": 1 } })),
+            utxo("aa", 0, json!({ "ada": { "lovelace": 2 } })),
+        ];
+        assert!(matches!(
+            UtxoMap::try_from(duplicated),
+            Err(OgmiosError::InvalidResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_lookup_and_total_value() {
+        let utxos = utxos();
+        let reference = TransactionOutputReference::new(TransactionId::filled("aa"), 0);
+        assert_eq!(utxos[&reference].value.lovelace(), 5_000_000);
+
+        let total = utxos.total_value().unwrap();
+        assert_eq!(total.lovelace(), 9_000_000);
+        let Value::WithAssets { assets, .. } = total else {
+            panic!("expected assets");
+        };
+        assert_eq!(assets[POLICY]["746f6b656e"], 15);
+    }
+
+    #[test]
+    fn test_total_value_overflow_is_an_error() {
+        let utxos: UtxoMap = vec![
+            utxo("aa", 0, json!({ "ada": { "lovelace": u64::MAX } })),
+            utxo("aa", 1, json!({ "ada": { "lovelace": 1 } })),
+        ]
+        .try_into()
+        .unwrap();
+        assert!(matches!(
+            utxos.total_value(),
+            Err(OgmiosError::InvalidResponse { .. })
+        ));
+    }
+
+    #[test]
+    fn test_select_ada() {
+        let utxos = utxos();
+        let selected: Vec<_> = utxos
+            .select_ada(6_000_000)
+            .unwrap()
+            .into_iter()
+            .map(|(reference, _)| (reference.id.clone(), reference.index))
+            .collect();
+        let aa = TransactionId::filled("aa");
+        assert_eq!(selected, [(aa.clone(), 0), (aa, 1)]);
+
+        assert_eq!(utxos.select_ada(0).unwrap().len(), 0);
+        assert!(utxos.select_ada(9_000_001).is_none());
+    }
+}
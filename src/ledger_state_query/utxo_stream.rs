@@ -0,0 +1,221 @@
+//! Incremental decoding of large UTXO query responses.
+
+use crate::connection::InteractionContext;
+use crate::error::{OgmiosError, Result};
+use crate::schema::{JsonRpcError, Utxo};
+use futures_util::{Stream, stream};
+use serde::de::{self, DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use std::fmt;
+use std::sync::Arc;
+use tokio::sync::mpsc;
+use tracing::debug;
+
+use super::query::UtxoQuery;
+
+/// Default number of decoded UTXOs buffered ahead of the consumer.
+pub const DEFAULT_UTXO_STREAM_BUFFER: usize = 256;
+
+/// Query UTXOs, yielding them one by one as they are decoded.
+///
+/// The response text is decoded on a blocking thread that pushes each UTXO
+/// through a channel holding at most `buffer` items, so decoded UTXOs never
+/// pile up beyond that even for responses of tens of megabytes. The raw
+/// response text itself is still received in one WebSocket message. Dropping
+/// the stream stops decoding.
+pub(crate) fn utxo_stream(
+    context: Arc<InteractionContext>,
+    query: UtxoQuery,
+    buffer: usize,
+) -> impl Stream<Item = Result<Utxo>> {
+    let (tx, rx) = mpsc::channel(buffer.max(1));
+
+    tokio::spawn(async move {
+        let response = match context
+            .request_raw("queryLedgerState/utxo", query.params())
+            .await
+        {
+            Ok(response) => response,
+            Err(e) => {
+                let _ = tx.send(Err(e)).await;
+                return;
+            }
+        };
+
+        let decoded = tokio::task::spawn_blocking(move || decode_response(&response, &tx)).await;
+        if let Err(e) = decoded {
+            debug!("UTXO decoding task failed: {}", e);
+        }
+    });
+
+    stream::unfold(rx, |mut rx| async move {
+        rx.recv().await.map(|item| (item, rx))
+    })
+}
+
+/// Decode a JSON-RPC response, sending each element of its `result` array.
+fn decode_response(response: &str, tx: &mpsc::Sender<Result<Utxo>>) {
+    let mut deserializer = serde_json::Deserializer::from_str(response);
+    let decoded = deserializer
+        .deserialize_map(ResponseVisitor { tx })
+        .and_then(|()| deserializer.end());
+    if let Err(e) = decoded {
+        // A closed channel means the consumer went away; nobody to report to.
+        if !tx.is_closed() {
+            let _ = tx.blocking_send(Err(OgmiosError::Json(e)));
+        }
+    }
+}
+
+struct ResponseVisitor<'a> {
+    tx: &'a mpsc::Sender<Result<Utxo>>,
+}
+
+impl<'de> Visitor<'de> for ResponseVisitor<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("a JSON-RPC response")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> std::result::Result<(), A::Error> {
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "result" => map.next_value_seed(ResultSeed { tx: self.tx })?,
+                "error" => {
+                    let error: JsonRpcError = map.next_value()?;
+                    let error = OgmiosError::rpc("queryLedgerState/utxo", error);
+                    let _ = self.tx.blocking_send(Err(error));
+                }
+                _ => {
+                    map.next_value::<IgnoredAny>()?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+struct ResultSeed<'a> {
+    tx: &'a mpsc::Sender<Result<Utxo>>,
+}
+
+impl<'de> DeserializeSeed<'de> for ResultSeed<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(
+        self,
+        deserializer: D,
+    ) -> std::result::Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for ResultSeed<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("an array of UTXOs")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> std::result::Result<(), A::Error> {
+        while let Some(utxo) = seq.next_element::<Utxo>()? {
+            if self.tx.blocking_send(Ok(utxo)).is_err() {
+                return Err(de::Error::custom("UTXO stream dropped"));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    fn utxo(index: u64) -> serde_json::Value {
+        json!({
+            "transaction": { "id": "ab".repeat(32), "index": index },
+            "address": "addr_test1",
+            "value": { "ada": { "lovelace": 1_000_000 } }
+        })
+    }
+
+    #[tokio::test]
+    async fn test_utxo_stream_yields_each_utxo() {
+        let server = MockServer::start(|_, _| {
+            MockReply::result(json!((0..1000).map(utxo).collect::<Vec<_>>()))
+        })
+        .await;
+        let context = Arc::new(server.context().await);
+
+        let utxos: Vec<_> = utxo_stream(
+            context,
+            UtxoQuery::ByAddresses(vec!["addr_test1".into()]),
+            8,
+        )
+        .collect()
+        .await;
+
+        assert_eq!(utxos.len(), 1000);
+        assert_eq!(utxos[999].as_ref().unwrap().transaction.index, 999);
+        assert_eq!(
+            server.requests()[0]["params"],
+            json!({ "addresses": ["addr_test1"] })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_utxo_stream_reports_errors() {
+        let server = MockServer::start(|_, _| MockReply::error(2001, "Unknown address")).await;
+        let context = Arc::new(server.context().await);
+
+        let items: Vec<_> = utxo_stream(context, UtxoQuery::WholeSet, 8).collect().await;
+        assert!(matches!(
+            items.as_slice(),
+            [Err(OgmiosError::Rpc { code: 2001, .. })]
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_utxo_stream_checks_response_id() {
+        let server = MockServer::start(|_, _| {
+            MockReply::Raw(json!({ "jsonrpc": "2.0", "result": [utxo(0)] }).to_string())
+        })
+        .await;
+        let context = Arc::new(server.context().await);
+
+        let items: Vec<_> = utxo_stream(context, UtxoQuery::WholeSet, 8).collect().await;
+        assert!(
+            matches!(
+                items.as_slice(),
+                [Err(OgmiosError::ResponseIdMismatch { got, .. })] if got.is_null()
+            ),
+            "{items:?}"
+        );
+    }
+
+    #[test]
+    fn test_decode_rejects_trailing_characters() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let response = format!("{} garbage", json!({ "result": [utxo(0)] }));
+
+        decode_response(&response, &tx);
+        assert!(rx.try_recv().unwrap().is_ok());
+        assert!(matches!(rx.try_recv(), Ok(Err(OgmiosError::Json(_)))));
+    }
+
+    #[test]
+    fn test_decode_stops_when_consumer_drops() {
+        let (tx, mut rx) = mpsc::channel(1);
+        let response = json!({ "result": [utxo(0), utxo(1), utxo(2)] }).to_string();
+
+        std::thread::scope(|scope| {
+            let decoder = scope.spawn(|| decode_response(&response, &tx));
+            assert!(rx.blocking_recv().unwrap().is_ok());
+            drop(rx);
+            decoder.join().unwrap();
+        });
+    }
+}
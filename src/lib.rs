@@ -21,35 +21,14 @@
 //! # Quick Start
 //!
 //! ```rust,no_run
-//! use ogmios_client::{
-//!     connection::{ConnectionConfig, create_interaction_context, InteractionContextOptions},
-//!     server_health::get_server_health,
-//!     ledger_state_query::LedgerStateQueryClient,
-//! };
+//! use ogmios_client::prelude::*;
 //!
 //! #[tokio::main]
-//! async fn main() -> Result<(), Box<dyn std::error::Error>> {
-//!     // Check server health
-//!     let health = get_server_health(None).await?;
-//!     println!("Connected to {} network", health.network.as_str());
-//!     println!("Sync: {:.2}%", health.network_synchronization * 100.0);
-//!
-//!     // Create a ledger state query client
-//!     let client = LedgerStateQueryClient::connect(
-//!         ConnectionConfig::default(),
-//!         None
-//!     ).await?;
-//!
-//!     // Query current epoch
-//!     let epoch = client.epoch().await?;
-//!     println!("Current epoch: {}", epoch);
-//!
-//!     // Query protocol parameters
-//!     let params = client.protocol_parameters().await?;
-//!     println!("Min fee coefficient: {}", params.min_fee_coefficient);
-//!
-//!     client.shutdown().await?;
-//!     Ok(())
+//! async fn main() -> Result<()> {
+//!     let client = OgmiosClient::connect_url("ws://localhost:1337").await?;
+//!     println!("Sync: {:.2}%", client.health().await?.network_synchronization * 100.0);
+//!     println!("Current epoch: {}", client.ledger().epoch().await?);
+//!     client.shutdown().await
 //! }
 //! ```
 //!
@@ -115,9 +94,8 @@
 //! // Evaluate transaction costs
 //! let tx_cbor = "84a400..."; // Your transaction CBOR
 //! let costs = client.evaluate_transaction(tx_cbor, None).await?;
-//! for cost in &costs {
-//!     println!("Script {:?}: {} mem, {} cpu",
-//!         cost.validator, cost.budget.memory, cost.budget.cpu);
+//! for (validator, budget) in &costs {
+//!     println!("Script {:?}: {} mem, {} cpu", validator, budget.memory, budget.cpu);
 //! }
 //!
 //! // Submit the transaction
@@ -131,24 +109,31 @@
 //!
 //! # Module Structure
 //!
+//! - [`client`]: One client for all mini-protocols
 //! - [`schema`]: All Cardano type definitions (blocks, transactions, etc.)
 //! - [`connection`]: Connection management and WebSocket handling
 //! - [`server_health`]: Server health checking
+//! - [`prometheus`]: Prometheus metrics parsing
 //! - [`chain_synchronization`]: Chain sync client for following the blockchain
 //! - [`transaction_submission`]: Transaction submission and evaluation
 //! - [`mempool_monitoring`]: Mempool monitoring client
 //! - [`ledger_state_query`]: Ledger state queries
 //! - [`util`]: Utility functions
+//! - `fetch`: Off-chain metadata fetching and verification (`fetch` feature)
 //! - [`error`]: Error types
 
 #![warn(missing_docs)]
 #![warn(rust_2018_idioms)]
 
 pub mod chain_synchronization;
+pub mod client;
 pub mod connection;
 pub mod error;
+#[cfg(feature = "fetch")]
+pub mod fetch;
 pub mod ledger_state_query;
 pub mod mempool_monitoring;
+pub mod prometheus;
 // TODO: Add documentation for the schema module
 #[allow(missing_docs)]
 pub mod schema;
@@ -156,7 +141,13 @@ pub mod server_health;
 pub mod transaction_submission;
 pub mod util;
 
+#[cfg(test)]
+#[allow(dead_code)]
+mod mock_server;
+
 // Re-export main types at crate root for convenience
+pub use client::OgmiosClient;
+
 pub use chain_synchronization::{
     ChainSynchronizationClient, ChainSynchronizationClientOptions,
     ChainSynchronizationMessageHandlers, Intersection, create_chain_synchronization_client,
@@ -167,7 +158,10 @@ pub use connection::{
     create_connection_object, create_interaction_context,
 };
 
-pub use error::{OgmiosError, Result};
+pub use error::{
+    AcquireFailure, EvaluateTransactionError, NotReadyReason, OgmiosError, OgmiosErrorCategory,
+    Result, ScriptError, ScriptFailure, SubmitTransactionError, WebSocketError,
+};
 
 pub use ledger_state_query::{
     LedgerStateQueryClient, LedgerStateQueryClientOptions, create_ledger_state_query_client,
@@ -176,7 +170,10 @@ pub use ledger_state_query::{
 pub use mempool_monitoring::{MempoolMonitoringClient, create_mempool_monitoring_client};
 
 pub use server_health::{
-    EnsureServerHealthOptions, ensure_server_health, get_server_health, wait_for_server_ready,
+    EnsureServerHealthOptions, HealthCheckOptions, HealthClient, HealthError, HealthReceiverExt,
+    PollBackoff, ProgressCallback, ServerNotReady, WaitForServerReadyOptions, WatchHandle,
+    ensure_server_health, get_server_health, get_server_health_with_options, get_server_metrics,
+    wait_for_server_ready, wait_for_server_ready_with_options, watch_server_health,
 };
 
 pub use transaction_submission::{
@@ -234,6 +231,7 @@ pub mod prelude {
         ChainSynchronizationClient, ChainSynchronizationClientOptions,
         ChainSynchronizationMessageHandlers, create_chain_synchronization_client,
     };
+    pub use crate::client::OgmiosClient;
     pub use crate::connection::{
         Connection, ConnectionConfig, InteractionContext, InteractionContextOptions,
         InteractionType, create_connection_object, create_interaction_context,
@@ -256,6 +254,7 @@ pub mod prelude {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::schema::DigestBlake2b256;
 
     #[test]
     fn test_prelude_imports() {
@@ -269,13 +268,102 @@ mod tests {
         let origin = Point::origin();
         assert!(matches!(origin, Point::Origin(_)));
 
-        let point = Point::at(12345, "abcdef");
+        let point = Point::at(12345, DigestBlake2b256::filled("abcd"));
         assert!(matches!(point, Point::Point { .. }));
     }
 
+    #[test]
+    fn test_origin_is_strict() {
+        let origin: Point = serde_json::from_str("\"origin\"").unwrap();
+        assert!(origin.is_origin());
+        assert_eq!(origin.slot(), None);
+        assert!(serde_json::from_str::<Point>("\"genesis\"").is_err());
+
+        let tip: Tip = serde_json::from_str("\"origin\"").unwrap();
+        assert_eq!(tip.to_point(), Point::origin());
+        assert!(serde_json::from_str::<Tip>("\"tip\"").is_err());
+
+        let tip: Tip = serde_json::from_value(serde_json::json!({
+            "slot": 5,
+            "id": "ab".repeat(32),
+            "height": 2
+        }))
+        .unwrap();
+        assert_eq!(tip.slot(), Some(5));
+        assert_eq!(tip.to_point(), Point::at(5, DigestBlake2b256::filled("ab")));
+    }
+
     #[test]
     fn test_value_creation() {
         let value = Value::ada_only(1_000_000);
         assert_eq!(value.lovelace(), 1_000_000);
     }
+
+    #[test]
+    fn test_value_deserialization() {
+        let value: Value = serde_json::from_str(r#"{"ada":{"lovelace":2}}"#).unwrap();
+        assert_eq!(value, Value::ada_only(2));
+
+        let policy = "0029cb7c88c7567b63d1a512c0ed626aa169688ec980730c0473b913";
+        let json = format!(r#"{{"ada":{{"lovelace":2}},"{policy}":{{"746f6b656e":-3}}}}"#);
+        let value: Value = serde_json::from_str(&json).unwrap();
+        let Value::WithAssets { assets, .. } = value else {
+            panic!("expected assets");
+        };
+        assert_eq!(assets[policy]["746f6b656e"], -3);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_server_health_timestamps() {
+        let health: schema::ServerHealth = serde_json::from_str(
+            r#"{
+                "currentEra": "conway",
+                "lastKnownTip": {
+                    "slot": 63547246,
+                    "id": "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7",
+                    "height": 2453212
+                },
+                "lastTipUpdate": "2024-06-25T10:47:42.713582433Z",
+                "metrics": {
+                    "sessionDurations": { "max": 0, "mean": 0, "min": 0 },
+                    "totalConnections": 0,
+                    "totalMessages": 0,
+                    "totalUnrouted": 0,
+                    "activeConnections": 0
+                },
+                "startTime": "2024-06-25T08:12:01.019421Z",
+                "network": "preprod",
+                "networkSynchronization": 1,
+                "version": "v6.4.0 (a2a3ab5d)"
+            }"#,
+        )
+        .unwrap();
+
+        let start = health.start_time;
+        assert_eq!(start.timestamp(), 1_719_303_121);
+        assert_eq!(start.timestamp_subsec_micros(), 19_421);
+
+        let now = chrono::DateTime::parse_from_rfc3339("2024-06-25T10:48:00.213582433+00:00")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+        assert_eq!(health.seconds_since_last_tip_update(now), Some(17.5));
+
+        let never_updated = schema::ServerHealth {
+            last_tip_update: None,
+            ..health
+        };
+        assert_eq!(never_updated.seconds_since_last_tip_update(now), None);
+    }
+
+    #[test]
+    fn test_connection_status_serialization() {
+        use schema::ConnectionStatus;
+
+        let status: ConnectionStatus = serde_json::from_str("\"disconnected\"").unwrap();
+        assert_eq!(status, ConnectionStatus::Disconnected);
+        let status: ConnectionStatus = serde_json::from_str("\"syncing\"").unwrap();
+        assert_eq!(status, ConnectionStatus::Other("syncing".to_string()));
+        assert_eq!(serde_json::to_string(&status).unwrap(), "\"syncing\"");
+    }
 }
@@ -6,11 +6,12 @@ use crate::connection::{
 };
 use crate::error::Result;
 use crate::schema::{MempoolSizeAndCapacity, Slot, Transaction, TransactionId};
+use futures_util::stream::Stream;
 use std::sync::Arc;
 
 use super::{
     acquire_mempool, has_transaction, next_transaction, next_transaction_id, release_mempool,
-    size_of_mempool,
+    size_of_mempool, tracker, MempoolStats, TrackOptions, TrackedTxEvent,
 };
 
 /// A mempool monitoring client for observing pending transactions.
@@ -60,6 +61,16 @@ impl MempoolMonitoringClient {
         }
     }
 
+    /// Create a mempool monitoring client on a context shared with other
+    /// clients.
+    ///
+    /// The acquired mempool snapshot belongs to the connection, so share it
+    /// with at most one mempool monitoring client. [`shutdown`](Self::shutdown)
+    /// closes the connection for all users.
+    pub fn with_shared(context: Arc<InteractionContext>) -> Self {
+        Self { context }
+    }
+
     /// Connect to Ogmios and create a new mempool monitoring client.
     ///
     /// # Arguments
@@ -94,8 +105,12 @@ impl MempoolMonitoringClient {
     ///
     /// # Arguments
     ///
-    /// * `id` - The transaction ID to check.
-    pub async fn has_transaction(&self, id: &str) -> Result<bool> {
+    /// * `id` - The transaction ID to check, as a [`TransactionId`] or a string.
+    ///
+    /// A malformed ID fails with
+    /// [`OgmiosError::InvalidDigest`](crate::error::OgmiosError::InvalidDigest)
+    /// before anything is sent.
+    pub async fn has_transaction(&self, id: impl AsRef<str>) -> Result<bool> {
         has_transaction(&self.context, id).await
     }
 
@@ -127,6 +142,80 @@ impl MempoolMonitoringClient {
         release_mempool(&self.context).await
     }
 
+    /// Acquire a snapshot, drain all of its transactions, and summarize them.
+    ///
+    /// The snapshot is released before returning, including when draining fails.
+    pub async fn stats(&self) -> Result<MempoolStats> {
+        self.acquire_mempool().await?;
+
+        let drained: Result<_> = async {
+            let size = self.size_of_mempool().await?;
+            let mut transactions = Vec::with_capacity(size.transactions as usize);
+            while let Some(tx) = self.next_transaction().await? {
+                transactions.push(tx);
+            }
+            Ok((size, transactions))
+        }
+        .await;
+
+        let released = self.release_mempool().await;
+        let (size, transactions) = drained?;
+        released?;
+
+        Ok(MempoolStats::from_transactions(&transactions, &size))
+    }
+
+    /// Track transactions through successive mempool snapshots with default options.
+    ///
+    /// See [`track_with`](Self::track_with).
+    pub fn track(
+        &self,
+        ids: Vec<TransactionId>,
+    ) -> impl Stream<Item = Result<TrackedTxEvent>> + use<> {
+        self.track_with(ids, TrackOptions::default())
+    }
+
+    /// Track transactions through successive mempool snapshots.
+    ///
+    /// Emits `Seen` when a transaction first appears, `StillPending` on each later
+    /// snapshot still containing it, and `Gone` once it disappears — upgraded to
+    /// `LikelyConfirmed`/`LikelyDropped` when a confirmation check is configured.
+    /// Tracking ends for each transaction after its final event, and for all of
+    /// them once `max_duration` elapses.
+    ///
+    /// Failed snapshots are retried on the next poll. When the connection is
+    /// lost, or a snapshot fails with an error that is not
+    /// [retryable](crate::error::OgmiosError::is_retryable), the stream yields
+    /// that error as its last item, leaving the remaining transactions without
+    /// a final event.
+    ///
+    /// The tracker polls on this client's connection, acquiring and releasing
+    /// mempool snapshots; don't use the client for other mempool calls meanwhile.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use ogmios_client::mempool_monitoring::MempoolMonitoringClient;
+    /// use ogmios_client::connection::ConnectionConfig;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let client = MempoolMonitoringClient::connect(ConnectionConfig::default()).await?;
+    /// let mut events = Box::pin(client.track(vec!["3e4f...".parse()?]));
+    /// while let Some(event) = events.next().await {
+    ///     println!("{:?}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn track_with(
+        &self,
+        ids: Vec<TransactionId>,
+        options: TrackOptions,
+    ) -> impl Stream<Item = Result<TrackedTxEvent>> + use<> {
+        tracker::track(self.context.clone(), ids, options)
+    }
+
     /// Shutdown the client.
     pub async fn shutdown(&self) -> Result<()> {
         self.context.shutdown().await
@@ -4,14 +4,42 @@
 //! via Ogmios.
 
 mod client;
+mod stats;
+mod tracker;
 
 pub use client::*;
+pub use stats::*;
+pub use tracker::{
+    ConfirmationCheck, DEFAULT_TRACK_MAX_DURATION, DEFAULT_TRACK_POLL_INTERVAL,
+    DEFAULT_TRACK_UNSEEN_TIMEOUT, TrackOptions, TrackedTxEvent,
+};
 
 use crate::connection::InteractionContext;
-use crate::error::Result;
-use crate::schema::{MempoolSizeAndCapacity, Slot, Transaction, TransactionId};
+use crate::error::{AcquireFailure, OgmiosError, Result};
+use crate::schema::{MempoolSizeAndCapacity, Slot, Transaction, TransactionId, error_codes};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 
+/// Send a mempool request, reporting a request sent before
+/// [`acquire_mempool`] as [`OgmiosError::Acquisition`]. Other errors,
+/// including those of `acquireMempool` itself, are [`OgmiosError::Rpc`].
+async fn request<P: Serialize, R: DeserializeOwned>(
+    context: &InteractionContext,
+    method: &str,
+    params: Option<P>,
+) -> Result<R> {
+    context.request_rpc(method, params).await?.map_err(|e| {
+        if e.code == error_codes::MEMPOOL_MUST_ACQUIRE_FIRST {
+            OgmiosError::Acquisition {
+                failure: AcquireFailure::from(e),
+                point: None,
+            }
+        } else {
+            OgmiosError::rpc(method, e)
+        }
+    })
+}
+
 /// Acquire a snapshot of the mempool.
 ///
 /// This function acquires exclusive access to a snapshot of the current mempool
@@ -23,14 +51,15 @@ use serde::{Deserialize, Serialize};
 ///
 /// # Returns
 ///
-/// The slot number at which the mempool was acquired.
+/// The slot number at which the mempool was acquired, or
+/// [`OgmiosError::Rpc`] if the server rejected the request.
 pub async fn acquire_mempool(context: &InteractionContext) -> Result<Slot> {
     #[derive(Deserialize)]
     struct Response {
         slot: Slot,
     }
 
-    let response: Response = context.request("acquireMempool", None::<()>).await?;
+    let response: Response = request(context, "acquireMempool", None::<()>).await?;
     Ok(response.slot)
 }
 
@@ -39,15 +68,20 @@ pub async fn acquire_mempool(context: &InteractionContext) -> Result<Slot> {
 /// # Arguments
 ///
 /// * `context` - The interaction context.
-/// * `id` - The transaction ID to check.
+/// * `id` - The transaction ID to check, as a [`TransactionId`] or a string.
 ///
 /// # Returns
 ///
 /// `true` if the transaction is in the mempool.
-pub async fn has_transaction(context: &InteractionContext, id: &str) -> Result<bool> {
+///
+/// # Errors
+///
+/// A malformed ID fails with [`OgmiosError::InvalidDigest`] before anything
+/// is sent.
+pub async fn has_transaction(context: &InteractionContext, id: impl AsRef<str>) -> Result<bool> {
     #[derive(Serialize)]
-    struct Params<'a> {
-        id: &'a str,
+    struct Params {
+        id: TransactionId,
     }
 
     #[derive(Deserialize)]
@@ -56,9 +90,8 @@ pub async fn has_transaction(context: &InteractionContext, id: &str) -> Result<b
         has_transaction: bool,
     }
 
-    let response: Response = context
-        .request("hasTransaction", Some(Params { id }))
-        .await?;
+    let id = id.as_ref().parse()?;
+    let response: Response = request(context, "hasTransaction", Some(Params { id })).await?;
     Ok(response.has_transaction)
 }
 
@@ -78,13 +111,14 @@ pub async fn next_transaction_id(context: &InteractionContext) -> Result<Option<
     }
 
     #[derive(Deserialize)]
+    #[allow(clippy::large_enum_variant)]
     #[serde(untagged)]
     enum TransactionWrapper {
         Id { id: TransactionId },
         Full(Transaction),
     }
 
-    let response: Response = context.request("nextTransaction", None::<()>).await?;
+    let response: Response = request(context, "nextTransaction", None::<()>).await?;
 
     Ok(response.transaction.map(|t| match t {
         TransactionWrapper::Id { id } => id,
@@ -112,9 +146,8 @@ pub async fn next_transaction(context: &InteractionContext) -> Result<Option<Tra
         transaction: Option<Transaction>,
     }
 
-    let response: Response = context
-        .request("nextTransaction", Some(Params { fields: "all" }))
-        .await?;
+    let params = Some(Params { fields: "all" });
+    let response: Response = request(context, "nextTransaction", params).await?;
 
     Ok(response.transaction)
 }
@@ -129,7 +162,7 @@ pub async fn next_transaction(context: &InteractionContext) -> Result<Option<Tra
 ///
 /// The mempool size and capacity information.
 pub async fn size_of_mempool(context: &InteractionContext) -> Result<MempoolSizeAndCapacity> {
-    context.request("sizeOfMempool", None::<()>).await
+    request(context, "sizeOfMempool", None::<()>).await
 }
 
 /// Release the acquired mempool snapshot.
@@ -138,14 +171,59 @@ pub async fn size_of_mempool(context: &InteractionContext) -> Result<MempoolSize
 ///
 /// * `context` - The interaction context.
 pub async fn release_mempool(context: &InteractionContext) -> Result<()> {
-    let _: serde_json::Value = context.request("releaseMempool", None::<()>).await?;
+    let _: serde_json::Value = request(context, "releaseMempool", None::<()>).await?;
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+
     #[test]
     fn test_module_compiles() {
         // Basic compilation test
     }
+
+    #[tokio::test]
+    async fn test_requests_before_acquiring_report_acquisition_failure() {
+        let server = MockServer::start(|method, _| match method {
+            "sizeOfMempool" => MockReply::error(4000, "What about acquiring something first?"),
+            _ => MockReply::error(-32601, "Method not found"),
+        })
+        .await;
+        let context = server.context().await;
+
+        let error = size_of_mempool(&context).await.unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::Acquisition {
+                failure: AcquireFailure::MempoolNotAcquired,
+                point: None,
+            }
+        ));
+
+        let error = acquire_mempool(&context).await.unwrap_err();
+        assert!(matches!(error, OgmiosError::Rpc { code: -32601, .. }), "{error:?}");
+
+        let error = release_mempool(&context).await.unwrap_err();
+        assert_eq!(error.code(), Some(-32601));
+    }
+    #[tokio::test]
+    async fn test_has_transaction_takes_string_ids() {
+        let server = MockServer::start(|_, params| {
+            MockReply::result(serde_json::json!({ "hasTransaction": params["id"] == "ab".repeat(32) }))
+        })
+        .await;
+        let context = server.context().await;
+        let id: TransactionId = "ab".repeat(32).parse().unwrap();
+
+        assert!(has_transaction(&context, &id).await.unwrap());
+        assert!(has_transaction(&context, "ab".repeat(32).as_str()).await.unwrap());
+        assert!(!has_transaction(&context, "cd".repeat(32)).await.unwrap());
+
+        let error = has_transaction(&context, "abc").await.unwrap_err();
+        assert!(matches!(error, OgmiosError::InvalidDigest(_)), "{error:?}");
+        assert_eq!(server.count("hasTransaction"), 3);
+    }
 }
@@ -0,0 +1,201 @@
+//! Mempool snapshot statistics.
+
+use crate::schema::{ExUnits, Lovelace, MempoolSizeAndCapacity, NumberOfBytes, Transaction};
+use serde::{Deserialize, Serialize};
+
+/// Fee distribution over the transactions of a mempool snapshot.
+///
+/// Percentiles use the nearest-rank method over the fees that are known.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeStatistics {
+    /// Smallest fee.
+    pub min: Lovelace,
+    /// Median fee (50th percentile).
+    pub median: Lovelace,
+    /// 90th percentile fee.
+    pub p90: Lovelace,
+    /// Largest fee.
+    pub max: Lovelace,
+    /// Sum of all fees.
+    pub total: Lovelace,
+}
+
+impl FeeStatistics {
+    /// Compute fee statistics from a list of fees.
+    ///
+    /// Returns `None` if the list is empty.
+    pub fn from_fees(fees: &[Lovelace]) -> Option<Self> {
+        if fees.is_empty() {
+            return None;
+        }
+
+        let mut sorted = fees.to_vec();
+        sorted.sort_unstable();
+
+        Some(Self {
+            min: sorted[0],
+            median: percentile(&sorted, 50),
+            p90: percentile(&sorted, 90),
+            max: sorted[sorted.len() - 1],
+            total: sorted.iter().sum(),
+        })
+    }
+}
+
+/// Nearest-rank percentile over a sorted, non-empty slice.
+fn percentile(sorted: &[Lovelace], p: usize) -> Lovelace {
+    let rank = (p * sorted.len()).div_ceil(100).max(1);
+    sorted[rank - 1]
+}
+
+/// Summary of a drained mempool snapshot.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::mempool_monitoring::MempoolMonitoringClient;
+/// use ogmios_client::connection::ConnectionConfig;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = MempoolMonitoringClient::connect(ConnectionConfig::default()).await?;
+/// let stats = client.stats().await?;
+/// println!("{} transactions, {} bytes", stats.transactions, stats.bytes);
+/// if let Some(fees) = stats.fees {
+///     println!("median fee: {} lovelace", fees.median);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MempoolStats {
+    /// Number of transactions summarized.
+    pub transactions: u64,
+    /// Number of bytes in the mempool, as reported by the node.
+    pub bytes: NumberOfBytes,
+    /// Size and capacity reported for the snapshot.
+    pub capacity: MempoolSizeAndCapacity,
+    /// Fee distribution over transactions with a known fee.
+    #[serde(default)]
+    pub fees: Option<FeeStatistics>,
+    /// Number of transactions without a `fee` field (e.g. Byron-format).
+    pub transactions_without_fee: u64,
+    /// Number of transactions carrying at least one redeemer.
+    pub script_transactions: u64,
+    /// Sum of the execution units declared by all redeemers.
+    pub total_execution_units: ExUnits,
+}
+
+impl MempoolStats {
+    /// Summarize the transactions of a mempool snapshot.
+    ///
+    /// Transactions missing a `fee` are counted in `transactions_without_fee`
+    /// and excluded from the fee distribution rather than treated as zero.
+    pub fn from_transactions(
+        transactions: &[Transaction],
+        size: &MempoolSizeAndCapacity,
+    ) -> Self {
+        let mut fees = Vec::with_capacity(transactions.len());
+        let mut transactions_without_fee = 0;
+        let mut script_transactions = 0;
+        let mut total_execution_units = ExUnits::new(0, 0);
+
+        for tx in transactions {
+            match tx.fee {
+                Some(fee) => fees.push(fee),
+                None => transactions_without_fee += 1,
+            }
+
+            if !tx.redeemers.is_empty() {
+                script_transactions += 1;
+            }
+
+            for redeemer in &tx.redeemers {
+                total_execution_units.memory += redeemer.execution_units.memory;
+                total_execution_units.cpu += redeemer.execution_units.cpu;
+            }
+        }
+
+        Self {
+            transactions: transactions.len() as u64,
+            bytes: size.bytes,
+            capacity: size.clone(),
+            fees: FeeStatistics::from_fees(&fees),
+            transactions_without_fee,
+            script_transactions,
+            total_execution_units,
+        }
+    }
+
+    /// Fraction of the mempool byte capacity in use (0.0 to 1.0).
+    pub fn utilization(&self) -> f64 {
+        if self.capacity.max_bytes == 0 {
+            return 0.0;
+        }
+        self.bytes as f64 / self.capacity.max_bytes as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn size() -> MempoolSizeAndCapacity {
+        MempoolSizeAndCapacity {
+            bytes: 3000,
+            transactions: 4,
+            max_bytes: 12000,
+            max_transactions: 100,
+        }
+    }
+
+    fn tx(value: serde_json::Value) -> Transaction {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_fee_statistics() {
+        let stats = FeeStatistics::from_fees(&[400, 100, 300, 200]).unwrap();
+        assert_eq!(stats.min, 100);
+        assert_eq!(stats.median, 200);
+        assert_eq!(stats.p90, 400);
+        assert_eq!(stats.max, 400);
+        assert_eq!(stats.total, 1000);
+
+        assert!(FeeStatistics::from_fees(&[]).is_none());
+    }
+
+    #[test]
+    fn test_mempool_stats_from_transactions() {
+        let redeemer = json!({
+            "validator": { "purpose": "mint", "index": 0 },
+            "redeemer": "d87980",
+            "executionUnits": { "memory": 10, "cpu": 20 }
+        });
+        let transactions = vec![
+            tx(json!({ "id": "a".repeat(64), "fee": 170000 })),
+            tx(json!({ "id": "b".repeat(64), "fee": 200000 })),
+            tx(json!({ "id": "c".repeat(64) })),
+            tx(json!({
+                "id": "d".repeat(64),
+                "fee": 350000,
+                "redeemers": [redeemer.clone(), redeemer]
+            })),
+        ];
+
+        let stats = MempoolStats::from_transactions(&transactions, &size());
+        assert_eq!(stats.transactions, 4);
+        assert_eq!(stats.bytes, 3000);
+        assert_eq!(stats.transactions_without_fee, 1);
+        assert_eq!(stats.script_transactions, 1);
+        assert_eq!(stats.total_execution_units, ExUnits::new(20, 40));
+
+        let fees = stats.fees.unwrap();
+        assert_eq!(fees.min, 170000);
+        assert_eq!(fees.median, 200000);
+        assert_eq!(fees.max, 350000);
+        assert_eq!(stats.utilization(), 0.25);
+    }
+}
@@ -0,0 +1,503 @@
+//! Tracking of transactions through successive mempool snapshots.
+
+use crate::connection::InteractionContext;
+use crate::error::Result;
+use crate::schema::{Slot, TransactionId};
+use futures_util::future::BoxFuture;
+use futures_util::stream::{self, Stream};
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::Instant;
+use tracing::{debug, warn};
+
+use super::{acquire_mempool, has_transaction, release_mempool};
+
+/// Default interval between two mempool snapshots (5 seconds).
+pub const DEFAULT_TRACK_POLL_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Default maximum tracking duration (1 hour).
+pub const DEFAULT_TRACK_MAX_DURATION: Duration = Duration::from_secs(60 * 60);
+
+/// Default time to wait for a transaction to show up at all (2 minutes).
+pub const DEFAULT_TRACK_UNSEEN_TIMEOUT: Duration = Duration::from_secs(2 * 60);
+
+/// Callback deciding whether a transaction that left the mempool made it on-chain.
+///
+/// Typically backed by a chain index or a UTXO lookup on one of the transaction outputs.
+pub type ConfirmationCheck = Arc<dyn Fn(TransactionId) -> BoxFuture<'static, bool> + Send + Sync>;
+
+/// An event emitted while tracking transactions through the mempool.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TrackedTxEvent {
+    /// The transaction was seen in the mempool for the first time.
+    Seen {
+        /// Transaction ID.
+        id: TransactionId,
+        /// Slot of the snapshot the transaction was first seen in.
+        slot: Slot,
+    },
+    /// The transaction is still in the mempool.
+    StillPending {
+        /// Transaction ID.
+        id: TransactionId,
+        /// Time elapsed since the transaction was first seen.
+        age: Duration,
+    },
+    /// The transaction left the mempool and no confirmation check is configured.
+    Gone {
+        /// Transaction ID.
+        id: TransactionId,
+        /// Slot of the last snapshot containing the transaction.
+        last_seen_slot: Slot,
+    },
+    /// The transaction left the mempool and the confirmation check succeeded.
+    LikelyConfirmed {
+        /// Transaction ID.
+        id: TransactionId,
+        /// Slot of the last snapshot containing the transaction.
+        last_seen_slot: Slot,
+    },
+    /// The transaction left the mempool and the confirmation check failed.
+    LikelyDropped {
+        /// Transaction ID.
+        id: TransactionId,
+        /// Slot of the last snapshot containing the transaction.
+        last_seen_slot: Slot,
+    },
+    /// The transaction never showed up in the mempool within the unseen timeout.
+    NeverSeen {
+        /// Transaction ID.
+        id: TransactionId,
+    },
+    /// The maximum tracking duration elapsed while the transaction was still tracked.
+    Expired {
+        /// Transaction ID.
+        id: TransactionId,
+    },
+}
+
+impl TrackedTxEvent {
+    /// Get the ID of the transaction this event is about.
+    pub fn id(&self) -> &TransactionId {
+        match self {
+            TrackedTxEvent::Seen { id, .. }
+            | TrackedTxEvent::StillPending { id, .. }
+            | TrackedTxEvent::Gone { id, .. }
+            | TrackedTxEvent::LikelyConfirmed { id, .. }
+            | TrackedTxEvent::LikelyDropped { id, .. }
+            | TrackedTxEvent::NeverSeen { id }
+            | TrackedTxEvent::Expired { id } => id,
+        }
+    }
+
+    /// Check whether this event ends the tracking of its transaction.
+    pub fn is_final(&self) -> bool {
+        !matches!(
+            self,
+            TrackedTxEvent::Seen { .. } | TrackedTxEvent::StillPending { .. }
+        )
+    }
+}
+
+/// Options for tracking transactions through the mempool.
+#[derive(Clone)]
+pub struct TrackOptions {
+    /// Interval between two mempool snapshots.
+    pub poll_interval: Duration,
+    /// Maximum time to track any transaction; remaining ones are reported as expired.
+    pub max_duration: Duration,
+    /// Time to wait for a transaction to show up before reporting it as never seen.
+    pub unseen_timeout: Duration,
+    /// Optional check upgrading `Gone` to `LikelyConfirmed` or `LikelyDropped`.
+    pub confirmation_check: Option<ConfirmationCheck>,
+}
+
+impl Default for TrackOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: DEFAULT_TRACK_POLL_INTERVAL,
+            max_duration: DEFAULT_TRACK_MAX_DURATION,
+            unseen_timeout: DEFAULT_TRACK_UNSEEN_TIMEOUT,
+            confirmation_check: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for TrackOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TrackOptions")
+            .field("poll_interval", &self.poll_interval)
+            .field("max_duration", &self.max_duration)
+            .field("unseen_timeout", &self.unseen_timeout)
+            .field("confirmation_check", &self.confirmation_check.is_some())
+            .finish()
+    }
+}
+
+/// Tracking state of a single transaction.
+#[derive(Debug, Clone, Copy)]
+enum TrackState {
+    /// Not seen in any snapshot yet.
+    Unseen,
+    /// Present in the last snapshot.
+    Present { first_seen: Instant, last_seen_slot: Slot },
+}
+
+/// Pure state machine turning successive snapshots into tracking events.
+#[derive(Debug)]
+pub(crate) struct Tracker {
+    started: Instant,
+    max_duration: Duration,
+    unseen_timeout: Duration,
+    tracked: HashMap<TransactionId, TrackState>,
+}
+
+impl Tracker {
+    pub(crate) fn new(ids: Vec<TransactionId>, options: &TrackOptions, now: Instant) -> Self {
+        Self {
+            started: now,
+            max_duration: options.max_duration,
+            unseen_timeout: options.unseen_timeout,
+            tracked: ids.into_iter().map(|id| (id, TrackState::Unseen)).collect(),
+        }
+    }
+
+    pub(crate) fn is_done(&self) -> bool {
+        self.tracked.is_empty()
+    }
+
+    pub(crate) fn ids(&self) -> Vec<TransactionId> {
+        self.tracked.keys().cloned().collect()
+    }
+
+    /// Apply a snapshot, given the presence of each tracked id at `slot`.
+    ///
+    /// Departed transactions are returned as `Gone`; upgrading them is up to the caller.
+    pub(crate) fn observe(
+        &mut self,
+        slot: Slot,
+        presence: &HashMap<TransactionId, bool>,
+        now: Instant,
+    ) -> Vec<TrackedTxEvent> {
+        let mut events = Vec::new();
+        let mut finished = Vec::new();
+
+        for (id, state) in self.tracked.iter_mut() {
+            let present = presence.get(id).copied().unwrap_or(false);
+            match (*state, present) {
+                (TrackState::Unseen, true) => {
+                    *state = TrackState::Present {
+                        first_seen: now,
+                        last_seen_slot: slot,
+                    };
+                    events.push(TrackedTxEvent::Seen {
+                        id: id.clone(),
+                        slot,
+                    });
+                }
+                (TrackState::Unseen, false) => {}
+                (TrackState::Present { first_seen, .. }, true) => {
+                    *state = TrackState::Present {
+                        first_seen,
+                        last_seen_slot: slot,
+                    };
+                    events.push(TrackedTxEvent::StillPending {
+                        id: id.clone(),
+                        age: now.duration_since(first_seen),
+                    });
+                }
+                (TrackState::Present { last_seen_slot, .. }, false) => {
+                    events.push(TrackedTxEvent::Gone {
+                        id: id.clone(),
+                        last_seen_slot,
+                    });
+                    finished.push(id.clone());
+                }
+            }
+        }
+
+        for id in finished {
+            self.tracked.remove(&id);
+        }
+
+        events.extend(self.expire(now));
+        events
+    }
+
+    /// Give up on transactions past the unseen timeout or the maximum duration.
+    pub(crate) fn expire(&mut self, now: Instant) -> Vec<TrackedTxEvent> {
+        let elapsed = now.duration_since(self.started);
+
+        if elapsed >= self.max_duration {
+            return self
+                .tracked
+                .drain()
+                .map(|(id, _)| TrackedTxEvent::Expired { id })
+                .collect();
+        }
+
+        if elapsed < self.unseen_timeout {
+            return Vec::new();
+        }
+
+        let unseen: Vec<TransactionId> = self
+            .tracked
+            .iter()
+            .filter(|(_, state)| matches!(state, TrackState::Unseen))
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        unseen
+            .into_iter()
+            .map(|id| {
+                self.tracked.remove(&id);
+                TrackedTxEvent::NeverSeen { id }
+            })
+            .collect()
+    }
+}
+
+/// Check the presence of each id in a freshly acquired mempool snapshot.
+async fn snapshot_presence(
+    context: &InteractionContext,
+    ids: &[TransactionId],
+) -> Result<(Slot, HashMap<TransactionId, bool>)> {
+    let slot = acquire_mempool(context).await?;
+
+    let checked: Result<HashMap<TransactionId, bool>> = async {
+        let mut presence = HashMap::with_capacity(ids.len());
+        for id in ids {
+            presence.insert(id.clone(), has_transaction(context, id).await?);
+        }
+        Ok(presence)
+    }
+    .await;
+
+    let released = release_mempool(context).await;
+    let presence = checked?;
+    released?;
+
+    Ok((slot, presence))
+}
+
+/// Upgrade a `Gone` event using the confirmation check, if any.
+async fn resolve_gone(event: TrackedTxEvent, check: Option<&ConfirmationCheck>) -> TrackedTxEvent {
+    match (event, check) {
+        (TrackedTxEvent::Gone { id, last_seen_slot }, Some(check)) => {
+            if check(id.clone()).await {
+                TrackedTxEvent::LikelyConfirmed { id, last_seen_slot }
+            } else {
+                TrackedTxEvent::LikelyDropped { id, last_seen_slot }
+            }
+        }
+        (event, _) => event,
+    }
+}
+
+/// Track transactions through successive mempool snapshots.
+///
+/// A background task polls the mempool every `poll_interval` and emits events
+/// through the returned stream. The task stops when every transaction reached
+/// a final event, when `max_duration` elapses, or when the stream is dropped.
+/// Failed polls are logged and retried on the next interval, unless the error
+/// is not [retryable](crate::error::OgmiosError::is_retryable) or the connection
+/// was lost: the stream then yields that error as its last item.
+pub(crate) fn track(
+    context: Arc<InteractionContext>,
+    ids: Vec<TransactionId>,
+    options: TrackOptions,
+) -> impl Stream<Item = Result<TrackedTxEvent>> {
+    let (tx, rx) = mpsc::channel(ids.len().max(1) * 2);
+
+    tokio::spawn(async move {
+        let mut tracker = Tracker::new(ids, &options, Instant::now());
+
+        while !tracker.is_done() {
+            match snapshot_presence(&context, &tracker.ids()).await {
+                Ok((slot, presence)) => {
+                    for event in tracker.observe(slot, &presence, Instant::now()) {
+                        let event = resolve_gone(event, options.confirmation_check.as_ref()).await;
+                        if tx.send(Ok(event)).await.is_err() {
+                            debug!("Mempool tracking stream dropped, stopping tracker");
+                            return;
+                        }
+                    }
+                }
+                Err(e) if !e.is_retryable() || e.is_connection_error() => {
+                    warn!("Mempool snapshot failed, stopping tracker: {}", e);
+                    let _ = tx.send(Err(e)).await;
+                    return;
+                }
+                Err(e) => {
+                    warn!("Mempool snapshot failed while tracking transactions: {}", e);
+                    for event in tracker.expire(Instant::now()) {
+                        if tx.send(Ok(event)).await.is_err() {
+                            return;
+                        }
+                    }
+                }
+            }
+
+            if tracker.is_done() {
+                break;
+            }
+            // Waiting for a transaction to show up sends nothing, so watch the
+            // stream rather than notice its drop on the next send.
+            tokio::select! {
+                _ = tokio::time::sleep(options.poll_interval) => {}
+                _ = tx.closed() => {
+                    debug!("Mempool tracking stream dropped, stopping tracker");
+                    return;
+                }
+            }
+        }
+    });
+
+    stream::unfold(rx, |mut rx| async move { rx.recv().await.map(|event| (event, rx)) })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+    use futures_util::StreamExt;
+    use serde_json::json;
+
+    /// A transaction ID made of `c` repeated.
+    fn tx(c: &str) -> TransactionId {
+        c.repeat(64).parse().unwrap()
+    }
+
+    fn presence(entries: &[(&str, bool)]) -> HashMap<TransactionId, bool> {
+        entries.iter().map(|(id, present)| (tx(id), *present)).collect()
+    }
+
+    #[test]
+    fn test_tracker_seen_pending_gone() {
+        let start = Instant::now();
+        let ids = vec![tx("a")];
+        let mut tracker = Tracker::new(ids, &TrackOptions::default(), start);
+
+        let events = tracker.observe(10, &presence(&[("a", true)]), start);
+        assert_eq!(
+            events,
+            vec![TrackedTxEvent::Seen {
+                id: tx("a"),
+                slot: 10
+            }]
+        );
+
+        let later = start + Duration::from_secs(5);
+        let events = tracker.observe(15, &presence(&[("a", true)]), later);
+        assert_eq!(
+            events,
+            vec![TrackedTxEvent::StillPending {
+                id: tx("a"),
+                age: Duration::from_secs(5)
+            }]
+        );
+
+        let events = tracker.observe(20, &presence(&[("a", false)]), later);
+        assert_eq!(
+            events,
+            vec![TrackedTxEvent::Gone {
+                id: tx("a"),
+                last_seen_slot: 15
+            }]
+        );
+        assert!(tracker.is_done());
+    }
+
+    #[test]
+    fn test_tracker_timeouts() {
+        let start = Instant::now();
+        let options = TrackOptions {
+            unseen_timeout: Duration::from_secs(10),
+            max_duration: Duration::from_secs(60),
+            ..Default::default()
+        };
+        let ids = vec![tx("a"), tx("b")];
+        let mut tracker = Tracker::new(ids, &options, start);
+
+        tracker.observe(1, &presence(&[("a", true), ("b", false)]), start);
+        let events = tracker.observe(
+            2,
+            &presence(&[("a", true), ("b", false)]),
+            start + Duration::from_secs(10),
+        );
+        assert!(events.contains(&TrackedTxEvent::NeverSeen { id: tx("b") }));
+
+        let events = tracker.observe(
+            3,
+            &presence(&[("a", true)]),
+            start + Duration::from_secs(60),
+        );
+        assert!(events.contains(&TrackedTxEvent::Expired { id: tx("a") }));
+        assert!(tracker.is_done());
+    }
+
+    #[tokio::test]
+    async fn test_track_stops_when_stream_dropped() {
+        let server = MockServer::start(|method, _| match method {
+            "acquireMempool" => MockReply::result(json!({ "acquired": "mempool", "slot": 1 })),
+            "hasTransaction" => MockReply::result(json!({ "hasTransaction": false })),
+            _ => MockReply::result(json!({ "released": "mempool" })),
+        })
+        .await;
+        let options = TrackOptions {
+            poll_interval: Duration::from_millis(10),
+            ..Default::default()
+        };
+
+        let ids = vec![tx("a")];
+        let events = track(Arc::new(server.context().await), ids, options);
+        while server.count("releaseMempool") < 2 {
+            tokio::time::sleep(Duration::from_millis(5)).await;
+        }
+        drop(events);
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        let snapshots = server.count("acquireMempool");
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        assert_eq!(server.count("acquireMempool"), snapshots);
+    }
+
+    #[tokio::test]
+    async fn test_track_stops_on_errors_not_retryable() {
+        let server = MockServer::start(|_, _| MockReply::error(-32601, "Method not found")).await;
+        let options = TrackOptions {
+            poll_interval: Duration::from_millis(10),
+            ..Default::default()
+        };
+
+        let events = track(Arc::new(server.context().await), vec![tx("a")], options);
+        let events: Vec<_> = tokio::time::timeout(Duration::from_secs(1), events.collect())
+            .await
+            .unwrap();
+        let [Err(error)] = events.as_slice() else {
+            panic!("expected only the error, got {events:?}");
+        };
+        assert_eq!(error.code(), Some(-32601));
+        assert_eq!(server.count("acquireMempool"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_gone_with_check() {
+        let check: ConfirmationCheck = Arc::new(|id| Box::pin(async move { id == tx("a") }));
+        let gone = |id: &str| TrackedTxEvent::Gone {
+            id: tx(id),
+            last_seen_slot: 7,
+        };
+
+        assert!(matches!(
+            resolve_gone(gone("a"), Some(&check)).await,
+            TrackedTxEvent::LikelyConfirmed { .. }
+        ));
+        assert!(matches!(
+            resolve_gone(gone("b"), Some(&check)).await,
+            TrackedTxEvent::LikelyDropped { .. }
+        ));
+        assert!(resolve_gone(gone("c"), None).await.is_final());
+    }
+}
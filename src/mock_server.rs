@@ -0,0 +1,213 @@
+//! In-process mock Ogmios server for tests.
+//!
+//! Accepts WebSocket connections, records every JSON-RPC request it receives,
+//! and answers each one with the reply produced by a handler closure.
+
+use crate::connection::{
+    ConnectionConfig, InteractionContext, InteractionContextOptions, create_interaction_context,
+};
+use futures_util::{SinkExt, StreamExt};
+use serde_json::{Value, json};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+/// The reply the mock server sends for a request.
+#[derive(Debug, Clone)]
+pub(crate) enum MockReply {
+    /// A successful JSON-RPC result.
+    Result(Value),
+    /// A JSON-RPC error.
+    Error {
+        code: i32,
+        message: String,
+        data: Option<Value>,
+    },
+    /// Send the inner reply after a delay.
+    Delayed(Duration, Box<MockReply>),
+    /// Send a raw text frame instead of a well-formed response.
+    Raw(String),
+    /// Never answer.
+    NoReply,
+    /// Close the connection instead of answering.
+    Close,
+}
+
+impl MockReply {
+    pub(crate) fn result(value: Value) -> Self {
+        MockReply::Result(value)
+    }
+
+    pub(crate) fn error(code: i32, message: &str) -> Self {
+        MockReply::Error {
+            code,
+            message: message.to_string(),
+            data: None,
+        }
+    }
+
+    pub(crate) fn delayed(self, delay: Duration) -> Self {
+        MockReply::Delayed(delay, Box::new(self))
+    }
+}
+
+type Handler = dyn Fn(&str, &Value) -> MockReply + Send + Sync;
+
+/// A running mock server.
+pub(crate) struct MockServer {
+    port: u16,
+    requests: Arc<Mutex<Vec<Value>>>,
+    accepts: Arc<Mutex<usize>>,
+}
+
+impl MockServer {
+    /// Start a mock server answering every request with `handler(method, params)`.
+    pub(crate) async fn start<F>(handler: F) -> Self
+    where
+        F: Fn(&str, &Value) -> MockReply + Send + Sync + 'static,
+    {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let accepts = Arc::new(Mutex::new(0));
+        let handler: Arc<Handler> = Arc::new(handler);
+
+        let requests_clone = requests.clone();
+        let accepts_clone = accepts.clone();
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                *accepts_clone.lock().unwrap() += 1;
+                let handler = handler.clone();
+                let requests = requests_clone.clone();
+                tokio::spawn(async move {
+                    let Ok(ws) = tokio_tungstenite::accept_async(stream).await else {
+                        return;
+                    };
+                    serve(ws, handler, requests).await;
+                });
+            }
+        });
+
+        Self {
+            port,
+            requests,
+            accepts,
+        }
+    }
+
+    /// Connection configuration pointing at this server.
+    pub(crate) fn config(&self) -> ConnectionConfig {
+        ConnectionConfig::new("127.0.0.1", self.port)
+    }
+
+    /// Open an interaction context against this server.
+    pub(crate) async fn context(&self) -> InteractionContext {
+        create_interaction_context(InteractionContextOptions {
+            connection: self.config(),
+            ..Default::default()
+        })
+        .await
+        .unwrap()
+    }
+
+    /// All requests received so far, in arrival order.
+    pub(crate) fn requests(&self) -> Vec<Value> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    /// Methods of all requests received so far, in arrival order.
+    pub(crate) fn methods(&self) -> Vec<String> {
+        self.requests()
+            .iter()
+            .filter_map(|r| r["method"].as_str().map(str::to_string))
+            .collect()
+    }
+
+    /// Number of requests received for a given method.
+    pub(crate) fn count(&self, method: &str) -> usize {
+        self.methods().iter().filter(|m| *m == method).count()
+    }
+
+    /// Number of accepted TCP connections.
+    pub(crate) fn accepts(&self) -> usize {
+        *self.accepts.lock().unwrap()
+    }
+}
+
+async fn serve(
+    ws: tokio_tungstenite::WebSocketStream<tokio::net::TcpStream>,
+    handler: Arc<Handler>,
+    requests: Arc<Mutex<Vec<Value>>>,
+) {
+    let (mut write, mut read) = ws.split();
+    let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+
+    tokio::spawn(async move {
+        while let Some(message) = rx.recv().await {
+            if write.send(message).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    while let Some(Ok(message)) = read.next().await {
+        let Message::Text(text) = message else {
+            continue;
+        };
+        let Ok(request) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        requests.lock().unwrap().push(request.clone());
+
+        let method = request["method"].as_str().unwrap_or_default().to_string();
+        let reply = handler(&method, &request["params"]);
+        if matches!(reply, MockReply::Close) {
+            let _ = tx.send(Message::Close(None));
+            break;
+        }
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            if let Some(text) = render(reply, &method, &request["id"]).await {
+                let _ = tx.send(Message::Text(text));
+            }
+        });
+    }
+}
+
+async fn render(mut reply: MockReply, method: &str, id: &Value) -> Option<String> {
+    while let MockReply::Delayed(delay, inner) = reply {
+        tokio::time::sleep(delay).await;
+        reply = *inner;
+    }
+
+    let response = match reply {
+        MockReply::Result(result) => json!({
+            "jsonrpc": "2.0",
+            "method": method,
+            "result": result,
+            "id": id,
+        }),
+        MockReply::Error {
+            code,
+            message,
+            data,
+        } => {
+            let mut error = json!({ "code": code, "message": message });
+            if let Some(data) = data {
+                error["data"] = data;
+            }
+            json!({
+                "jsonrpc": "2.0",
+                "method": method,
+                "error": error,
+                "id": id,
+            })
+        }
+        MockReply::Raw(text) => return Some(text),
+        MockReply::NoReply | MockReply::Close | MockReply::Delayed(..) => return None,
+    };
+
+    Some(response.to_string())
+}
@@ -0,0 +1,310 @@
+//! Prometheus metrics exposed by Ogmios.
+//!
+//! Ogmios serves `/metrics` in the Prometheus text exposition format, with
+//! counters that are not part of `/health`. Use
+//! [`get_server_metrics`](crate::server_health::get_server_metrics) to fetch
+//! and parse it.
+
+use crate::error::{OgmiosError, Result};
+use std::collections::{BTreeMap, HashMap};
+use std::ops::Deref;
+
+/// One sample of a metric.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MetricSample {
+    /// Sample labels.
+    pub labels: BTreeMap<String, String>,
+    /// Sample value.
+    pub value: f64,
+    /// Timestamp in milliseconds since the Unix epoch, if given.
+    pub timestamp: Option<i64>,
+}
+
+/// Metrics parsed from the Prometheus text format, keyed by metric name.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::prometheus::PrometheusMetrics;
+///
+/// let metrics = PrometheusMetrics::parse(
+///     "# TYPE ogmios_active_connections gauge\nogmios_active_connections 3\n",
+/// )
+/// .unwrap();
+/// assert_eq!(metrics.active_connections(), Some(3));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PrometheusMetrics(HashMap<String, Vec<MetricSample>>);
+
+impl PrometheusMetrics {
+    /// Parse a body in the Prometheus text exposition format.
+    ///
+    /// Comments, including `# HELP` and `# TYPE`, are skipped.
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut metrics: HashMap<String, Vec<MetricSample>> = HashMap::new();
+
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, sample) =
+                parse_sample(line).ok_or_else(|| OgmiosError::InvalidResponse {
+                    message: format!("invalid metrics line {}: {}", number + 1, line),
+                })?;
+            metrics.entry(name).or_default().push(sample);
+        }
+
+        Ok(Self(metrics))
+    }
+
+    /// Value of the first sample of a metric.
+    pub fn value(&self, name: &str) -> Option<f64> {
+        self.0.get(name)?.first().map(|sample| sample.value)
+    }
+
+    /// Value of the sample of a metric carrying the given label.
+    pub fn value_with_label(&self, name: &str, label: &str, value: &str) -> Option<f64> {
+        self.0
+            .get(name)?
+            .iter()
+            .find(|sample| sample.labels.get(label).is_some_and(|v| v == value))
+            .map(|sample| sample.value)
+    }
+
+    /// Number of currently connected clients.
+    pub fn active_connections(&self) -> Option<u64> {
+        self.count("ogmios_active_connections")
+    }
+
+    /// Number of connections since the server started.
+    pub fn total_connections(&self) -> Option<u64> {
+        self.count("ogmios_total_connections")
+    }
+
+    /// Number of messages processed since the server started.
+    pub fn total_messages(&self) -> Option<u64> {
+        self.count("ogmios_total_messages")
+    }
+
+    /// Number of messages that could not be routed since the server started.
+    pub fn total_unrouted(&self) -> Option<u64> {
+        self.count("ogmios_total_unrouted")
+    }
+
+    /// CPU time spent in garbage collection, in seconds.
+    pub fn runtime_gc_cpu_time(&self) -> Option<f64> {
+        self.value("ogmios_runtime_gc_cpu_time")
+    }
+
+    /// CPU time spent by the server, in seconds.
+    pub fn runtime_cpu_time(&self) -> Option<f64> {
+        self.value("ogmios_runtime_cpu_time")
+    }
+
+    /// Maximum heap size, in bytes.
+    pub fn runtime_max_heap_size(&self) -> Option<u64> {
+        self.count("ogmios_runtime_max_heap_size")
+    }
+
+    /// Current heap size, in bytes.
+    pub fn runtime_current_heap_size(&self) -> Option<u64> {
+        self.count("ogmios_runtime_current_heap_size")
+    }
+
+    /// Network synchronization (0.0 to 1.0).
+    pub fn network_synchronization(&self) -> Option<f64> {
+        self.value("ogmios_network_synchronization")
+    }
+
+    /// Convert into the underlying map.
+    pub fn into_inner(self) -> HashMap<String, Vec<MetricSample>> {
+        self.0
+    }
+
+    fn count(&self, name: &str) -> Option<u64> {
+        self.value(name)
+            .filter(|value| value.is_finite() && *value >= 0.0)
+            .map(|value| value as u64)
+    }
+}
+
+impl Deref for PrometheusMetrics {
+    type Target = HashMap<String, Vec<MetricSample>>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl IntoIterator for PrometheusMetrics {
+    type Item = (String, Vec<MetricSample>);
+    type IntoIter = std::collections::hash_map::IntoIter<String, Vec<MetricSample>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// Parse `name{label="value",...} value [timestamp]`.
+fn parse_sample(line: &str) -> Option<(String, MetricSample)> {
+    let name_end = line
+        .find(|c: char| c == '{' || c.is_whitespace())
+        .unwrap_or(line.len());
+    let name = &line[..name_end];
+    if name.is_empty() {
+        return None;
+    }
+
+    let mut rest = &line[name_end..];
+    let mut labels = BTreeMap::new();
+    if let Some(after_brace) = rest.strip_prefix('{') {
+        rest = parse_labels(after_brace, &mut labels)?;
+    }
+
+    let mut fields = rest.split_whitespace();
+    let value = parse_value(fields.next()?)?;
+    let timestamp = match fields.next() {
+        Some(timestamp) => Some(timestamp.parse().ok()?),
+        None => None,
+    };
+    if fields.next().is_some() {
+        return None;
+    }
+
+    Some((
+        name.to_string(),
+        MetricSample {
+            labels,
+            value,
+            timestamp,
+        },
+    ))
+}
+
+/// Parse labels up to the closing brace, returning the rest of the line.
+fn parse_labels<'a>(mut rest: &'a str, labels: &mut BTreeMap<String, String>) -> Option<&'a str> {
+    loop {
+        rest = rest.trim_start();
+        if let Some(after) = rest.strip_prefix('}') {
+            return Some(after);
+        }
+
+        let (name, after_name) = rest.split_once('=')?;
+        let mut chars = after_name.trim_start().strip_prefix('"')?.char_indices();
+        let mut value = String::new();
+        let end = loop {
+            match chars.next()? {
+                (i, '"') => break i,
+                (_, '\\') => match chars.next()?.1 {
+                    'n' => value.push('\n'),
+                    c => value.push(c),
+                },
+                (_, c) => value.push(c),
+            }
+        };
+        labels.insert(name.trim().to_string(), value);
+
+        let quoted = after_name.trim_start();
+        rest = quoted[1 + end + 1..].trim_start();
+        rest = rest.strip_prefix(',').unwrap_or(rest);
+    }
+}
+
+fn parse_value(value: &str) -> Option<f64> {
+    match value {
+        "+Inf" => Some(f64::INFINITY),
+        "-Inf" => Some(f64::NEG_INFINITY),
+        "NaN" => Some(f64::NAN),
+        _ => value.parse().ok(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METRICS: &str = r#"# TYPE ogmios_active_connections gauge
+ogmios_active_connections  2
+# TYPE ogmios_runtime_cpu_time counter
+ogmios_runtime_cpu_time  1056.326712
+# TYPE ogmios_runtime_current_heap_size gauge
+ogmios_runtime_current_heap_size  1217
+# TYPE ogmios_runtime_gc_cpu_time counter
+ogmios_runtime_gc_cpu_time  734.481
+# TYPE ogmios_runtime_max_heap_size gauge
+ogmios_runtime_max_heap_size  5301
+# TYPE ogmios_session_duration_max gauge
+ogmios_session_duration_max  3512.5
+# TYPE ogmios_total_connections counter
+ogmios_total_connections  124
+# TYPE ogmios_total_messages counter
+ogmios_total_messages  18249
+# TYPE ogmios_total_unrouted counter
+ogmios_total_unrouted  3
+# TYPE ogmios_network_synchronization gauge
+ogmios_network_synchronization  0.99998
+"#;
+
+    #[test]
+    fn test_parse_ogmios_metrics() {
+        let metrics = PrometheusMetrics::parse(METRICS).unwrap();
+        assert_eq!(metrics.len(), 10);
+        assert_eq!(metrics.active_connections(), Some(2));
+        assert_eq!(metrics.total_connections(), Some(124));
+        assert_eq!(metrics.total_messages(), Some(18249));
+        assert_eq!(metrics.total_unrouted(), Some(3));
+        assert_eq!(metrics.runtime_gc_cpu_time(), Some(734.481));
+        assert_eq!(metrics.runtime_cpu_time(), Some(1056.326712));
+        assert_eq!(metrics.runtime_max_heap_size(), Some(5301));
+        assert_eq!(metrics.runtime_current_heap_size(), Some(1217));
+        assert_eq!(metrics.network_synchronization(), Some(0.99998));
+        assert_eq!(metrics.value("ogmios_session_duration_max"), Some(3512.5));
+        assert_eq!(metrics.value("ogmios_unknown"), None);
+    }
+
+    #[test]
+    fn test_parse_labels_and_timestamps() {
+        let metrics = PrometheusMetrics::parse(
+            "http_requests_total{method=\"post\",code=\"200\"} 1027 1395066363000\n\
+             http_requests_total{method=\"get\", code=\"400\",} 3\n\
+             msdos_file_access_time{path=\"C:\\\\DIR\\\\\",error=\"Cannot find file:\\n\\\"FILE.TXT\\\"\"} 1.458e+06\n\
+             latency_bucket{le=\"+Inf\"} +Inf\n",
+        )
+        .unwrap();
+
+        let requests = &metrics["http_requests_total"];
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].labels["code"], "200");
+        assert_eq!(requests[0].timestamp, Some(1395066363000));
+        assert_eq!(
+            metrics.value_with_label("http_requests_total", "method", "get"),
+            Some(3.0)
+        );
+
+        let access = &metrics["msdos_file_access_time"][0];
+        assert_eq!(access.labels["path"], "C:\\DIR\\");
+        assert_eq!(access.labels["error"], "Cannot find file:\n\"FILE.TXT\"");
+        assert_eq!(access.value, 1.458e6);
+
+        assert_eq!(metrics.value("latency_bucket"), Some(f64::INFINITY));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_lines() {
+        for line in [
+            "ogmios_total_messages",
+            "ogmios_total_messages abc",
+            "ogmios_total_messages{method=\"get\" 1",
+            "ogmios_total_messages 1 2 3",
+        ] {
+            assert!(
+                matches!(
+                    PrometheusMetrics::parse(line),
+                    Err(OgmiosError::InvalidResponse { .. })
+                ),
+                "{line}"
+            );
+        }
+    }
+}
@@ -0,0 +1,646 @@
+//! Cardano addresses.
+//!
+//! [`Address`] keeps the textual encoding used on the wire. With the `address`
+//! feature, addresses can be parsed and inspected: Shelley addresses are
+//! Bech32-encoded (CIP-19), Byron addresses are Base58-encoded CBOR.
+
+use serde::{Deserialize, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+
+#[cfg(feature = "address")]
+use super::digest::{DigestBlake2b224, InvalidDigest, ScriptHash};
+#[cfg(feature = "address")]
+use super::network::Network;
+#[cfg(feature = "address")]
+use super::primitives::{PaymentCredential, StakeAddress, StakeCredential};
+#[cfg(feature = "address")]
+use crate::util::{bech32_decode, bech32_encode};
+#[cfg(feature = "address")]
+use std::str::FromStr;
+
+/// A Cardano address (Bech32 or Base58 encoded).
+///
+/// Deserialization and the `From` conversions take the string as is. With the
+/// `address` feature, [`FromStr`](std::str::FromStr) validates the encoding
+/// and the accessors decode the address.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Address(String);
+
+impl Address {
+    /// Get the encoded address.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Convert into the encoded address.
+    pub fn into_string(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Address {
+    fn from(s: String) -> Self {
+        Self(s)
+    }
+}
+
+impl From<&str> for Address {
+    fn from(s: &str) -> Self {
+        Self(s.to_string())
+    }
+}
+
+impl From<Address> for String {
+    fn from(address: Address) -> Self {
+        address.0
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for Address {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Borrow<str> for Address {
+    fn borrow(&self) -> &str {
+        &self.0
+    }
+}
+
+impl PartialEq<str> for Address {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl PartialEq<&str> for Address {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
+
+/// A string that is not a valid Cardano address.
+#[cfg(feature = "address")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid address {value:?}: {reason}")]
+pub struct InvalidAddress {
+    /// Rejected value.
+    pub value: String,
+    /// Why the value was rejected.
+    pub reason: String,
+}
+
+/// Type of an address, from its CIP-19 header.
+#[cfg(feature = "address")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AddressKind {
+    /// Payment and stake credentials.
+    Base,
+    /// Payment credential and a pointer to a stake registration certificate.
+    Pointer,
+    /// Payment credential only.
+    Enterprise,
+    /// Stake credential only: a stake address.
+    Reward,
+    /// Byron (bootstrap) address.
+    Byron,
+}
+
+/// Components of a decoded address.
+#[cfg(feature = "address")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AddressInfo {
+    /// Type of the address.
+    pub kind: AddressKind,
+    /// Network ID of the header: 1 on mainnet, 0 on test networks. `None` for
+    /// Byron addresses, which carry a protocol magic instead.
+    pub network_id: Option<u8>,
+    /// Credential controlling the funds, `None` for stake and Byron addresses.
+    pub payment_credential: Option<PaymentCredential>,
+    /// Credential the address delegates with, `None` for enterprise, pointer
+    /// and Byron addresses.
+    pub stake_credential: Option<StakeCredential>,
+}
+
+/// Protocol magic of the Byron mainnet.
+#[cfg(feature = "address")]
+const BYRON_MAINNET_MAGIC: u64 = 764824073;
+
+/// Address decoded to its raw bytes.
+#[cfg(feature = "address")]
+enum Decoded {
+    /// Shelley address bytes; the header is the first byte.
+    Shelley(Vec<u8>),
+    /// Byron address bytes, with the protocol magic if one is set.
+    Byron(Vec<u8>, Option<u64>),
+}
+
+#[cfg(feature = "address")]
+impl Address {
+    /// Check that the address is well formed.
+    ///
+    /// Only needed for addresses that were not parsed, e.g. deserialized or
+    /// converted from a string.
+    pub fn validate(&self) -> Result<(), InvalidAddress> {
+        self.decode().map(|_| ())
+    }
+
+    /// Decode the address bytes.
+    pub fn to_bytes(&self) -> Result<Vec<u8>, InvalidAddress> {
+        match self.decode()? {
+            Decoded::Shelley(bytes) | Decoded::Byron(bytes, _) => Ok(bytes),
+        }
+    }
+
+    /// Whether this is a Byron (bootstrap) address.
+    pub fn is_byron(&self) -> bool {
+        matches!(self.decode(), Ok(Decoded::Byron(..)))
+    }
+
+    /// Network ID of the address: 1 for mainnet, 0 for the test networks.
+    ///
+    /// Shelley addresses carry the ID in their header; they do not tell the
+    /// test networks apart. Byron addresses are given the ID matching their
+    /// protocol magic. `None` if the address is invalid.
+    pub fn network_id(&self) -> Option<u8> {
+        let network_id = match self.decode().ok()? {
+            Decoded::Shelley(bytes) => bytes[0] & 0x0f,
+            Decoded::Byron(_, None | Some(BYRON_MAINNET_MAGIC)) => 1,
+            Decoded::Byron(_, Some(_)) => 0,
+        };
+        Some(network_id)
+    }
+
+    /// Credential controlling the funds at the address.
+    ///
+    /// `None` for Byron and stake addresses, and if the address is invalid.
+    pub fn payment_credential(&self) -> Option<StakeCredential> {
+        self.info().ok()?.payment_credential
+    }
+
+    /// Credential the address delegates with, or the credential of a stake
+    /// address.
+    ///
+    /// `None` for enterprise, pointer and Byron addresses, and if the address
+    /// is invalid.
+    pub fn stake_credential(&self) -> Option<StakeCredential> {
+        self.info().ok()?.stake_credential
+    }
+
+    /// Decode the type, network ID and credentials of the address.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Address, AddressKind};
+    ///
+    /// let address = Address::from("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8");
+    /// let info = address.info().unwrap();
+    /// assert_eq!(info.kind, AddressKind::Enterprise);
+    /// assert_eq!(info.network_id, Some(1));
+    /// assert_eq!(info.stake_credential, None);
+    /// ```
+    pub fn info(&self) -> Result<AddressInfo, InvalidAddress> {
+        let bytes = match self.decode()? {
+            Decoded::Shelley(bytes) => bytes,
+            Decoded::Byron(..) => {
+                return Ok(AddressInfo {
+                    kind: AddressKind::Byron,
+                    network_id: None,
+                    payment_credential: None,
+                    stake_credential: None,
+                });
+            }
+        };
+
+        let header = bytes[0] >> 4;
+        let payment_credential =
+            (header <= 7).then(|| credential(&bytes[1..29], header & 0b0001 != 0));
+        let stake_credential = match header {
+            0..=3 => Some(credential(&bytes[29..57], header & 0b0010 != 0)),
+            14..=15 => Some(credential(&bytes[1..29], header == 15)),
+            _ => None,
+        };
+        let kind = match header {
+            0..=3 => AddressKind::Base,
+            4..=5 => AddressKind::Pointer,
+            6..=7 => AddressKind::Enterprise,
+            _ => AddressKind::Reward,
+        };
+        Ok(AddressInfo {
+            kind,
+            network_id: Some(bytes[0] & 0x0f),
+            payment_credential,
+            stake_credential,
+        })
+    }
+
+    fn decode(&self) -> Result<Decoded, InvalidAddress> {
+        let invalid = |reason: &str| InvalidAddress {
+            value: self.0.clone(),
+            reason: reason.to_string(),
+        };
+
+        if self.0.starts_with("addr") || self.0.starts_with("stake") {
+            let (hrp, bytes) =
+                bech32_decode(&self.0).map_err(|e| invalid(&format!("invalid Bech32: {e}")))?;
+            return check_shelley(hrp.as_str(), bytes).map_err(invalid);
+        }
+
+        let bytes = bs58::decode(&self.0)
+            .into_vec()
+            .map_err(|_| invalid("neither Bech32 nor Base58"))?;
+        let magic = byron_magic(&bytes).ok_or_else(|| invalid("malformed Byron address"))?;
+        Ok(Decoded::Byron(bytes, magic))
+    }
+}
+
+#[cfg(feature = "address")]
+impl FromStr for Address {
+    type Err = InvalidAddress;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = Self(s.to_string());
+        address.validate()?;
+        Ok(address)
+    }
+}
+
+#[cfg(feature = "address")]
+impl StakeCredential {
+    /// Encode the credential as a stake address (CIP-19 reward address).
+    ///
+    /// Shelley addresses only tell mainnet from test networks, so every
+    /// network other than mainnet gives a `stake_test` address. Credential
+    /// hashes are checked when they are parsed, so this no longer fails; the
+    /// `Result` is kept so existing callers still compile.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Network, StakeCredential};
+    ///
+    /// let credential = StakeCredential::Key {
+    ///     key: "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251".parse().unwrap(),
+    /// };
+    /// assert_eq!(
+    ///     credential.to_stake_address(&Network::Mainnet).unwrap(),
+    ///     "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw"
+    /// );
+    /// ```
+    pub fn to_stake_address(&self, network: &Network) -> Result<StakeAddress, InvalidDigest> {
+        let (kind, hash) = match self {
+            StakeCredential::Key { key } => (0xe0, key.to_bytes()),
+            StakeCredential::Script { script } => (0xf0, script.to_bytes()),
+        };
+        let (network_id, hrp) = match network {
+            Network::Mainnet => (1, "stake"),
+            _ => (0, "stake_test"),
+        };
+        let mut bytes = Vec::with_capacity(29);
+        bytes.push(kind | network_id);
+        bytes.extend_from_slice(&hash);
+        Ok(bech32_encode(hrp, &bytes).expect("a stake address is valid Bech32"))
+    }
+
+    /// Decode a stake address into its network ID and credential.
+    ///
+    /// As with [`Address::network_id`], the ID is 1 for mainnet and 0 for the
+    /// test networks.
+    pub fn from_stake_address(s: &str) -> Result<(u8, StakeCredential), InvalidAddress> {
+        match Address::from(s).decode()? {
+            Decoded::Shelley(bytes) if bytes[0] >> 4 >= 14 => {
+                let network_id = bytes[0] & 0x0f;
+                Ok((network_id, credential(&bytes[1..29], bytes[0] >> 4 == 15)))
+            }
+            _ => Err(InvalidAddress {
+                value: s.to_string(),
+                reason: "not a stake address".to_string(),
+            }),
+        }
+    }
+}
+
+/// Parses a stake address, dropping the network.
+#[cfg(feature = "address")]
+impl TryFrom<&str> for StakeCredential {
+    type Error = InvalidAddress;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        Self::from_stake_address(s).map(|(_, credential)| credential)
+    }
+}
+
+/// Parses a stake address, dropping the network.
+#[cfg(feature = "address")]
+impl TryFrom<String> for StakeCredential {
+    type Error = InvalidAddress;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        Self::try_from(s.as_str())
+    }
+}
+
+#[cfg(feature = "address")]
+fn credential(hash: &[u8], script: bool) -> StakeCredential {
+    if script {
+        let mut bytes = [0u8; 28];
+        bytes.copy_from_slice(hash);
+        StakeCredential::Script {
+            script: ScriptHash::from_bytes(bytes),
+        }
+    } else {
+        let mut bytes = [0u8; 28];
+        bytes.copy_from_slice(hash);
+        StakeCredential::Key {
+            key: DigestBlake2b224::from_bytes(bytes),
+        }
+    }
+}
+
+/// Check the header, length and prefix of Shelley address bytes.
+#[cfg(feature = "address")]
+fn check_shelley(hrp: &str, bytes: Vec<u8>) -> Result<Decoded, &'static str> {
+    let header = *bytes.first().ok_or("empty address")?;
+    let (kind, network_id) = (header >> 4, header & 0x0f);
+
+    let length_ok = match kind {
+        0..=3 => bytes.len() == 57,
+        4..=5 => bytes.len() > 29,
+        6..=7 | 14..=15 => bytes.len() == 29,
+        _ => return Err("unknown address type"),
+    };
+    if !length_ok {
+        return Err("wrong length for the address type");
+    }
+
+    let expected = match (kind >= 14, network_id == 1) {
+        (false, true) => "addr",
+        (false, false) => "addr_test",
+        (true, true) => "stake",
+        (true, false) => "stake_test",
+    };
+    if hrp != expected {
+        return Err("prefix does not match the address type and network");
+    }
+    Ok(Decoded::Shelley(bytes))
+}
+
+/// Check the structure and CRC of Byron address bytes, returning the protocol
+/// magic if the address has one.
+///
+/// Byron addresses are `[#6.24(bytes .cbor [root, attributes, type]), crc32]`.
+#[cfg(feature = "address")]
+fn byron_magic(bytes: &[u8]) -> Option<Option<u64>> {
+    let mut outer = Cbor(bytes);
+    if outer.head()? != (4, 2) || outer.head()? != (6, 24) {
+        return None;
+    }
+    let payload = outer.bytes()?;
+    let (major, crc) = outer.head()?;
+    if major != 0 || crc != u64::from(crc32(payload)) || !outer.0.is_empty() {
+        return None;
+    }
+
+    let mut inner = Cbor(payload);
+    if inner.head()? != (4, 3) || inner.bytes()?.len() != 28 {
+        return None;
+    }
+    let (major, attributes) = inner.head()?;
+    if major != 5 {
+        return None;
+    }
+    let mut magic = None;
+    for _ in 0..attributes {
+        match inner.head()? {
+            (0, 2) => {
+                let (major, value) = Cbor(inner.bytes()?).head()?;
+                if major != 0 {
+                    return None;
+                }
+                magic = Some(value);
+            }
+            _ => inner.skip()?,
+        }
+    }
+    let (major, _) = inner.head()?;
+    (major == 0 && inner.0.is_empty()).then_some(magic)
+}
+
+/// Just enough of a CBOR reader for Byron addresses.
+#[cfg(feature = "address")]
+struct Cbor<'a>(&'a [u8]);
+
+#[cfg(feature = "address")]
+impl<'a> Cbor<'a> {
+    /// Read an item head: major type and argument.
+    fn head(&mut self) -> Option<(u8, u64)> {
+        let (&initial, rest) = self.0.split_first()?;
+        let size = match initial & 0x1f {
+            info @ 0..=23 => {
+                self.0 = rest;
+                return Some((initial >> 5, u64::from(info)));
+            }
+            24 => 1,
+            25 => 2,
+            26 => 4,
+            27 => 8,
+            _ => return None,
+        };
+        if rest.len() < size {
+            return None;
+        }
+        let (argument, rest) = rest.split_at(size);
+        self.0 = rest;
+        let value = argument
+            .iter()
+            .fold(0u64, |value, byte| (value << 8) | u64::from(*byte));
+        Some((initial >> 5, value))
+    }
+
+    fn bytes(&mut self) -> Option<&'a [u8]> {
+        let (major, len) = self.head()?;
+        self.take(major, len)
+    }
+
+    fn take(&mut self, major: u8, len: u64) -> Option<&'a [u8]> {
+        let len = usize::try_from(len).ok()?;
+        if !matches!(major, 2 | 3) || self.0.len() < len {
+            return None;
+        }
+        let (bytes, rest) = self.0.split_at(len);
+        self.0 = rest;
+        Some(bytes)
+    }
+
+    /// Skip one complete item.
+    fn skip(&mut self) -> Option<()> {
+        match self.head()? {
+            (0 | 1 | 7, _) => {}
+            (major @ (2 | 3), len) => {
+                self.take(major, len)?;
+            }
+            (4, items) => (0..items).try_for_each(|_| self.skip())?,
+            (5, entries) => (0..entries * 2).try_for_each(|_| self.skip())?,
+            (6, _) => self.skip()?,
+            _ => return None,
+        }
+        Some(())
+    }
+}
+
+/// CRC-32 (IEEE), as used in Byron addresses.
+#[cfg(feature = "address")]
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(!0u32, |crc, byte| {
+        (0..8).fold(crc ^ u32::from(*byte), |crc, _| {
+            (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg())
+        })
+    })
+}
+
+#[cfg(all(test, feature = "address"))]
+mod tests {
+    use super::*;
+
+    const BASE: &str = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x";
+    const SCRIPT_KEY: &str = "addr1z8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gten0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs9yc0hh";
+    const POINTER: &str = "addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k";
+    const ENTERPRISE_TEST: &str = "addr_test1vz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerspjrlsz";
+    const STAKE: &str = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw";
+    const STAKE_SCRIPT: &str = "stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5";
+    const STAKE_TEST: &str = "stake_test1uqehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gssrtvn";
+    const STAKE_SCRIPT_TEST: &str =
+        "stake_test17rphkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcljw6kf";
+    const BYRON: &str = "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi";
+    // The address above with protocol magic 1 (preprod).
+    const BYRON_TEST: &str = "FHnt4NL7yPYA1L1TQC4GfpGbYUQMTBQsjR3nKtUysJrLJKpEBmym6aLnEjKicff";
+
+    const PAYMENT_KEY: &str = "9493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e";
+    const STAKE_KEY: &str = "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251";
+    const SCRIPT: &str = "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f";
+
+    fn key(hash: &str) -> StakeCredential {
+        StakeCredential::Key {
+            key: hash.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_shelley_addresses() {
+        let base: Address = BASE.parse().unwrap();
+        assert_eq!(base.network_id(), Some(1));
+        assert!(!base.is_byron());
+        assert_eq!(base.to_bytes().unwrap().len(), 57);
+        assert_eq!(base.payment_credential(), Some(key(PAYMENT_KEY)));
+        assert_eq!(base.stake_credential(), Some(key(STAKE_KEY)));
+
+        let script: Address = SCRIPT_KEY.parse().unwrap();
+        assert_eq!(
+            script.payment_credential(),
+            Some(StakeCredential::Script {
+                script: SCRIPT.parse().unwrap()
+            })
+        );
+        assert_eq!(script.stake_credential(), Some(key(STAKE_KEY)));
+
+        let pointer: Address = POINTER.parse().unwrap();
+        assert_eq!(pointer.payment_credential(), Some(key(PAYMENT_KEY)));
+        assert_eq!(pointer.stake_credential(), None);
+
+        let enterprise: Address = ENTERPRISE_TEST.parse().unwrap();
+        assert_eq!(enterprise.network_id(), Some(0));
+        assert_eq!(enterprise.stake_credential(), None);
+
+        let stake: Address = STAKE.parse().unwrap();
+        assert_eq!(stake.payment_credential(), None);
+        assert_eq!(stake.stake_credential(), Some(key(STAKE_KEY)));
+    }
+
+    #[test]
+    fn test_stake_address_conversion() {
+        // Reward addresses from the CIP-19 test vectors.
+        let script = StakeCredential::Script {
+            script: SCRIPT.parse().unwrap(),
+        };
+        let vectors = [
+            (key(STAKE_KEY), Network::Mainnet, STAKE),
+            (script.clone(), Network::Mainnet, STAKE_SCRIPT),
+            (key(STAKE_KEY), Network::Preprod, STAKE_TEST),
+            (script, Network::Preview, STAKE_SCRIPT_TEST),
+        ];
+        for (credential, network, address) in vectors {
+            assert_eq!(credential.to_stake_address(&network).unwrap(), address);
+
+            let (network_id, decoded) = StakeCredential::from_stake_address(address).unwrap();
+            assert_eq!(decoded, credential);
+            let mainnet = network == Network::Mainnet;
+            assert_eq!(network_id, u8::from(mainnet), "{address}");
+            assert_eq!(StakeCredential::try_from(address).unwrap(), credential);
+        }
+
+        assert_eq!(StakeCredential::from_stake_address(STAKE_TEST).unwrap().0, 0);
+        for value in [BASE, BYRON, &STAKE[..STAKE.len() - 1]] {
+            let error = StakeCredential::from_stake_address(value).unwrap_err();
+            assert_eq!(error.value, value);
+        }
+    }
+
+    #[test]
+    fn test_byron_addresses() {
+        let byron: Address = BYRON.parse().unwrap();
+        assert!(byron.is_byron());
+        assert_eq!(byron.network_id(), Some(1));
+        assert_eq!(byron.payment_credential(), None);
+        assert_eq!(byron.stake_credential(), None);
+
+        let test: Address = BYRON_TEST.parse().unwrap();
+        assert!(test.is_byron());
+        assert_eq!(test.network_id(), Some(0));
+    }
+
+    #[test]
+    fn test_network_serialization() {
+        let network: Network = serde_json::from_str("\"preprod\"").unwrap();
+        assert_eq!(network, Network::Preprod);
+        for name in ["sanchonet", "my-devnet"] {
+            let network: Network = serde_json::from_str(&format!("\"{name}\"")).unwrap();
+            assert_eq!(network, Network::Other(name.to_string()));
+            assert_eq!(network.as_str(), name);
+            assert_eq!(network.to_string(), name);
+            assert_eq!(serde_json::to_string(&network).unwrap(), format!("\"{name}\""));
+        }
+        assert_eq!(Network::Other("mainnet".to_string()), Network::Mainnet);
+        assert_ne!(Network::Other("sanchonet".to_string()), Network::Preview);
+    }
+
+    #[test]
+    fn test_rejects_malformed_addresses() {
+        let mut typo = BASE.to_string();
+        typo.replace_range(20..21, "q");
+        for value in [
+            typo.as_str(),
+            &BASE[..BASE.len() - 1],
+            &BYRON[..BYRON.len() - 1],
+            "addr_test1",
+            "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7",
+        ] {
+            let error = value.parse::<Address>().unwrap_err();
+            assert_eq!(error.value, value);
+        }
+
+        // Unparsed values are only checked when decoded.
+        let unchecked = Address::from("addr_test1");
+        assert_eq!(unchecked.network_id(), None);
+        assert!(unchecked.validate().is_err());
+    }
+}
@@ -0,0 +1,139 @@
+//! Asset identifiers.
+
+use super::digest::PolicyId;
+use super::primitives::AssetName;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Maximum length of an asset name, in hex characters (32 bytes).
+pub(super) const MAX_ASSET_NAME_LENGTH: usize = 64;
+
+/// Whether `name` is a hex-encoded asset name of at most 32 bytes.
+pub(super) fn is_valid_asset_name(name: &str) -> bool {
+    name.len() <= MAX_ASSET_NAME_LENGTH
+        && name.len().is_multiple_of(2)
+        && name.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// A string that is not a valid asset subject.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error(
+    "invalid asset subject {value:?}: expected a 56-character policy ID followed by an asset name \
+     of up to 64 hex characters"
+)]
+pub struct InvalidSubject {
+    /// Rejected value.
+    pub value: String,
+}
+
+/// An asset identified by its policy ID and hex-encoded name.
+///
+/// Formats as the policy ID directly followed by the asset name, the key used
+/// by token registries (CIP-26) and most off-chain systems.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::Subject;
+///
+/// let subject: Subject =
+///     "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a74e4654".parse().unwrap();
+/// assert_eq!(subject.asset_name, "4e4654");
+/// assert_eq!(subject.policy_id, "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Subject {
+    /// Minting policy of the asset.
+    pub policy_id: PolicyId,
+    /// Hex-encoded asset name, possibly empty.
+    pub asset_name: AssetName,
+}
+
+impl Subject {
+    /// Create a subject from its parts.
+    pub fn new(policy_id: PolicyId, asset_name: impl Into<AssetName>) -> Self {
+        Self {
+            policy_id,
+            asset_name: asset_name.into(),
+        }
+    }
+}
+
+impl FromStr for Subject {
+    type Err = InvalidSubject;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidSubject {
+            value: s.to_string(),
+        };
+        let policy_length = PolicyId::BYTES * 2;
+        if s.len() < policy_length || !s.is_char_boundary(policy_length) {
+            return Err(invalid());
+        }
+
+        let (policy_id, asset_name) = s.split_at(policy_length);
+        let policy_id: PolicyId = policy_id.parse().map_err(|_| invalid())?;
+        if !is_valid_asset_name(asset_name) {
+            return Err(invalid());
+        }
+        Ok(Self::new(policy_id, asset_name.to_ascii_lowercase()))
+    }
+}
+
+impl TryFrom<String> for Subject {
+    type Error = InvalidSubject;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl From<Subject> for String {
+    fn from(subject: Subject) -> Self {
+        subject.to_string()
+    }
+}
+
+impl fmt::Display for Subject {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.policy_id, self.asset_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: &str = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7";
+
+    #[test]
+    fn test_subject_round_trip() {
+        let subject: Subject = format!("{POLICY}4E4654").parse().unwrap();
+        assert_eq!(subject.policy_id, POLICY);
+        assert_eq!(subject.asset_name, "4e4654");
+        assert_eq!(subject.to_string(), format!("{POLICY}4e4654"));
+
+        let unnamed: Subject = POLICY.parse().unwrap();
+        assert_eq!(unnamed.asset_name, "");
+
+        let json = serde_json::to_string(&subject).unwrap();
+        assert_eq!(json, format!("\"{POLICY}4e4654\""));
+        assert_eq!(serde_json::from_str::<Subject>(&json).unwrap(), subject);
+    }
+
+    #[test]
+    fn test_subject_rejects_malformed() {
+        for value in [
+            POLICY[..54].to_string(),
+            format!("{POLICY}4e465"),
+            format!("{POLICY}zz"),
+            format!("{POLICY}{}", "00".repeat(33)),
+            format!("{}é{}", &POLICY[..55], "00"),
+        ] {
+            assert_eq!(value.parse::<Subject>(), Err(InvalidSubject { value }));
+        }
+        assert!(serde_json::from_str::<Subject>("\"ab\"").is_err());
+    }
+}
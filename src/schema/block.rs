@@ -1,12 +1,16 @@
 //! Block types for Cardano.
 
 use serde::{Deserialize, Serialize};
+use super::digest::DigestBlake2b256;
 use super::primitives::*;
+use super::protocol::ProtocolVersion;
 use super::transaction::Transaction;
 
 /// A Cardano block - can be EBB, BFT (Byron), or Praos (Shelley+).
+///
+/// Selected by the `type` field of the block: `ebb`, `bft` or `praos`.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "lowercase")]
 pub enum Block {
     /// Epoch Boundary Block (Byron era).
     EBB(BlockEBB),
@@ -17,6 +21,24 @@ pub enum Block {
 }
 
 impl Block {
+    /// Get the block type identifier (`ebb`, `bft` or `praos`).
+    pub fn block_type(&self) -> &'static str {
+        match self {
+            Block::EBB(_) => "ebb",
+            Block::BFT(_) => "bft",
+            Block::Praos(_) => "praos",
+        }
+    }
+
+    /// Get the era the block belongs to.
+    pub fn era(&self) -> &str {
+        match self {
+            Block::EBB(b) => &b.era,
+            Block::BFT(b) => &b.era,
+            Block::Praos(b) => &b.era,
+        }
+    }
+
     /// Get the block's slot number.
     pub fn slot(&self) -> Slot {
         match self {
@@ -29,9 +51,9 @@ impl Block {
     /// Get the block's ID (hash).
     pub fn id(&self) -> &str {
         match self {
-            Block::EBB(b) => &b.id,
-            Block::BFT(b) => &b.id,
-            Block::Praos(b) => &b.id,
+            Block::EBB(b) => b.id.as_str(),
+            Block::BFT(b) => b.id.as_str(),
+            Block::Praos(b) => b.id.as_str(),
         }
     }
 
@@ -47,9 +69,9 @@ impl Block {
     /// Get the ancestor block ID.
     pub fn ancestor(&self) -> &str {
         match self {
-            Block::EBB(b) => &b.ancestor,
-            Block::BFT(b) => &b.ancestor,
-            Block::Praos(b) => &b.ancestor,
+            Block::EBB(b) => b.ancestor.as_str(),
+            Block::BFT(b) => b.ancestor.as_str(),
+            Block::Praos(b) => b.ancestor.as_str(),
         }
     }
 
@@ -72,10 +94,8 @@ impl Block {
 /// Epoch Boundary Block (EBB) - Byron era.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockEBB {
-    /// Block type identifier.
-    #[serde(rename = "type")]
-    pub block_type: String,
     /// Era (always "byron" for EBB).
     pub era: String,
     /// Block ID (hash).
@@ -91,10 +111,8 @@ pub struct BlockEBB {
 /// BFT block - Byron era.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockBFT {
-    /// Block type identifier.
-    #[serde(rename = "type")]
-    pub block_type: String,
     /// Era (always "byron" for BFT).
     pub era: String,
     /// Block ID (hash).
@@ -111,24 +129,43 @@ pub struct BlockBFT {
     pub protocol: ProtocolVersionByron,
     /// Block issuer information.
     pub issuer: BlockIssuerByron,
+    /// Key the block was signed with on behalf of the issuer, if delegated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<BlockIssuerByron>,
     /// Transactions in the block.
     #[serde(default)]
     pub transactions: Vec<Transaction>,
+    /// Delegation certificates included in the block.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub operational_certificates: Vec<ByronDelegationCertificate>,
+}
+
+/// Byron heavyweight delegation certificate, from a genesis key to a
+/// delegate key.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ByronDelegationCertificate {
+    /// Delegating (genesis) key.
+    pub issuer: BlockIssuerByron,
+    /// Key the issuer delegates to.
+    pub delegate: BlockIssuerByron,
 }
 
 /// Praos block - Shelley era and later.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockPraos {
-    /// Block type identifier.
-    #[serde(rename = "type")]
-    pub block_type: String,
     /// Era (shelley, allegra, mary, alonzo, babbage, conway).
     pub era: String,
     /// Block ID (hash).
     pub id: DigestBlake2b256,
     /// Ancestor block ID.
     pub ancestor: DigestBlake2b256,
+    /// Nonce VRF output (TPraos eras, Shelley to Alonzo).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<CertifiedVrf>,
     /// Slot number.
     pub slot: Slot,
     /// Block height.
@@ -147,26 +184,67 @@ pub struct BlockPraos {
 /// Block size information.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockSize {
     /// Size in bytes.
     pub bytes: NumberOfBytes,
 }
 
 /// Protocol version for Byron blocks.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ProtocolVersionByron {
+    /// Protocol magic of the network.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<u32>,
+    /// Protocol version the block was produced with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<ProtocolVersion>,
     /// Software version.
     #[serde(default)]
     pub software: Option<SoftwareVersion>,
-    /// Update proposal (if any).
-    #[serde(default)]
-    pub update: Option<serde_json::Value>,
+    /// Update proposal and votes carried by the block, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update: Option<ByronUpdate>,
+}
+
+/// Update payload of a Byron block.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ByronUpdate {
+    /// Proposed protocol and software update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proposal: Option<ByronUpdateProposal>,
+    /// Votes on pending proposals, as sent by Ogmios.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub votes: Vec<serde_json::Value>,
+}
+
+/// Byron update proposal.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ByronUpdateProposal {
+    /// Proposed protocol version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<ProtocolVersion>,
+    /// Proposed software version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub software: Option<SoftwareVersion>,
+    /// Proposed protocol parameter changes, as sent by Ogmios.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub parameters: serde_json::Value,
+    /// Installer hashes by system tag, as sent by Ogmios.
+    #[serde(default, skip_serializing_if = "serde_json::Value::is_null")]
+    pub metadata: serde_json::Value,
 }
 
 /// Software version information.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct SoftwareVersion {
     /// Application name.
     pub app_name: String,
@@ -177,19 +255,16 @@ pub struct SoftwareVersion {
 /// Protocol version for Praos blocks.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ProtocolVersionPraos {
-    /// Major version.
-    pub major: u32,
-    /// Minor version.
-    pub minor: u32,
-    /// Patch version (optional).
-    #[serde(default)]
-    pub patch: Option<u32>,
+    /// Protocol version the block was produced with.
+    pub version: ProtocolVersion,
 }
 
 /// Block issuer for Byron blocks.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockIssuerByron {
     /// Verification key hash.
     pub verification_key: VerificationKey,
@@ -198,22 +273,24 @@ pub struct BlockIssuerByron {
 /// Block issuer for Praos blocks.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockIssuerPraos {
     /// Verification key hash (pool ID).
     pub verification_key: VerificationKeyHash,
     /// VRF verification key.
     pub vrf_verification_key: VrfVerificationKey,
     /// Leader value (VRF output).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub leader_value: Option<CertifiedVrf>,
     /// Operational certificate.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub operational_certificate: Option<OperationalCertificate>,
 }
 
 /// VRF certified output.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct CertifiedVrf {
     /// VRF output.
     pub output: String,
@@ -222,17 +299,261 @@ pub struct CertifiedVrf {
 }
 
 /// Operational certificate for block production.
+///
+/// Ogmios v6 sends `{ count, kes: { period, verificationKey } }`. The flat
+/// form of Ogmios v5, `{ count, kesPeriod, hotVk, sigma }`, is accepted too.
+/// None of the captured headers carries the cold key signature `sigma`; it is
+/// kept when a server sends it.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(rename_all = "camelCase", from = "OperationalCertificateRepr")]
 pub struct OperationalCertificate {
-    /// KES verification key.
-    pub kes_verification_key: KesVerificationKey,
-    /// Counter.
+    /// Issue counter of the certificate.
     pub count: u64,
-    /// KES period.
-    #[serde(default)]
-    pub kes_period: Option<u64>,
-    /// Issue number.
-    #[serde(default)]
-    pub issue_number: Option<u64>,
+    /// Hot key the certificate delegates to.
+    pub kes: KesKey,
+    /// Signature of the certificate by the pool's cold key.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sigma: Option<Signature>,
+}
+
+/// Wire forms of [`OperationalCertificate`].
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum OperationalCertificateRepr {
+    Nested {
+        count: u64,
+        kes: KesKey,
+        #[serde(default)]
+        sigma: Option<Signature>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Flat {
+        count: u64,
+        kes_period: u64,
+        hot_vk: KesVerificationKey,
+        #[serde(default)]
+        sigma: Option<Signature>,
+    },
+}
+
+impl From<OperationalCertificateRepr> for OperationalCertificate {
+    fn from(repr: OperationalCertificateRepr) -> Self {
+        match repr {
+            OperationalCertificateRepr::Nested { count, kes, sigma } => Self { count, kes, sigma },
+            OperationalCertificateRepr::Flat {
+                count,
+                kes_period,
+                hot_vk,
+                sigma,
+            } => Self {
+                count,
+                kes: KesKey {
+                    period: kes_period,
+                    verification_key: hot_vk,
+                },
+                sigma,
+            },
+        }
+    }
+}
+
+/// KES key of an operational certificate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct KesKey {
+    /// KES period the key is valid from.
+    pub period: u64,
+    /// KES verification key.
+    pub verification_key: KesVerificationKey,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// Captured block of a `chain-sync/nextBlock-*` fixture.
+    fn captured(name: &str) -> serde_json::Value {
+        let response = crate::schema::testing::fixture(&format!("chain-sync/nextBlock-{name}"));
+        response.result()["block"].clone()
+    }
+
+    #[test]
+    fn test_block_variants_round_trip() {
+        for (name, block_type, era) in [
+            ("byron-ebb", "ebb", "byron"),
+            ("byron-bft", "bft", "byron"),
+            ("conway", "praos", "conway"),
+        ] {
+            let fixture = captured(name);
+            let block: Block = serde_json::from_value(fixture.clone()).unwrap();
+            assert_eq!(block.block_type(), block_type);
+            assert_eq!(block.era(), era);
+            assert_eq!(block.id(), fixture["id"]);
+
+            let serialized = serde_json::to_value(&block).unwrap();
+            assert_eq!(serialized["type"], block_type);
+            assert_eq!(serde_json::from_value::<Block>(serialized).unwrap(), block);
+        }
+
+        let Block::Praos(praos) = serde_json::from_value(captured("conway")).unwrap() else {
+            panic!("expected a praos block");
+        };
+        assert_eq!(praos.slot, 146_707_210);
+        assert_eq!(praos.transactions.len(), 1);
+    }
+
+    #[test]
+    fn test_praos_headers_round_trip_losslessly() {
+        use crate::schema::assert_lossless_roundtrip;
+
+        // Eras before Babbage (TPraos) carry a separate nonce VRF output.
+        for (era, major, kes_period) in [
+            ("shelley", 2, 39),
+            ("allegra", 3, 138),
+            ("mary", 4, 178),
+            ("alonzo", 6, 308),
+            ("babbage", 8, 673),
+            ("conway", 10, 1_132),
+        ] {
+            let Block::Praos(block) = assert_lossless_roundtrip::<Block>(&captured(era)) else {
+                panic!("expected a praos block");
+            };
+            assert_eq!(block.era, era);
+            assert_eq!(block.nonce.is_some(), major < 7, "{era}");
+            assert_eq!(block.protocol.version.major, major);
+            let certificate = block.issuer.operational_certificate.as_ref().unwrap();
+            assert_eq!(certificate.kes.period, kes_period, "{era}");
+        }
+    }
+
+    /// Issuer of the captured Conway block.
+    fn captured_issuer() -> serde_json::Value {
+        captured("conway")["issuer"].clone()
+    }
+
+    #[test]
+    fn test_operational_certificate_round_trip() {
+        let fixture = captured_issuer();
+        let issuer: BlockIssuerPraos = serde_json::from_value(fixture.clone()).unwrap();
+        let certificate = issuer.operational_certificate.as_ref().unwrap();
+        assert_eq!(certificate.count, 9);
+        assert_eq!(certificate.kes.period, 1_132);
+        assert_eq!(certificate.sigma, None);
+        assert_eq!(serde_json::to_value(&issuer).unwrap(), fixture);
+    }
+
+    /// Synthetic: the captured issuer with a made-up `sigma`, since no header
+    /// with one is captured yet (see `fixtures/README.md`).
+    #[test]
+    fn test_operational_certificate_keeps_sigma() {
+        let mut signed = captured_issuer();
+        signed["operationalCertificate"]["sigma"] = json!("5f".repeat(64));
+        let issuer: BlockIssuerPraos = serde_json::from_value(signed.clone()).unwrap();
+        let certificate = issuer.operational_certificate.as_ref().unwrap();
+        assert_eq!(certificate.sigma.as_ref().map(String::len), Some(128));
+        assert_eq!(serde_json::to_value(&issuer).unwrap(), signed);
+    }
+
+    #[test]
+    fn test_operational_certificate_flat_form() {
+        let nested = captured_issuer()["operationalCertificate"].clone();
+        let flat = json!({
+            "count": nested["count"],
+            "kesPeriod": nested["kes"]["period"],
+            "hotVk": nested["kes"]["verificationKey"]
+        });
+        let certificate: OperationalCertificate = serde_json::from_value(flat).unwrap();
+        assert_eq!(certificate, serde_json::from_value(nested.clone()).unwrap());
+        // Always serialized in the current form.
+        assert_eq!(serde_json::to_value(&certificate).unwrap(), nested);
+
+        let error = serde_json::from_value::<OperationalCertificate>(json!({ "count": 1 }));
+        assert!(error.is_err());
+    }
+
+    /// Covers the one EBB and one BFT block captured so far, not the whole
+    /// era: the sample of consecutive mainnet Byron blocks this should replay
+    /// is listed under "Wanted" in `fixtures/README.md`.
+    #[test]
+    fn test_byron_blocks_replay_losslessly() {
+        use crate::schema::assert_lossless_roundtrip;
+        use crate::schema::responses::NextBlockResponse;
+        use crate::schema::testing::fixture;
+
+        for (name, block_type) in [
+            ("chain-sync/nextBlock-byron-ebb", "ebb"),
+            ("chain-sync/nextBlock-byron-bft", "bft"),
+        ] {
+            let result = fixture(name).result();
+            let response = assert_lossless_roundtrip::<NextBlockResponse>(&result);
+            let NextBlockResponse::Forward { block, .. } = response else {
+                panic!("expected a forward result in {name}");
+            };
+            assert_eq!(block.era(), "byron");
+            assert_eq!(block.block_type(), block_type);
+        }
+    }
+
+    #[test]
+    fn test_byron_block_fields() {
+        use crate::schema::testing::fixture;
+        use crate::schema::{Signatory, assert_lossless_roundtrip};
+
+        // A captured block, with an update proposal, a vote and a delegation
+        // certificate added.
+        let mut json = fixture("chain-sync/nextBlock-byron-bft").result()["block"].clone();
+        let key = json["delegate"]["verificationKey"].clone();
+        json["protocol"]["update"] = json!({
+            "proposal": {
+                "version": { "major": 1, "minor": 0, "patch": 0 },
+                "software": { "appName": "cardano-sl", "number": 2 },
+                "parameters": { "maxBlockBodySize": { "bytes": 2_000_000 } }
+            },
+            "votes": [{ "voter": { "verificationKey": key }, "proposal": { "id": json["id"] } }]
+        });
+        json["operationalCertificates"] = json!([{
+            "issuer": { "verificationKey": json["issuer"]["verificationKey"] },
+            "delegate": { "verificationKey": key }
+        }]);
+        let Block::BFT(block) = assert_lossless_roundtrip::<Block>(&json) else {
+            panic!("expected a bft block");
+        };
+        assert_eq!(block.protocol.id, Some(764_824_073));
+        assert_eq!(block.delegate.as_ref().unwrap().verification_key, key);
+        let update = block.protocol.update.as_ref().unwrap();
+        assert_eq!(update.proposal.as_ref().unwrap().version.as_ref().unwrap().major, 1);
+        assert_eq!(update.votes.len(), 1);
+        assert_eq!(block.operational_certificates.len(), 1);
+
+        let transaction = &block.transactions[0];
+        assert_eq!(transaction.fee, None);
+        assert!(transaction.cbor.is_some());
+        let [signatory] = transaction.signatories.as_slice() else {
+            panic!("expected one signatory, got {:?}", transaction.signatories);
+        };
+        assert!(signatory.is_bootstrap());
+        assert_eq!(signatory.address_attributes.as_deref(), Some("a0"));
+        assert_eq!(signatory.chain_code.as_ref().map(String::len), Some(64));
+
+        // Witnesses of redeem addresses have no chain code, and stay without.
+        let mut redeem = json["transactions"][0]["signatories"][0].clone();
+        redeem.as_object_mut().unwrap().remove("chainCode");
+        let signatory = assert_lossless_roundtrip::<Signatory>(&redeem);
+        assert_eq!(signatory.chain_code, None);
+    }
+
+    #[test]
+    fn test_errors_name_the_problem() {
+        let mut block = captured("conway");
+        block.as_object_mut().unwrap().remove("issuer");
+        let error = serde_json::from_value::<Block>(block).unwrap_err().to_string();
+        assert!(error.contains("missing field `issuer`"), "{error}");
+
+        let mut block = captured("byron-ebb");
+        block["type"] = json!("unknown");
+        let error = serde_json::from_value::<Block>(block).unwrap_err().to_string();
+        assert!(error.contains("unknown variant `unknown`"), "{error}");
+    }
 }
@@ -1,8 +1,11 @@
 //! Certificate types for Cardano.
 
 use serde::{Deserialize, Serialize};
+use super::digest::{DigestBlake2b224, DigestBlake2b256};
 use super::primitives::*;
-use super::governance::{DelegateRepresentativeCredential, ConstitutionalCommitteeMemberCredential};
+use super::governance::{
+    ConstitutionalCommitteeMemberCredential, DRepId, DelegateRepresentativeCredential,
+};
 
 /// A Cardano certificate.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -11,6 +14,7 @@ pub enum Certificate {
     /// Stake credential registration.
     #[serde(rename = "stakeCredentialRegistration")]
     StakeCredentialRegistration {
+        #[serde(flatten)]
         credential: StakeCredential,
         #[serde(default)]
         deposit: Option<AdaValue>,
@@ -18,6 +22,7 @@ pub enum Certificate {
     /// Stake credential deregistration.
     #[serde(rename = "stakeCredentialDeregistration")]
     StakeCredentialDeregistration {
+        #[serde(flatten)]
         credential: StakeCredential,
         #[serde(default)]
         deposit: Option<AdaValue>,
@@ -25,8 +30,9 @@ pub enum Certificate {
     /// Stake delegation.
     #[serde(rename = "stakeDelegation")]
     StakeDelegation {
+        #[serde(flatten)]
         credential: StakeCredential,
-        #[serde(rename = "stakePool")]
+        #[serde(rename = "stakePool", with = "stake_pool_reference")]
         stake_pool: StakePoolId,
     },
     /// Stake pool registration.
@@ -77,6 +83,7 @@ pub enum Certificate {
     /// Vote delegation (Conway).
     #[serde(rename = "voteDelegation")]
     VoteDelegation {
+        #[serde(flatten)]
         credential: StakeCredential,
         #[serde(rename = "delegateRepresentative")]
         delegate_representative: Delegatee,
@@ -84,8 +91,9 @@ pub enum Certificate {
     /// Stake and vote delegation (Conway).
     #[serde(rename = "stakeAndVoteDelegation")]
     StakeAndVoteDelegation {
+        #[serde(flatten)]
         credential: StakeCredential,
-        #[serde(rename = "stakePool")]
+        #[serde(rename = "stakePool", with = "stake_pool_reference")]
         stake_pool: StakePoolId,
         #[serde(rename = "delegateRepresentative")]
         delegate_representative: Delegatee,
@@ -93,14 +101,16 @@ pub enum Certificate {
     /// Stake registration and delegation (Conway).
     #[serde(rename = "stakeCredentialRegistrationAndDelegation")]
     StakeCredentialRegistrationAndDelegation {
+        #[serde(flatten)]
         credential: StakeCredential,
-        #[serde(rename = "stakePool")]
+        #[serde(rename = "stakePool", with = "stake_pool_reference")]
         stake_pool: StakePoolId,
         deposit: AdaValue,
     },
     /// Vote registration and delegation (Conway).
     #[serde(rename = "stakeCredentialRegistrationAndVoteDelegation")]
     StakeCredentialRegistrationAndVoteDelegation {
+        #[serde(flatten)]
         credential: StakeCredential,
         #[serde(rename = "delegateRepresentative")]
         delegate_representative: Delegatee,
@@ -109,8 +119,9 @@ pub enum Certificate {
     /// Stake registration and both delegations (Conway).
     #[serde(rename = "stakeCredentialRegistrationAndBothDelegations")]
     StakeCredentialRegistrationAndBothDelegations {
+        #[serde(flatten)]
         credential: StakeCredential,
-        #[serde(rename = "stakePool")]
+        #[serde(rename = "stakePool", with = "stake_pool_reference")]
         stake_pool: StakePoolId,
         #[serde(rename = "delegateRepresentative")]
         delegate_representative: Delegatee,
@@ -134,16 +145,66 @@ pub enum Certificate {
 
 /// Delegatee for vote delegation.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "camelCase")]
 pub enum Delegatee {
-    /// Delegate to a specific DRep.
-    DRep(DelegateRepresentativeCredential),
+    /// Delegate to a registered DRep.
+    Registered {
+        /// Whether the DRep is identified by a key or a script.
+        from: CredentialOrigin,
+        /// DRep credential hash.
+        id: DigestBlake2b224,
+    },
     /// Delegate to always abstain.
-    #[serde(rename = "abstain")]
-    Abstain(String),
+    Abstain,
     /// Delegate to always vote no confidence.
-    #[serde(rename = "noConfidence")]
-    NoConfidence(String),
+    NoConfidence,
+}
+
+impl From<Delegatee> for DRepId {
+    fn from(delegatee: Delegatee) -> Self {
+        match delegatee {
+            Delegatee::Registered {
+                from: CredentialOrigin::Key,
+                id,
+            } => DRepId::Key(id),
+            Delegatee::Registered {
+                from: CredentialOrigin::Script,
+                id,
+            } => DRepId::Script(id.into()),
+            Delegatee::Abstain => DRepId::AlwaysAbstain,
+            Delegatee::NoConfidence => DRepId::AlwaysNoConfidence,
+        }
+    }
+}
+
+/// A stake pool as delegation certificates and stake pool queries send it:
+/// `{ "id": … }`.
+pub(crate) mod stake_pool_reference {
+    use super::StakePoolId;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct Reference<T> {
+        id: T,
+    }
+
+    pub fn serialize<S: Serializer>(id: &StakePoolId, serializer: S) -> Result<S::Ok, S::Error> {
+        Reference { id }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<StakePoolId, D::Error> {
+        Ok(Reference::deserialize(deserializer)?.id)
+    }
+
+    /// Serialize an optional list of stake pools, each as `{ "id": … }`.
+    pub fn serialize_list<S: Serializer>(
+        ids: &Option<Vec<StakePoolId>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        ids.as_ref()
+            .map(|ids| ids.iter().map(|id| Reference { id }).collect::<Vec<_>>())
+            .serialize(serializer)
+    }
 }
 
 /// Stake pool registration parameters.
@@ -153,6 +214,7 @@ pub struct StakePool {
     /// Pool ID.
     pub id: StakePoolId,
     /// VRF key hash.
+    #[serde(rename = "vrfVerificationKeyHash", alias = "vrf")]
     pub vrf: VrfVerificationKey,
     /// Pledge amount.
     pub pledge: AdaValue,
@@ -168,37 +230,37 @@ pub struct StakePool {
     #[serde(default)]
     pub relays: Vec<Relay>,
     /// Pool metadata.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<PoolMetadata>,
 }
 
 /// Pool relay configuration.
+///
+/// A hostname relay without a port is resolved through DNS SRV records.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
+#[serde(tag = "type", rename_all = "camelCase")]
 pub enum Relay {
     /// IP address relay.
     IpAddress {
-        #[serde(rename = "ipv4")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         ipv4: Option<String>,
-        #[serde(rename = "ipv6")]
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         ipv6: Option<String>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         port: Option<u16>,
     },
     /// DNS hostname relay.
     Hostname {
         hostname: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         port: Option<u16>,
     },
-    /// DNS SRV record relay.
-    #[serde(rename = "dnsA")]
-    DnsA {
-        hostname: String,
-    },
 }
 
 /// Pool metadata reference.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct PoolMetadata {
     /// Metadata URL.
     pub url: String,
@@ -207,37 +269,371 @@ pub struct PoolMetadata {
 }
 
 /// Stake pool view (for queries).
+///
+/// The pool ID is part of the [`parameters`](StakePoolView::parameters), as on
+/// the wire.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct StakePoolView {
-    /// Pool ID.
-    pub id: StakePoolId,
     /// Pool parameters.
     #[serde(flatten)]
     pub parameters: StakePool,
     /// Pool status.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub status: Option<StakePoolStatus>,
+    /// Scheduled retirement, for retiring pools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retirement: Option<StakePoolRetirement>,
     /// Live stake (if requested).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stake: Option<AdaValue>,
 }
 
-/// Stake pool status.
+impl StakePoolView {
+    /// Pool ID.
+    pub fn id(&self) -> &StakePoolId {
+        &self.parameters.id
+    }
+}
+
+/// Scheduled retirement of a stake pool.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct StakePoolRetirement {
+    /// Epoch at the start of which the pool retires.
+    pub epoch: Epoch,
+}
+
+/// Stake pool status.
+///
+/// Statuses not known to this version are kept as
+/// [`Other`](StakePoolStatus::Other).
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum StakePoolStatus {
     Active,
     Retiring,
     Retired,
+    Other(String),
 }
 
+named_enum!(StakePoolStatus {
+    Active => "active",
+    Retiring => "retiring",
+    Retired => "retired",
+});
+
 /// Stake pool performance.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct StakePoolPerformance {
     /// Pool ID.
     pub id: StakePoolId,
     /// Performance ratio.
     pub performance: f64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Laid out as in the Ogmios v6 specification; no block with Conway
+    // certificates is captured under `fixtures/` yet (see its README).
+    const STAKE_KEY: &str = "a1d3c1d2ab8a8e1a5b5f1cbf6a2e9d32a8e4c7d2a7bcf2f1a1d5e6f7";
+    const DREP: &str = "03ccae794affbe27a5f5f74da6266002db11daa6ae446aea783b972d";
+    const POOL: &str = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+    const RETIRING_POOL: &str = "pool1z5uqdk7dzdxaae5633fqfcu2eqzy3a3rgtuvy087fdld7yws0xt";
+
+    fn round_trip(certificate: serde_json::Value) -> Certificate {
+        let parsed: Certificate = serde_json::from_value(certificate.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), certificate);
+        parsed
+    }
+
+    #[test]
+    fn test_vote_delegation_targets() {
+        for (target, expected) in [
+            (
+                json!({ "type": "registered", "from": "verificationKey", "id": DREP }),
+                DRepId::Key(DREP.parse().unwrap()),
+            ),
+            (
+                json!({ "type": "registered", "from": "script", "id": DREP }),
+                DRepId::Script(DREP.parse().unwrap()),
+            ),
+            (json!({ "type": "abstain" }), DRepId::AlwaysAbstain),
+            (json!({ "type": "noConfidence" }), DRepId::AlwaysNoConfidence),
+        ] {
+            let certificate = round_trip(json!({
+                "type": "voteDelegation",
+                "credential": STAKE_KEY,
+                "from": "verificationKey",
+                "delegateRepresentative": target
+            }));
+            let Certificate::VoteDelegation {
+                delegate_representative,
+                ..
+            } = certificate
+            else {
+                panic!("unexpected certificate {certificate:?}");
+            };
+            assert_eq!(DRepId::from(delegate_representative), expected);
+        }
+    }
+
+    #[test]
+    fn test_combined_vote_delegations() {
+        let abstain = json!({ "type": "abstain" });
+        let deposit = json!({ "ada": { "lovelace": 2000000 } });
+
+        let certificate = round_trip(json!({
+            "type": "stakeAndVoteDelegation",
+            "credential": STAKE_KEY,
+            "from": "verificationKey",
+            "stakePool": { "id": POOL },
+            "delegateRepresentative": abstain
+        }));
+        assert!(matches!(
+            certificate,
+            Certificate::StakeAndVoteDelegation {
+                delegate_representative: Delegatee::Abstain,
+                ..
+            }
+        ));
+
+        let certificate = round_trip(json!({
+            "type": "stakeCredentialRegistrationAndVoteDelegation",
+            "credential": STAKE_KEY,
+            "from": "script",
+            "delegateRepresentative": { "type": "noConfidence" },
+            "deposit": deposit
+        }));
+        assert!(matches!(
+            certificate,
+            Certificate::StakeCredentialRegistrationAndVoteDelegation {
+                delegate_representative: Delegatee::NoConfidence,
+                ..
+            }
+        ));
+
+        let certificate = round_trip(json!({
+            "type": "stakeCredentialRegistrationAndBothDelegations",
+            "credential": STAKE_KEY,
+            "from": "verificationKey",
+            "stakePool": { "id": POOL },
+            "delegateRepresentative": { "type": "registered", "from": "script", "id": DREP },
+            "deposit": deposit
+        }));
+        let Certificate::StakeCredentialRegistrationAndBothDelegations {
+            delegate_representative,
+            ..
+        } = certificate
+        else {
+            panic!("unexpected certificate {certificate:?}");
+        };
+        assert_eq!(
+            delegate_representative,
+            Delegatee::Registered {
+                from: CredentialOrigin::Script,
+                id: DREP.parse().unwrap(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_credentials_follow_their_origin() {
+        let certificate = round_trip(json!({
+            "type": "stakeDelegation",
+            "credential": STAKE_KEY,
+            "from": "script",
+            "stakePool": { "id": POOL }
+        }));
+        assert_eq!(
+            certificate,
+            Certificate::StakeDelegation {
+                credential: StakeCredential::Script {
+                    script: STAKE_KEY.parse().unwrap(),
+                },
+                stake_pool: POOL.to_string(),
+            }
+        );
+
+        let certificate = round_trip(json!({
+            "type": "delegateRepresentativeRetirement",
+            "delegateRepresentative": { "from": "script", "id": DREP },
+            "deposit": { "ada": { "lovelace": 500000000 } }
+        }));
+        assert!(matches!(
+            certificate,
+            Certificate::DelegateRepresentativeRetirement {
+                delegate_representative: DelegateRepresentativeCredential::Script { .. },
+                ..
+            }
+        ));
+
+        let certificate = round_trip(json!({
+            "type": "constitutionalCommitteeHotKeyRegistration",
+            "member": { "from": "verificationKey", "id": DREP },
+            "hotKey": { "from": "script", "id": STAKE_KEY }
+        }));
+        assert_eq!(
+            certificate,
+            Certificate::ConstitutionalCommitteeHotKeyRegistration {
+                member: ConstitutionalCommitteeMemberCredential::Key {
+                    key: DREP.parse().unwrap(),
+                },
+                hot_key: ConstitutionalCommitteeMemberCredential::Script {
+                    script: STAKE_KEY.parse().unwrap(),
+                },
+            }
+        );
+
+        // The pre-v6 shapes carry no origin and are rejected.
+        for legacy in [
+            json!({ "type": "stakeDelegation", "credential": { "key": STAKE_KEY }, "stakePool": { "id": POOL } }),
+            json!({ "type": "stakeDelegation", "credential": STAKE_KEY, "from": "verificationKey", "stakePool": POOL }),
+            json!({ "type": "delegateRepresentativeRetirement", "delegateRepresentative": { "id": DREP }, "deposit": { "ada": { "lovelace": 0 } } }),
+        ] {
+            assert!(serde_json::from_value::<Certificate>(legacy).is_err());
+        }
+    }
+
+    #[test]
+    fn test_pool_registration_relays() {
+        let certificate = round_trip(json!({
+            "type": "stakePoolRegistration",
+            "stakePool": {
+                "id": POOL,
+                "vrfVerificationKeyHash":
+                    "c2b62ffa92ad18ffc117ea3abeb161a68885000a466f9c71db5e4731d6630061",
+                "pledge": { "ada": { "lovelace": 450000000000u64 } },
+                "cost": { "ada": { "lovelace": 340000000 } },
+                "margin": "1/100",
+                "rewardAccount": "stake1uxpdrerp9wrxunfh6ukyv5267j70fzxgw0fr3z8zeac5vyqhf9jhy",
+                "owners": ["829f04fe15e3e6bb5ccbcdb84ac1c7a0e0d7fa1e5b2ef13d1a2c3b4d"],
+                "relays": [
+                    { "type": "ipAddress", "ipv4": "18.158.229.3", "port": 3001 },
+                    { "type": "ipAddress", "ipv6": "2a05:d014:1cfa:8c00::1", "port": 3001 },
+                    { "type": "hostname", "hostname": "relays.example.com", "port": 3001 },
+                    { "type": "hostname", "hostname": "_cardano._tcp.example.com" }
+                ],
+                "metadata": {
+                    "url": "https://example.com/pool.json",
+                    "hash": "b4f6c4c3e2f1a0d9c8b7a6e5f4d3c2b1a0f9e8d7c6b5a4f3e2d1c0b9a8f7e6d5"
+                }
+            }
+        }));
+        let Certificate::StakePoolRegistration { stake_pool } = certificate else {
+            panic!("unexpected certificate {certificate:?}");
+        };
+        assert_eq!(
+            stake_pool.relays,
+            [
+                Relay::IpAddress {
+                    ipv4: Some("18.158.229.3".to_string()),
+                    ipv6: None,
+                    port: Some(3001),
+                },
+                Relay::IpAddress {
+                    ipv4: None,
+                    ipv6: Some("2a05:d014:1cfa:8c00::1".to_string()),
+                    port: Some(3001),
+                },
+                Relay::Hostname {
+                    hostname: "relays.example.com".to_string(),
+                    port: Some(3001),
+                },
+                Relay::Hostname {
+                    hostname: "_cardano._tcp.example.com".to_string(),
+                    port: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_stake_pools_query_relays() {
+        let view: StakePoolView = serde_json::from_value(json!({
+            "id": POOL,
+            "vrf": "c2b62ffa92ad18ffc117ea3abeb161a68885000a466f9c71db5e4731d6630061",
+            "pledge": { "ada": { "lovelace": 450000000000u64 } },
+            "cost": { "ada": { "lovelace": 340000000 } },
+            "margin": "1/100",
+            "rewardAccount": "stake1uxpdrerp9wrxunfh6ukyv5267j70fzxgw0fr3z8zeac5vyqhf9jhy",
+            "owners": [],
+            "relays": [{ "type": "hostname", "hostname": "relays.example.com", "port": 3001 }],
+            "status": "active"
+        }))
+        .unwrap();
+        assert_eq!(
+            view.parameters.relays,
+            [Relay::Hostname {
+                hostname: "relays.example.com".to_string(),
+                port: Some(3001),
+            }]
+        );
+        // A relay without its type tag is rejected rather than guessed.
+        let untagged = json!({ "hostname": "relays.example.com" });
+        assert!(serde_json::from_value::<Relay>(untagged).is_err());
+    }
+
+    /// The captured `queryLedgerState/stakePools` result, which holds a
+    /// single pool, plus a copy of it marked as retiring. The copy is
+    /// synthetic: no response with a retiring pool is captured under
+    /// `fixtures/` yet (see its README).
+    fn stake_pools_response() -> serde_json::Value {
+        let mut response = crate::schema::testing::fixture("ledger-state/stakePools").result();
+        let mut retiring = response[POOL].clone();
+        retiring["id"] = json!(RETIRING_POOL);
+        retiring["status"] = json!("retiring");
+        retiring["retirement"] = json!({ "epoch": 163 });
+        response[RETIRING_POOL] = retiring;
+        response
+    }
+
+    #[test]
+    fn test_stake_pools_response_round_trip() {
+        let pools: std::collections::HashMap<StakePoolId, StakePoolView> =
+            serde_json::from_value(stake_pools_response()).unwrap();
+
+        let active = &pools[POOL];
+        assert_eq!(active.id(), POOL);
+        assert_eq!(active.status, None);
+        assert_eq!(active.retirement, None);
+        assert!(active.parameters.metadata.is_some());
+        assert_eq!(active.stake.as_ref().unwrap().lovelace, 61234567890123);
+
+        let retiring = &pools[RETIRING_POOL];
+        assert_eq!(retiring.id(), RETIRING_POOL);
+        assert_eq!(retiring.status, Some(StakePoolStatus::Retiring));
+        assert_eq!(retiring.retirement, Some(StakePoolRetirement { epoch: 163 }));
+
+        for view in pools.values() {
+            let serialized = serde_json::to_value(view).unwrap();
+            assert_eq!(serialized["id"], view.id().as_str());
+            assert!(serialized.get("vrfVerificationKeyHash").is_some());
+            assert_eq!(serde_json::from_value::<StakePoolView>(serialized).unwrap(), *view);
+        }
+    }
+
+    #[test]
+    fn test_rejects_untagged_delegatee() {
+        for target in [json!("abstain"), json!({ "id": DREP }), json!({ "type": "other" })] {
+            assert!(serde_json::from_value::<Delegatee>(target).is_err());
+        }
+    }
+
+    #[test]
+    fn test_unknown_pool_status_keeps_response_parsing() {
+        let mut response = stake_pools_response();
+        response[POOL]["status"] = json!("delisted");
+        let pools: std::collections::HashMap<StakePoolId, StakePoolView> =
+            serde_json::from_value(response).unwrap();
+        assert_eq!(pools.len(), 2);
+        assert_eq!(pools[POOL].status, Some(StakePoolStatus::Other("delisted".to_string())));
+        assert_eq!(serde_json::to_value(&pools[POOL]).unwrap()["status"], "delisted");
+    }
+}
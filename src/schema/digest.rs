@@ -0,0 +1,294 @@
+//! Hex-encoded digest newtypes.
+//!
+//! Every digest is validated to be hex of the expected length, whether parsed
+//! with [`FromStr`], converted with [`TryFrom`] or deserialized, and is kept
+//! in lowercase.
+
+use crate::util::{hex_decode, hex_encode};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::borrow::Borrow;
+use std::fmt;
+use std::str::FromStr;
+
+/// A string that is not a hex digest of the expected size.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid {kind}: expected {expected} hex characters, got {value:?}")]
+pub struct InvalidDigest {
+    /// Name of the digest type.
+    pub kind: &'static str,
+    /// Expected number of hex characters.
+    pub expected: usize,
+    /// Rejected value.
+    pub value: String,
+}
+
+pub(super) fn decode_hex<const N: usize>(
+    kind: &'static str,
+    value: &str,
+) -> Result<[u8; N], InvalidDigest> {
+    let invalid = || InvalidDigest {
+        kind,
+        expected: N * 2,
+        value: value.to_string(),
+    };
+    if value.len() != N * 2 {
+        return Err(invalid());
+    }
+    // A `0x` prefix decodes to fewer than N bytes.
+    hex_decode(value)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)
+}
+
+macro_rules! hex_digest {
+    ($(#[$meta:meta])* $name:ident, $bytes:literal) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
+        #[serde(transparent)]
+        pub struct $name(String);
+
+        impl $name {
+            /// Size of the digest in bytes.
+            pub const BYTES: usize = $bytes;
+
+            /// Create from the digest bytes.
+            pub fn from_bytes(bytes: [u8; $bytes]) -> Self {
+                Self(hex_encode(&bytes))
+            }
+
+            /// Decode the digest bytes.
+            pub fn to_bytes(&self) -> [u8; $bytes] {
+                decode_hex(stringify!($name), &self.0).expect("a digest is validated on creation")
+            }
+
+            /// Get the hex encoding.
+            pub fn as_str(&self) -> &str {
+                &self.0
+            }
+
+            /// Convert into the hex encoding.
+            pub fn into_string(self) -> String {
+                self.0
+            }
+
+            /// A digest made of `pattern` repeated, for tests.
+            #[cfg(test)]
+            #[allow(dead_code)]
+            pub(crate) fn filled(pattern: &str) -> Self {
+                pattern.repeat($bytes * 2 / pattern.len()).parse().unwrap()
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = InvalidDigest;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                decode_hex::<$bytes>(stringify!($name), s)?;
+                Ok(Self(s.to_ascii_lowercase()))
+            }
+        }
+
+        impl TryFrom<&str> for $name {
+            type Error = InvalidDigest;
+
+            fn try_from(s: &str) -> Result<Self, Self::Error> {
+                s.parse()
+            }
+        }
+
+        impl TryFrom<String> for $name {
+            type Error = InvalidDigest;
+
+            fn try_from(mut s: String) -> Result<Self, Self::Error> {
+                decode_hex::<$bytes>(stringify!($name), &s)?;
+                s.make_ascii_lowercase();
+                Ok(Self(s))
+            }
+        }
+
+        impl<'de> Deserialize<'de> for $name {
+            fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                Self::try_from(String::deserialize(deserializer)?).map_err(serde::de::Error::custom)
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(digest: $name) -> Self {
+                digest.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(&self.0)
+            }
+        }
+
+        impl AsRef<str> for $name {
+            fn as_ref(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl Borrow<str> for $name {
+            fn borrow(&self) -> &str {
+                &self.0
+            }
+        }
+
+        impl PartialEq<str> for $name {
+            fn eq(&self, other: &str) -> bool {
+                self.0 == other
+            }
+        }
+
+        impl PartialEq<&str> for $name {
+            fn eq(&self, other: &&str) -> bool {
+                self.0 == *other
+            }
+        }
+    };
+}
+
+hex_digest!(
+    /// Transaction ID: Blake2b-256 digest of the transaction body (64 hex characters).
+    TransactionId,
+    32
+);
+
+/// Which half of a `<transaction id>#<index>` string is malformed.
+pub(super) enum InvalidIndexedId {
+    /// The `#` separator is missing.
+    Format,
+    /// The transaction ID half is malformed.
+    TransactionId(InvalidDigest),
+    /// The index half is malformed.
+    Index(std::num::ParseIntError),
+}
+
+/// Parse the `<transaction id>#<index>` form shared by output references and
+/// governance action IDs.
+pub(super) fn parse_indexed_id(s: &str) -> Result<(TransactionId, u32), InvalidIndexedId> {
+    let (id, index) = s.split_once('#').ok_or(InvalidIndexedId::Format)?;
+    let id = id.parse().map_err(InvalidIndexedId::TransactionId)?;
+    let index = index.parse().map_err(InvalidIndexedId::Index)?;
+    Ok((id, index))
+}
+
+hex_digest!(
+    /// Datum hash: Blake2b-256 digest of a datum (64 hex characters).
+    DatumHash,
+    32
+);
+
+hex_digest!(
+    /// Script hash: Blake2b-224 digest of a script (56 hex characters).
+    ScriptHash,
+    28
+);
+
+hex_digest!(
+    /// Policy ID: hash of the minting policy script (56 hex characters).
+    PolicyId,
+    28
+);
+
+hex_digest!(
+    /// Blake2b-224 digest, e.g. of a verification key (56 hex characters).
+    DigestBlake2b224,
+    28
+);
+
+/// A credential hash whose origin says it is a script.
+impl From<DigestBlake2b224> for ScriptHash {
+    fn from(digest: DigestBlake2b224) -> Self {
+        Self(digest.0)
+    }
+}
+
+hex_digest!(
+    /// Blake2b-256 digest, e.g. of a block or of an anchor's content (64 hex
+    /// characters).
+    DigestBlake2b256,
+    32
+);
+
+/// The `String` aliases the digest types replaced, kept for one release.
+///
+/// Code that relies on digests being strings can import these instead of the
+/// newtypes while it migrates.
+pub mod string_digests {
+    /// Transaction ID as a hex-encoded string (64 characters).
+    #[deprecated(note = "use the validated `schema::TransactionId`")]
+    pub type TransactionId = String;
+
+    /// Policy ID as a hex-encoded string (56 characters).
+    #[deprecated(note = "use the validated `schema::PolicyId`")]
+    pub type PolicyId = String;
+
+    /// Script hash as a hex-encoded string.
+    #[deprecated(note = "use the validated `schema::ScriptHash`")]
+    pub type ScriptHash = String;
+
+    /// Datum hash as a hex-encoded string (64 characters, Blake2b-256).
+    #[deprecated(note = "use the validated `schema::DatumHash`")]
+    pub type DatumHash = String;
+
+    /// Blake2b-224 digest (56 hex characters).
+    #[deprecated(note = "use the validated `schema::DigestBlake2b224`")]
+    pub type DigestBlake2b224 = String;
+
+    /// Blake2b-256 digest (64 hex characters).
+    #[deprecated(note = "use the validated `schema::DigestBlake2b256`")]
+    pub type DigestBlake2b256 = String;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    const TX_ID: &str = "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7";
+
+    #[test]
+    fn test_parse_validates_length_and_hex() {
+        let id: TransactionId = TX_ID.parse().unwrap();
+        assert_eq!(id.as_str(), TX_ID);
+        assert_eq!(id.to_bytes()[0], 0x3c);
+        assert_eq!(TransactionId::from_bytes(id.to_bytes()), id);
+        assert_eq!(TransactionId::try_from(TX_ID.to_string()).unwrap(), id);
+        assert!(TransactionId::try_from("a".to_string()).is_err());
+
+        let error = TransactionId::try_from(&TX_ID[..56]).unwrap_err();
+        assert_eq!(error.expected, 64);
+        assert!(TransactionId::try_from(TX_ID.replace('3', "g").as_str()).is_err());
+        assert!("addr_test1vz".parse::<TransactionId>().is_err());
+        assert!(format!("0x{}", &TX_ID[2..]).parse::<TransactionId>().is_err());
+
+        assert!(ScriptHash::try_from(&TX_ID[..56]).is_ok());
+        assert!(ScriptHash::try_from(TX_ID).is_err());
+    }
+
+    #[test]
+    fn test_parse_normalizes_case() {
+        let id: TransactionId = TX_ID.to_uppercase().parse().unwrap();
+        assert_eq!(id, TX_ID);
+    }
+
+    #[test]
+    fn test_wire_format_is_a_plain_string() {
+        let id: TransactionId = serde_json::from_str(&format!("\"{TX_ID}\"")).unwrap();
+        assert_eq!(serde_json::to_string(&id).unwrap(), format!("\"{TX_ID}\""));
+
+        let error = serde_json::from_str::<TransactionId>("\"ab\"").unwrap_err();
+        assert!(error.to_string().contains("expected 64 hex characters"));
+        assert!(serde_json::from_str::<DigestBlake2b256>(&format!("\"{}\"", &TX_ID[..56])).is_err());
+    }
+
+    #[test]
+    fn test_map_lookup_by_str() {
+        let map: HashMap<TransactionId, u32> = [(TransactionId::try_from(TX_ID).unwrap(), 1)].into();
+        assert_eq!(map.get(TX_ID), Some(&1));
+    }
+}
@@ -2,9 +2,30 @@
 
 use serde::{Deserialize, Serialize};
 use super::primitives::*;
+use std::str::FromStr;
 
-/// Cardano era names.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// A string that is not the name of an era, or an era without genesis configuration.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid era {value:?}: expected one of {expected}")]
+pub struct InvalidEra {
+    /// Rejected value.
+    pub value: String,
+    /// Accepted era names.
+    pub expected: &'static str,
+}
+
+/// Cardano era names, ordered chronologically.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::Era;
+///
+/// let era: Era = "Babbage".parse().unwrap();
+/// assert!(era >= Era::Alonzo);
+/// assert_eq!(era.next(), Some(Era::Conway));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum Era {
     Byron,
@@ -17,6 +38,27 @@ pub enum Era {
 }
 
 impl Era {
+    /// All eras, in chronological order.
+    pub const ALL: [Era; 7] = [
+        Era::Byron,
+        Era::Shelley,
+        Era::Allegra,
+        Era::Mary,
+        Era::Alonzo,
+        Era::Babbage,
+        Era::Conway,
+    ];
+
+    /// Get the era following this one, if any.
+    pub fn next(self) -> Option<Era> {
+        Self::ALL.get(self as usize + 1).copied()
+    }
+
+    /// Get the era preceding this one, if any.
+    pub fn previous(self) -> Option<Era> {
+        (self as usize).checked_sub(1).map(|index| Self::ALL[index])
+    }
+
     /// Get the era as a string.
     pub fn as_str(&self) -> &'static str {
         match self {
@@ -37,8 +79,31 @@ impl std::fmt::Display for Era {
     }
 }
 
+impl FromStr for Era {
+    type Err = InvalidEra;
+
+    /// Parse an era name, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|era| era.as_str().eq_ignore_ascii_case(s))
+            .ok_or_else(|| InvalidEra {
+                value: s.to_string(),
+                expected: "byron, shelley, allegra, mary, alonzo, babbage, conway",
+            })
+    }
+}
+
+impl TryFrom<&str> for Era {
+    type Error = InvalidEra;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// Eras that have genesis configuration.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum EraWithGenesis {
     Byron,
@@ -59,9 +124,67 @@ impl EraWithGenesis {
     }
 }
 
+impl std::fmt::Display for EraWithGenesis {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl From<EraWithGenesis> for Era {
+    fn from(era: EraWithGenesis) -> Self {
+        match era {
+            EraWithGenesis::Byron => Era::Byron,
+            EraWithGenesis::Shelley => Era::Shelley,
+            EraWithGenesis::Alonzo => Era::Alonzo,
+            EraWithGenesis::Conway => Era::Conway,
+        }
+    }
+}
+
+impl TryFrom<Era> for EraWithGenesis {
+    type Error = InvalidEra;
+
+    fn try_from(era: Era) -> Result<Self, Self::Error> {
+        match era {
+            Era::Byron => Ok(EraWithGenesis::Byron),
+            Era::Shelley => Ok(EraWithGenesis::Shelley),
+            Era::Alonzo => Ok(EraWithGenesis::Alonzo),
+            Era::Conway => Ok(EraWithGenesis::Conway),
+            Era::Allegra | Era::Mary | Era::Babbage => Err(InvalidEra {
+                value: era.as_str().to_string(),
+                expected: "byron, shelley, alonzo, conway",
+            }),
+        }
+    }
+}
+
+impl FromStr for EraWithGenesis {
+    type Err = InvalidEra;
+
+    /// Parse the name of an era with genesis configuration, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse::<Era>() {
+            Ok(era) => era.try_into(),
+            Err(error) => Err(InvalidEra {
+                expected: "byron, shelley, alonzo, conway",
+                ..error
+            }),
+        }
+    }
+}
+
+impl TryFrom<&str> for EraWithGenesis {
+    type Error = InvalidEra;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
 /// Era summary information.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EraSummary {
     /// Start of the era.
     pub start: EraBound,
@@ -75,22 +198,37 @@ pub struct EraSummary {
 /// Era boundary point.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EraBound {
     /// Slot number.
     pub slot: Slot,
     /// Epoch number.
     pub epoch: Epoch,
     /// Time since system start.
+    #[serde(with = "super::primitives::relative_time")]
     pub time: RelativeTime,
 }
 
+#[cfg(feature = "chrono")]
+impl EraBound {
+    /// Wall-clock time of the bound, given the network start time.
+    pub fn utc_time(&self, system_start: UtcTime) -> UtcTime {
+        relative_time_to_utc(system_start, self.time)
+    }
+}
+
 /// Parameters for an era.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EraParameters {
     /// Length of an epoch in slots.
     pub epoch_length: u64,
     /// Duration of a slot in seconds.
+    #[serde(
+        deserialize_with = "super::primitives::relative_time::deserialize",
+        serialize_with = "super::primitives::relative_time::serialize_milliseconds"
+    )]
     pub slot_length: RelativeTime,
     /// Safe zone (slots before era end to stop accepting certain operations).
     #[serde(default)]
@@ -100,11 +238,61 @@ pub struct EraParameters {
 /// Era start information from ledger queries.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EraStart {
     /// Time since system start.
+    #[serde(with = "super::primitives::relative_time")]
     pub time: RelativeTime,
     /// Slot number.
     pub slot: Slot,
     /// Epoch number.
     pub epoch: Epoch,
 }
+
+#[cfg(feature = "chrono")]
+impl EraStart {
+    /// Wall-clock time at which the era started, given the network start time.
+    pub fn utc_time(&self, system_start: UtcTime) -> UtcTime {
+        relative_time_to_utc(system_start, self.time)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_era_order_and_navigation() {
+        assert!(Era::ALL.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(Era::Babbage >= Era::Alonzo);
+        assert_eq!(Era::Byron.previous(), None);
+        assert_eq!(Era::Byron.next(), Some(Era::Shelley));
+        assert_eq!(Era::Conway.previous(), Some(Era::Babbage));
+        assert_eq!(Era::Conway.next(), None);
+    }
+
+    #[test]
+    fn test_era_from_str() {
+        for era in Era::ALL {
+            assert_eq!(era.as_str().parse::<Era>(), Ok(era));
+            assert_eq!(Era::try_from(era.as_str().to_uppercase().as_str()), Ok(era));
+        }
+        let error = "dijkstra".parse::<Era>().unwrap_err();
+        assert_eq!(error.value, "dijkstra");
+        assert!(error.to_string().contains("conway"));
+    }
+
+    #[test]
+    fn test_era_with_genesis_conversions() {
+        assert_eq!(EraWithGenesis::try_from("Alonzo"), Ok(EraWithGenesis::Alonzo));
+        assert_eq!(EraWithGenesis::try_from(Era::Conway), Ok(EraWithGenesis::Conway));
+        assert_eq!(Era::from(EraWithGenesis::Shelley), Era::Shelley);
+
+        let error = EraWithGenesis::try_from(Era::Mary).unwrap_err();
+        assert_eq!(error.value, "mary");
+        let error = "babbage".parse::<EraWithGenesis>().unwrap_err();
+        assert_eq!(error.expected, "byron, shelley, alonzo, conway");
+        let error = "unknown".parse::<EraWithGenesis>().unwrap_err();
+        assert_eq!(error.expected, "byron, shelley, alonzo, conway");
+    }
+}
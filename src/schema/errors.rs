@@ -0,0 +1,205 @@
+//! Data payloads of Ogmios failures.
+//!
+//! Ogmios attaches a `data` object to most transaction submission and
+//! evaluation failures, whose shape depends on the error code. These types
+//! are parsed into [`SubmitTransactionError`](crate::error::SubmitTransactionError)
+//! and [`EvaluateTransactionError`](crate::error::EvaluateTransactionError).
+
+use super::block::BlockSize;
+use super::digest::{DigestBlake2b224, ScriptHash, TransactionId};
+use super::era::Era;
+use super::primitives::{AdaValue, Slot, Value};
+use super::transaction::{ExUnits, TransactionOutputReference};
+use serde::{Deserialize, Deserializer, Serialize};
+
+/// The transaction was built for another era than the ledger's.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct EraMismatch {
+    /// Era of the transaction.
+    pub query_era: Era,
+    /// Era of the ledger.
+    pub ledger_era: Era,
+}
+
+/// Signatures required by the transaction but absent from its witnesses.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MissingSignatories {
+    /// Hashes of the verification keys that must sign.
+    pub missing_signatories: Vec<DigestBlake2b224>,
+}
+
+/// Scripts required by the transaction but neither witnessed nor referenced.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MissingScripts {
+    /// Hashes of the missing scripts.
+    pub missing_scripts: Vec<ScriptHash>,
+}
+
+/// Inputs spending outputs that do not exist, or were already spent.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct UnknownOutputReferences {
+    /// References of the unknown outputs.
+    #[serde(deserialize_with = "deserialize_output_references")]
+    pub unknown_output_references: Vec<TransactionOutputReference>,
+}
+
+/// The transaction is not valid at the current slot.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct OutsideOfValidityInterval {
+    /// Validity interval of the transaction.
+    pub validity_interval: ValidityInterval,
+    /// Slot the ledger is at.
+    pub current_slot: Slot,
+}
+
+/// Validity interval of a transaction; both bounds are optional.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ValidityInterval {
+    /// First slot the transaction is valid in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invalid_before: Option<Slot>,
+    /// First slot the transaction is no longer valid in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub invalid_after: Option<Slot>,
+}
+
+/// The transaction exceeds the maximum transaction size.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TransactionTooLarge {
+    /// Size of the transaction.
+    pub measured_transaction_size: BlockSize,
+    /// Maximum size allowed by the protocol parameters.
+    pub maximum_transaction_size: BlockSize,
+}
+
+/// The fee is lower than the minimum fee of the transaction.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TransactionFeeTooSmall {
+    /// Minimum fee for the transaction.
+    pub minimum_required_fee: AdaValue,
+    /// Fee declared by the transaction.
+    pub provided_fee: AdaValue,
+}
+
+/// The value consumed by the transaction differs from the value it produces.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ValueNotConserved {
+    /// Value of the inputs, withdrawals, refunds and minted assets.
+    pub value_consumed: Value,
+    /// Value of the outputs, fee, deposits and burned assets.
+    pub value_produced: Value,
+}
+
+/// The collateral does not cover the required percentage of the fee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct InsufficientCollateral {
+    /// Value of the collateral inputs, minus the collateral return.
+    pub provided_collateral: Value,
+    /// Minimum collateral for the transaction.
+    pub minimum_required_collateral: Value,
+}
+
+/// The execution units of the transaction exceed the maximum per transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ExecutionUnitsTooLarge {
+    /// Execution units of the transaction.
+    pub provided_execution_units: ExUnits,
+    /// Maximum allowed by the protocol parameters.
+    pub maximum_execution_units: ExUnits,
+}
+
+/// The transaction cannot be evaluated in the era of the ledger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct IncompatibleEra {
+    /// Era the transaction was built for.
+    pub incompatible_era: Era,
+}
+
+/// Additional UTXOs given for evaluation that are already in the ledger.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct OverlappingAdditionalUtxo {
+    /// References of the overlapping outputs.
+    #[serde(deserialize_with = "deserialize_output_references")]
+    pub overlapping_output_references: Vec<TransactionOutputReference>,
+}
+
+/// The evaluation context could not be created, e.g. for lack of cost
+/// models.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct CannotCreateEvaluationContext {
+    /// Why the context could not be created.
+    pub reason: String,
+}
+
+/// A script failed to validate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ValidationFailure {
+    /// Error reported by the script evaluator.
+    pub validation_error: String,
+    /// Messages traced by the script before it failed, in order.
+    #[serde(default)]
+    pub traces: Vec<String>,
+}
+
+/// Output references in failures are sent as
+/// `{ "transaction": { "id": .. }, "index": .. }`; the flat form of
+/// [`TransactionOutputReference`] is accepted as well.
+fn deserialize_output_references<'de, D>(
+    deserializer: D,
+) -> Result<Vec<TransactionOutputReference>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Reference {
+        Nested { transaction: Transaction, index: u32 },
+        Flat(TransactionOutputReference),
+    }
+
+    #[derive(Deserialize)]
+    struct Transaction {
+        id: TransactionId,
+    }
+
+    Ok(Vec::<Reference>::deserialize(deserializer)?
+        .into_iter()
+        .map(|reference| match reference {
+            Reference::Nested { transaction, index } => TransactionOutputReference {
+                id: transaction.id,
+                index,
+            },
+            Reference::Flat(reference) => reference,
+        })
+        .collect())
+}
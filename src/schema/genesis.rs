@@ -1,12 +1,17 @@
 //! Genesis configuration types for different Cardano eras.
 
+use serde::de::{DeserializeOwned, Deserializer, Error as _};
+use serde::ser::{Error as _, Serializer};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use super::address::Address;
+use super::digest::DigestBlake2b224;
 use super::primitives::*;
 use super::protocol::*;
 
 /// Genesis configuration - varies by era.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
 #[serde(tag = "era", rename_all = "camelCase")]
 pub enum GenesisConfiguration {
     /// Byron genesis configuration.
@@ -14,14 +19,71 @@ pub enum GenesisConfiguration {
     /// Shelley genesis configuration.
     Shelley(GenesisShelley),
     /// Alonzo genesis configuration.
+    #[serde(
+        deserialize_with = "deserialize_updatable_parameters",
+        serialize_with = "serialize_updatable_parameters"
+    )]
     Alonzo(GenesisAlonzo),
     /// Conway genesis configuration.
+    #[serde(
+        deserialize_with = "deserialize_updatable_parameters",
+        serialize_with = "serialize_updatable_parameters"
+    )]
     Conway(GenesisConway),
 }
 
+/// A genesis whose parameters Ogmios nests under `updatableParameters`.
+trait UpdatableParameters {
+    /// Fields sent next to `updatableParameters` rather than in it.
+    const OUTSIDE: &'static [&'static str];
+}
+
+impl UpdatableParameters for GenesisAlonzo {
+    const OUTSIDE: &'static [&'static str] = &[];
+}
+
+impl UpdatableParameters for GenesisConway {
+    const OUTSIDE: &'static [&'static str] = &["constitution", "constitutionalCommittee"];
+}
+
+/// Deserialize a genesis whose parameters Ogmios nests under `updatableParameters`,
+/// lifting them next to the other fields.
+fn deserialize_updatable_parameters<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let mut fields = serde_json::Map::deserialize(deserializer)?;
+    if let Some(serde_json::Value::Object(parameters)) = fields.remove("updatableParameters") {
+        fields.extend(parameters);
+    }
+    T::deserialize(serde_json::Value::Object(fields)).map_err(D::Error::custom)
+}
+
+/// Serialize a genesis with its parameters under `updatableParameters`, as
+/// Ogmios sends it.
+fn serialize_updatable_parameters<S, T>(genesis: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+    T: Serialize + UpdatableParameters,
+{
+    let serde_json::Value::Object(fields) =
+        serde_json::to_value(genesis).map_err(S::Error::custom)?
+    else {
+        return Err(S::Error::custom("expected a genesis object"));
+    };
+    let (outside, parameters): (serde_json::Map<_, _>, serde_json::Map<_, _>) = fields
+        .into_iter()
+        .partition(|(name, _)| T::OUTSIDE.contains(&name.as_str()));
+    let mut fields = outside;
+    fields.insert("updatableParameters".to_string(), parameters.into());
+    fields.serialize(serializer)
+}
+
 /// Byron genesis configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisByron {
     /// Genesis key hashes.
     #[serde(default)]
@@ -29,23 +91,30 @@ pub struct GenesisByron {
     /// Genesis delegates.
     #[serde(default)]
     pub genesis_delegates: HashMap<String, GenesisDelegate>,
+    /// Delegation certificates of the genesis keys, keyed by genesis key hash.
+    #[serde(default)]
+    pub genesis_delegations: HashMap<DigestBlake2b224, super::block::ByronDelegationCertificate>,
     /// Start time.
     pub start_time: UtcTime,
     /// Initial funds.
     #[serde(default)]
-    pub initial_funds: HashMap<Address, Lovelace>,
+    pub initial_funds: HashMap<Address, AdaValue>,
+    /// Initial AVVM vouchers, keyed by redemption key.
+    #[serde(default)]
+    pub initial_vouchers: HashMap<String, AdaValue>,
     /// Security parameter.
     pub security_parameter: u64,
     /// Network magic.
     pub network_magic: NetworkMagic,
-    /// Protocol parameters.
-    #[serde(default)]
+    /// Protocol parameters, sent as `updatableParameters`.
+    #[serde(default, rename = "updatableParameters", alias = "protocolParameters")]
     pub protocol_parameters: Option<BootstrapProtocolParameters>,
 }
 
 /// Genesis delegate.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisDelegate {
     /// Delegate key hash.
     pub delegate: DigestBlake2b224,
@@ -56,25 +125,70 @@ pub struct GenesisDelegate {
 /// Bootstrap (Byron) protocol parameters.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BootstrapProtocolParameters {
     /// Minimum fee coefficient.
     pub min_fee_coefficient: u64,
     /// Minimum fee constant.
-    pub min_fee_constant: Lovelace,
+    pub min_fee_constant: AdaValue,
     /// Maximum block size.
-    pub max_block_body_size: NumberOfBytes,
+    pub max_block_body_size: BlockSize,
     /// Maximum header size.
-    pub max_block_header_size: NumberOfBytes,
+    pub max_block_header_size: BlockSize,
     /// Maximum transaction size.
-    pub max_transaction_size: NumberOfBytes,
+    pub max_transaction_size: BlockSize,
     /// Max proposals per epoch.
     #[serde(default)]
     pub max_update_proposals_per_epoch: Option<u64>,
+    /// Maximum update proposal size.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_update_proposal_size: Option<BlockSize>,
+    /// Share of stake needed to issue a heavyweight delegation certificate.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub heavy_delegation_threshold: Option<Ratio>,
+    /// Share of stake needed to take part in randomness generation.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub multi_party_computation_threshold: Option<Ratio>,
+    /// Script version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub script_version: Option<u64>,
+    /// Slot duration in milliseconds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub slot_duration: Option<u64>,
+    /// Epoch from which stake is unlocked.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unlock_stake_epoch: Option<u64>,
+    /// Share of stake needed to propose an update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_proposal_threshold: Option<Ratio>,
+    /// Number of slots an update proposal stays open.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_proposal_time_to_live: Option<u64>,
+    /// Share of stake needed to vote on an update.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_vote_threshold: Option<Ratio>,
+    /// Thresholds for adopting a software fork.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub soft_fork_rule: Option<SoftForkRule>,
+}
+
+/// Byron soft fork adoption thresholds.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct SoftForkRule {
+    /// Initial adoption threshold.
+    pub init_threshold: Ratio,
+    /// Minimum adoption threshold.
+    pub min_threshold: Ratio,
+    /// Threshold decrement per epoch.
+    pub decrement_threshold: Ratio,
 }
 
 /// Shelley genesis configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisShelley {
     /// Network ID.
     pub network: String,
@@ -93,6 +207,10 @@ pub struct GenesisShelley {
     /// Max KES evolutions.
     pub max_kes_evolutions: u64,
     /// Slot length in seconds.
+    #[serde(
+        deserialize_with = "super::primitives::relative_time::deserialize",
+        serialize_with = "super::primitives::relative_time::serialize_milliseconds"
+    )]
     pub slot_length: RelativeTime,
     /// Update quorum.
     pub update_quorum: u64,
@@ -100,21 +218,74 @@ pub struct GenesisShelley {
     pub max_lovelace_supply: Lovelace,
     /// Initial funds.
     #[serde(default)]
-    pub initial_funds: HashMap<Address, Lovelace>,
+    pub initial_funds: HashMap<Address, AdaValue>,
     /// Initial stake pools.
     #[serde(default)]
     pub initial_stake_pools: GenesisStakePools,
     /// Initial delegates.
     #[serde(default)]
     pub initial_delegates: Vec<InitialDelegate>,
-    /// Protocol parameters.
-    #[serde(default)]
+    /// Protocol parameters, sent as `initialParameters`.
+    #[serde(default, rename = "initialParameters", alias = "protocolParameters")]
     pub protocol_parameters: Option<ProtocolParameters>,
 }
 
+/// Slot and epoch arithmetic for networks that have been in Shelley-based
+/// eras since slot 0.
+///
+/// These helpers assume fixed-length epochs of `epoch_length` slots from the
+/// start of the chain. On networks that began in Byron, such as mainnet and
+/// preprod, Byron epochs have a different length and slot length, so results
+/// are off for every slot; use
+/// [`SlotTimeInterpreter`](crate::ledger_state_query::SlotTimeInterpreter),
+/// built from the era summaries, there.
+impl GenesisShelley {
+    /// Epoch containing a slot, or `None` if the epoch length is 0.
+    pub fn epoch_of_slot(&self, slot: Slot) -> Option<Epoch> {
+        slot.checked_div(self.epoch_length)
+    }
+
+    /// First slot of an epoch, or `None` if it does not fit in a slot number.
+    pub fn first_slot_of_epoch(&self, epoch: Epoch) -> Option<Slot> {
+        epoch.checked_mul(self.epoch_length)
+    }
+
+    /// Number of slots from a slot, included, to the start of the next
+    /// epoch: the epoch length at its first slot, 1 at its last. `None` if
+    /// the epoch length is 0.
+    pub fn slots_remaining_in_epoch(&self, slot: Slot) -> Option<u64> {
+        let offset = slot.checked_rem(self.epoch_length)?;
+        Some(self.epoch_length - offset)
+    }
+
+    /// Wall-clock time at which an epoch starts.
+    pub fn epoch_boundary_time(&self, epoch: Epoch) -> crate::Result<UtcTime> {
+        use crate::ledger_state_query::UtcTimeInput;
+        use crate::ledger_state_query::time::utc_time_from_millis;
+
+        let out_of_range =
+            || crate::OgmiosError::InvalidTime(format!("epoch {epoch} is out of range"));
+        let slot_millis = (self.slot_length * 1000.0).round() as i64;
+        let offset = self
+            .first_slot_of_epoch(epoch)
+            .and_then(|slot| i64::try_from(slot).ok())
+            .and_then(|slot| slot.checked_mul(slot_millis))
+            .ok_or_else(out_of_range)?;
+        let start = self.start_time.to_utc_millis()?;
+        utc_time_from_millis(start.checked_add(offset).ok_or_else(out_of_range)?)
+    }
+
+    /// KES period containing a slot, as used in operational certificates, or
+    /// `None` if the KES period length is 0.
+    pub fn kes_period_of_slot(&self, slot: Slot) -> Option<u64> {
+        crate::util::kes_period_of_slot(slot, self.slots_per_kes_period)
+    }
+}
+
 /// Genesis stake pools configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisStakePools {
     /// Stake pool registrations.
     #[serde(default)]
@@ -126,7 +297,7 @@ pub struct GenesisStakePools {
 
 /// Initial delegate configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(from = "InitialDelegateRepr", into = "InitialDelegateRepr")]
 pub struct InitialDelegate {
     /// Issuer (genesis key hash).
     pub issuer: DigestBlake2b224,
@@ -136,29 +307,101 @@ pub struct InitialDelegate {
     pub vrf: VrfVerificationKey,
 }
 
+/// Wire shapes of an initial delegate: Ogmios nests the keys, as in
+/// `{ "issuer": { "id": .. }, "delegate": { "id": .., "vrfVerificationKeyHash": .. } }`.
+/// The flat form is accepted as well.
+#[derive(Serialize, Deserialize)]
+#[serde(untagged)]
+enum InitialDelegateRepr {
+    #[serde(rename_all = "camelCase")]
+    Nested {
+        issuer: InitialDelegateIssuer,
+        delegate: InitialDelegateDelegate,
+    },
+    Flat {
+        issuer: DigestBlake2b224,
+        delegate: DigestBlake2b224,
+        vrf: VrfVerificationKey,
+    },
+}
+
+#[derive(Serialize, Deserialize)]
+struct InitialDelegateIssuer {
+    id: DigestBlake2b224,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct InitialDelegateDelegate {
+    id: DigestBlake2b224,
+    vrf_verification_key_hash: VrfVerificationKey,
+}
+
+impl From<InitialDelegateRepr> for InitialDelegate {
+    fn from(repr: InitialDelegateRepr) -> Self {
+        match repr {
+            InitialDelegateRepr::Nested { issuer, delegate } => Self {
+                issuer: issuer.id,
+                delegate: delegate.id,
+                vrf: delegate.vrf_verification_key_hash,
+            },
+            InitialDelegateRepr::Flat {
+                issuer,
+                delegate,
+                vrf,
+            } => Self {
+                issuer,
+                delegate,
+                vrf,
+            },
+        }
+    }
+}
+
+impl From<InitialDelegate> for InitialDelegateRepr {
+    fn from(delegate: InitialDelegate) -> Self {
+        InitialDelegateRepr::Nested {
+            issuer: InitialDelegateIssuer {
+                id: delegate.issuer,
+            },
+            delegate: InitialDelegateDelegate {
+                id: delegate.delegate,
+                vrf_verification_key_hash: delegate.vrf,
+            },
+        }
+    }
+}
+
 /// Alonzo genesis configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisAlonzo {
     /// Plutus cost models.
+    #[serde(rename = "plutusCostModels", alias = "costModels")]
     pub cost_models: CostModels,
     /// Script execution prices.
+    #[serde(rename = "scriptExecutionPrices", alias = "prices")]
     pub prices: ScriptExecutionPrices,
     /// Max execution units per transaction.
     pub max_execution_units_per_transaction: super::transaction::ExUnits,
     /// Max execution units per block.
     pub max_execution_units_per_block: super::transaction::ExUnits,
     /// Max value size.
-    pub max_value_size: NumberOfBytes,
+    pub max_value_size: BlockSize,
     /// Collateral percentage.
     pub collateral_percentage: u64,
     /// Max collateral inputs.
     pub max_collateral_inputs: u64,
+    /// Minimum UTXO deposit coefficient (lovelace per byte).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_utxo_deposit_coefficient: Option<u64>,
 }
 
 /// Conway genesis configuration.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GenesisConway {
     /// Constitution.
     #[serde(default)]
@@ -201,10 +444,81 @@ pub struct GenesisConway {
 /// Constitutional committee initial configuration.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ConstitutionalCommitteeConfig {
     /// Initial members.
     #[serde(default)]
-    pub members: Vec<super::governance::ConstitutionalCommitteeMember>,
+    pub members: Vec<super::governance::ConstitutionalCommitteeMemberSummary>,
     /// Quorum threshold.
     pub quorum: Ratio,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mainnet() -> GenesisShelley {
+        let genesis = crate::schema::testing::fixture("network/genesisConfiguration-shelley");
+        match serde_json::from_value(genesis.result()).unwrap() {
+            GenesisConfiguration::Shelley(genesis) => genesis,
+            other => panic!("expected a Shelley genesis, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_epoch_arithmetic() {
+        let genesis = mainnet();
+        assert_eq!(genesis.epoch_length, 432_000);
+
+        assert_eq!(genesis.epoch_of_slot(0), Some(0));
+        assert_eq!(genesis.epoch_of_slot(431_999), Some(0));
+        assert_eq!(genesis.epoch_of_slot(432_000), Some(1));
+        assert_eq!(genesis.first_slot_of_epoch(0), Some(0));
+        assert_eq!(genesis.first_slot_of_epoch(2), Some(864_000));
+        assert_eq!(genesis.first_slot_of_epoch(u64::MAX), None);
+        assert_eq!(genesis.slots_remaining_in_epoch(0), Some(432_000));
+        assert_eq!(genesis.slots_remaining_in_epoch(431_999), Some(1));
+        assert_eq!(genesis.slots_remaining_in_epoch(432_000), Some(432_000));
+        for slot in [0, 1, 431_999, 432_000, 1_000_000] {
+            let epoch = genesis.epoch_of_slot(slot).unwrap();
+            let next = genesis.first_slot_of_epoch(epoch + 1).unwrap();
+            assert_eq!(slot + genesis.slots_remaining_in_epoch(slot).unwrap(), next);
+        }
+
+        let genesis = GenesisShelley { epoch_length: 0, ..mainnet() };
+        assert_eq!(genesis.epoch_of_slot(42), None);
+        assert_eq!(genesis.slots_remaining_in_epoch(42), None);
+    }
+
+    #[test]
+    fn test_epoch_boundary_time() {
+        let genesis = mainnet();
+        let time = |epoch| genesis.epoch_boundary_time(epoch).unwrap().to_string();
+        #[cfg(not(feature = "chrono"))]
+        {
+            assert_eq!(time(0), "2017-09-23T21:44:51Z");
+            assert_eq!(time(1), "2017-09-28T21:44:51Z");
+        }
+        #[cfg(feature = "chrono")]
+        {
+            assert_eq!(time(0), "2017-09-23 21:44:51 UTC");
+            assert_eq!(time(1), "2017-09-28 21:44:51 UTC");
+        }
+        assert!(matches!(
+            genesis.epoch_boundary_time(u64::MAX),
+            Err(crate::OgmiosError::InvalidTime(_))
+        ));
+    }
+
+    #[test]
+    fn test_kes_period_of_slot() {
+        let genesis = mainnet();
+        assert_eq!(genesis.kes_period_of_slot(0), Some(0));
+        assert_eq!(genesis.kes_period_of_slot(129_599), Some(0));
+        assert_eq!(genesis.kes_period_of_slot(129_600), Some(1));
+        assert_eq!(genesis.kes_period_of_slot(140_000_000), Some(1080));
+
+        let genesis = GenesisShelley { slots_per_kes_period: 0, ..mainnet() };
+        assert_eq!(genesis.kes_period_of_slot(42), None);
+    }
+}
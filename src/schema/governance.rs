@@ -1,22 +1,40 @@
 //! Governance types for Conway era.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use super::digest::{DigestBlake2b224, InvalidDigest, InvalidIndexedId, ScriptHash, TransactionId, parse_indexed_id};
 use super::primitives::*;
 use super::protocol::PartialProtocolParameters;
+use std::collections::HashMap;
 
 /// Constitution for Conway governance.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Constitution {
     /// Metadata anchor.
     pub metadata: Anchor,
     /// Guardian script hash (optional).
     #[serde(default)]
     pub guardian_script: Option<ScriptHash>,
+    /// Guardrails script, as sent for the genesis constitution.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub guardrails: Option<ConstitutionGuardrails>,
+}
+
+/// Guardrails script of a constitution.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ConstitutionGuardrails {
+    /// Script hash.
+    pub hash: ScriptHash,
 }
 
 /// Governance action types.
+///
+/// Actions of an unknown type, e.g. introduced by a later hard fork, are kept
+/// as [`Other`](GovernanceAction::Other).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
 #[serde(tag = "type", rename_all = "camelCase")]
 pub enum GovernanceAction {
     /// Motion of no confidence.
@@ -31,6 +49,7 @@ pub enum GovernanceAction {
         #[serde(default)]
         ancestor: Option<GovernanceActionId>,
         members: ConstitutionalCommitteeMembers,
+        quorum: Ratio,
     },
     /// Update constitution.
     #[serde(rename = "constitution")]
@@ -52,76 +71,275 @@ pub enum GovernanceAction {
         #[serde(default)]
         ancestor: Option<GovernanceActionId>,
         parameters: PartialProtocolParameters,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        guardrails: Option<ConstitutionGuardrails>,
     },
-    /// Treasury withdrawal.
+    /// Treasury withdrawals, by reward account.
     #[serde(rename = "treasuryWithdrawals")]
     TreasuryWithdrawals {
-        withdrawals: Vec<TreasuryWithdrawal>,
+        withdrawals: HashMap<RewardAccount, AdaValue>,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        guardrails: Option<ConstitutionGuardrails>,
     },
     /// Information action.
     #[serde(rename = "information")]
     Information,
+    /// Action of a type not known to this version.
+    #[serde(untagged)]
+    Other(serde_json::Value),
 }
 
 /// Governance action ID.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Formats and parses as `<transaction id>#<index>`, the convention of
+/// governance tools and explorers.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::GovernanceActionId;
+///
+/// let id: GovernanceActionId =
+///     "8c653ee5c9800e6d31e79b5a7f7d4400c81d44717ad4db633dc18d4c07e4a4fd#0".parse().unwrap();
+/// assert_eq!(id.index, 0);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GovernanceActionId {
     /// Transaction ID.
+    #[serde(
+        serialize_with = "serialize_transaction_id",
+        deserialize_with = "deserialize_transaction_id"
+    )]
     pub transaction: TransactionId,
     /// Action index.
     pub index: u32,
 }
 
-/// Constitutional committee members.
+/// A string that is not a governance action ID.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidGovernanceActionId {
+    /// The `#` separator is missing.
+    #[error("invalid governance action ID {value:?}: expected <transaction id>#<index>")]
+    Format {
+        /// Rejected value.
+        value: String,
+    },
+    /// The transaction ID half is malformed.
+    #[error("invalid transaction ID in governance action ID {value:?}: {source}")]
+    TransactionId {
+        /// Rejected value.
+        value: String,
+        /// Why the transaction ID was rejected.
+        source: InvalidDigest,
+    },
+    /// The index half is malformed.
+    #[error("invalid index in governance action ID {value:?}: {source}")]
+    Index {
+        /// Rejected value.
+        value: String,
+        /// Why the index was rejected.
+        source: std::num::ParseIntError,
+    },
+}
+
+impl std::fmt::Display for GovernanceActionId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.transaction, self.index)
+    }
+}
+
+impl std::str::FromStr for GovernanceActionId {
+    type Err = InvalidGovernanceActionId;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.to_string();
+        let (transaction, index) = parse_indexed_id(s).map_err(|error| match error {
+            InvalidIndexedId::Format => InvalidGovernanceActionId::Format { value },
+            InvalidIndexedId::TransactionId(source) => {
+                InvalidGovernanceActionId::TransactionId { value, source }
+            }
+            InvalidIndexedId::Index(source) => InvalidGovernanceActionId::Index { value, source },
+        })?;
+        Ok(Self { transaction, index })
+    }
+}
+
+impl TryFrom<&str> for GovernanceActionId {
+    type Error = InvalidGovernanceActionId;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+/// Write the transaction ID as `{ "id": ... }`, as Ogmios expects it.
+fn serialize_transaction_id<S: Serializer>(
+    id: &TransactionId,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    #[derive(Serialize)]
+    struct Reference<'a> {
+        id: &'a TransactionId,
+    }
+
+    Reference { id }.serialize(serializer)
+}
+
+/// Accept both `{ "id": ... }`, as Ogmios sends it, and a bare ID.
+fn deserialize_transaction_id<'de, D>(deserializer: D) -> Result<TransactionId, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Reference {
+        Nested { id: TransactionId },
+        Bare(TransactionId),
+    }
+
+    Ok(match Reference::deserialize(deserializer)? {
+        Reference::Nested { id } | Reference::Bare(id) => id,
+    })
+}
+
+/// Changes to the constitutional committee members.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ConstitutionalCommitteeMembers {
     /// Members to add with their term limits.
     #[serde(default)]
     pub added: Vec<ConstitutionalCommitteeMember>,
     /// Members to remove.
     #[serde(default)]
-    pub removed: Vec<ConstitutionalCommitteeMemberCredential>,
-    /// New quorum threshold.
+    pub removed: Vec<ConstitutionalCommitteeMemberId>,
+}
+
+/// A constitutional committee member added by a governance action.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ConstitutionalCommitteeMember {
+    /// Cold credential hash.
+    pub id: DigestBlake2b224,
+    /// Whether the credential is a key or a script.
+    pub from: CredentialOrigin,
+    /// End of the member's term.
+    pub mandate: Mandate,
+}
+
+/// Cold credential of a constitutional committee member.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ConstitutionalCommitteeMemberId {
+    /// Credential hash.
+    pub id: DigestBlake2b224,
+    /// Whether the credential is a key or a script.
+    pub from: CredentialOrigin,
+}
+
+/// Current state of the constitutional committee.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ConstitutionalCommittee {
+    /// Committee members.
+    #[serde(default)]
+    pub members: Vec<ConstitutionalCommitteeMemberSummary>,
+    /// Quorum threshold (absent when there is no committee).
     #[serde(default)]
     pub quorum: Option<Ratio>,
 }
 
-/// A constitutional committee member.
+/// A sitting constitutional committee member.
+///
+/// Members of the Conway genesis have no `from`, `delegate` or `nextStatus`.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ConstitutionalCommitteeMember {
-    /// Member ID (credential).
-    pub id: ConstitutionalCommitteeMemberCredential,
-    /// Term limit (epoch).
-    pub term: Epoch,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct ConstitutionalCommitteeMemberSummary {
+    /// Cold credential hash.
+    pub id: DigestBlake2b224,
+    /// Whether the cold credential is a key or a script.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub from: Option<CredentialOrigin>,
+    /// Hot credential the member votes with.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<ConstitutionalCommitteeDelegate>,
+    /// Member status.
+    pub status: ConstitutionalCommitteeMemberStatus,
+    /// End of the member's term.
+    #[serde(default)]
+    pub mandate: Option<Mandate>,
+    /// Status of the member at the next epoch boundary.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_status: Option<ConstitutionalCommitteeMemberStatus>,
 }
 
-/// Constitutional committee member credential.
+/// Hot credential of a constitutional committee member.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum ConstitutionalCommitteeMemberCredential {
-    Key { key: DigestBlake2b224 },
-    Script { script: ScriptHash },
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum ConstitutionalCommitteeDelegate {
+    /// The member authorized a hot credential.
+    Authorized {
+        /// Whether the hot credential is a key or a script.
+        from: CredentialOrigin,
+        /// Hot credential hash.
+        id: DigestBlake2b224,
+    },
+    /// The member resigned.
+    Resigned,
 }
 
-/// Treasury withdrawal.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Constitutional committee member status.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConstitutionalCommitteeMemberStatus {
+    Active,
+    Expired,
+    Unrecognized,
+}
+
+/// End of a term of office.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct TreasuryWithdrawal {
-    /// Destination reward account.
-    pub destination: RewardAccount,
-    /// Amount in lovelace.
-    pub amount: AdaValue,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct Mandate {
+    /// Last epoch of the term.
+    pub epoch: Epoch,
+}
+
+/// Constitutional committee member credential, sent as its hash in `id`
+/// and its origin in `from`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "from")]
+pub enum ConstitutionalCommitteeMemberCredential {
+    #[serde(rename = "verificationKey", alias = "key")]
+    Key {
+        #[serde(rename = "id")]
+        key: DigestBlake2b224,
+    },
+    #[serde(rename = "script")]
+    Script {
+        #[serde(rename = "id")]
+        script: ScriptHash,
+    },
 }
 
 /// Governance proposal.
+///
+/// Proposals submitted in a transaction have no ID; it is derived from the
+/// transaction ID and the index of the proposal.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GovernanceProposal {
-    /// Proposal ID.
-    pub id: GovernanceActionId,
+    /// Proposal ID (absent in transactions).
+    #[serde(default)]
+    pub id: Option<GovernanceActionId>,
     /// The governance action.
     pub action: GovernanceAction,
     /// Deposit amount.
@@ -133,50 +351,71 @@ pub struct GovernanceProposal {
     pub metadata: Option<Anchor>,
 }
 
-/// Governance proposal state.
+/// A proposal pending in the ledger, with the votes cast on it, as
+/// `queryLedgerState/governanceProposals` sends it.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GovernanceProposalState {
-    /// The proposal.
-    pub proposal: GovernanceProposal,
-    /// When the proposal was proposed (epoch).
+    /// Proposal ID.
+    pub proposal: GovernanceActionId,
+    /// Deposit amount.
+    pub deposit: AdaValue,
+    /// Deposit return account.
+    pub return_account: RewardAccount,
+    /// Metadata anchor.
+    #[serde(default)]
+    pub metadata: Option<Anchor>,
+    /// The governance action.
+    pub action: GovernanceAction,
+    /// Epoch the proposal was submitted in.
+    #[serde(rename = "since", with = "epoch_bound")]
     pub proposed_in: Epoch,
-    /// When the proposal expires (epoch).
+    /// Last epoch the proposal can be ratified in.
+    #[serde(rename = "until", with = "epoch_bound")]
     pub expires_after: Epoch,
-    /// Votes cast for this proposal.
+    /// Votes cast on the proposal so far.
     #[serde(default)]
-    pub votes: GovernanceVotes,
+    pub votes: Vec<GovernanceVote>,
 }
 
-/// Votes on a governance proposal.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
-#[serde(rename_all = "camelCase")]
-pub struct GovernanceVotes {
-    /// Stake pool votes.
-    #[serde(default)]
-    pub stake_pools: Vec<GovernanceVote>,
-    /// DRep votes.
-    #[serde(default)]
-    pub delegate_representatives: Vec<GovernanceVote>,
-    /// Constitutional committee votes.
-    #[serde(default)]
-    pub constitutional_committee: Vec<GovernanceVote>,
+/// An epoch, serialized as `{ "epoch": n }`.
+mod epoch_bound {
+    use super::*;
+
+    pub fn serialize<S: Serializer>(epoch: &Epoch, serializer: S) -> Result<S::Ok, S::Error> {
+        Mandate { epoch: *epoch }.serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Epoch, D::Error> {
+        Ok(Mandate::deserialize(deserializer)?.epoch)
+    }
 }
 
 /// A governance vote.
+///
+/// Votes cast in a transaction carry the proposal voted on. The voter is
+/// serialized as `issuer`, as Ogmios sends it; `voter` is accepted too.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct GovernanceVote {
     /// Voter ID.
+    #[serde(rename = "issuer", alias = "voter")]
     pub voter: GovernanceVoter,
+    /// Proposal voted on (only in transactions).
+    #[serde(default)]
+    pub proposal: Option<GovernanceActionId>,
     /// The vote.
     pub vote: Vote,
     /// Optional metadata anchor.
-    #[serde(default)]
+    #[serde(default, alias = "anchor")]
     pub metadata: Option<Anchor>,
 }
 
 /// A governance voter.
+///
+/// Voters of an unknown role are kept as [`Other`](GovernanceVoter::Other).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(tag = "role", rename_all = "camelCase")]
 pub enum GovernanceVoter {
@@ -186,64 +425,557 @@ pub enum GovernanceVoter {
     /// Delegate representative.
     #[serde(rename = "delegateRepresentative")]
     DelegateRepresentative {
-        #[serde(flatten)]
-        credential: DelegateRepresentativeCredential,
+        id: DigestBlake2b224,
+        from: CredentialOrigin,
     },
-    /// Constitutional committee member.
+    /// Constitutional committee member, identified by its hot credential.
     #[serde(rename = "constitutionalCommittee")]
     ConstitutionalCommittee {
-        #[serde(flatten)]
-        credential: ConstitutionalCommitteeMemberCredential,
+        id: DigestBlake2b224,
+        from: CredentialOrigin,
     },
+    /// Voter of a role not known to this version.
+    #[serde(untagged)]
+    Other(serde_json::Value),
 }
 
-/// Delegate representative credential.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
+/// Delegate representative credential, sent as its hash in `id` and its
+/// origin in `from`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "from")]
 pub enum DelegateRepresentativeCredential {
+    #[serde(rename = "verificationKey", alias = "key")]
     Key { id: DigestBlake2b224 },
+    #[serde(rename = "script")]
     Script { id: ScriptHash },
 }
 
 /// Vote choice.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Choices not known to this version are kept as [`Other`](Vote::Other).
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Vote {
     Yes,
     No,
     Abstain,
+    Other(String),
 }
 
-/// Delegate representative (DRep) information.
+named_enum!(Vote {
+    Yes => "yes",
+    No => "no",
+    Abstain => "abstain",
+});
+
+/// A registered delegate representative (DRep).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DelegateRepresentative {
-    /// DRep ID (credential).
-    pub id: DelegateRepresentativeCredential,
+    /// Whether the DRep is identified by a key or a script.
+    pub from: CredentialOrigin,
+    /// DRep credential hash.
+    pub id: DigestBlake2b224,
+    /// Last epoch the DRep is active in without voting.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub mandate: Option<Mandate>,
     /// Deposit amount.
-    pub deposit: AdaValue,
-    /// Status.
-    pub status: DRepStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deposit: Option<AdaValue>,
+    /// Stake delegated to the DRep.
+    pub stake: AdaValue,
     /// Metadata anchor.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub metadata: Option<Anchor>,
+    /// Stake credentials delegated to the DRep.
+    #[serde(default)]
+    pub delegators: Vec<DelegateRepresentativeDelegator>,
 }
 
-/// DRep status.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+/// A stake credential delegated to a DRep.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub enum DRepStatus {
-    Registered,
-    Unregistered,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct DelegateRepresentativeDelegator {
+    /// Whether the credential is a key or a script.
+    pub from: CredentialOrigin,
+    /// Credential hash.
+    pub credential: DigestBlake2b224,
+}
+
+impl From<DelegateRepresentativeDelegator> for StakeCredential {
+    fn from(delegator: DelegateRepresentativeDelegator) -> Self {
+        match delegator.from {
+            CredentialOrigin::Key => StakeCredential::Key {
+                key: delegator.credential,
+            },
+            CredentialOrigin::Script => StakeCredential::Script {
+                script: delegator.credential.into(),
+            },
+        }
+    }
 }
 
-/// DRep summary with voting power.
+/// Identifies a DRep stake can be delegated to: a registered DRep, keyed by
+/// its credential, or one of the two pre-defined voting options.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum DRepId {
+    /// DRep registered with a verification key hash.
+    Key(DigestBlake2b224),
+    /// DRep registered with a script hash.
+    Script(ScriptHash),
+    /// Pre-defined option abstaining from every vote.
+    AlwaysAbstain,
+    /// Pre-defined option voting no confidence on every vote.
+    AlwaysNoConfidence,
+}
+
+impl DRepId {
+    /// Whether this is one of the two pre-defined voting options.
+    pub fn is_predefined(&self) -> bool {
+        matches!(self, DRepId::AlwaysAbstain | DRepId::AlwaysNoConfidence)
+    }
+
+    /// Get the credential hash of a registered DRep.
+    pub fn credential(&self) -> Option<&str> {
+        match self {
+            DRepId::Key(hash) => Some(hash.as_str()),
+            DRepId::Script(hash) => Some(hash.as_str()),
+            DRepId::AlwaysAbstain | DRepId::AlwaysNoConfidence => None,
+        }
+    }
+}
+
+/// A DRep stake is delegated to, with that stake, as
+/// `queryLedgerState/delegateRepresentatives` sends it.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct DelegateRepresentativeSummary {
-    /// DRep info.
-    #[serde(flatten)]
-    pub drep: DelegateRepresentative,
-    /// Voting power (stake delegated).
-    pub voting_power: Lovelace,
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum DelegateRepresentativeSummary {
+    /// A registered DRep.
+    Registered(DelegateRepresentative),
+    /// Pre-defined option abstaining from every vote.
+    Abstain {
+        /// Stake delegated to the option.
+        stake: AdaValue,
+    },
+    /// Pre-defined option voting no confidence on every vote.
+    NoConfidence {
+        /// Stake delegated to the option.
+        stake: AdaValue,
+    },
+}
+
+impl DelegateRepresentativeSummary {
+    /// ID of the DRep or pre-defined option.
+    pub fn id(&self) -> DRepId {
+        match self {
+            Self::Registered(drep) => match drep.from {
+                CredentialOrigin::Key => DRepId::Key(drep.id.clone()),
+                CredentialOrigin::Script => DRepId::Script(drep.id.clone().into()),
+            },
+            Self::Abstain { .. } => DRepId::AlwaysAbstain,
+            Self::NoConfidence { .. } => DRepId::AlwaysNoConfidence,
+        }
+    }
+
+    /// Voting power: the stake delegated, in lovelace.
+    pub fn stake(&self) -> Lovelace {
+        match self {
+            Self::Registered(DelegateRepresentative { stake, .. })
+            | Self::Abstain { stake }
+            | Self::NoConfidence { stake } => stake.lovelace,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Transaction;
+    use serde_json::json;
+
+    const TX_ID: &str = "8c653ee5c9800e6d31e79b5a7f7d4400c81d44717ad4db633dc18d4c07e4a4fd";
+    const RETURN_ACCOUNT: &str = "stake1u9lc42wr3y6jhuxc3vlqz4y0dyyx4c5pqxd3c6p4whyvg2qzpkp5h";
+
+    /// Conway transaction in the shape Ogmios v6 sends it, with fields other
+    /// than the proposals left out.
+    fn conway_transaction(proposals: serde_json::Value) -> Transaction {
+        serde_json::from_value(json!({
+            "id": "6fdb8a2bd1c0fb6ad9c0a3e58a0f3b2df5a8ce68d4ef9c1d55b2d0b1cb4e0a4c",
+            "spends": "inputs",
+            "proposals": proposals
+        }))
+        .unwrap()
+    }
+
+    fn proposal(action: serde_json::Value) -> serde_json::Value {
+        json!({
+            "deposit": { "ada": { "lovelace": 100000000000u64 } },
+            "returnAccount": RETURN_ACCOUNT,
+            "metadata": {
+                "url": "ipfs://QmR1XjBMbK8yCVSyxMfXVJAbTVsDsV2PJHqBZWw6QCYjYQ",
+                "hash": "d2db0c8b2b0fb5b7a53e02c8c2d34a7c8d9e26a2c0e1f0b3e61fd9b1f1b0d6a1"
+            },
+            "action": action
+        })
+    }
+
+    #[test]
+    fn test_transaction_proposals() {
+        let transaction = conway_transaction(json!([
+            proposal(json!({ "type": "information" })),
+            proposal(json!({
+                "type": "hardForkInitiation",
+                "ancestor": { "transaction": { "id": TX_ID }, "index": 0 },
+                "version": { "major": 10, "minor": 0 }
+            })),
+            proposal(json!({ "type": "noConfidence", "ancestor": null })),
+        ]));
+
+        let [information, hard_fork, no_confidence] = transaction.proposals.as_slice() else {
+            panic!("expected 3 proposals, got {:?}", transaction.proposals);
+        };
+        assert_eq!(information.id, None);
+        assert_eq!(information.action, GovernanceAction::Information);
+        assert_eq!(information.deposit.lovelace, 100_000_000_000);
+        assert_eq!(information.return_account, RETURN_ACCOUNT);
+        assert!(information.metadata.as_ref().unwrap().url.starts_with("ipfs://"));
+
+        let GovernanceAction::HardForkInitiation { ancestor, version } = &hard_fork.action else {
+            panic!("unexpected action {:?}", hard_fork.action);
+        };
+        assert_eq!(ancestor.as_ref().unwrap().transaction, TX_ID);
+        assert_eq!((version.major, version.minor), (10, 0));
+        assert_eq!(
+            no_confidence.action,
+            GovernanceAction::NoConfidence { ancestor: None }
+        );
+    }
+
+    #[test]
+    fn test_unknown_action_is_kept() {
+        let action = json!({ "type": "futureAction", "payload": [1, 2, 3] });
+        let transaction = conway_transaction(json!([proposal(action.clone())]));
+
+        assert_eq!(
+            transaction.proposals[0].action,
+            GovernanceAction::Other(action.clone())
+        );
+        assert_eq!(
+            serde_json::to_value(&transaction.proposals[0].action).unwrap(),
+            action
+        );
+    }
+
+    #[test]
+    fn test_transaction_votes() {
+        let proposal = json!({ "transaction": { "id": TX_ID }, "index": 0 });
+        let transaction: Transaction = serde_json::from_value(json!({
+            "id": "6fdb8a2bd1c0fb6ad9c0a3e58a0f3b2df5a8ce68d4ef9c1d55b2d0b1cb4e0a4c",
+            "votes": [
+                {
+                    "issuer": {
+                        "role": "constitutionalCommittee",
+                        "from": "verificationKey",
+                        "id": "7ceede7d6a89e006408e6b7c6acb3dd094b3f6817e43b4a36d01535b"
+                    },
+                    "proposal": proposal,
+                    "vote": "yes",
+                    "anchor": {
+                        "url": "https://example.com/rationale.jsonld",
+                        "hash": "6ec1fa4b3f4c5e3bb1e0cdbeb1da3ab88dc4ad4ee3f0ba2ac7e4ba2c4df4a1a3"
+                    }
+                },
+                {
+                    "issuer": {
+                        "role": "delegateRepresentative",
+                        "from": "script",
+                        "id": "e8cd9ddd0ac2a0fd8b2e9b17e8c2a0c4c8a3ef2b8e6a77fd8a3c0a5e"
+                    },
+                    "proposal": proposal,
+                    "vote": "abstain"
+                },
+                {
+                    "issuer": {
+                        "role": "stakePoolOperator",
+                        "id": "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy"
+                    },
+                    "proposal": proposal,
+                    "vote": "no"
+                },
+                {
+                    "issuer": { "role": "genesisDelegate", "id": "ab" },
+                    "proposal": proposal,
+                    "vote": "yes"
+                }
+            ]
+        }))
+        .unwrap();
+
+        let votes = &transaction.votes;
+        assert_eq!(votes.len(), 4);
+        assert!(votes.iter().all(|vote| vote.proposal.as_ref().unwrap().transaction == TX_ID));
+
+        assert_eq!(
+            votes[0].voter,
+            GovernanceVoter::ConstitutionalCommittee {
+                id: "7ceede7d6a89e006408e6b7c6acb3dd094b3f6817e43b4a36d01535b".parse().unwrap(),
+                from: CredentialOrigin::Key,
+            }
+        );
+        assert_eq!(votes[0].vote, Vote::Yes);
+        assert!(votes[0].metadata.is_some());
+
+        assert!(matches!(
+            votes[1].voter,
+            GovernanceVoter::DelegateRepresentative {
+                from: CredentialOrigin::Script,
+                ..
+            }
+        ));
+        assert_eq!(votes[1].vote, Vote::Abstain);
+        assert_eq!(votes[1].metadata, None);
+
+        assert!(matches!(
+            &votes[2].voter,
+            GovernanceVoter::StakePoolOperator { id } if id.starts_with("pool1")
+        ));
+        assert_eq!(votes[2].vote, Vote::No);
+
+        assert_eq!(
+            votes[3].voter,
+            GovernanceVoter::Other(json!({ "role": "genesisDelegate", "id": "ab" }))
+        );
+    }
+
+    #[test]
+    fn test_action_id_accepts_bare_transaction_id() {
+        let id: GovernanceActionId =
+            serde_json::from_value(json!({ "transaction": TX_ID, "index": 1 })).unwrap();
+        assert_eq!(id.transaction, TX_ID);
+        assert_eq!(id.index, 1);
+        assert_eq!(
+            serde_json::to_value(&id).unwrap(),
+            json!({ "transaction": { "id": TX_ID }, "index": 1 })
+        );
+    }
+
+    #[test]
+    fn test_action_id_string_form() {
+        let id: GovernanceActionId = format!("{TX_ID}#12").parse().unwrap();
+        assert_eq!(id.transaction, TX_ID);
+        assert_eq!(id.index, 12);
+        assert_eq!(id.to_string(), format!("{TX_ID}#12"));
+
+        let proposals = std::collections::HashMap::from([(id.clone(), "treasury")]);
+        assert_eq!(proposals[&id], "treasury");
+
+        assert!(matches!(
+            TX_ID.parse::<GovernanceActionId>(),
+            Err(InvalidGovernanceActionId::Format { .. })
+        ));
+        assert!(matches!(
+            "abc#0".parse::<GovernanceActionId>(),
+            Err(InvalidGovernanceActionId::TransactionId { .. })
+        ));
+        let error = GovernanceActionId::try_from(format!("{TX_ID}#-1").as_str()).unwrap_err();
+        assert!(matches!(error, InvalidGovernanceActionId::Index { .. }));
+        assert!(error.to_string().contains("invalid index"), "{error}");
+    }
+
+    #[test]
+    fn test_unknown_vote_round_trip() {
+        let vote = json!({
+            "voter": { "role": "stakePoolOperator", "id": "pool1" },
+            "proposal": { "transaction": { "id": TX_ID }, "index": 0 },
+            "vote": "veto"
+        });
+        let parsed: GovernanceVote = serde_json::from_value(vote.clone()).unwrap();
+        assert_eq!(parsed.vote, Vote::Other("veto".to_string()));
+        assert_eq!(serde_json::to_value(&parsed).unwrap()["vote"], "veto");
+
+        // Known names never end up in `Other`.
+        assert_eq!(Vote::from("abstain".to_string()), Vote::Abstain);
+        assert_eq!(Vote::Other("yes".to_string()), Vote::Yes);
+    }
+
+    #[test]
+    fn test_governance_proposals_fixture() {
+        let json = crate::schema::testing::fixture("ledger-state/governanceProposals").result();
+        let proposals =
+            crate::schema::assert_lossless_roundtrip::<Vec<GovernanceProposalState>>(&json);
+
+        let [proposal] = proposals.as_slice() else {
+            panic!("expected one proposal, got {proposals:?}");
+        };
+        assert_eq!(
+            proposal.proposal.to_string(),
+            "9823cf909c4480e4d30797408a748c83933cb1a19d29f85399181ff3f462b5cb#0"
+        );
+        assert_eq!(proposal.deposit.lovelace, 100_000_000_000);
+        assert_eq!(proposal.return_account, RETURN_ACCOUNT);
+        assert_eq!((proposal.proposed_in, proposal.expires_after), (553, 559));
+
+        let GovernanceAction::TreasuryWithdrawals { withdrawals, guardrails } = &proposal.action
+        else {
+            panic!("unexpected action {:?}", proposal.action);
+        };
+        let account = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw";
+        assert_eq!(withdrawals.len(), 1);
+        assert_eq!(withdrawals[account].lovelace, 42_000_000_000);
+        assert_eq!(
+            guardrails.as_ref().unwrap().hash,
+            "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64"
+        );
+
+        let [drep, committee] = proposal.votes.as_slice() else {
+            panic!("expected two votes, got {:?}", proposal.votes);
+        };
+        assert!(matches!(
+            drep.voter,
+            GovernanceVoter::DelegateRepresentative { from: CredentialOrigin::Key, .. }
+        ));
+        assert_eq!(drep.vote, Vote::Yes);
+        assert_eq!(drep.proposal, None);
+        assert!(matches!(
+            committee.voter,
+            GovernanceVoter::ConstitutionalCommittee { from: CredentialOrigin::Script, .. }
+        ));
+        assert_eq!(committee.vote, Vote::No);
+    }
+
+    #[test]
+    fn test_constitutional_committee_action() {
+        let action = json!({
+            "type": "constitutionalCommittee",
+            "ancestor": { "transaction": { "id": TX_ID }, "index": 0 },
+            "members": {
+                "added": [{
+                    "id": "ba3efb05554bc08a5ee959a0070c7178de8801a615a7fcadb454d80e",
+                    "from": "script",
+                    "mandate": { "epoch": 580 }
+                }],
+                "removed": [{
+                    "id": "7ceede7d6a89e006408e6b7c6acb3dd094b3f6817e43b4a36d01535b",
+                    "from": "verificationKey"
+                }]
+            },
+            "quorum": "2/3"
+        });
+        let GovernanceAction::ConstitutionalCommittee { members, quorum, .. } =
+            crate::schema::assert_lossless_roundtrip::<GovernanceAction>(&action)
+        else {
+            panic!("expected a constitutional committee action");
+        };
+        assert_eq!(members.added[0].from, CredentialOrigin::Script);
+        assert_eq!(members.added[0].mandate.epoch, 580);
+        assert_eq!(members.removed[0].from, CredentialOrigin::Key);
+        assert_eq!(quorum.to_string(), "2/3");
+    }
+
+    #[test]
+    fn test_delegate_representatives_fixture() {
+        let json = crate::schema::testing::fixture("ledger-state/delegateRepresentatives").result();
+        let dreps =
+            crate::schema::assert_lossless_roundtrip::<Vec<DelegateRepresentativeSummary>>(&json);
+
+        let [DelegateRepresentativeSummary::Registered(drep), abstain, no_confidence] =
+            dreps.as_slice()
+        else {
+            panic!("unexpected DReps {dreps:?}");
+        };
+        assert_eq!(drep.from, CredentialOrigin::Key);
+        assert_eq!(drep.mandate, Some(Mandate { epoch: 601 }));
+        assert_eq!(drep.deposit.as_ref().unwrap().lovelace, 500_000_000);
+        assert_eq!(drep.stake.lovelace, 21_502_148_003_117);
+        assert!(drep.metadata.as_ref().unwrap().url.ends_with("drep.jsonld"));
+        assert_eq!(
+            drep.delegators,
+            [DelegateRepresentativeDelegator {
+                from: CredentialOrigin::Key,
+                credential: "e15e6096c55278f0b5ba343ea9a4ae806dd336cd7587bdb19ade1090".parse().unwrap(),
+            }]
+        );
+        // Delegators use the same `credential` and `from` fields as
+        // certificates do.
+        let delegator = &json[0]["delegators"][0];
+        let credential: StakeCredential = serde_json::from_value(delegator.clone()).unwrap();
+        assert_eq!(credential, StakeCredential::from(drep.delegators[0].clone()));
+        assert_eq!(serde_json::to_value(&credential).unwrap(), *delegator);
+        assert_eq!(
+            dreps[0].id(),
+            DRepId::Key("1ded5672637c4eb156011ff7c9da715d8860528bb10d563841c362b5".parse().unwrap())
+        );
+
+        assert_eq!(abstain.id(), DRepId::AlwaysAbstain);
+        assert_eq!(abstain.stake(), 4_112_941_774_032_118);
+        assert_eq!(no_confidence.id(), DRepId::AlwaysNoConfidence);
+        assert_eq!(no_confidence.stake(), 181_019_275_006_244);
+    }
+
+    #[test]
+    fn test_vote_of_captured_block() {
+        let response = crate::schema::testing::fixture("chain-sync/nextBlock-conway").result();
+        let transaction = &response["block"]["transactions"][0];
+        let votes = crate::schema::assert_lossless_roundtrip::<Vec<GovernanceVote>>(
+            &transaction["votes"],
+        );
+
+        assert_eq!(votes.len(), 1);
+        assert_eq!(
+            votes[0].voter,
+            GovernanceVoter::DelegateRepresentative {
+                id: "30449da88b97345863d3e075da1f21d0378ff9df6eaea2627377ff87".parse().unwrap(),
+                from: CredentialOrigin::Key,
+            }
+        );
+        let proposal = votes[0].proposal.as_ref().unwrap();
+        assert_eq!(
+            proposal.transaction,
+            "2b657d01bf752a7bc18f5dcdcd124b21c47b8f024eb4fbd0433753be456fea61"
+        );
+        assert_eq!(proposal.index, 0);
+        assert_eq!(votes[0].vote, Vote::Yes);
+        assert_eq!(
+            votes[0].metadata.as_ref().unwrap().url,
+            "https://example.com/rationale.jsonld"
+        );
+    }
+
+    #[test]
+    fn test_protocol_parameters_update_round_trip() {
+        use crate::schema::testing::fixture;
+
+        // No parameter change is among the captured proposals: propose the
+        // captured parameters, under the captured constitution's guardrails,
+        // in the envelope of a captured proposal.
+        let mut proposal = fixture("ledger-state/governanceProposals").result()[0].clone();
+        let parameters = fixture("ledger-state/protocolParameters").result();
+        let guardrails = fixture("ledger-state/constitution").result()["guardrails"].clone();
+        proposal["action"] = json!({
+            "type": "protocolParametersUpdate",
+            "ancestor": null,
+            "parameters": parameters,
+            "guardrails": guardrails
+        });
+
+        let state = crate::schema::assert_lossless_roundtrip::<GovernanceProposalState>(&proposal);
+        let GovernanceAction::ProtocolParametersUpdate {
+            parameters,
+            guardrails,
+            ..
+        } = &state.action
+        else {
+            panic!("unexpected action {:?}", state.action);
+        };
+        assert_eq!(parameters.constitutional_committee_min_size, Some(7));
+        assert_eq!(parameters.governance_action_lifetime, Some(6));
+        let max_reference_scripts_size = parameters.max_reference_scripts_size.as_ref();
+        assert_eq!(max_reference_scripts_size.map(|size| size.bytes), Some(204_800));
+        assert_eq!(
+            guardrails.as_ref().unwrap().hash,
+            "fa24fb305126805cf2164c161d852a0e7330cf988f1fe558cf7d4a64"
+        );
+    }
 }
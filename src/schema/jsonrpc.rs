@@ -79,6 +79,41 @@ impl<R> JsonRpcResponse<R> {
             })
         }
     }
+
+    /// Check that the response answers the request with the `expected` id.
+    ///
+    /// Numeric ids echoed as strings, e.g. `"7"` for `7`, are accepted. A
+    /// missing or `null` id is only accepted on errors, which the server
+    /// reports that way when it could not read the request id.
+    pub fn expect_id(&self, expected: &Value) -> Result<(), JsonRpcError> {
+        let matches = match self.id.as_ref().unwrap_or(&Value::Null) {
+            Value::Null => self.error.is_some(),
+            id => match (numeric_id(id), numeric_id(expected)) {
+                (Some(id), Some(expected)) => id == expected,
+                _ => id == expected,
+            },
+        };
+        if matches {
+            return Ok(());
+        }
+        Err(JsonRpcError {
+            code: error_codes::INTERNAL_ERROR,
+            message: format!(
+                "Response id {} does not match request id {expected}",
+                self.id.as_ref().unwrap_or(&Value::Null)
+            ),
+            data: None,
+        })
+    }
+}
+
+/// Read a numeric id, also when sent as a string.
+pub(crate) fn numeric_id(id: &Value) -> Option<u64> {
+    match id {
+        Value::Number(id) => id.as_u64(),
+        Value::String(id) => id.parse().ok(),
+        _ => None,
+    }
 }
 
 /// JSON-RPC error.
@@ -117,6 +152,54 @@ pub mod error_codes {
     pub const SERVER_ERROR_START: i32 = -32000;
     /// Server error range end.
     pub const SERVER_ERROR_END: i32 = -32099;
+    /// Ogmios: no point of a `findIntersection` request is on the chain.
+    pub const INTERSECTION_NOT_FOUND: i32 = 1000;
+    /// Ogmios: `findIntersection` was sent while blocks were still requested.
+    pub const INTERSECTION_INTERLEAVED: i32 = 1001;
+    /// Ogmios: acquisition failed because the point is too old.
+    pub const ACQUIRE_POINT_TOO_OLD: i32 = 2000;
+    /// Ogmios: acquisition failed because the point is not on chain.
+    pub const ACQUIRE_POINT_NOT_ON_CHAIN: i32 = 2001;
+    /// Ogmios: the acquired ledger state point is no longer available (e.g. rolled back).
+    pub const ACQUIRED_EXPIRED: i32 = 2003;
+    /// Ogmios: the transaction was built for another era than the ledger's.
+    pub const SUBMIT_ERA_MISMATCH: i32 = 3005;
+    /// Ogmios: signatures required by the transaction are missing.
+    pub const SUBMIT_MISSING_SIGNATORIES: i32 = 3101;
+    /// Ogmios: scripts required by the transaction are missing.
+    pub const SUBMIT_MISSING_SCRIPTS: i32 = 3102;
+    /// Ogmios: inputs spend outputs that do not exist or were already spent.
+    pub const SUBMIT_UNKNOWN_OUTPUT_REFERENCES: i32 = 3117;
+    /// Ogmios: the transaction is not valid at the current slot.
+    pub const SUBMIT_OUTSIDE_OF_VALIDITY_INTERVAL: i32 = 3118;
+    /// Ogmios: the transaction exceeds the maximum transaction size.
+    pub const SUBMIT_TRANSACTION_TOO_LARGE: i32 = 3119;
+    /// Ogmios: the fee is lower than the minimum fee.
+    pub const SUBMIT_TRANSACTION_FEE_TOO_SMALL: i32 = 3122;
+    /// Ogmios: the consumed and produced values differ.
+    pub const SUBMIT_VALUE_NOT_CONSERVED: i32 = 3123;
+    /// Ogmios: the collateral is insufficient.
+    pub const SUBMIT_INSUFFICIENT_COLLATERAL: i32 = 3128;
+    /// Ogmios: the execution units exceed the maximum per transaction.
+    pub const SUBMIT_EXECUTION_UNITS_TOO_LARGE: i32 = 3134;
+    /// Ogmios: the address of an output is for another network.
+    pub const SUBMIT_NETWORK_MISMATCH: i32 = 3124;
+    /// Ogmios: outputs hold less than the minimum ada value.
+    pub const SUBMIT_INSUFFICIENTLY_FUNDED_OUTPUTS: i32 = 3125;
+    /// Ogmios: the transaction cannot be evaluated in the ledger's era.
+    pub const EVALUATE_INCOMPATIBLE_ERA: i32 = 3000;
+    /// Ogmios: evaluation is not supported in the ledger's era.
+    pub const EVALUATE_UNSUPPORTED_ERA: i32 = 3001;
+    /// Ogmios: additional UTXOs overlap with outputs of the ledger.
+    pub const EVALUATE_OVERLAPPING_ADDITIONAL_UTXO: i32 = 3002;
+    /// Ogmios: the evaluation context could not be created.
+    pub const EVALUATE_CANNOT_CREATE_EVALUATION_CONTEXT: i32 = 3004;
+    /// Ogmios: some scripts failed; the data lists the failure of each.
+    pub const EVALUATE_SCRIPT_EXECUTION_FAILURE: i32 = 3010;
+    /// Ogmios: a script failed, with its error and traces.
+    pub const EVALUATE_VALIDATION_FAILURE: i32 = 3012;
+    /// Ogmios: a mempool request was sent before `acquireMempool`.
+    pub const MEMPOOL_MUST_ACQUIRE_FIRST: i32 = 4000;
 }
 
 /// Ogmios-specific response types.
@@ -124,11 +207,13 @@ pub mod responses {
     use serde::{Deserialize, Serialize};
     use serde_json::Value as JsonValue;
     use super::super::block::Block;
-    use super::super::primitives::{Point, Slot, Tip, TransactionId};
+    use super::super::digest::TransactionId;
+    use super::super::primitives::{Point, Slot, Tip};
     use super::super::transaction::{Transaction, EvaluationResult};
 
     /// Chain sync next block response.
     #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[allow(clippy::large_enum_variant)]
     #[serde(tag = "direction", rename_all = "camelCase")]
     pub enum NextBlockResponse {
         /// Forward direction - new block.
@@ -209,6 +294,7 @@ pub mod responses {
 
     /// Transaction or just its ID.
     #[derive(Debug, Clone, Serialize, Deserialize)]
+    #[allow(clippy::large_enum_variant)]
     #[serde(untagged)]
     pub enum TransactionOrId {
         /// Full transaction.
@@ -225,3 +311,37 @@ pub mod responses {
         pub has_transaction: bool,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn response(id: Option<Value>, error: bool) -> JsonRpcResponse {
+        JsonRpcResponse {
+            jsonrpc: JSONRPC_VERSION.to_string(),
+            result: (!error).then(|| json!(1)),
+            error: error.then(|| JsonRpcError {
+                code: error_codes::PARSE_ERROR,
+                message: "Invalid request".to_string(),
+                data: None,
+            }),
+            id,
+        }
+    }
+
+    #[test]
+    fn test_expect_id() {
+        assert!(response(Some(json!(7)), false).expect_id(&json!(7)).is_ok());
+        assert!(response(Some(json!("7")), false).expect_id(&json!(7)).is_ok());
+        assert!(response(Some(json!("abc")), false).expect_id(&json!("abc")).is_ok());
+        // Errors about unreadable requests carry no id.
+        assert!(response(Some(Value::Null), true).expect_id(&json!(7)).is_ok());
+        assert!(response(None, true).expect_id(&json!(7)).is_ok());
+
+        let error = response(Some(json!(8)), false).expect_id(&json!(7)).unwrap_err();
+        assert_eq!(error.message, "Response id 8 does not match request id 7");
+        assert!(response(None, false).expect_id(&json!(7)).is_err());
+        assert!(response(Some(json!("7a")), false).expect_id(&json!(7)).is_err());
+    }
+}
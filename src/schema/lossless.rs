@@ -0,0 +1,171 @@
+//! Detection of fields lost in a deserialization round trip.
+//!
+//! Schema types ignore fields they do not know, so data added to the Ogmios
+//! schema is dropped silently. [`lossless_diff`] deserializes a document,
+//! serializes it back and reports what did not survive. To reject unknown
+//! fields outright instead, enable the `strict-schema` feature.
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// List the parts of `json` that do not survive a round trip through `T`.
+///
+/// Each difference names a JSON pointer: `dropped <path>` for a field that
+/// disappeared, `changed <path>: <before> -> <after>` for a value that came
+/// back different. Fields added by serialization, e.g. defaults, and `null`
+/// fields left out are not differences.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::lossless_diff;
+/// use serde::{Deserialize, Serialize};
+/// use serde_json::json;
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Size {
+///     bytes: u64,
+/// }
+///
+/// let diff = lossless_diff::<Size>(&json!({ "bytes": 42, "kilobytes": 0 })).unwrap();
+/// assert_eq!(diff, ["dropped /kilobytes"]);
+/// ```
+pub fn lossless_diff<T>(json: &Value) -> Result<Vec<String>, serde_json::Error>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let serialized = serde_json::to_value(serde_json::from_value::<T>(json.clone())?)?;
+    let mut differences = Vec::new();
+    diff(json, &serialized, &mut String::new(), &mut differences);
+    Ok(differences)
+}
+
+/// Deserialize `json` as `T`, panicking if anything is lost on the way back.
+///
+/// Meant for test suites that want schema drift to fail loudly.
+///
+/// # Panics
+///
+/// If `json` does not deserialize, or if [`lossless_diff`] finds differences.
+#[track_caller]
+pub fn assert_lossless_roundtrip<T>(json: &Value) -> T
+where
+    T: DeserializeOwned + Serialize,
+{
+    let differences = match lossless_diff::<T>(json) {
+        Ok(differences) => differences,
+        Err(error) => panic!("failed to deserialize {}: {error}", std::any::type_name::<T>()),
+    };
+    assert!(
+        differences.is_empty(),
+        "{} does not round-trip losslessly:\n  {}",
+        std::any::type_name::<T>(),
+        differences.join("\n  ")
+    );
+    serde_json::from_value(json.clone()).expect("deserialized above")
+}
+
+fn diff(
+    before: &Value,
+    after: &Value,
+    path: &mut String,
+    differences: &mut Vec<String>,
+) {
+    match (before, after) {
+        (Value::Object(before), Value::Object(after)) => {
+            for (key, value) in before {
+                let length = path.len();
+                path.push('/');
+                path.push_str(&key.replace('~', "~0").replace('/', "~1"));
+                match after.get(key) {
+                    Some(other) => diff(value, other, path, differences),
+                    None if value.is_null() => {}
+                    None => differences.push(format!("dropped {path}")),
+                }
+                path.truncate(length);
+            }
+        }
+        (Value::Array(before), Value::Array(after)) if before.len() == after.len() => {
+            for (index, (value, other)) in before.iter().zip(after).enumerate() {
+                let length = path.len();
+                path.push_str(&format!("/{index}"));
+                diff(value, other, path, differences);
+                path.truncate(length);
+            }
+        }
+        (Value::Number(before), Value::Number(after)) if before.as_f64() == after.as_f64() => {}
+        _ if before == after => {}
+        _ => differences.push(format!("changed {path}: {before} -> {after}")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schema::Block;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    #[derive(Serialize, Deserialize)]
+    struct Header {
+        slot: u64,
+        #[serde(default)]
+        issuer: Option<String>,
+    }
+
+    #[test]
+    fn test_diff_reports_dropped_and_changed_fields() {
+        let header = json!({ "slot": 1, "issuer": null, "protocol/magic": { "id": 2 } });
+        assert_eq!(
+            lossless_diff::<Header>(&header).unwrap(),
+            ["dropped /protocol~1magic"]
+        );
+        // Asset names are normalized to lowercase.
+        let policy = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7";
+        assert_eq!(
+            lossless_diff::<Vec<crate::schema::Subject>>(&json!([format!("{policy}4E")])).unwrap(),
+            [format!("changed /0: \"{policy}4E\" -> \"{policy}4e\"")]
+        );
+        assert!(lossless_diff::<Header>(&json!({ "slot": 1.5 })).is_err());
+    }
+
+    #[test]
+    fn test_assert_lossless_roundtrip() {
+        let block = json!({
+            "type": "ebb",
+            "era": "byron",
+            "id": "89d9b5a5b8ddc8d7e5a6e5ffb5c7b0e5ab15a6b1c5e4e3cd3f0c1d0e6c6a5f4b",
+            "ancestor": "5f20df933584822601f9e3f8c024eb5eb252fe8cefb24d1317dc3d432e940ebb",
+            "height": 0,
+            "slot": 0
+        });
+        assert!(assert_lossless_roundtrip::<Block>(&block).is_ebb());
+    }
+
+    #[test]
+    #[cfg(feature = "strict-schema")]
+    fn test_strict_schema_rejects_unknown_fields() {
+        let size = json!({ "bytes": 1, "kilobytes": 0 });
+        let error = serde_json::from_value::<crate::schema::BlockSize>(size).unwrap_err();
+        assert!(error.to_string().contains("unknown field `kilobytes`"), "{error}");
+
+        // Real blocks decode, and a field added to one of them is rejected.
+        let fixture = crate::schema::testing::fixture("chain-sync/nextBlock-conway");
+        let mut block = fixture.result()["block"].clone();
+        serde_json::from_value::<Block>(block.clone()).unwrap();
+        block["transactions"][0]["futureField"] = json!(true);
+        let error = serde_json::from_value::<Block>(block).unwrap_err();
+        assert!(error.to_string().contains("unknown field `futureField`"), "{error}");
+    }
+
+    #[test]
+    #[should_panic(expected = "dropped /issuer")]
+    fn test_assert_panics_on_dropped_fields() {
+        #[derive(Serialize, Deserialize)]
+        struct Slot {
+            slot: u64,
+        }
+        assert_lossless_roundtrip::<Slot>(&json!({ "slot": 1, "issuer": "ab" }));
+    }
+}
@@ -0,0 +1,401 @@
+//! Transaction metadata values and their plain JSON form.
+
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Metadata values by numeric label, e.g. `674` for CIP-20 messages.
+///
+/// Labels are sent as JSON object keys, i.e. strings, and serialized back as
+/// such.
+///
+/// Migration: labels used to be keyed by `String`. Look up values with the
+/// number instead, e.g. `labels.get(&674)` rather than `labels.get("674")`.
+pub type MetadataLabels = BTreeMap<u64, Metadatum>;
+
+/// Deserialize metadata labels keyed by numbers or by decimal strings.
+pub(super) fn deserialize_labels<'de, D>(deserializer: D) -> Result<MetadataLabels, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let labels = BTreeMap::<Label, Metadatum>::deserialize(deserializer)?;
+    Ok(labels.into_iter().map(|(Label(label), value)| (label, value)).collect())
+}
+
+/// A metadata label given as a number or as a decimal string.
+#[derive(PartialEq, Eq, PartialOrd, Ord)]
+struct Label(u64);
+
+impl<'de> Deserialize<'de> for Label {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct Visitor;
+
+        impl serde::de::Visitor<'_> for Visitor {
+            type Value = Label;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a metadata label")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, label: u64) -> Result<Label, E> {
+                Ok(Label(label))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, label: &str) -> Result<Label, E> {
+                label
+                    .parse()
+                    .map(Label)
+                    .map_err(|_| E::custom(format!("invalid metadata label {label:?}")))
+            }
+        }
+
+        deserializer.deserialize_any(Visitor)
+    }
+}
+
+/// Metadata value that can be various types.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Metadatum {
+    /// Integer value.
+    Int(i128),
+    /// Byte string (hex-encoded).
+    Bytes(String),
+    /// Text string.
+    String(String),
+    /// List of metadatums.
+    List(Vec<Metadatum>),
+    /// Map of metadatums.
+    Map(Vec<MetadatumMapEntry>),
+}
+
+/// A key-value entry in a metadatum map.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct MetadatumMapEntry {
+    pub k: Metadatum,
+    pub v: Metadatum,
+}
+
+/// How [`Metadatum::to_json_with`] renders maps whose keys are not all strings.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NonStringKeys {
+    /// Render the map as an array of `[key, value]` pairs.
+    #[default]
+    Pairs,
+    /// Render the map as an object, with integers as decimal strings and lists
+    /// or maps as their compact JSON.
+    Stringify,
+}
+
+/// Options for converting between [`Metadatum`] and plain JSON.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MetadatumJsonOptions {
+    /// Prefix of hex-encoded byte strings. With an empty prefix, bytes are
+    /// rendered as bare hex and every JSON string is read back as text.
+    pub bytes_prefix: &'static str,
+    /// Rendering of maps with non-string keys.
+    pub non_string_keys: NonStringKeys,
+}
+
+impl Default for MetadatumJsonOptions {
+    /// Bytes as `0x`-prefixed hex, as in the `cardano-cli` "no schema" format.
+    fn default() -> Self {
+        Self {
+            bytes_prefix: "0x",
+            non_string_keys: NonStringKeys::default(),
+        }
+    }
+}
+
+/// A JSON value with no metadatum equivalent.
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+#[error("invalid metadatum {value}: expected an integer, string, array or object")]
+pub struct InvalidMetadatum {
+    /// Rejected value.
+    pub value: Value,
+}
+
+impl Metadatum {
+    /// Convert to plain JSON with the default options.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Metadatum, MetadatumMapEntry};
+    ///
+    /// let metadatum = Metadatum::Map(vec![MetadatumMapEntry {
+    ///     k: Metadatum::String("msg".to_string()),
+    ///     v: Metadatum::List(vec![Metadatum::Bytes("cafe".to_string()), Metadatum::Int(1)]),
+    /// }]);
+    /// assert_eq!(metadatum.to_json(), serde_json::json!({ "msg": ["0xcafe", 1] }));
+    /// ```
+    pub fn to_json(&self) -> Value {
+        self.to_json_with(&MetadatumJsonOptions::default())
+    }
+
+    /// Convert to plain JSON.
+    ///
+    /// Integers outside of the `i64` and `u64` ranges, which JSON numbers
+    /// cannot hold exactly, are rendered as decimal strings.
+    pub fn to_json_with(&self, options: &MetadatumJsonOptions) -> Value {
+        match self {
+            Metadatum::Int(int) => {
+                if let Ok(int) = i64::try_from(*int) {
+                    Value::from(int)
+                } else if let Ok(int) = u64::try_from(*int) {
+                    Value::from(int)
+                } else {
+                    Value::String(int.to_string())
+                }
+            }
+            Metadatum::Bytes(hex) => Value::String(format!("{}{hex}", options.bytes_prefix)),
+            Metadatum::String(text) => Value::String(text.clone()),
+            Metadatum::List(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| item.to_json_with(options))
+                    .collect(),
+            ),
+            Metadatum::Map(entries) => {
+                let string_keys = entries
+                    .iter()
+                    .all(|entry| matches!(entry.k, Metadatum::String(_) | Metadatum::Bytes(_)));
+                if !string_keys && options.non_string_keys == NonStringKeys::Pairs {
+                    return Value::Array(
+                        entries
+                            .iter()
+                            .map(|entry| {
+                                Value::Array(vec![
+                                    entry.k.to_json_with(options),
+                                    entry.v.to_json_with(options),
+                                ])
+                            })
+                            .collect(),
+                    );
+                }
+
+                let object: Map<String, Value> = entries
+                    .iter()
+                    .map(|entry| {
+                        let key = match entry.k.to_json_with(options) {
+                            Value::String(key) => key,
+                            key => key.to_string(),
+                        };
+                        (key, entry.v.to_json_with(options))
+                    })
+                    .collect();
+                Value::Object(object)
+            }
+        }
+    }
+
+    /// Convert from plain JSON with the default options.
+    ///
+    /// Strings with a `0x` prefix followed by hex are read as bytes.
+    pub fn try_from_json(value: &Value) -> Result<Self, InvalidMetadatum> {
+        Self::try_from_json_with(value, &MetadatumJsonOptions::default())
+    }
+
+    /// Convert from plain JSON.
+    ///
+    /// Arrays are always read as lists, and object keys as strings or bytes,
+    /// so maps rendered with non-string keys do not convert back to maps.
+    /// Fails on `null`, booleans and non-integer numbers.
+    pub fn try_from_json_with(
+        value: &Value,
+        options: &MetadatumJsonOptions,
+    ) -> Result<Self, InvalidMetadatum> {
+        let invalid = || InvalidMetadatum {
+            value: value.clone(),
+        };
+        match value {
+            Value::Null | Value::Bool(_) => Err(invalid()),
+            Value::Number(number) => number
+                .as_i64()
+                .map(i128::from)
+                .or_else(|| number.as_u64().map(i128::from))
+                .map(Metadatum::Int)
+                .ok_or_else(invalid),
+            Value::String(string) => Ok(Self::from_json_string(string, options)),
+            Value::Array(items) => items
+                .iter()
+                .map(|item| Self::try_from_json_with(item, options))
+                .collect::<Result<_, _>>()
+                .map(Metadatum::List),
+            Value::Object(object) => object
+                .iter()
+                .map(|(key, value)| {
+                    Ok(MetadatumMapEntry {
+                        k: Self::from_json_string(key, options),
+                        v: Self::try_from_json_with(value, options)?,
+                    })
+                })
+                .collect::<Result<_, _>>()
+                .map(Metadatum::Map),
+        }
+    }
+
+    fn from_json_string(string: &str, options: &MetadatumJsonOptions) -> Self {
+        match string.strip_prefix(options.bytes_prefix) {
+            Some(hex)
+                if !options.bytes_prefix.is_empty()
+                    && hex.len() % 2 == 0
+                    && hex.bytes().all(|b| b.is_ascii_hexdigit()) =>
+            {
+                Metadatum::Bytes(hex.to_ascii_lowercase())
+            }
+            _ => Metadatum::String(string.to_string()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use serde_json::json;
+
+    fn entry(k: Metadatum, v: Metadatum) -> MetadatumMapEntry {
+        MetadatumMapEntry { k, v }
+    }
+
+    /// Metadatums that survive a JSON round trip: text never looks like bytes,
+    /// and map keys are text.
+    fn arb_metadatum() -> impl Strategy<Value = Metadatum> {
+        let leaf = prop_oneof![
+            (i64::MIN as i128..=u64::MAX as i128).prop_map(Metadatum::Int),
+            "([0-9a-f]{2}){0,8}".prop_map(Metadatum::Bytes),
+            "[a-z ]{0,8}".prop_map(Metadatum::String),
+        ];
+        leaf.prop_recursive(4, 32, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Metadatum::List),
+                prop::collection::btree_map("[a-z]{1,4}", inner, 0..4).prop_map(|map| {
+                    Metadatum::Map(
+                        map.into_iter()
+                            .map(|(k, v)| entry(Metadatum::String(k), v))
+                            .collect(),
+                    )
+                }),
+            ]
+        })
+    }
+
+    /// Order map entries by key, as JSON objects do not keep insertion order.
+    fn sorted(metadatum: Metadatum) -> Metadatum {
+        match metadatum {
+            Metadatum::List(items) => Metadatum::List(items.into_iter().map(sorted).collect()),
+            Metadatum::Map(entries) => {
+                let mut entries: Vec<_> = entries
+                    .into_iter()
+                    .map(|entry| MetadatumMapEntry {
+                        k: entry.k,
+                        v: sorted(entry.v),
+                    })
+                    .collect();
+                entries.sort_by_key(|entry| match &entry.k {
+                    Metadatum::String(key) => key.clone(),
+                    key => panic!("unexpected key {key:?}"),
+                });
+                Metadatum::Map(entries)
+            }
+            metadatum => metadatum,
+        }
+    }
+
+    #[test]
+    fn test_non_string_keys() {
+        let metadatum = Metadatum::Map(vec![
+            entry(Metadatum::Int(1), Metadatum::String("one".to_string())),
+            entry(
+                Metadatum::List(vec![Metadatum::Int(2)]),
+                Metadatum::Bytes("ff".to_string()),
+            ),
+        ]);
+        assert_eq!(metadatum.to_json(), json!([[1, "one"], [[2], "0xff"]]));
+
+        let options = MetadatumJsonOptions {
+            bytes_prefix: "",
+            non_string_keys: NonStringKeys::Stringify,
+        };
+        assert_eq!(
+            metadatum.to_json_with(&options),
+            json!({ "1": "one", "[2]": "ff" })
+        );
+        assert_eq!(
+            Metadatum::try_from_json_with(&json!("ff"), &options).unwrap(),
+            Metadatum::String("ff".to_string())
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        assert_eq!(
+            Metadatum::try_from_json(&json!({ "0xAB": ["0x", "0xabc", -1] })).unwrap(),
+            Metadatum::Map(vec![entry(
+                Metadatum::Bytes("ab".to_string()),
+                Metadatum::List(vec![
+                    Metadatum::Bytes(String::new()),
+                    Metadatum::String("0xabc".to_string()),
+                    Metadatum::Int(-1),
+                ]),
+            )])
+        );
+        assert_eq!(
+            Metadatum::try_from_json(&json!(u64::MAX)).unwrap(),
+            Metadatum::Int(u64::MAX as i128)
+        );
+        for value in [json!(null), json!(true), json!(1.5), json!([1, null])] {
+            assert!(Metadatum::try_from_json(&value).is_err(), "{value}");
+        }
+    }
+
+    #[test]
+    fn test_labels_keyed_by_number() {
+        use crate::schema::Metadata;
+
+        let metadata: Metadata = serde_json::from_value(json!({
+            "hash": "a5ac0d2a4d7df4a3b2bdd6c84bb5fb1b4dbce8d6da78d8e5f0a6a9d8f8d6ef9c",
+            "labels": {
+                "674": [{ "k": "msg", "v": ["gm"] }],
+                "721": [],
+                "1968": "x"
+            }
+        }))
+        .unwrap();
+        assert!(metadata.label(674).is_some());
+        assert_eq!(metadata.label(721), Some(&Metadatum::List(vec![])));
+        assert_eq!(metadata.label(20), None);
+
+        let cip = metadata.labels_in(600..1000).map(|(label, _)| label).collect::<Vec<_>>();
+        assert_eq!(cip, [674, 721]);
+
+        let serialized = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(serialized["labels"]["721"], json!([]));
+        assert_eq!(serde_json::from_value::<Metadata>(serialized).unwrap(), metadata);
+
+        for label in ["-1", "x", "18446744073709551616"] {
+            let labels = json!({ "labels": { label: [] } });
+            assert!(serde_json::from_value::<Metadata>(labels).is_err(), "{label}");
+        }
+    }
+
+    #[test]
+    fn test_large_negative_int_is_a_string() {
+        let int = -(u64::MAX as i128);
+        assert_eq!(Metadatum::Int(int).to_json(), json!(int.to_string()));
+    }
+
+    proptest! {
+        #[test]
+        fn test_json_round_trip(metadatum in arb_metadatum()) {
+            let json = metadatum.to_json();
+            prop_assert_eq!(
+                sorted(Metadatum::try_from_json(&json).unwrap()),
+                sorted(metadatum)
+            );
+        }
+    }
+}
@@ -3,7 +3,71 @@
 //! This module contains all the type definitions that correspond to the
 //! Ogmios JSON schema, mirroring the `@cardano-ogmios/schema` TypeScript package.
 
+/// Implement the conversions of an enum of names serialized as strings, where
+/// names this crate does not know are kept in its `Other(String)` variant.
+///
+/// The enum derives `Serialize` and `Deserialize` with
+/// `#[serde(from = "String", into = "String")]`. Values compare and hash by
+/// name, so `Other` never shadows a known variant.
+macro_rules! named_enum {
+    ($name:ident { $($variant:ident => $value:literal),+ $(,)? }) => {
+        impl $name {
+            /// Get the name as sent by Ogmios.
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $($name::$variant => $value,)+
+                    $name::Other(name) => name,
+                }
+            }
+        }
+
+        impl PartialEq for $name {
+            fn eq(&self, other: &Self) -> bool {
+                self.as_str() == other.as_str()
+            }
+        }
+
+        impl std::hash::Hash for $name {
+            fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                self.as_str().hash(state);
+            }
+        }
+
+        impl From<String> for $name {
+            fn from(name: String) -> Self {
+                match name.as_str() {
+                    $($value => $name::$variant,)+
+                    _ => $name::Other(name),
+                }
+            }
+        }
+
+        impl From<$name> for String {
+            fn from(value: $name) -> Self {
+                match value {
+                    $name::Other(name) => name,
+                    value => value.as_str().to_string(),
+                }
+            }
+        }
+
+        impl std::fmt::Display for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
 mod primitives;
+mod address;
+mod asset;
+mod digest;
+mod metadatum;
+#[cfg(feature = "plutus-data")]
+mod plutus_data;
+#[cfg(feature = "script-hash")]
+mod script_hash;
 mod block;
 mod transaction;
 mod protocol;
@@ -14,67 +78,167 @@ mod genesis;
 mod era;
 mod network;
 mod jsonrpc;
+mod errors;
+mod lossless;
+#[cfg(any(test, feature = "test-fixtures"))]
+pub mod testing;
 
 // Primitives - export all (including Value, Address, etc.)
 pub use primitives::*;
 
+// Addresses
+pub use address::Address;
+#[cfg(feature = "address")]
+pub use address::{AddressInfo, AddressKind, InvalidAddress};
+
+// Asset identifiers
+pub use asset::{InvalidSubject, Subject};
+
+// Hex-encoded digests
+pub use digest::{
+    DatumHash, DigestBlake2b224, DigestBlake2b256, InvalidDigest, PolicyId, ScriptHash, TransactionId,
+};
+pub use digest::string_digests;
+
+// Transaction metadata values
+pub use metadatum::{
+    InvalidMetadatum, MetadataLabels, Metadatum, MetadatumJsonOptions, MetadatumMapEntry,
+    NonStringKeys,
+};
+
 // Block types
 pub use block::{
     Block, BlockBFT, BlockEBB, BlockPraos, BlockIssuerByron, BlockIssuerPraos,
-    BlockSize, CertifiedVrf, OperationalCertificate, ProtocolVersionByron,
-    ProtocolVersionPraos, SoftwareVersion,
+    BlockSize, ByronDelegationCertificate, ByronUpdate, ByronUpdateProposal, CertifiedVrf,
+    KesKey, OperationalCertificate, ProtocolVersionByron, ProtocolVersionPraos, SoftwareVersion,
 };
 
 // Transaction types
 pub use transaction::{
-    BootstrapWitness, EvaluationResult, ExUnits, InputSource, KeyWitness, Metadata,
-    ScriptPurpose, Transaction, TransactionInput, TransactionOutput,
-    TransactionOutputReference, Utxo, ValidatorIndex, Witnesses,
+    EvaluationResult, ExUnits, InputSource, InvalidOutputReference, Metadata, ScriptPurpose,
+    Signatory, Transaction, TransactionInput, TransactionOutput, TransactionOutputReference,
+    TreasuryTransfer, Utxo, ValidatorIndex,
 };
+#[allow(deprecated)]
+pub use transaction::{BootstrapWitness, KeyWitness, Witnesses};
 
 // Protocol types (excluding BlockSize which is already exported from block)
 pub use protocol::{
     ConstitutionalCommitteeThresholds, CostModels, DelegateRepresentativeVotingThresholds,
-    DRepProtocolParametersUpdateThresholds, MinFeeReferenceScripts, PartialProtocolParameters,
+    DRepProtocolParametersUpdateThresholds, InvalidProtocolVersion, MinFeeReferenceScripts,
+    PartialProtocolParameters,
     ProposedProtocolParameters, ProtocolParameters, ProtocolParametersUpdateThresholds,
     ProtocolVersion, ScriptExecutionPrices, StakePoolVotingThresholds,
 };
 
 // Governance types
 pub use governance::{
-    ConstitutionalCommitteeMembers, ConstitutionalCommitteeMember,
-    ConstitutionalCommitteeMemberCredential, Constitution, DelegateRepresentative,
-    DelegateRepresentativeCredential, DelegateRepresentativeSummary, DRepStatus,
-    GovernanceAction, GovernanceActionId, GovernanceProposal, GovernanceProposalState,
-    GovernanceVote, GovernanceVoter, GovernanceVotes, TreasuryWithdrawal, Vote,
+    ConstitutionalCommittee, ConstitutionalCommitteeDelegate, ConstitutionalCommitteeMembers,
+    ConstitutionalCommitteeMember, ConstitutionalCommitteeMemberCredential,
+    ConstitutionalCommitteeMemberId, ConstitutionalCommitteeMemberStatus,
+    ConstitutionalCommitteeMemberSummary, Constitution, ConstitutionGuardrails,
+    DelegateRepresentative, DelegateRepresentativeCredential, DelegateRepresentativeDelegator,
+    DelegateRepresentativeSummary, DRepId, GovernanceAction, GovernanceActionId,
+    GovernanceProposal, GovernanceProposalState, GovernanceVote, GovernanceVoter,
+    InvalidGovernanceActionId, Mandate, Vote,
 };
 
 // Certificate types (excluding ConstitutionalCommitteeMemberCredential which is from governance)
 pub use certificates::{
     Certificate, Delegatee, PoolMetadata, Relay, StakePool, StakePoolPerformance,
-    StakePoolStatus, StakePoolView,
+    StakePoolRetirement, StakePoolStatus, StakePoolView,
 };
 
 // Script types
 pub use scripts::{Datum, Language, NativeScript, OutputReference, Redeemer, RedeemerPurpose, Script, ScriptReference};
+#[cfg(feature = "plutus-data")]
+pub use plutus_data::{InvalidPlutusData, MAX_PLUTUS_DATA_DEPTH, PlutusData};
+#[cfg(feature = "script-hash")]
+pub use script_hash::InvalidScript;
 
 // Genesis types
 pub use genesis::{
     BootstrapProtocolParameters, ConstitutionalCommitteeConfig, GenesisAlonzo,
     GenesisByron, GenesisConfiguration, GenesisConway, GenesisDelegate,
-    GenesisShelley, GenesisStakePools, InitialDelegate,
+    GenesisShelley, GenesisStakePools, InitialDelegate, SoftForkRule,
 };
 
 // Era types
-pub use era::{Era, EraBound, EraParameters, EraStart, EraSummary, EraWithGenesis};
+pub use era::{Era, EraBound, EraParameters, EraStart, EraSummary, EraWithGenesis, InvalidEra};
 
 // Network types
 pub use network::{
-    LiveStakeDistributionEntry, MempoolSizeAndCapacity, Network, ProjectedRewards,
-    RewardAccountSummary, RuntimeStats, ServerHealth, ServerMetrics, SessionDurations,
+    ConnectionStatus, LiveStakeDistributionEntry, MempoolSizeAndCapacity, Network, ProjectedRewards,
+    RewardAccountDelegate, RewardAccountSummary, RuntimeStats, ServerHealth, ServerMetrics,
+    SessionDurations, TreasuryAndReserves,
 };
 
+// Failure payloads
+pub use errors::{
+    CannotCreateEvaluationContext, EraMismatch, ExecutionUnitsTooLarge, IncompatibleEra,
+    InsufficientCollateral, MissingScripts, MissingSignatories, OutsideOfValidityInterval,
+    OverlappingAdditionalUtxo, TransactionFeeTooSmall, TransactionTooLarge,
+    UnknownOutputReferences, ValidationFailure, ValidityInterval, ValueNotConserved,
+};
+
+// Round-trip checks
+pub use lossless::{assert_lossless_roundtrip, lossless_diff};
+
 // JSON-RPC types
 pub use jsonrpc::{
     error_codes, responses, JsonRpcError, JsonRpcRequest, JsonRpcResponse, JSONRPC_VERSION,
 };
+pub(crate) use jsonrpc::numeric_id;
+pub(crate) use certificates::stake_pool_reference;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::Hash;
+
+    fn assert_key<T: Eq + Hash>() {}
+
+    /// Types used as `HashMap` and `HashSet` keys. Types holding floats or
+    /// JSON values cannot be keys and are left out.
+    #[test]
+    fn test_key_types_are_hashable() {
+        assert_key::<TransactionOutputReference>();
+        assert_key::<OutputReference>();
+        assert_key::<TransactionId>();
+        assert_key::<ScriptHash>();
+        assert_key::<PolicyId>();
+        assert_key::<DatumHash>();
+        assert_key::<Subject>();
+        assert_key::<Address>();
+        assert_key::<StakeCredential>();
+        assert_key::<CredentialOrigin>();
+        assert_key::<GovernanceActionId>();
+        assert_key::<DelegateRepresentativeCredential>();
+        assert_key::<ConstitutionalCommitteeMemberCredential>();
+        assert_key::<ConstitutionalCommitteeMemberStatus>();
+        assert_key::<DelegateRepresentativeDelegator>();
+        assert_key::<DRepId>();
+        assert_key::<Vote>();
+        assert_key::<StakePoolStatus>();
+        assert_key::<ValidatorIndex>();
+        assert_key::<ScriptPurpose>();
+        assert_key::<EvaluationResult>();
+        assert_key::<ExUnits>();
+        assert_key::<Language>();
+        assert_key::<Network>();
+        assert_key::<Era>();
+    }
+
+    #[test]
+    fn test_hash_agrees_with_eq() {
+        use std::collections::HashSet;
+
+        // Names compare equal whatever the variant, so they must hash alike.
+        let networks: HashSet<_> =
+            [Network::Mainnet, Network::Other("mainnet".to_string())].into();
+        assert_eq!(networks.len(), 1);
+        let languages: HashSet<_> =
+            [Language::PlutusV3, Language::Other("plutus:v3".to_string())].into();
+        assert_eq!(languages.len(), 1);
+    }
+}
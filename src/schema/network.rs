@@ -1,41 +1,37 @@
 //! Network types for Cardano.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use super::primitives::*;
 use super::era::Era;
 
 /// Cardano network names.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Names other than the public networks, e.g. `sanchonet` or a local devnet,
+/// are kept as [`Other`](Network::Other). Networks compare by name.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Network {
+    /// Cardano mainnet.
     Mainnet,
+    /// The preview testnet.
     Preview,
+    /// The pre-production testnet.
     Preprod,
-    #[serde(other)]
-    Other,
+    /// Network not known to this version.
+    Other(String),
 }
 
-impl Network {
-    /// Get the network as a string.
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Network::Mainnet => "mainnet",
-            Network::Preview => "preview",
-            Network::Preprod => "preprod",
-            Network::Other => "unknown",
-        }
-    }
-}
-
-impl std::fmt::Display for Network {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.as_str())
-    }
-}
+named_enum!(Network {
+    Mainnet => "mainnet",
+    Preview => "preview",
+    Preprod => "preprod",
+});
 
 /// Server health information.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ServerHealth {
     /// Current era.
     pub current_era: Era,
@@ -54,11 +50,62 @@ pub struct ServerHealth {
     pub network_synchronization: f64,
     /// Server version.
     pub version: String,
+    /// Whether Ogmios is connected to its node (absent on older servers).
+    #[serde(default)]
+    pub connection_status: Option<ConnectionStatus>,
+    /// Current epoch (absent on older servers).
+    #[serde(default)]
+    pub current_epoch: Option<Epoch>,
+    /// Slot within the current epoch (absent on older servers).
+    #[serde(default)]
+    pub slot_in_epoch: Option<Slot>,
+}
+
+impl ServerHealth {
+    /// Whether Ogmios is connected to its node.
+    ///
+    /// Servers that do not report a connection status are assumed connected.
+    pub fn is_node_connected(&self) -> bool {
+        self.connection_status != Some(ConnectionStatus::Disconnected)
+    }
+}
+
+/// Connection status between Ogmios and its node.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
+pub enum ConnectionStatus {
+    /// Ogmios is connected to the node.
+    Connected,
+    /// Ogmios cannot reach the node.
+    Disconnected,
+    /// A status unknown to this client.
+    Other(String),
+}
+
+named_enum!(ConnectionStatus {
+    Connected => "connected",
+    Disconnected => "disconnected",
+});
+
+#[cfg(feature = "chrono")]
+impl ServerHealth {
+    /// Seconds elapsed between the last tip update and `now`.
+    ///
+    /// This is the main liveness signal of a node: it grows when the node
+    /// stops receiving blocks. The result is negative if `now` precedes the
+    /// update (clock skew), and `None` if the tip was never updated.
+    pub fn seconds_since_last_tip_update(&self, now: chrono::DateTime<chrono::Utc>) -> Option<f64> {
+        self.last_tip_update.map(|updated| {
+            let elapsed = now - updated;
+            elapsed.num_seconds() as f64 + f64::from(elapsed.subsec_nanos()) / 1e9
+        })
+    }
 }
 
 /// Server metrics.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ServerMetrics {
     /// Runtime statistics (optional).
     #[serde(default)]
@@ -78,6 +125,7 @@ pub struct ServerMetrics {
 /// Runtime statistics.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct RuntimeStats {
     /// GC CPU time in seconds.
     #[serde(default)]
@@ -96,6 +144,7 @@ pub struct RuntimeStats {
 /// Session duration statistics.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct SessionDurations {
     /// Maximum session duration.
     pub max: f64,
@@ -108,6 +157,7 @@ pub struct SessionDurations {
 /// Mempool size and capacity.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct MempoolSizeAndCapacity {
     /// Current number of bytes in the mempool.
     pub bytes: NumberOfBytes,
@@ -119,37 +169,79 @@ pub struct MempoolSizeAndCapacity {
     pub max_transactions: u64,
 }
 
-/// Reward account summaries.
+/// Reward account summary, keyed by stake credential in query results.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct RewardAccountSummary {
-    /// Reward account address.
-    pub address: RewardAccount,
     /// Delegated stake pool (if any).
-    #[serde(default)]
-    pub delegate: Option<StakePoolId>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<RewardAccountDelegate>,
     /// Current rewards balance.
     pub rewards: AdaValue,
     /// Deposit amount.
     pub deposit: AdaValue,
 }
 
-/// Live stake distribution entry.
+impl RewardAccountSummary {
+    /// ID of the delegated stake pool (if any).
+    pub fn delegate_id(&self) -> Option<&StakePoolId> {
+        self.delegate.as_ref().map(|delegate| &delegate.id)
+    }
+}
+
+/// Stake pool a reward account delegates to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct RewardAccountDelegate {
+    /// Stake pool ID.
+    pub id: StakePoolId,
+}
+
+/// Live stake distribution entry, keyed by stake pool ID in query results.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct LiveStakeDistributionEntry {
-    /// Stake pool ID.
-    pub stake_pool: StakePoolId,
-    /// Total stake delegated.
-    pub stake: AdaValue,
+    /// Fraction of the total active stake delegated to the pool.
+    pub stake: Ratio,
+    /// Hash of the pool's VRF verification key.
+    pub vrf: VrfVerificationKey,
 }
 
-/// Projected rewards.
+/// Ada held in the treasury and the reserves.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
-pub struct ProjectedRewards {
-    /// Stake address.
-    pub address: StakeAddress,
-    /// Projected rewards.
-    pub rewards: AdaValue,
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TreasuryAndReserves {
+    /// Treasury balance.
+    pub treasury: Value,
+    /// Reserves balance.
+    pub reserves: Value,
+}
+
+/// Projected rewards.
+///
+/// Maps each queried input (a stake amount in lovelace, rendered as a decimal
+/// string, or a credential) to the rewards it would earn with each stake pool.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ProjectedRewards(pub HashMap<String, HashMap<StakePoolId, AdaValue>>);
+
+impl ProjectedRewards {
+    /// Projected rewards per stake pool for a queried stake amount.
+    pub fn for_stake(&self, lovelace: Lovelace) -> Option<&HashMap<StakePoolId, AdaValue>> {
+        self.0.get(&lovelace.to_string())
+    }
+
+    /// Projected rewards per stake pool for a queried key or script credential,
+    /// or a stake address, as the response keys it.
+    pub fn for_credential(&self, credential: &str) -> Option<&HashMap<StakePoolId, AdaValue>> {
+        self.0.get(credential)
+    }
+
+    /// Iterate over queried inputs and their projected rewards per pool.
+    pub fn iter(&self) -> impl Iterator<Item = (&String, &HashMap<StakePoolId, AdaValue>)> {
+        self.0.iter()
+    }
 }
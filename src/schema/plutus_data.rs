@@ -0,0 +1,373 @@
+//! Plutus data decoded from CBOR datums.
+//!
+//! Requires the `plutus-data` feature.
+
+use super::digest::DatumHash;
+use super::scripts::Datum;
+use crate::util::hex_encode;
+use blake2::digest::consts::U32;
+use blake2::{Blake2b, Digest};
+use minicbor::Decoder;
+use minicbor::data::{Tag, Type};
+
+/// Deepest nesting of constructors, lists and maps accepted when decoding.
+///
+/// Decoding is recursive: the cap keeps a hostile datum from overflowing the
+/// stack. On-chain datums nest far less.
+pub const MAX_PLUTUS_DATA_DEPTH: usize = 256;
+
+/// Plutus data, the structured form of datums and redeemers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlutusData {
+    /// Constructor application.
+    Constr {
+        /// Constructor index.
+        constructor: u64,
+        /// Constructor fields.
+        fields: Vec<PlutusData>,
+    },
+    /// Map of key-value pairs, in encoding order.
+    Map(Vec<(PlutusData, PlutusData)>),
+    /// List of values.
+    List(Vec<PlutusData>),
+    /// Integer.
+    Int(i128),
+    /// Integer outside the `i128` range, as CBOR encodes it: the big-endian
+    /// bytes (hex-encoded) of `n`, for the value `n` or, if `negative`,
+    /// `-1 - n`.
+    BigInt {
+        /// Whether the value is `-1 - n` rather than `n`.
+        negative: bool,
+        /// Big-endian bytes of `n`, hex-encoded and without leading zeros.
+        bytes: String,
+    },
+    /// Byte string (hex-encoded).
+    Bytes(String),
+}
+
+/// A datum that is not CBOR-encoded Plutus data.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid Plutus data: {reason}")]
+pub struct InvalidPlutusData {
+    /// Why the datum was rejected.
+    pub reason: String,
+}
+
+impl InvalidPlutusData {
+    fn new(reason: impl ToString) -> Self {
+        Self {
+            reason: reason.to_string(),
+        }
+    }
+}
+
+impl From<minicbor::decode::Error> for InvalidPlutusData {
+    fn from(error: minicbor::decode::Error) -> Self {
+        Self::new(error)
+    }
+}
+
+impl PlutusData {
+    /// Decode from CBOR bytes, which must hold exactly one item.
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, InvalidPlutusData> {
+        let mut decoder = Decoder::new(bytes);
+        let data = decode(&mut decoder, 0)?;
+        if decoder.position() != bytes.len() {
+            return Err(InvalidPlutusData::new("trailing bytes after the datum"));
+        }
+        Ok(data)
+    }
+}
+
+impl Datum {
+    /// Decode the CBOR datum into Plutus data.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Datum, PlutusData};
+    ///
+    /// let unit = Datum::Cbor("d87980".to_string());
+    /// assert_eq!(
+    ///     unit.decode().unwrap(),
+    ///     PlutusData::Constr { constructor: 0, fields: vec![] }
+    /// );
+    /// ```
+    pub fn decode(&self) -> Result<PlutusData, InvalidPlutusData> {
+        PlutusData::from_cbor(&self.cbor_bytes()?)
+    }
+
+    /// Blake2b-256 hash of the CBOR datum, as found in `datumHash` fields.
+    ///
+    /// The hash covers the bytes as encoded on chain, so it is computed
+    /// without re-encoding the datum.
+    pub fn hash(&self) -> Result<DatumHash, InvalidPlutusData> {
+        let digest = Blake2b::<U32>::digest(self.cbor_bytes()?);
+        Ok(DatumHash::from_bytes(digest.into()))
+    }
+
+    fn cbor_bytes(&self) -> Result<Vec<u8>, InvalidPlutusData> {
+        let Datum::Cbor(hex) = self else {
+            return Err(InvalidPlutusData::new("the datum is not CBOR-encoded"));
+        };
+        if hex.len() % 2 != 0 || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return Err(InvalidPlutusData::new("the datum is not hex-encoded"));
+        }
+        crate::util::hex_decode(hex).map_err(InvalidPlutusData::new)
+    }
+}
+
+fn decode(decoder: &mut Decoder<'_>, depth: usize) -> Result<PlutusData, InvalidPlutusData> {
+    if depth > MAX_PLUTUS_DATA_DEPTH {
+        return Err(InvalidPlutusData::new(format!(
+            "nested deeper than {MAX_PLUTUS_DATA_DEPTH} levels"
+        )));
+    }
+    let depth = depth + 1;
+    match decoder.datatype()? {
+        Type::U8 | Type::U16 | Type::U32 | Type::U64 | Type::I8 | Type::I16 | Type::I32
+        | Type::I64 | Type::Int => Ok(PlutusData::Int(decoder.int()?.into())),
+        Type::Bytes | Type::BytesIndef => Ok(PlutusData::Bytes(hex_encode(&bytes(decoder)?))),
+        Type::Array | Type::ArrayIndef => {
+            let length = decoder.array()?;
+            Ok(PlutusData::List(items(decoder, length, depth)?))
+        }
+        Type::Map | Type::MapIndef => {
+            let length = decoder.map()?;
+            let mut entries = Vec::new();
+            while more(decoder, length, entries.len())? {
+                entries.push((decode(decoder, depth)?, decode(decoder, depth)?));
+            }
+            Ok(PlutusData::Map(entries))
+        }
+        Type::Tag => match decoder.tag()? {
+            Tag::PosBignum => Ok(bignum(decoder, false)?),
+            Tag::NegBignum => Ok(bignum(decoder, true)?),
+            Tag::Unassigned(tag @ 121..=127) => constr(decoder, tag - 121, depth),
+            Tag::Unassigned(tag @ 1280..=1400) => constr(decoder, tag - 1280 + 7, depth),
+            Tag::Unassigned(102) => {
+                if decoder.array()? != Some(2) {
+                    return Err(InvalidPlutusData::new(
+                        "expected a constructor index and fields",
+                    ));
+                }
+                let constructor = decoder.u64()?;
+                constr(decoder, constructor, depth)
+            }
+            tag => Err(InvalidPlutusData::new(format!("unexpected tag {tag:?}"))),
+        },
+        other => Err(InvalidPlutusData::new(format!("unexpected {other}"))),
+    }
+}
+
+fn constr(
+    decoder: &mut Decoder<'_>,
+    constructor: u64,
+    depth: usize,
+) -> Result<PlutusData, InvalidPlutusData> {
+    let length = decoder.array()?;
+    Ok(PlutusData::Constr {
+        constructor,
+        fields: items(decoder, length, depth)?,
+    })
+}
+
+fn items(
+    decoder: &mut Decoder<'_>,
+    length: Option<u64>,
+    depth: usize,
+) -> Result<Vec<PlutusData>, InvalidPlutusData> {
+    let mut items = Vec::new();
+    while more(decoder, length, items.len())? {
+        items.push(decode(decoder, depth)?);
+    }
+    Ok(items)
+}
+
+/// Whether a definite or indefinite container has more items, consuming the
+/// break of an indefinite one.
+fn more(
+    decoder: &mut Decoder<'_>,
+    length: Option<u64>,
+    read: usize,
+) -> Result<bool, InvalidPlutusData> {
+    match length {
+        Some(length) => Ok((read as u64) < length),
+        None if decoder.datatype()? == Type::Break => {
+            decoder.set_position(decoder.position() + 1);
+            Ok(false)
+        }
+        None => Ok(true),
+    }
+}
+
+/// Read a definite or chunked byte string.
+fn bytes(decoder: &mut Decoder<'_>) -> Result<Vec<u8>, InvalidPlutusData> {
+    let mut bytes = Vec::new();
+    for chunk in decoder.bytes_iter()? {
+        bytes.extend_from_slice(chunk?);
+    }
+    Ok(bytes)
+}
+
+/// Read the bytes of a bignum, an [`Int`](PlutusData::Int) when it fits.
+fn bignum(decoder: &mut Decoder<'_>, negative: bool) -> Result<PlutusData, InvalidPlutusData> {
+    let bytes = bytes(decoder)?;
+    let significant = &bytes[bytes.iter().take_while(|byte| **byte == 0).count()..];
+    let small = (significant.len() <= 16)
+        .then(|| significant.iter().fold(0u128, |n, byte| (n << 8) | u128::from(*byte)))
+        .and_then(|n| i128::try_from(n).ok());
+    Ok(match small {
+        Some(n) if negative => PlutusData::Int(-1 - n),
+        Some(n) => PlutusData::Int(n),
+        None => PlutusData::BigInt {
+            negative,
+            bytes: hex_encode(significant),
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SELLER: &str = "5a3b2fd1f7c26e1d8e3b1c1a7e9d2c4b6a8f0e1d3c5b7a9f1e2d3c4b";
+    const MARKETPLACE: &str = "70e60f3b5ea7153e0acc7a803e4401d44b8ed1bae1c7baaad1a62a72";
+
+    /// Inline datum of a marketplace listing: the seller, and the payouts as
+    /// `(payment credential, lovelace)` pairs, with an indefinite-length list
+    /// as emitted by PlutusTx.
+    fn listing_datum() -> Datum {
+        Datum::Cbor(format!(
+            "d8799f581c{SELLER}9fd8799f581c{SELLER}1a05f5e100ffd8799f581c{MARKETPLACE}1a004c4b40ffffff"
+        ))
+    }
+
+    fn bytes(hex: &str) -> PlutusData {
+        PlutusData::Bytes(hex.to_string())
+    }
+
+    fn payout(credential: &str, lovelace: i128) -> PlutusData {
+        PlutusData::Constr {
+            constructor: 0,
+            fields: vec![bytes(credential), PlutusData::Int(lovelace)],
+        }
+    }
+
+    #[test]
+    fn test_decode_listing_datum() {
+        assert_eq!(
+            listing_datum().decode().unwrap(),
+            PlutusData::Constr {
+                constructor: 0,
+                fields: vec![
+                    bytes(SELLER),
+                    PlutusData::List(vec![
+                        payout(SELLER, 100_000_000),
+                        payout(MARKETPLACE, 5_000_000),
+                    ]),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_captured_inline_datum() {
+        let response = crate::schema::testing::fixture("chain-sync/nextBlock-babbage").result();
+        let output = &response["block"]["transactions"][0]["outputs"][0];
+        let datum: Datum = serde_json::from_value(output["datum"].clone()).unwrap();
+        assert_eq!(
+            datum.decode().unwrap(),
+            PlutusData::Constr {
+                constructor: 0,
+                fields: vec![
+                    bytes("b66db8604f594cef9731a61dd6756ca53db3304ab857ae9f50788fef"),
+                    PlutusData::Int(100_000_000),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn test_datum_hash() {
+        assert_eq!(
+            listing_datum().hash().unwrap(),
+            "d09a029963979266b98432acd0f9d33dc0d28b6d6c4e1455f867766cbae73a44"
+        );
+        // The unit datum, `Constr 0 []`.
+        assert_eq!(
+            Datum::Cbor("d87980".to_string()).hash().unwrap(),
+            "923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec"
+        );
+    }
+
+    #[test]
+    fn test_decode_all_shapes() {
+        // Constructor 7, the general constructor form, a map, a chunked byte
+        // string and bignums.
+        let datum = Datum::Cbor(
+            "9fd9050080d866820a80a1010243abcdef5f41ab41cdffc249010000000000000000c34100ff"
+                .to_string(),
+        );
+        assert_eq!(
+            datum.decode().unwrap(),
+            PlutusData::List(vec![
+                PlutusData::Constr { constructor: 7, fields: vec![] },
+                PlutusData::Constr { constructor: 10, fields: vec![] },
+                PlutusData::Map(vec![(PlutusData::Int(1), PlutusData::Int(2))]),
+                bytes("abcdef"),
+                bytes("abcd"),
+                PlutusData::Int(1 << 64),
+                PlutusData::Int(-1),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_decode_big_integers() {
+        // 2^128 and -1 - 2^128 do not fit an i128.
+        let two_to_128 = format!("01{}", "00".repeat(16));
+        let datum = Datum::Cbor(format!("82c251{two_to_128}c351{two_to_128}"));
+        assert_eq!(
+            datum.decode().unwrap(),
+            PlutusData::List(vec![
+                PlutusData::BigInt {
+                    negative: false,
+                    bytes: two_to_128.clone(),
+                },
+                PlutusData::BigInt {
+                    negative: true,
+                    bytes: two_to_128,
+                },
+            ])
+        );
+
+        // i128::MIN, encoded as -1 - (2^127 - 1).
+        let datum = Datum::Cbor(format!("c3507f{}", "ff".repeat(15)));
+        assert_eq!(datum.decode().unwrap(), PlutusData::Int(i128::MIN));
+    }
+
+    #[test]
+    fn test_decode_depth_is_capped() {
+        let nested = |depth: usize| Datum::Cbor(format!("{}80", "81".repeat(depth)));
+        assert!(nested(MAX_PLUTUS_DATA_DEPTH).decode().is_ok());
+        let error = nested(MAX_PLUTUS_DATA_DEPTH + 1).decode().unwrap_err();
+        assert!(error.reason.contains("nested deeper"), "{error}");
+        // Far deeper than the stack could recurse.
+        assert!(nested(1_000_000).decode().is_err());
+    }
+
+    #[test]
+    fn test_rejects_malformed_datums() {
+        for datum in [
+            Datum::Cbor("d87980ff".to_string()),
+            Datum::Cbor("d879".to_string()),
+            Datum::Cbor("f5".to_string()),
+            Datum::Cbor("d8798".to_string()),
+            Datum::Value(serde_json::json!({ "constructor": 0 })),
+        ] {
+            assert!(datum.decode().is_err(), "{datum:?}");
+        }
+        assert!(Datum::Value(serde_json::json!(0)).hash().is_err());
+    }
+}
@@ -1,7 +1,10 @@
 //! Primitive types used throughout the Ogmios schema.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::iter::Sum;
+use super::asset::{MAX_ASSET_NAME_LENGTH, Subject, is_valid_asset_name};
+use super::digest::{DigestBlake2b224, DigestBlake2b256, PolicyId, ScriptHash};
 
 /// A 64-bit unsigned integer slot number.
 pub type Slot = u64;
@@ -18,24 +21,12 @@ pub type Lovelace = u64;
 /// A signed lovelace delta for value changes.
 pub type LovelaceDelta = i128;
 
-/// Transaction ID as a hex-encoded string (64 characters).
-pub type TransactionId = String;
-
-/// Policy ID as a hex-encoded string (56 characters).
-pub type PolicyId = String;
-
 /// Asset name as a hex-encoded string.
 pub type AssetName = String;
 
 /// Asset quantity (can be negative for burning).
 pub type AssetQuantity = i128;
 
-/// Script hash as a hex-encoded string.
-pub type ScriptHash = String;
-
-/// Datum hash as a hex-encoded string (64 characters, Blake2b-256).
-pub type DatumHash = String;
-
 /// Pool ID as a Bech32-encoded string.
 pub type PoolId = String;
 
@@ -48,12 +39,6 @@ pub type VerificationKeyHash = String;
 /// A generic digest type (hex-encoded).
 pub type Digest = String;
 
-/// Blake2b-224 digest (56 hex characters).
-pub type DigestBlake2b224 = String;
-
-/// Blake2b-256 digest (64 hex characters).
-pub type DigestBlake2b256 = String;
-
 /// A ratio represented as a string "numerator/denominator".
 pub type RatioString = String;
 
@@ -63,9 +48,84 @@ pub type NetworkMagic = u32;
 /// Relative time in seconds.
 pub type RelativeTime = f64;
 
+/// Relative times are sent as `{ "seconds": n }` and slot lengths as
+/// `{ "milliseconds": n }`; plain numbers of seconds are accepted as well.
+pub(crate) mod relative_time {
+    use super::RelativeTime;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Repr {
+        Number(RelativeTime),
+        Seconds { seconds: RelativeTime },
+        Milliseconds { milliseconds: RelativeTime },
+    }
+
+    #[derive(Serialize)]
+    struct Seconds<T> {
+        seconds: T,
+    }
+
+    #[derive(Serialize)]
+    struct Milliseconds<T> {
+        milliseconds: T,
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<RelativeTime, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        Ok(match Repr::deserialize(deserializer)? {
+            Repr::Number(seconds) | Repr::Seconds { seconds } => seconds,
+            Repr::Milliseconds { milliseconds } => milliseconds / 1000.0,
+        })
+    }
+
+    /// Serialize as `{ "seconds": n }`, an integer when there is no fraction.
+    pub fn serialize<S: Serializer>(time: &RelativeTime, serializer: S) -> Result<S::Ok, S::Error> {
+        match whole(*time) {
+            Some(seconds) => Seconds { seconds }.serialize(serializer),
+            None => Seconds { seconds: *time }.serialize(serializer),
+        }
+    }
+
+    /// Serialize as `{ "milliseconds": n }`, an integer when there is no fraction.
+    pub fn serialize_milliseconds<S: Serializer>(
+        time: &RelativeTime,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let milliseconds = time * 1000.0;
+        match whole(milliseconds) {
+            Some(milliseconds) => Milliseconds { milliseconds }.serialize(serializer),
+            None => Milliseconds { milliseconds }.serialize(serializer),
+        }
+    }
+
+    fn whole(value: f64) -> Option<u64> {
+        (value >= 0.0 && value.fract() == 0.0 && value < u64::MAX as f64).then_some(value as u64)
+    }
+}
+
 /// UTC time as ISO 8601 string.
+#[cfg(not(feature = "chrono"))]
 pub type UtcTime = String;
 
+/// UTC time, parsed from the ISO 8601 strings Ogmios emits.
+///
+/// A `String` unless the `chrono` feature is enabled.
+#[cfg(feature = "chrono")]
+pub type UtcTime = chrono::DateTime<chrono::Utc>;
+
+/// Convert a time relative to the network start into an absolute time.
+///
+/// Relative times (e.g. era bounds) count seconds since `system_start`, as
+/// returned by `queryNetwork/startTime`. Rounded to the millisecond.
+#[cfg(feature = "chrono")]
+pub fn relative_time_to_utc(system_start: UtcTime, time: RelativeTime) -> UtcTime {
+    system_start + chrono::TimeDelta::milliseconds((time * 1000.0).round() as i64)
+}
+
 /// Number of bytes.
 pub type NumberOfBytes = u64;
 
@@ -79,11 +139,17 @@ impl Origin {
 }
 
 /// A point on the blockchain, either origin or a specific slot/hash.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Only the literal string `"origin"` deserializes as [`Point::Origin`]; any
+/// other bare string is rejected.
+///
+/// Points order by chain position: the origin comes first, then points by
+/// slot, with ties broken by block ID so that the order is total.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Point {
     /// The origin point.
-    Origin(String),
+    Origin(#[serde(deserialize_with = "deserialize_origin")] String),
     /// A specific point with slot and block ID.
     Point {
         slot: Slot,
@@ -98,8 +164,68 @@ impl Point {
     }
 
     /// Create a point at a specific slot and block ID.
-    pub fn at(slot: Slot, id: impl Into<String>) -> Self {
-        Point::Point { slot, id: id.into() }
+    pub fn at(slot: Slot, id: DigestBlake2b256) -> Self {
+        Point::Point { slot, id }
+    }
+
+    /// Check whether this is the origin.
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::Point;
+    ///
+    /// let id = "ab".repeat(32).parse().unwrap();
+    /// assert!(Point::origin().is_origin());
+    /// assert!(!Point::at(0, id).is_origin());
+    /// ```
+    pub fn is_origin(&self) -> bool {
+        matches!(self, Point::Origin(_))
+    }
+
+    /// Number of slots from this point to `tip`.
+    ///
+    /// The origin counts as slot 0. Returns `None` if the tip is the origin or
+    /// the point is past the tip.
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Point, Tip};
+    ///
+    /// let id: ogmios_client::schema::DigestBlake2b256 = "ab".repeat(32).parse().unwrap();
+    /// let tip = Tip::Tip { slot: 100, id: id.clone(), height: 7 };
+    /// assert_eq!(Point::at(40, id.clone()).distance_to(&tip), Some(60));
+    /// assert_eq!(Point::origin().distance_to(&tip), Some(100));
+    /// assert_eq!(Point::at(120, id).distance_to(&tip), None);
+    /// ```
+    pub fn distance_to(&self, tip: &Tip) -> Option<u64> {
+        tip.slot()?.checked_sub(self.slot().unwrap_or(0))
+    }
+
+    /// Get the slot, or `None` for the origin.
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            Point::Origin(_) => None,
+            Point::Point { slot, .. } => Some(*slot),
+        }
+    }
+
+    /// Get the block ID, or `None` for the origin.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            Point::Origin(_) => None,
+            Point::Point { id, .. } => Some(id.as_str()),
+        }
+    }
+}
+
+/// Deserialize the literal `"origin"`, rejecting any other string.
+fn deserialize_origin<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let value = String::deserialize(deserializer)?;
+    if value == Origin::ORIGIN {
+        Ok(value)
+    } else {
+        Err(serde::de::Error::invalid_value(
+            serde::de::Unexpected::Str(&value),
+            &"\"origin\"",
+        ))
     }
 }
 
@@ -107,8 +233,8 @@ impl Point {
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Tip {
-    /// The origin (empty chain).
-    Origin(String),
+    /// The origin (empty chain). Only the literal string `"origin"` is accepted.
+    Origin(#[serde(deserialize_with = "deserialize_origin")] String),
     /// A specific tip with slot, ID, and height.
     Tip {
         slot: Slot,
@@ -117,13 +243,127 @@ pub enum Tip {
     },
 }
 
+impl Tip {
+    /// Check whether this is the origin.
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::Tip;
+    ///
+    /// assert!(Tip::Origin("origin".into()).is_origin());
+    /// let id = "ab".repeat(32).parse().unwrap();
+    /// assert!(!Tip::Tip { slot: 1, id, height: 1 }.is_origin());
+    /// ```
+    pub fn is_origin(&self) -> bool {
+        matches!(self, Tip::Origin(_))
+    }
+
+    /// Get the slot, or `None` for the origin.
+    pub fn slot(&self) -> Option<Slot> {
+        match self {
+            Tip::Origin(_) => None,
+            Tip::Tip { slot, .. } => Some(*slot),
+        }
+    }
+
+    /// Get the block height, or `None` for the origin.
+    pub fn height(&self) -> Option<BlockHeight> {
+        match self {
+            Tip::Origin(_) => None,
+            Tip::Tip { height, .. } => Some(*height),
+        }
+    }
+
+    /// Convert the tip into a point, e.g. to find an intersection at it.
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{Point, Tip};
+    ///
+    /// let id: ogmios_client::schema::DigestBlake2b256 = "ab".repeat(32).parse().unwrap();
+    /// let tip = Tip::Tip { slot: 100, id: id.clone(), height: 7 };
+    /// assert_eq!(tip.to_point(), Point::at(100, id));
+    /// assert_eq!(Tip::Origin("origin".into()).to_point(), Point::origin());
+    /// ```
+    pub fn to_point(&self) -> Point {
+        match self {
+            Tip::Origin(_) => Point::origin(),
+            Tip::Tip { slot, id, .. } => Point::at(*slot, id.clone()),
+        }
+    }
+}
+
+impl From<&Tip> for Point {
+    fn from(tip: &Tip) -> Self {
+        tip.to_point()
+    }
+}
+
+impl From<Tip> for Point {
+    fn from(tip: Tip) -> Self {
+        match tip {
+            Tip::Origin(_) => Point::origin(),
+            Tip::Tip { slot, id, .. } => Point::Point { slot, id },
+        }
+    }
+}
+
 /// A rational number represented as numerator and denominator.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Ogmios encodes ratios as `"numerator/denominator"` strings, which is the
+/// serialized form. The `{ "numerator", "denominator" }` object form is also
+/// accepted when deserializing.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Ratio {
     pub numerator: u64,
     pub denominator: u64,
 }
 
+impl std::fmt::Display for Ratio {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.numerator, self.denominator)
+    }
+}
+
+impl std::str::FromStr for Ratio {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (numerator, denominator) = s
+            .split_once('/')
+            .ok_or_else(|| format!("expected a ratio of the form n/d, got {s:?}"))?;
+        let parse = |part: &str| {
+            part.trim()
+                .parse::<u64>()
+                .map_err(|e| format!("invalid ratio {s:?}: {e}"))
+        };
+        Ok(Self::new(parse(numerator)?, parse(denominator)?))
+    }
+}
+
+impl Serialize for Ratio {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Ratio {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Repr {
+            Text(String),
+            Object { numerator: u64, denominator: u64 },
+        }
+
+        match Repr::deserialize(deserializer)? {
+            Repr::Text(text) => text.parse().map_err(serde::de::Error::custom),
+            Repr::Object {
+                numerator,
+                denominator,
+            } => Ok(Self::new(numerator, denominator)),
+        }
+    }
+}
+
 impl Ratio {
     pub fn new(numerator: u64, denominator: u64) -> Self {
         Self { numerator, denominator }
@@ -138,8 +378,12 @@ impl Ratio {
 pub type Assets = HashMap<PolicyId, HashMap<AssetName, AssetQuantity>>;
 
 /// Value containing ADA and optional multi-assets.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// On the wire, a value is an object with an `ada` entry and one entry per
+/// policy ID, mapping hex asset names to quantities. Deserializes to
+/// [`Value::AdaOnly`] exactly when there are no other assets; policy IDs and
+/// asset names are validated and lowercased.
+#[derive(Debug, Clone, PartialEq)]
 pub enum Value {
     /// ADA only value.
     AdaOnly {
@@ -148,17 +392,200 @@ pub enum Value {
     /// Value with ADA and other assets.
     WithAssets {
         ada: AdaValue,
-        #[serde(flatten)]
         assets: Assets,
     },
 }
 
+impl Serialize for Value {
+    /// Policies and asset names are written in ascending order.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let (ada, assets) = match self {
+            Value::AdaOnly { ada } => (ada, None),
+            Value::WithAssets { ada, assets } => (ada, Some(assets)),
+        };
+        let policies: BTreeMap<_, BTreeMap<_, _>> = assets
+            .into_iter()
+            .flatten()
+            .map(|(policy, tokens)| (policy, tokens.iter().collect()))
+            .collect();
+
+        let mut map = serializer.serialize_map(Some(1 + policies.len()))?;
+        map.serialize_entry("ada", &LovelaceEntry { lovelace: ada.lovelace })?;
+        for (policy, tokens) in policies {
+            map.serialize_entry(policy, &tokens)?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("a value with an `ada` entry and assets by policy ID")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Value, A::Error> {
+                use serde::de::Error;
+
+                let mut ada = None;
+                let mut assets = Assets::new();
+                while let Some(key) = map.next_key::<String>()? {
+                    if key == "ada" {
+                        if ada.is_some() {
+                            return Err(A::Error::duplicate_field("ada"));
+                        }
+                        ada = Some(map.next_value()?);
+                        continue;
+                    }
+
+                    let policy: PolicyId = key.parse().map_err(|_| {
+                        A::Error::custom(format!(
+                            "invalid value entry {key:?}: expected `ada` or a policy ID"
+                        ))
+                    })?;
+                    let tokens = map.next_value::<HashMap<String, AssetQuantityRepr>>()?;
+                    let mut names = HashMap::with_capacity(tokens.len());
+                    for (name, quantity) in tokens {
+                        if !is_valid_asset_name(&name) {
+                            return Err(A::Error::custom(format!(
+                                "invalid asset name {name:?} under policy {policy}: expected up \
+                                 to {MAX_ASSET_NAME_LENGTH} hex characters"
+                            )));
+                        }
+                        names.insert(name.to_ascii_lowercase(), quantity.0);
+                    }
+                    if names.is_empty() {
+                        continue;
+                    }
+                    if assets.insert(policy.clone(), names).is_some() {
+                        return Err(A::Error::custom(format!("duplicate policy {policy}")));
+                    }
+                }
+
+                let ada = ada.ok_or_else(|| A::Error::missing_field("ada"))?;
+                Ok(if assets.is_empty() {
+                    Value::AdaOnly { ada }
+                } else {
+                    Value::WithAssets { ada, assets }
+                })
+            }
+        }
+
+        deserializer.deserialize_map(ValueVisitor)
+    }
+}
+
+/// An asset quantity read through `deserialize_any`, since serde cannot
+/// deserialize `i128` from buffered (flattened or untagged) content.
+struct AssetQuantityRepr(AssetQuantity);
+
+impl<'de> Deserialize<'de> for AssetQuantityRepr {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct QuantityVisitor;
+
+        impl serde::de::Visitor<'_> for QuantityVisitor {
+            type Value = AssetQuantity;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.write_str("an asset quantity")
+            }
+
+            fn visit_i64<E>(self, v: i64) -> Result<AssetQuantity, E> {
+                Ok(v.into())
+            }
+
+            fn visit_u64<E>(self, v: u64) -> Result<AssetQuantity, E> {
+                Ok(v.into())
+            }
+
+            fn visit_i128<E>(self, v: i128) -> Result<AssetQuantity, E> {
+                Ok(v)
+            }
+
+            fn visit_u128<E: serde::de::Error>(self, v: u128) -> Result<AssetQuantity, E> {
+                AssetQuantity::try_from(v).map_err(E::custom)
+            }
+        }
+
+        deserializer.deserialize_any(QuantityVisitor).map(AssetQuantityRepr)
+    }
+}
+
+/// Deserialize [`Assets`], reading quantities through [`AssetQuantityRepr`].
+pub(crate) fn deserialize_assets<'de, D>(deserializer: D) -> Result<Assets, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let assets: HashMap<PolicyId, HashMap<AssetName, AssetQuantityRepr>> =
+        HashMap::deserialize(deserializer)?;
+    Ok(assets
+        .into_iter()
+        .map(|(policy, assets)| {
+            let assets = assets.into_iter().map(|(name, quantity)| (name, quantity.0));
+            (policy, assets.collect())
+        })
+        .collect())
+}
+
 /// ADA value container.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+///
+/// Serializes as the ada-only value `{ "ada": { "lovelace": n } }` Ogmios
+/// sends for deposits, fees and rewards. Deserializes from that form as well
+/// as from `{ "lovelace": n }`, the `ada` entry of a [`Value`].
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(from = "AdaValueRepr")]
 pub struct AdaValue {
     pub lovelace: Lovelace,
 }
 
+/// The `ada` entry of a value.
+#[derive(Serialize)]
+struct LovelaceEntry {
+    lovelace: Lovelace,
+}
+
+impl Serialize for AdaValue {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        #[derive(Serialize)]
+        struct AdaOnly {
+            ada: LovelaceEntry,
+        }
+
+        AdaOnly {
+            ada: LovelaceEntry {
+                lovelace: self.lovelace,
+            },
+        }
+        .serialize(serializer)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AdaValueRepr {
+    Lovelace { lovelace: Lovelace },
+    AdaOnly { ada: AdaValue },
+}
+
+impl From<AdaValueRepr> for AdaValue {
+    fn from(repr: AdaValueRepr) -> Self {
+        match repr {
+            AdaValueRepr::Lovelace { lovelace } => AdaValue { lovelace },
+            AdaValueRepr::AdaOnly { ada } => ada,
+        }
+    }
+}
+
 impl Value {
     /// Create an ADA-only value.
     pub fn ada_only(lovelace: Lovelace) -> Self {
@@ -174,10 +601,330 @@ impl Value {
             Value::WithAssets { ada, .. } => ada.lovelace,
         }
     }
+
+    /// Get the quantity of an asset, zero if the value does not hold it.
+    pub fn asset(&self, policy: &str, name: &str) -> AssetQuantity {
+        match self {
+            Value::AdaOnly { .. } => 0,
+            Value::WithAssets { assets, .. } => assets
+                .get(policy)
+                .and_then(|tokens| tokens.get(name))
+                .copied()
+                .unwrap_or(0),
+        }
+    }
+
+    /// Iterate over the policies of the native assets.
+    pub fn policies(&self) -> impl Iterator<Item = &PolicyId> {
+        let assets = match self {
+            Value::AdaOnly { .. } => None,
+            Value::WithAssets { assets, .. } => Some(assets),
+        };
+        assets.into_iter().flat_map(|assets| assets.keys())
+    }
+
+    /// Number of distinct native assets.
+    pub fn asset_count(&self) -> usize {
+        match self {
+            Value::AdaOnly { .. } => 0,
+            Value::WithAssets { assets, .. } => assets.values().map(HashMap::len).sum(),
+        }
+    }
+
+    /// Set the quantity of an asset.
+    ///
+    /// A zero quantity removes the asset, and the value becomes
+    /// [`Value::AdaOnly`] once no assets are left.
+    pub fn with_asset(
+        self,
+        policy: PolicyId,
+        name: impl Into<AssetName>,
+        quantity: AssetQuantity,
+    ) -> Self {
+        let (ada, mut assets) = match self {
+            Value::AdaOnly { ada } => (ada, Assets::new()),
+            Value::WithAssets { ada, assets } => (ada, assets),
+        };
+        let tokens = assets.entry(policy.clone()).or_default();
+        if quantity == 0 {
+            tokens.remove(&name.into());
+            if tokens.is_empty() {
+                assets.remove(&policy);
+            }
+        } else {
+            tokens.insert(name.into(), quantity);
+        }
+
+        if assets.is_empty() {
+            Value::AdaOnly { ada }
+        } else {
+            Value::WithAssets { ada, assets }
+        }
+    }
+
+    /// Whether the value holds no lovelace and no assets.
+    pub fn is_zero(&self) -> bool {
+        self.lovelace() == 0 && self.assets_iter().all(|(_, _, quantity)| quantity == 0)
+    }
+
+    /// Whether the value holds at least the lovelace and every asset of `other`.
+    pub fn contains(&self, other: &Value) -> bool {
+        self.lovelace() >= other.lovelace()
+            && other
+                .assets_iter()
+                .all(|(policy, name, quantity)| self.asset(policy.as_str(), name) >= quantity)
+    }
+
+    /// Add two values, returning `None` on overflow.
+    ///
+    /// Assets whose quantity adds up to zero are removed.
+    pub fn checked_add(&self, other: &Value) -> Option<Value> {
+        let lovelace = self.lovelace().checked_add(other.lovelace())?;
+        self.merge_assets(other, lovelace, AssetQuantity::checked_add)
+    }
+
+    /// Subtract `other`, returning `None` if the lovelace or any asset
+    /// quantity would become negative.
+    ///
+    /// Assets whose quantity drops to zero are removed.
+    pub fn checked_sub(&self, other: &Value) -> Option<Value> {
+        let lovelace = self.lovelace().checked_sub(other.lovelace())?;
+        self.merge_assets(other, lovelace, |a, b| a.checked_sub(b).filter(|q| *q >= 0))
+    }
+
+    /// Iterate over the native assets as `(policy, name, quantity)`.
+    pub fn assets_iter(&self) -> impl Iterator<Item = (&PolicyId, &AssetName, AssetQuantity)> {
+        let assets = match self {
+            Value::AdaOnly { .. } => None,
+            Value::WithAssets { assets, .. } => Some(assets),
+        };
+        assets.into_iter().flatten().flat_map(|(policy, tokens)| {
+            tokens
+                .iter()
+                .map(move |(name, quantity)| (policy, name, *quantity))
+        })
+    }
+
+    /// List the native assets as `(fingerprint, quantity)`, with their CIP-14
+    /// fingerprints (`asset1…`).
+    ///
+    /// Fails if a policy ID or asset name is not valid hex, which only happens
+    /// for values that were not received from Ogmios.
+    #[cfg(feature = "asset-fingerprint")]
+    pub fn asset_fingerprints(
+        &self,
+    ) -> Result<Vec<(String, AssetQuantity)>, crate::util::Bech32Error> {
+        self.assets_iter()
+            .map(|(policy, name, quantity)| {
+                let fingerprint = crate::util::asset_fingerprint(policy.as_str(), name)?;
+                Ok((fingerprint, quantity))
+            })
+            .collect()
+    }
+
+    /// Combine the assets of `self` and `other` with `op`, dropping zero
+    /// quantities and empty policies.
+    fn merge_assets(
+        &self,
+        other: &Value,
+        lovelace: Lovelace,
+        op: impl Fn(AssetQuantity, AssetQuantity) -> Option<AssetQuantity>,
+    ) -> Option<Value> {
+        let mut assets = match self {
+            Value::AdaOnly { .. } => Assets::new(),
+            Value::WithAssets { assets, .. } => assets.clone(),
+        };
+        for (policy, name, quantity) in other.assets_iter() {
+            let tokens = assets.entry(policy.clone()).or_default();
+            let entry = tokens.entry(name.clone()).or_insert(0);
+            *entry = op(*entry, quantity)?;
+        }
+        // Quantities only held by `self` go through `op` too, so that a
+        // subtraction never leaves a negative quantity behind.
+        for tokens in assets.values_mut() {
+            for quantity in tokens.values_mut() {
+                *quantity = op(*quantity, 0)?;
+            }
+            tokens.retain(|_, quantity| *quantity != 0);
+        }
+        assets.retain(|_, tokens| !tokens.is_empty());
+
+        let ada = AdaValue { lovelace };
+        Some(if assets.is_empty() {
+            Value::AdaOnly { ada }
+        } else {
+            Value::WithAssets { ada, assets }
+        })
+    }
+}
+
+/// Display details of a token, e.g. from a CIP-26 token registry.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TokenInfo {
+    /// Name shown instead of the asset name, such as a ticker.
+    pub ticker: Option<String>,
+    /// Number of decimals of quantities.
+    pub decimals: u8,
+}
+
+/// Options for [`Value::to_pretty_string`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PrettyValueOptions<'a> {
+    /// Show asset names that are printable UTF-8 as text rather than hex.
+    pub decode_asset_names: bool,
+    /// Show at most this many assets and elide the rest; `None` shows all.
+    pub max_assets: Option<usize>,
+    /// Tickers and decimals of known tokens.
+    pub registry: Option<&'a HashMap<Subject, TokenInfo>>,
+}
+
+impl Default for PrettyValueOptions<'_> {
+    /// Decoded asset names, at most 3 assets, no registry.
+    fn default() -> Self {
+        Self {
+            decode_asset_names: true,
+            max_assets: Some(3),
+            registry: None,
+        }
+    }
 }
 
-/// A Cardano address (Bech32 or Base58 encoded).
-pub type Address = String;
+impl Value {
+    /// Render the value for humans, e.g.
+    /// `12.345678 ADA + 2 assets (b0d07d45…38a7: NFT ×1, 1e349c9b…f209: 4e4654 ×5)`.
+    ///
+    /// Assets are listed by policy ID, then asset name; policy IDs are
+    /// abbreviated. With a registry, known tokens show their ticker and a
+    /// quantity with their decimals.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{PolicyId, PrettyValueOptions, Subject, TokenInfo, Value};
+    /// use std::collections::HashMap;
+    ///
+    /// let policy: PolicyId = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7"
+    ///     .parse()
+    ///     .unwrap();
+    /// let value = Value::ada_only(2_500_000).with_asset(policy.clone(), "4e4654", 1_500);
+    /// assert_eq!(value.to_string(), "2.500000 ADA + 1 asset (b0d07d45…38a7: NFT ×1500)");
+    ///
+    /// let registry = HashMap::from([(
+    ///     Subject::new(policy, "4e4654"),
+    ///     TokenInfo { ticker: Some("TOKEN".to_string()), decimals: 2 },
+    /// )]);
+    /// let options = PrettyValueOptions { registry: Some(&registry), ..Default::default() };
+    /// assert_eq!(
+    ///     value.to_pretty_string(&options),
+    ///     "2.500000 ADA + 1 asset (b0d07d45…38a7: TOKEN ×15.00)"
+    /// );
+    /// ```
+    pub fn to_pretty_string(&self, options: &PrettyValueOptions<'_>) -> String {
+        let mut pretty = format!("{} ADA", crate::util::lovelace_to_ada(self.lovelace()));
+        let mut assets: Vec<_> = self.assets_iter().collect();
+        if assets.is_empty() {
+            return pretty;
+        }
+        assets.sort_unstable_by(|a, b| (a.0, a.1).cmp(&(b.0, b.1)));
+
+        let count = assets.len();
+        let shown = options.max_assets.unwrap_or(count).min(count);
+        let plural = if count == 1 { "" } else { "s" };
+        pretty.push_str(&format!(" + {count} asset{plural} ("));
+        for (i, (policy, name, quantity)) in assets.into_iter().take(shown).enumerate() {
+            if i > 0 {
+                pretty.push_str(", ");
+            }
+            let info = options
+                .registry
+                .and_then(|registry| registry.get(&Subject::new(policy.clone(), name.as_str())));
+            let label = match info.and_then(|info| info.ticker.as_deref()) {
+                Some(ticker) => ticker.to_string(),
+                None => pretty_asset_name(name, options.decode_asset_names),
+            };
+            let quantity = format_token_quantity(quantity, info.map_or(0, |info| info.decimals));
+            let policy = policy.as_str();
+            match policy.get(..8).zip(policy.get(policy.len().saturating_sub(4)..)) {
+                Some((start, end)) if policy.len() > 12 => {
+                    pretty.push_str(&format!("{start}…{end}"));
+                }
+                _ => pretty.push_str(policy),
+            }
+            if label.is_empty() {
+                pretty.push_str(&format!(" ×{quantity}"));
+            } else {
+                pretty.push_str(&format!(": {label} ×{quantity}"));
+            }
+        }
+        if shown < count {
+            if shown > 0 {
+                pretty.push_str(", ");
+            }
+            pretty.push_str(&format!("… {} more", count - shown));
+        }
+        pretty.push(')');
+        pretty
+    }
+}
+
+/// Formats as [`Value::to_pretty_string`] with the default options.
+impl std::fmt::Display for Value {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_pretty_string(&PrettyValueOptions::default()))
+    }
+}
+
+/// Asset name as text when it is printable UTF-8 and decoding is asked for,
+/// as hex otherwise.
+fn pretty_asset_name(name: &str, decode: bool) -> String {
+    if decode
+        && let Ok(bytes) = crate::util::hex_decode(name)
+        && let Ok(text) = String::from_utf8(bytes)
+        && !text.is_empty()
+        && text.chars().all(|c| c == ' ' || !(c.is_control() || c.is_whitespace()))
+    {
+        return text;
+    }
+    name.to_string()
+}
+
+/// Token quantity with a fixed number of decimals.
+fn format_token_quantity(quantity: AssetQuantity, decimals: u8) -> String {
+    if decimals == 0 {
+        return quantity.to_string();
+    }
+    let sign = if quantity < 0 { "-" } else { "" };
+    let digits = format!("{:0>width$}", quantity.unsigned_abs(), width = decimals as usize + 1);
+    let (whole, fraction) = digits.split_at(digits.len() - decimals as usize);
+    format!("{sign}{whole}.{fraction}")
+}
+
+/// Sum values.
+///
+/// # Panics
+///
+/// Panics on overflow, like summing integers. Fold with
+/// [`Value::checked_add`] to handle it.
+impl<'a> Sum<&'a Value> for Value {
+    fn sum<I: Iterator<Item = &'a Value>>(iter: I) -> Self {
+        iter.fold(Value::ada_only(0), |total, value| {
+            total.checked_add(value).expect("overflow when summing values")
+        })
+    }
+}
+
+/// Sum values.
+///
+/// # Panics
+///
+/// Panics on overflow, like summing integers.
+impl Sum for Value {
+    fn sum<I: Iterator<Item = Value>>(iter: I) -> Self {
+        iter.fold(Value::ada_only(0), |total, value| {
+            total.checked_add(&value).expect("overflow when summing values")
+        })
+    }
+}
 
 /// A stake address (Bech32 encoded, starts with "stake").
 pub type StakeAddress = String;
@@ -186,16 +933,40 @@ pub type StakeAddress = String;
 pub type RewardAccount = String;
 
 /// Nonce for protocol parameters.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Sent as the string `"neutral"` or as a hex-encoded Blake2b-256 digest.
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Nonce {
     /// Neutral nonce.
     Neutral,
-    /// Specific nonce value.
-    #[serde(untagged)]
+    /// Specific nonce value (64 hex characters).
     Value(String),
 }
 
+impl Serialize for Nonce {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Nonce::Neutral => serializer.serialize_str("neutral"),
+            Nonce::Value(digest) => serializer.serialize_str(digest),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Nonce {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let nonce = String::deserialize(deserializer)?;
+        if nonce == "neutral" {
+            Ok(Nonce::Neutral)
+        } else if nonce.len() == 64 && nonce.bytes().all(|b| b.is_ascii_hexdigit()) {
+            Ok(Nonce::Value(nonce))
+        } else {
+            Err(serde::de::Error::custom(format!(
+                "invalid nonce {nonce:?}: expected \"neutral\" or 64 hex characters"
+            )))
+        }
+    }
+}
+
 /// A verification key (hex-encoded).
 pub type VerificationKey = String;
 
@@ -211,34 +982,9 @@ pub type VrfVerificationKey = String;
 /// A signature (hex-encoded).
 pub type Signature = String;
 
-/// Metadata labels type.
-pub type MetadataLabels = HashMap<String, Metadatum>;
-
-/// Metadata value that can be various types.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum Metadatum {
-    /// Integer value.
-    Int(i128),
-    /// Byte string (hex-encoded).
-    Bytes(String),
-    /// Text string.
-    String(String),
-    /// List of metadatums.
-    List(Vec<Metadatum>),
-    /// Map of metadatums.
-    Map(Vec<MetadatumMapEntry>),
-}
-
-/// A key-value entry in a metadatum map.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-pub struct MetadatumMapEntry {
-    pub k: Metadatum,
-    pub v: Metadatum,
-}
-
 /// Anchor for governance actions.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Anchor {
     pub url: String,
     #[serde(rename = "hash")]
@@ -246,21 +992,33 @@ pub struct Anchor {
 }
 
 /// Credential origin - either from a key or a script.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum CredentialOrigin {
+    #[serde(rename = "verificationKey", alias = "key")]
     Key,
     Script,
 }
 
 /// A stake credential.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
+///
+/// Ogmios sends the hash as `credential`, next to a `from` field telling a
+/// key hash from a script hash.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(tag = "from")]
 pub enum StakeCredential {
     /// Key-based credential.
-    Key { key: DigestBlake2b224 },
+    #[serde(rename = "verificationKey", alias = "key")]
+    Key {
+        #[serde(rename = "credential")]
+        key: DigestBlake2b224,
+    },
     /// Script-based credential.
-    Script { script: ScriptHash },
+    #[serde(rename = "script")]
+    Script {
+        #[serde(rename = "credential")]
+        script: ScriptHash,
+    },
 }
 
 /// A payment credential.
@@ -295,3 +1053,414 @@ impl<T> From<Option<T>> for Nullable<T> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn value(lovelace: Lovelace, assets: &[(&str, &str, AssetQuantity)]) -> Value {
+        let mut map = Assets::new();
+        for (policy, name, quantity) in assets {
+            map.entry(PolicyId::filled(policy))
+                .or_default()
+                .insert(name.to_string(), *quantity);
+        }
+        Value::WithAssets {
+            ada: AdaValue { lovelace },
+            assets: map,
+        }
+    }
+
+    fn has_zero_quantity(value: &Value) -> bool {
+        match value {
+            Value::AdaOnly { .. } => false,
+            Value::WithAssets { assets, .. } => {
+                assets.is_empty()
+                    || assets
+                        .values()
+                        .any(|tokens| tokens.is_empty() || tokens.values().any(|q| *q == 0))
+            }
+        }
+    }
+
+    fn arb_value() -> impl Strategy<Value = Value> {
+        let asset = (
+            prop::sample::select(vec!["aa", "bb", "cc"]),
+            prop::sample::select(vec!["", "01", "02"]),
+            0..1_000_000_000_000i128,
+        );
+        (0..u64::MAX / 4, prop::collection::vec(asset, 0..6)).prop_map(|(lovelace, assets)| {
+            // Normalize, e.g. to AdaOnly when there are no assets.
+            value(lovelace, &assets).checked_add(&Value::ada_only(0)).unwrap()
+        })
+    }
+
+    #[test]
+    fn test_point_ordering() {
+        let mut points = vec![
+            Point::at(10, DigestBlake2b256::filled("bb")),
+            Point::at(2, DigestBlake2b256::filled("ff")),
+            Point::origin(),
+            Point::at(10, DigestBlake2b256::filled("aa")),
+        ];
+        points.sort();
+        assert_eq!(
+            points,
+            vec![
+                Point::origin(),
+                Point::at(2, DigestBlake2b256::filled("ff")),
+                Point::at(10, DigestBlake2b256::filled("aa")),
+                Point::at(10, DigestBlake2b256::filled("bb")),
+            ]
+        );
+        assert!(Point::origin() < Point::at(0, DigestBlake2b256::filled("00")));
+        assert_eq!(points[2].id(), Some("aa".repeat(32).as_str()));
+        assert_eq!(Point::origin().id(), None);
+    }
+
+    #[test]
+    fn test_tip_accessors() {
+        let tip = Tip::Tip {
+            slot: 5,
+            id: DigestBlake2b256::filled("ab"),
+            height: 3,
+        };
+        assert_eq!((tip.slot(), tip.height()), (Some(5), Some(3)));
+        assert_eq!(Point::from(&tip), Point::at(5, DigestBlake2b256::filled("ab")));
+        assert_eq!(Point::from(tip), Point::at(5, DigestBlake2b256::filled("ab")));
+
+        let origin = Tip::Origin("origin".to_string());
+        assert_eq!(origin.height(), None);
+        assert_eq!(Point::from(origin), Point::origin());
+    }
+
+    #[test]
+    fn test_distance_at_origin() {
+        let origin = Tip::Origin("origin".to_string());
+        assert!(origin.is_origin() && origin.to_point().is_origin());
+        assert_eq!(Point::origin().distance_to(&origin), None);
+        assert_eq!(Point::at(0, DigestBlake2b256::filled("ab")).distance_to(&origin), None);
+
+        let genesis = Tip::Tip {
+            slot: 0,
+            id: DigestBlake2b256::filled("ab"),
+            height: 0,
+        };
+        assert_eq!(Point::origin().distance_to(&genesis), Some(0));
+        assert_eq!(Point::at(0, DigestBlake2b256::filled("ab")).distance_to(&genesis), Some(0));
+        assert_eq!(Point::at(1, DigestBlake2b256::filled("cd")).distance_to(&genesis), None);
+    }
+
+    #[test]
+    fn test_value_arithmetic() {
+        let a = value(5, &[("aa", "01", 3)]);
+        let b = value(2, &[("aa", "01", 3), ("bb", "", 1)]);
+
+        let sum = a.checked_add(&b).unwrap();
+        assert_eq!(sum.lovelace(), 7);
+        assert_eq!(sum.asset(&"aa".repeat(28), "01"), 6);
+        assert!(sum.contains(&a) && sum.contains(&b));
+        assert!(!a.contains(&b));
+
+        assert_eq!(sum.checked_sub(&b).unwrap(), a);
+        assert_eq!(a.checked_sub(&b), None);
+        assert_eq!(a.checked_sub(&Value::ada_only(6)), None);
+
+        // Spending all assets goes back to an ADA-only value.
+        assert_eq!(a.checked_sub(&value(1, &[("aa", "01", 3)])), Some(Value::ada_only(4)));
+        assert!(a.checked_sub(&a).unwrap().is_zero());
+        assert!(value(0, &[("aa", "01", 0)]).is_zero());
+
+        assert_eq!(Value::ada_only(u64::MAX).checked_add(&Value::ada_only(1)), None);
+        assert_eq!([a.clone(), b.clone()].iter().sum::<Value>(), sum);
+        assert_eq!(vec![a, b].into_iter().sum::<Value>(), sum);
+    }
+
+    #[test]
+    fn test_value_asset_helpers() {
+        let policy = PolicyId::filled("aa");
+        let value = Value::ada_only(2)
+            .with_asset(policy.clone(), "01", 5)
+            .with_asset(policy.clone(), "02", 7)
+            .with_asset(PolicyId::filled("bb"), "", 1);
+        assert_eq!(value.asset(policy.as_str(), "01"), 5);
+        assert_eq!(value.asset(policy.as_str(), "03"), 0);
+        assert_eq!(value.asset_count(), 3);
+        assert_eq!(value.policies().count(), 2);
+
+        let mut assets: Vec<_> = value.assets_iter().collect();
+        assets.sort();
+        assert_eq!(assets[0], (&policy, &"01".to_string(), 5));
+
+        let value = value
+            .with_asset(policy.clone(), "01", 0)
+            .with_asset(policy.clone(), "02", 0)
+            .with_asset(PolicyId::filled("bb"), "", 0);
+        assert_eq!(value, Value::ada_only(2));
+        assert_eq!(value.asset(policy.as_str(), "01"), 0);
+        assert_eq!(value.assets_iter().count(), 0);
+    }
+
+    /// Value of a mainnet UTXO holding tokens under several policies, in the
+    /// shape Ogmios sends it.
+    fn multi_asset_value() -> serde_json::Value {
+        serde_json::json!({
+            "ada": { "lovelace": 1_344_798 },
+            "0029cb7c88c7567b63d1a512c0ed626aa169688ec980730c0473b913": {
+                "6c702083": 1,
+                "6c70208b": 1
+            },
+            "1d7f33bd23d85e1a25d87d86fac4f199c3197a2f7afeb662a0f34e1e": {
+                "776f726c646d6f62696c65746f6b656e": 1_000_000
+            },
+            "279c909f348e533da5808898f87f9a14bb2c3dfbbacccd631d927a3f": {
+                "534e454b": 42_000_000
+            },
+            "a0028f350aaabe0545fdcb56b039bfb08e4bb4d8c4d7c3c7d481c235": {
+                "484f534b59": 1_234_567_890_123u64
+            },
+            "f0ff48bbb7bbe9d59a40f1ce90e9e9d0ff5002ec48f232b49ca0fb9a": {
+                "": 18_446_744_073_709_551_615u64
+            }
+        })
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        let json = multi_asset_value();
+        let value: Value = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(value.lovelace(), 1_344_798);
+        assert_eq!(value.policies().count(), 5);
+        assert_eq!(value.asset_count(), 6);
+        assert_eq!(
+            value.asset("279c909f348e533da5808898f87f9a14bb2c3dfbbacccd631d927a3f", "534e454b"),
+            42_000_000
+        );
+        assert_eq!(
+            value.asset("f0ff48bbb7bbe9d59a40f1ce90e9e9d0ff5002ec48f232b49ca0fb9a", ""),
+            u64::MAX as i128
+        );
+        assert_eq!(serde_json::to_value(&value).unwrap(), json);
+
+        // Entries are written in order, with `ada` first.
+        let text = serde_json::to_string(&value).unwrap();
+        assert!(text.starts_with(r#"{"ada":{"lovelace":1344798},"0029cb7c"#), "{text}");
+        assert!(text.find("6c702083").unwrap() < text.find("6c70208b").unwrap());
+    }
+
+    #[test]
+    fn test_value_normalizes_empty_assets() {
+        let ada_only = serde_json::json!({ "ada": { "lovelace": 2 } });
+        assert_eq!(
+            serde_json::from_value::<Value>(ada_only.clone()).unwrap(),
+            Value::ada_only(2)
+        );
+        let empty_policy = serde_json::json!({
+            "ada": { "lovelace": 2 },
+            "0029cb7c88c7567b63d1a512c0ed626aa169688ec980730c0473b913": {}
+        });
+        assert_eq!(serde_json::from_value::<Value>(empty_policy).unwrap(), Value::ada_only(2));
+
+        let no_assets = Value::WithAssets {
+            ada: AdaValue { lovelace: 2 },
+            assets: Assets::new(),
+        };
+        assert_eq!(serde_json::to_value(&no_assets).unwrap(), ada_only);
+    }
+
+    #[test]
+    fn test_value_rejects_malformed_entries() {
+        let policy = "0029cb7c88c7567b63d1a512c0ed626aa169688ec980730c0473b913";
+        for (json, expected) in [
+            (serde_json::json!({ policy: { "01": 1 } }), "missing field `ada`"),
+            (
+                serde_json::json!({ "ada": { "lovelace": 1 }, "lovelace": 1 }),
+                "invalid value entry \"lovelace\"",
+            ),
+            (
+                serde_json::json!({ "ada": { "lovelace": 1 }, policy: { "0g": 1 } }),
+                "invalid asset name \"0g\" under policy",
+            ),
+            (
+                serde_json::json!({ "ada": { "lovelace": 1 }, policy: { "01".repeat(33): 1 } }),
+                "invalid asset name",
+            ),
+            (
+                serde_json::json!({ "ada": { "lovelace": 1 }, policy: { "01": 1.5 } }),
+                "expected an asset quantity",
+            ),
+            (serde_json::json!([1]), "expected a value with an `ada` entry"),
+        ] {
+            let error = serde_json::from_value::<Value>(json).unwrap_err().to_string();
+            assert!(error.contains(expected), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_ratio_serialization() {
+        let ratio: Ratio = serde_json::from_str("\"3/10\"").unwrap();
+        assert_eq!(ratio, Ratio::new(3, 10));
+        assert_eq!(ratio.to_string(), "3/10");
+        assert_eq!(serde_json::to_string(&ratio).unwrap(), "\"3/10\"");
+
+        let object = r#"{"numerator":1,"denominator":20}"#;
+        assert_eq!(serde_json::from_str::<Ratio>(object).unwrap(), Ratio::new(1, 20));
+        assert_eq!("1/20".parse::<Ratio>().unwrap(), Ratio::new(1, 20));
+        assert!("3-10".parse::<Ratio>().is_err());
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_utc_time_formats() {
+        let parse = |time: &str| serde_json::from_value::<UtcTime>(serde_json::json!(time)).unwrap();
+
+        let fractional = parse("2024-06-25T10:47:42.713582433Z");
+        assert_eq!(fractional.timestamp(), 1_719_312_462);
+        assert_eq!(fractional.timestamp_subsec_nanos(), 713_582_433);
+
+        let whole = parse("2022-06-01T00:00:00Z");
+        assert_eq!(parse("2022-06-01T00:00:00+00:00"), whole);
+        assert_eq!(parse("2022-06-01T02:00:00+02:00"), whole);
+        assert_eq!(
+            serde_json::to_value(whole).unwrap(),
+            serde_json::json!("2022-06-01T00:00:00Z")
+        );
+
+        assert_eq!(
+            relative_time_to_utc(whole, 86_400.5),
+            parse("2022-06-02T00:00:00.500Z")
+        );
+        assert!(serde_json::from_value::<UtcTime>(serde_json::json!("2022-06-01")).is_err());
+    }
+
+    #[cfg(feature = "asset-fingerprint")]
+    #[test]
+    fn test_asset_fingerprints() {
+        let policy: PolicyId = "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373"
+            .parse()
+            .unwrap();
+        let value = Value::ada_only(2).with_asset(policy.clone(), "504154415445", 7);
+        assert_eq!(
+            value.asset_fingerprints().unwrap(),
+            [("asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92".to_string(), 7)]
+        );
+        assert!(Value::ada_only(2).asset_fingerprints().unwrap().is_empty());
+        assert!(value.with_asset(policy, "PATATE", 1).asset_fingerprints().is_err());
+    }
+
+    #[test]
+    fn test_value_display() {
+        assert_eq!(Value::ada_only(12_345_678).to_string(), "12.345678 ADA");
+
+        let nft: PolicyId = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7"
+            .parse()
+            .unwrap();
+        let other: PolicyId = "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209"
+            .parse()
+            .unwrap();
+        let value = Value::ada_only(12_345_678)
+            .with_asset(nft.clone(), "4e4654", 1)
+            .with_asset(other.clone(), "", 5)
+            .with_asset(other.clone(), "00ff", 2)
+            .with_asset(other.clone(), "6869", -3);
+        assert_eq!(
+            value.to_string(),
+            "12.345678 ADA + 4 assets \
+             (1e349c9b…f209 ×5, 1e349c9b…f209: 00ff ×2, 1e349c9b…f209: hi ×-3, … 1 more)"
+        );
+
+        let registry = HashMap::from([
+            (
+                Subject::new(other, "6869"),
+                TokenInfo {
+                    ticker: Some("HI".to_string()),
+                    decimals: 4,
+                },
+            ),
+            (
+                Subject::new(nft, "4e4654"),
+                TokenInfo {
+                    ticker: None,
+                    decimals: 3,
+                },
+            ),
+        ]);
+        let options = PrettyValueOptions {
+            decode_asset_names: false,
+            max_assets: None,
+            registry: Some(&registry),
+        };
+        assert_eq!(
+            value.to_pretty_string(&options),
+            "12.345678 ADA + 4 assets (1e349c9b…f209 ×5, 1e349c9b…f209: 00ff ×2, \
+             1e349c9b…f209: HI ×-0.0003, b0d07d45…38a7: 4e4654 ×0.001)"
+        );
+
+        let options = PrettyValueOptions {
+            max_assets: Some(0),
+            ..PrettyValueOptions::default()
+        };
+        assert_eq!(value.to_pretty_string(&options), "12.345678 ADA + 4 assets (… 4 more)");
+    }
+
+    #[test]
+    fn test_pretty_asset_name() {
+        assert_eq!(pretty_asset_name("4e4654", true), "NFT");
+        assert_eq!(pretty_asset_name("4e4654", false), "4e4654");
+        assert_eq!(pretty_asset_name("f09f988a", true), "😊");
+        assert_eq!(pretty_asset_name("48692049", true), "Hi I");
+        assert_eq!(pretty_asset_name("4869000a", true), "4869000a");
+        assert_eq!(pretty_asset_name("c328", true), "c328");
+        assert_eq!(pretty_asset_name("", true), "");
+    }
+
+    #[test]
+    fn test_format_token_quantity() {
+        assert_eq!(format_token_quantity(1_500, 0), "1500");
+        assert_eq!(format_token_quantity(1_500, 2), "15.00");
+        assert_eq!(format_token_quantity(5, 3), "0.005");
+        assert_eq!(format_token_quantity(-5, 1), "-0.5");
+        assert_eq!(
+            format_token_quantity(AssetQuantity::MIN, 2),
+            "-1701411834604692317316873037158841057.28"
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_add_then_sub_round_trips(a in arb_value(), b in arb_value()) {
+            let sum = a.checked_add(&b).unwrap();
+            prop_assert!(!has_zero_quantity(&sum));
+            prop_assert!(sum.contains(&a) && sum.contains(&b));
+            prop_assert_eq!(sum.checked_sub(&b), Some(a.clone()));
+            prop_assert_eq!(sum.checked_sub(&a), Some(b.clone()));
+        }
+
+        #[test]
+        fn test_sub_never_goes_negative(a in arb_value(), b in arb_value()) {
+            match a.checked_sub(&b) {
+                Some(difference) => {
+                    prop_assert!(a.contains(&b));
+                    prop_assert!(!has_zero_quantity(&difference));
+                    prop_assert_eq!(difference.checked_add(&b), Some(a.clone()));
+                }
+                None => prop_assert!(!a.contains(&b)),
+            }
+        }
+
+        #[test]
+        fn test_value_json_round_trip(value in arb_value()) {
+            let json = serde_json::to_value(&value).unwrap();
+            prop_assert_eq!(serde_json::from_value::<Value>(json).unwrap(), value);
+        }
+
+        #[test]
+        fn test_sum_matches_fold(values in prop::collection::vec(arb_value(), 0..5)) {
+            let folded = values
+                .iter()
+                .try_fold(Value::ada_only(0), |total, value| total.checked_add(value));
+            prop_assert_eq!(Some(values.iter().sum::<Value>()), folded);
+        }
+    }
+}
@@ -1,13 +1,20 @@
 //! Protocol parameter types for Cardano.
 
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use super::era::Era;
+use super::digest::DigestBlake2b224;
 use super::primitives::*;
+use super::scripts::Language;
 use super::transaction::ExUnits;
 
 /// Protocol parameters for Cardano.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ProtocolParameters {
     /// Minimum fee coefficient (per byte).
     pub min_fee_coefficient: u64,
@@ -22,6 +29,9 @@ pub struct ProtocolParameters {
     pub max_block_header_size: BlockSize,
     /// Maximum transaction size in bytes.
     pub max_transaction_size: BlockSize,
+    /// Maximum total size of reference scripts in a transaction.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_reference_scripts_size: Option<BlockSize>,
     /// Stake key deposit.
     pub stake_credential_deposit: AdaValue,
     /// Pool registration deposit.
@@ -40,6 +50,9 @@ pub struct ProtocolParameters {
     pub version: ProtocolVersion,
     /// Minimum stake pool cost.
     pub min_stake_pool_cost: AdaValue,
+    /// Share of blocks produced by genesis delegates (d), in Shelley genesis.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub federated_block_production_ratio: Option<Ratio>,
     /// Extra entropy (deprecated, always neutral).
     #[serde(default)]
     pub extra_entropy: Option<Nonce>,
@@ -97,6 +110,9 @@ pub struct ProtocolParameters {
 }
 
 /// Minimum fee for reference scripts configuration.
+///
+/// Fields this crate does not know yet are kept in
+/// [`extra`](MinFeeReferenceScripts::extra) so they survive a round trip.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct MinFeeReferenceScripts {
@@ -106,47 +122,224 @@ pub struct MinFeeReferenceScripts {
     pub range: u64,
     /// Multiplier.
     pub multiplier: f64,
+    /// Fields of later revisions of the fee calculation.
+    #[serde(flatten)]
+    pub extra: HashMap<String, serde_json::Value>,
 }
 
 /// Block size specification.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BlockSize {
     /// Size in bytes.
     pub bytes: NumberOfBytes,
 }
 
+impl ProtocolParameters {
+    /// Get the era the protocol version belongs to.
+    ///
+    /// See [`ProtocolVersion::era`].
+    pub fn era_from_version(&self) -> Era {
+        self.version.era()
+    }
+}
+
+/// A string that is not a protocol version.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid protocol version {value:?}: expected major.minor or major.minor.patch")]
+pub struct InvalidProtocolVersion {
+    /// Rejected value.
+    pub value: String,
+}
+
 /// Protocol version.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Versions compare by major, then minor, then patch version, a missing
+/// patch version counting as 0.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::{Era, ProtocolVersion};
+///
+/// let version: ProtocolVersion = "9.1".parse().unwrap();
+/// assert!(version >= ProtocolVersion::CONWAY);
+/// assert_eq!(version.era(), Era::Conway);
+/// assert_eq!(version.to_string(), "9.1");
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ProtocolVersion {
     /// Major version.
     pub major: u32,
     /// Minor version.
     pub minor: u32,
     /// Patch version (optional).
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub patch: Option<u32>,
 }
 
+impl ProtocolVersion {
+    /// First Shelley version.
+    pub const SHELLEY: Self = Self::new(2, 0);
+    /// First Allegra version.
+    pub const ALLEGRA: Self = Self::new(3, 0);
+    /// First Mary version.
+    pub const MARY: Self = Self::new(4, 0);
+    /// First Alonzo version.
+    pub const ALONZO: Self = Self::new(5, 0);
+    /// First Babbage version, from the Vasil hard fork.
+    pub const BABBAGE: Self = Self::new(7, 0);
+    /// Valentine intra-era hard fork, adding SECP256k1 builtins.
+    pub const VALENTINE: Self = Self::new(8, 0);
+    /// First Conway version, from the Chang hard fork, with governance
+    /// bootstrapping.
+    pub const CONWAY: Self = Self::new(9, 0);
+    /// Plomin intra-era hard fork, with full Conway governance.
+    pub const PLOMIN: Self = Self::new(10, 0);
+
+    /// Create a version without a patch version.
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch: None,
+        }
+    }
+
+    /// Get the era this version belongs to.
+    ///
+    /// Versions past the last known one map to the latest era.
+    pub fn era(&self) -> Era {
+        match self.major {
+            0 | 1 => Era::Byron,
+            2 => Era::Shelley,
+            3 => Era::Allegra,
+            4 => Era::Mary,
+            5 | 6 => Era::Alonzo,
+            7 | 8 => Era::Babbage,
+            _ => Era::Conway,
+        }
+    }
+
+    fn key(&self) -> (u32, u32, u32) {
+        (self.major, self.minor, self.patch.unwrap_or(0))
+    }
+}
+
+impl PartialEq for ProtocolVersion {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for ProtocolVersion {}
+
+impl Hash for ProtocolVersion {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
+impl PartialOrd for ProtocolVersion {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ProtocolVersion {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+impl std::fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(patch) = self.patch {
+            write!(f, ".{patch}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ProtocolVersion {
+    type Err = InvalidProtocolVersion;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || InvalidProtocolVersion {
+            value: s.to_string(),
+        };
+        let mut parts = s.split('.').map(|part| part.parse::<u32>().map_err(|_| invalid()));
+        let major = parts.next().ok_or_else(invalid)??;
+        let minor = parts.next().ok_or_else(invalid)??;
+        let patch = parts.next().transpose()?;
+        if parts.next().is_some() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            major,
+            minor,
+            patch,
+        })
+    }
+}
+
 /// Cost models for Plutus scripts.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Cost models of language versions this crate does not know yet are kept in
+/// [`extra`](CostModels::extra), keyed by their name, so they survive a round
+/// trip.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CostModels {
     /// Plutus V1 cost model.
-    #[serde(default, rename = "plutus:v1")]
+    #[serde(default, rename = "plutus:v1", skip_serializing_if = "Option::is_none")]
     pub plutus_v1: Option<Vec<i64>>,
     /// Plutus V2 cost model.
-    #[serde(default, rename = "plutus:v2")]
+    #[serde(default, rename = "plutus:v2", skip_serializing_if = "Option::is_none")]
     pub plutus_v2: Option<Vec<i64>>,
     /// Plutus V3 cost model.
-    #[serde(default, rename = "plutus:v3")]
+    #[serde(default, rename = "plutus:v3", skip_serializing_if = "Option::is_none")]
     pub plutus_v3: Option<Vec<i64>>,
+    /// Cost models of other language versions, e.g. `plutus:v4`.
+    #[serde(flatten)]
+    pub extra: HashMap<String, Vec<i64>>,
+}
+
+impl CostModels {
+    /// Plutus V1 cost model.
+    pub fn v1(&self) -> Option<&[i64]> {
+        self.plutus_v1.as_deref()
+    }
+
+    /// Plutus V2 cost model.
+    pub fn v2(&self) -> Option<&[i64]> {
+        self.plutus_v2.as_deref()
+    }
+
+    /// Plutus V3 cost model.
+    pub fn v3(&self) -> Option<&[i64]> {
+        self.plutus_v3.as_deref()
+    }
+
+    /// Cost model of a language version.
+    pub fn get(&self, language: &Language) -> Option<&[i64]> {
+        match language {
+            Language::PlutusV1 => self.v1(),
+            Language::PlutusV2 => self.v2(),
+            Language::PlutusV3 => self.v3(),
+            Language::Other(name) => self.extra.get(name).map(Vec::as_slice),
+        }
+    }
 }
 
 /// Script execution prices.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ScriptExecutionPrices {
     /// Memory price (lovelace per unit).
     pub memory: Ratio,
@@ -157,6 +350,7 @@ pub struct ScriptExecutionPrices {
 /// Stake pool voting thresholds (Conway governance).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct StakePoolVotingThresholds {
     /// No confidence threshold.
     pub no_confidence: Ratio,
@@ -172,6 +366,7 @@ pub struct StakePoolVotingThresholds {
 /// Constitutional committee thresholds.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ConstitutionalCommitteeThresholds {
     /// Default threshold.
     pub default: Ratio,
@@ -182,6 +377,7 @@ pub struct ConstitutionalCommitteeThresholds {
 /// Protocol parameters update thresholds.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ProtocolParametersUpdateThresholds {
     /// Security threshold.
     pub security: Ratio,
@@ -190,6 +386,7 @@ pub struct ProtocolParametersUpdateThresholds {
 /// DRep voting thresholds (Conway governance).
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DelegateRepresentativeVotingThresholds {
     /// No confidence threshold.
     pub no_confidence: Ratio,
@@ -208,6 +405,7 @@ pub struct DelegateRepresentativeVotingThresholds {
 /// DRep protocol parameters update thresholds.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct DRepProtocolParametersUpdateThresholds {
     /// Network threshold.
     pub network: Ratio,
@@ -225,47 +423,305 @@ pub type ProposedProtocolParameters = HashMap<DigestBlake2b224, PartialProtocolP
 /// Partial protocol parameters for updates.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct PartialProtocolParameters {
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_fee_coefficient: Option<u64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_fee_constant: Option<AdaValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_fee_reference_scripts: Option<MinFeeReferenceScripts>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_block_body_size: Option<BlockSize>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_block_header_size: Option<BlockSize>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_transaction_size: Option<BlockSize>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_reference_scripts_size: Option<BlockSize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stake_credential_deposit: Option<AdaValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stake_pool_deposit: Option<AdaValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stake_pool_retirement_epoch_bound: Option<u64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub desired_number_of_stake_pools: Option<u64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub stake_pool_pledge_influence: Option<Ratio>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub monetary_expansion: Option<Ratio>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub treasury_expansion: Option<Ratio>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub version: Option<ProtocolVersion>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub min_stake_pool_cost: Option<AdaValue>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub federated_block_production_ratio: Option<Ratio>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_entropy: Option<Nonce>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_utxo_deposit_coefficient: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_utxo_deposit_constant: Option<AdaValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub plutus_cost_models: Option<CostModels>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub script_execution_prices: Option<ScriptExecutionPrices>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_execution_units_per_transaction: Option<ExUnits>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_execution_units_per_block: Option<ExUnits>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_collateral_inputs: Option<u64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub collateral_percentage: Option<u64>,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_value_size: Option<BlockSize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate_representative_deposit: Option<AdaValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate_representative_max_idle_time: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub governance_action_deposit: Option<AdaValue>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub governance_action_lifetime: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constitutional_committee_min_size: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub constitutional_committee_max_term_length: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stake_pool_voting_thresholds: Option<StakePoolVotingThresholds>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate_representative_voting_thresholds: Option<DelegateRepresentativeVotingThresholds>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    /// `queryLedgerState/protocolParameters` result from a preprod node in
+    /// the Conway era (cost models shortened).
+    fn protocol_parameters() -> serde_json::Value {
+        json!({
+            "minFeeCoefficient": 44,
+            "minFeeConstant": { "ada": { "lovelace": 155381 } },
+            "minFeeReferenceScripts": { "range": 25600, "base": 15.0, "multiplier": 1.2 },
+            "maxBlockBodySize": { "bytes": 90112 },
+            "maxBlockHeaderSize": { "bytes": 1100 },
+            "maxTransactionSize": { "bytes": 16384 },
+            "maxReferenceScriptsSize": { "bytes": 204800 },
+            "stakeCredentialDeposit": { "ada": { "lovelace": 2000000 } },
+            "stakePoolDeposit": { "ada": { "lovelace": 500000000 } },
+            "stakePoolRetirementEpochBound": 18,
+            "desiredNumberOfStakePools": 500,
+            "stakePoolPledgeInfluence": "3/10",
+            "monetaryExpansion": "3/1000",
+            "treasuryExpansion": "1/5",
+            "minStakePoolCost": { "ada": { "lovelace": 170000000 } },
+            "minUtxoDepositConstant": { "ada": { "lovelace": 0 } },
+            "minUtxoDepositCoefficient": 4310,
+            "plutusCostModels": {
+                "plutus:v1": [100788, 420, 1, 1, 1000, 173, 0, 1],
+                "plutus:v2": [100788, 420, 1, 1, 1000, 173, 0, 1],
+                "plutus:v3": [100788, 420, 1, 1, 1000, 173, 0, 1]
+            },
+            "scriptExecutionPrices": { "memory": "577/10000", "cpu": "721/10000000" },
+            "maxExecutionUnitsPerTransaction": { "memory": 14000000, "cpu": 10000000000u64 },
+            "maxExecutionUnitsPerBlock": { "memory": 62000000, "cpu": 20000000000u64 },
+            "maxValueSize": { "bytes": 5000 },
+            "collateralPercentage": 150,
+            "maxCollateralInputs": 3,
+            "version": { "major": 9, "minor": 1 },
+            "stakePoolVotingThresholds": {
+                "noConfidence": "51/100",
+                "constitutionalCommittee": {
+                    "default": "51/100",
+                    "stateOfNoConfidence": "51/100"
+                },
+                "hardForkInitiation": "51/100",
+                "protocolParametersUpdate": { "security": "51/100" }
+            },
+            "delegateRepresentativeVotingThresholds": {
+                "noConfidence": "67/100",
+                "constitution": "3/4",
+                "constitutionalCommittee": {
+                    "default": "67/100",
+                    "stateOfNoConfidence": "3/5"
+                },
+                "hardForkInitiation": "3/5",
+                "protocolParametersUpdate": {
+                    "network": "67/100",
+                    "economic": "67/100",
+                    "technical": "67/100",
+                    "governance": "3/4"
+                },
+                "treasuryWithdrawals": "67/100"
+            },
+            "constitutionalCommitteeMinSize": 7,
+            "constitutionalCommitteeMaxTermLength": 146,
+            "governanceActionLifetime": 6,
+            "governanceActionDeposit": { "ada": { "lovelace": 100000000000u64 } },
+            "delegateRepresentativeDeposit": { "ada": { "lovelace": 500000000 } },
+            "delegateRepresentativeMaxIdleTime": 20
+        })
+    }
+
+    #[test]
+    fn test_protocol_parameters_ratios_round_trip() {
+        let params: ProtocolParameters = serde_json::from_value(protocol_parameters()).unwrap();
+        assert_eq!(params.stake_pool_pledge_influence, Ratio::new(3, 10));
+        assert_eq!(params.monetary_expansion, Ratio::new(3, 1000));
+        let prices = params.script_execution_prices.as_ref().unwrap();
+        assert_eq!(prices.cpu, Ratio::new(721, 10_000_000));
+        let dreps = params.delegate_representative_voting_thresholds.as_ref().unwrap();
+        assert_eq!(dreps.protocol_parameters_update.governance, Ratio::new(3, 4));
+
+        let serialized = serde_json::to_value(&params).unwrap();
+        assert_eq!(serialized["stakePoolPledgeInfluence"], "3/10");
+        assert_eq!(serialized["scriptExecutionPrices"]["memory"], "577/10000");
+        assert_eq!(
+            serialized["stakePoolVotingThresholds"]["constitutionalCommittee"]["default"],
+            "51/100"
+        );
+        let round_tripped: ProtocolParameters = serde_json::from_value(serialized).unwrap();
+        assert_eq!(round_tripped, params);
+    }
+
+    #[test]
+    fn test_cost_models_keep_unknown_languages() {
+        let mut value = protocol_parameters();
+        value["plutusCostModels"]["plutus:v4"] = json!([205665, 812, 1, 1]);
+        let params: ProtocolParameters = serde_json::from_value(value.clone()).unwrap();
+
+        let cost_models = params.plutus_cost_models.as_ref().unwrap();
+        assert_eq!(cost_models.v1(), Some(&[100788, 420, 1, 1, 1000, 173, 0, 1][..]));
+        assert_eq!(cost_models.get(&Language::PlutusV3), cost_models.v3());
+        let v4: Language = serde_json::from_value(json!("plutus:v4")).unwrap();
+        assert_eq!(v4, Language::Other("plutus:v4".to_string()));
+        assert_eq!(cost_models.get(&v4), Some(&[205665, 812, 1, 1][..]));
+        assert_eq!(cost_models.get(&"plutus:v5".to_string().into()), None);
+
+        let serialized = serde_json::to_value(&params).unwrap();
+        assert_eq!(serialized["plutusCostModels"], value["plutusCostModels"]);
+    }
+
+    /// `queryLedgerState/protocolParameters` result from a mainnet node in
+    /// the Mary era.
+    fn mary_protocol_parameters(extra_entropy: &str) -> serde_json::Value {
+        json!({
+            "minFeeCoefficient": 44,
+            "minFeeConstant": { "ada": { "lovelace": 155381 } },
+            "maxBlockBodySize": { "bytes": 65536 },
+            "maxBlockHeaderSize": { "bytes": 1100 },
+            "maxTransactionSize": { "bytes": 16384 },
+            "stakeCredentialDeposit": { "ada": { "lovelace": 2000000 } },
+            "stakePoolDeposit": { "ada": { "lovelace": 500000000 } },
+            "stakePoolRetirementEpochBound": 18,
+            "desiredNumberOfStakePools": 500,
+            "stakePoolPledgeInfluence": "3/10",
+            "monetaryExpansion": "3/1000",
+            "treasuryExpansion": "1/5",
+            "federatedBlockProductionRatio": "0/1",
+            "extraEntropy": extra_entropy,
+            "minUtxoDepositConstant": { "ada": { "lovelace": 1000000 } },
+            "minStakePoolCost": { "ada": { "lovelace": 340000000 } },
+            "version": { "major": 4, "minor": 0 }
+        })
+    }
+
+    #[test]
+    fn test_extra_entropy() {
+        let params: ProtocolParameters =
+            serde_json::from_value(mary_protocol_parameters("neutral")).unwrap();
+        assert_eq!(params.extra_entropy, Some(Nonce::Neutral));
+        assert_eq!(serde_json::to_value(&params).unwrap()["extraEntropy"], "neutral");
+
+        let digest = "d982e06fd33e7440b43cefad529b7ecafbaa255e38178ad4189a37e4ce9bf1fa";
+        let params: ProtocolParameters =
+            serde_json::from_value(mary_protocol_parameters(digest)).unwrap();
+        assert_eq!(params.extra_entropy, Some(Nonce::Value(digest.to_string())));
+        assert_eq!(serde_json::to_value(&params).unwrap()["extraEntropy"], digest);
+
+        for invalid in ["Neutral", "d982e06f", &digest.replace('d', "g")] {
+            assert!(
+                serde_json::from_value::<ProtocolParameters>(mary_protocol_parameters(invalid))
+                    .is_err(),
+                "{invalid}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_protocol_version_ordering() {
+        let patched = ProtocolVersion {
+            patch: Some(2),
+            ..ProtocolVersion::new(9, 1)
+        };
+        assert!(ProtocolVersion::new(9, 1) > ProtocolVersion::CONWAY);
+        assert!(patched > ProtocolVersion::new(9, 1));
+        assert!(ProtocolVersion::new(10, 0) > patched);
+        assert!(ProtocolVersion::new(8, 12) < ProtocolVersion::CONWAY);
+        // A missing patch version counts as 0.
+        let zero_patch = ProtocolVersion {
+            patch: Some(0),
+            ..ProtocolVersion::CONWAY
+        };
+        assert_eq!(zero_patch, ProtocolVersion::CONWAY);
+    }
+
+    #[test]
+    fn test_protocol_version_strings() {
+        for version in ["9.0", "9.1.2", "10.0"] {
+            let parsed: ProtocolVersion = version.parse().unwrap();
+            assert_eq!(parsed.to_string(), version);
+        }
+        assert_eq!("9.1.2".parse::<ProtocolVersion>().unwrap().patch, Some(2));
+        for invalid in ["", "9", "9.", "9.1.2.3", "v9.0", "-1.0"] {
+            let error = invalid.parse::<ProtocolVersion>().unwrap_err();
+            assert_eq!(error.value, invalid);
+        }
+    }
+
+    #[test]
+    fn test_era_from_version() {
+        let params: ProtocolParameters = serde_json::from_value(protocol_parameters()).unwrap();
+        assert_eq!(params.era_from_version(), Era::Conway);
+        let params: ProtocolParameters =
+            serde_json::from_value(mary_protocol_parameters("neutral")).unwrap();
+        assert_eq!(params.era_from_version(), Era::Mary);
+
+        assert_eq!(ProtocolVersion::new(0, 0).era(), Era::Byron);
+        assert_eq!(ProtocolVersion::new(6, 0).era(), Era::Alonzo);
+        assert_eq!(ProtocolVersion::VALENTINE.era(), Era::Babbage);
+        assert_eq!(ProtocolVersion::PLOMIN.era(), Era::Conway);
+    }
+
+    #[test]
+    fn test_protocol_parameters_round_trip_losslessly() {
+        use crate::schema::assert_lossless_roundtrip;
+
+        let json = crate::schema::testing::fixture("ledger-state/protocolParameters").result();
+        let params = assert_lossless_roundtrip::<ProtocolParameters>(&json);
+        assert_eq!(params.era_from_version(), Era::Conway);
+        assert_eq!(params.max_reference_scripts_size.unwrap().bytes, 204_800);
+
+        // Every parameter can be proposed for update.
+        let update = assert_lossless_roundtrip::<PartialProtocolParameters>(&json);
+        assert_eq!(update.governance_action_lifetime, Some(6));
+        assert_eq!(
+            update.delegate_representative_deposit,
+            Some(AdaValue { lovelace: 500_000_000 })
+        );
+        assert_eq!(update.stake_pool_voting_thresholds, params.stake_pool_voting_thresholds);
+    }
+
+    #[test]
+    fn test_min_fee_reference_scripts_keeps_unknown_fields() {
+        let json = json!({ "range": 25600, "base": 15.0, "multiplier": 1.2, "maxSize": 204800 });
+        let fee = crate::schema::assert_lossless_roundtrip::<MinFeeReferenceScripts>(&json);
+        assert_eq!(fee.extra["maxSize"], 204800);
+    }
 }
@@ -0,0 +1,226 @@
+//! Script hashes computed from scripts, per the ledger rules.
+//!
+//! A script hash is the Blake2b-224 digest of a language tag byte followed by
+//! the serialized script. Policy IDs are the hashes of minting scripts.
+//!
+//! Requires the `script-hash` feature.
+
+use super::digest::ScriptHash;
+use super::scripts::{NativeScript, Script};
+use blake2::digest::consts::U28;
+use blake2::{Blake2b, Digest};
+
+/// A script that cannot be serialized for hashing.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid script: {reason}")]
+pub struct InvalidScript {
+    /// Why the script was rejected.
+    pub reason: String,
+}
+
+impl Script {
+    /// Compute the script hash.
+    ///
+    /// Native scripts are hashed from their `cbor` when present, and encoded
+    /// from their JSON form otherwise. Plutus scripts are hashed from their
+    /// `cbor`, the script bytes wrapped once in a CBOR byte string, as sent by
+    /// Ogmios.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::Script;
+    ///
+    /// let always_succeeds = Script::PlutusV1 {
+    ///     cbor: "4d01000033222220051200120011".to_string(),
+    /// };
+    /// assert_eq!(
+    ///     always_succeeds.hash().unwrap(),
+    ///     "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656"
+    /// );
+    /// ```
+    pub fn hash(&self) -> Result<ScriptHash, InvalidScript> {
+        let (tag, bytes) = match self {
+            Script::Native {
+                cbor: Some(cbor), ..
+            } => (0, decode_hex(cbor)?),
+            Script::Native { script, cbor: None } => (0, script.to_cbor()?),
+            Script::PlutusV1 { cbor } => (1, decode_hex(cbor)?),
+            Script::PlutusV2 { cbor } => (2, decode_hex(cbor)?),
+            Script::PlutusV3 { cbor } => (3, decode_hex(cbor)?),
+        };
+        Ok(tagged_hash(tag, &bytes))
+    }
+}
+
+impl NativeScript {
+    /// Encode the script in CBOR, as the ledger serializes it.
+    pub fn to_cbor(&self) -> Result<Vec<u8>, InvalidScript> {
+        let mut cbor = Vec::new();
+        self.encode(&mut cbor)?;
+        Ok(cbor)
+    }
+
+    /// Compute the script hash, e.g. the policy ID of a minting script.
+    pub fn hash(&self) -> Result<ScriptHash, InvalidScript> {
+        Ok(tagged_hash(0, &self.to_cbor()?))
+    }
+
+    fn encode(&self, cbor: &mut Vec<u8>) -> Result<(), InvalidScript> {
+        match self {
+            NativeScript::Signature { from } => {
+                let key_hash = from.to_bytes();
+                head(cbor, 4, 2);
+                head(cbor, 0, 0);
+                head(cbor, 2, key_hash.len() as u64);
+                cbor.extend_from_slice(&key_hash);
+            }
+            NativeScript::All { from } => {
+                head(cbor, 4, 2);
+                head(cbor, 0, 1);
+                encode_all(cbor, from)?;
+            }
+            NativeScript::Any { from } => {
+                head(cbor, 4, 2);
+                head(cbor, 0, 2);
+                encode_all(cbor, from)?;
+            }
+            NativeScript::Some { at_least, from } => {
+                head(cbor, 4, 3);
+                head(cbor, 0, 3);
+                head(cbor, 0, u64::from(*at_least));
+                encode_all(cbor, from)?;
+            }
+            NativeScript::After { slot } => {
+                head(cbor, 4, 2);
+                head(cbor, 0, 4);
+                head(cbor, 0, *slot);
+            }
+            NativeScript::Before { slot } => {
+                head(cbor, 4, 2);
+                head(cbor, 0, 5);
+                head(cbor, 0, *slot);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn encode_all(cbor: &mut Vec<u8>, scripts: &[NativeScript]) -> Result<(), InvalidScript> {
+    head(cbor, 4, scripts.len() as u64);
+    scripts.iter().try_for_each(|script| script.encode(cbor))
+}
+
+/// Write a CBOR item head in its shortest form.
+fn head(cbor: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => cbor.push(major | argument as u8),
+        24..=0xff => cbor.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            cbor.push(major | 25);
+            cbor.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            cbor.push(major | 26);
+            cbor.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            cbor.push(major | 27);
+            cbor.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+fn tagged_hash(tag: u8, bytes: &[u8]) -> ScriptHash {
+    let digest = Blake2b::<U28>::new()
+        .chain_update([tag])
+        .chain_update(bytes)
+        .finalize();
+    ScriptHash::from_bytes(digest.into())
+}
+
+fn decode_hex(hex: &str) -> Result<Vec<u8>, InvalidScript> {
+    if !hex.len().is_multiple_of(2) || !hex.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return Err(InvalidScript {
+            reason: format!("invalid hex {hex:?}"),
+        });
+    }
+    crate::util::hex_decode(hex).map_err(|error| InvalidScript {
+        reason: error.to_string(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+
+    fn signature() -> NativeScript {
+        NativeScript::Signature {
+            from: KEY.parse().unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_plutus_script_hash() {
+        let cbor = "4d01000033222220051200120011".to_string();
+        assert_eq!(
+            Script::PlutusV1 { cbor: cbor.clone() }.hash().unwrap(),
+            "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656"
+        );
+        // The language tag is part of the hash.
+        assert_ne!(
+            Script::PlutusV2 { cbor }.hash().unwrap(),
+            "67f33146617a5e61936081db3b2117cbf59bd2123748f58ac9678656"
+        );
+        assert!(Script::PlutusV3 { cbor: "4d0".to_string() }.hash().is_err());
+    }
+
+    #[test]
+    fn test_native_script_encoding() {
+        assert_eq!(
+            crate::util::hex_encode(&signature().to_cbor().unwrap()),
+            format!("8200581c{KEY}")
+        );
+        let time_locked = NativeScript::All {
+            from: vec![signature(), NativeScript::Before { slot: 100_000_000 }],
+        };
+        assert_eq!(
+            crate::util::hex_encode(&time_locked.to_cbor().unwrap()),
+            format!("8201828200581c{KEY}82051a05f5e100")
+        );
+    }
+
+    #[test]
+    fn test_native_script_hash() {
+        assert_eq!(
+            signature().hash().unwrap(),
+            "208bdcaf2d83ae026964e23659c703a377473168a39cbdc2b0241115"
+        );
+        let policy = NativeScript::All {
+            from: vec![signature(), NativeScript::Before { slot: 100_000_000 }],
+        };
+        assert_eq!(
+            policy.hash().unwrap(),
+            "16f7628ff327aeef4624631fe7d9f7f84d8a77633e646e530e18effe"
+        );
+
+        // The hash of the JSON form matches the hash of its CBOR.
+        let script = Script::Native {
+            script: policy.clone(),
+            cbor: None,
+        };
+        let with_cbor = Script::Native {
+            script: policy.clone(),
+            cbor: Some(crate::util::hex_encode(&policy.to_cbor().unwrap())),
+        };
+        assert_eq!(script.hash().unwrap(), policy.hash().unwrap());
+        assert_eq!(with_cbor.hash().unwrap(), policy.hash().unwrap());
+
+        // A malformed key hash cannot make it into a script.
+        let invalid = serde_json::json!({ "clause": "signature", "from": "ab" });
+        assert!(serde_json::from_value::<NativeScript>(invalid).is_err());
+    }
+}
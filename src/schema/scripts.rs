@@ -1,8 +1,10 @@
 //! Script and datum types for Cardano.
 
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
+use super::digest::{DigestBlake2b224, PolicyId, ScriptHash, TransactionId};
 use super::primitives::*;
-use super::transaction::ExUnits;
+use super::transaction::{ExUnits, ScriptPurpose, ValidatorIndex};
 
 /// A Cardano script.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -70,26 +72,119 @@ pub enum NativeScript {
     },
 }
 
+impl NativeScript {
+    /// Whether the script is satisfied by a set of signers and a transaction
+    /// validity interval.
+    ///
+    /// The interval is `(invalid_before, invalid_hereafter)`, as in the
+    /// transaction body. As in the ledger, `after` requires a lower bound of
+    /// at least its slot, and `before` an upper bound of at most its slot;
+    /// a missing bound never satisfies a timelock.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::NativeScript;
+    /// use std::collections::HashSet;
+    ///
+    /// let key: ogmios_client::schema::DigestBlake2b224 =
+    ///     "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a".parse().unwrap();
+    /// let script = NativeScript::All {
+    ///     from: vec![
+    ///         NativeScript::Signature { from: key.clone() },
+    ///         NativeScript::Before { slot: 1000 },
+    ///     ],
+    /// };
+    /// let signers = HashSet::from([key]);
+    /// assert!(script.evaluate(&signers, (None, Some(1000))));
+    /// assert!(!script.evaluate(&signers, (None, Some(1001))));
+    /// ```
+    pub fn evaluate(
+        &self,
+        signers: &HashSet<DigestBlake2b224>,
+        valid_range: (Option<Slot>, Option<Slot>),
+    ) -> bool {
+        match self {
+            NativeScript::Signature { from } => signers.contains(from),
+            NativeScript::All { from } => {
+                from.iter().all(|script| script.evaluate(signers, valid_range))
+            }
+            NativeScript::Any { from } => {
+                from.iter().any(|script| script.evaluate(signers, valid_range))
+            }
+            NativeScript::Some { at_least, from } => {
+                let satisfied = from
+                    .iter()
+                    .filter(|script| script.evaluate(signers, valid_range))
+                    .count();
+                satisfied >= *at_least as usize
+            }
+            NativeScript::After { slot } => valid_range.0.is_some_and(|start| *slot <= start),
+            NativeScript::Before { slot } => valid_range.1.is_some_and(|end| end <= *slot),
+        }
+    }
+
+    /// Key hashes that sign every transaction satisfying the script.
+    ///
+    /// Keys that only some of the ways to satisfy an `any` or `some` clause
+    /// need are not included, so the set may be empty even for scripts that
+    /// require signatures.
+    pub fn required_signers(&self) -> HashSet<DigestBlake2b224> {
+        match self {
+            NativeScript::Signature { from } => HashSet::from([from.clone()]),
+            NativeScript::All { from } => from.iter().flat_map(Self::required_signers).collect(),
+            NativeScript::Any { from } => Self::required_by_at_least(1, from),
+            NativeScript::Some { at_least, from } => {
+                Self::required_by_at_least(*at_least as usize, from)
+            }
+            NativeScript::After { .. } | NativeScript::Before { .. } => HashSet::new(),
+        }
+    }
+
+    /// Keys required whichever `at_least` of the scripts are satisfied: those
+    /// without which fewer than `at_least` scripts remain.
+    fn required_by_at_least(
+        at_least: usize,
+        scripts: &[NativeScript],
+    ) -> HashSet<DigestBlake2b224> {
+        if at_least == 0 {
+            return HashSet::new();
+        }
+        let required: Vec<_> = scripts.iter().map(Self::required_signers).collect();
+        required
+            .iter()
+            .flatten()
+            .filter(|key| {
+                let without = required.iter().filter(|keys| !keys.contains(*key)).count();
+                without < at_least
+            })
+            .cloned()
+            .collect()
+    }
+}
+
 /// Plutus language versions.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Versions this crate does not know yet, e.g. `plutus:v4`, are kept as
+/// [`Other`](Language::Other). Languages compare by name.
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum Language {
-    #[serde(rename = "plutus:v1")]
+    /// Plutus V1 (Alonzo).
     PlutusV1,
-    #[serde(rename = "plutus:v2")]
+    /// Plutus V2 (Babbage).
     PlutusV2,
-    #[serde(rename = "plutus:v3")]
+    /// Plutus V3 (Conway).
     PlutusV3,
+    /// Language not known to this version.
+    Other(String),
 }
 
-impl Language {
-    pub fn as_str(&self) -> &'static str {
-        match self {
-            Language::PlutusV1 => "plutus:v1",
-            Language::PlutusV2 => "plutus:v2",
-            Language::PlutusV3 => "plutus:v3",
-        }
-    }
-}
+named_enum!(Language {
+    PlutusV1 => "plutus:v1",
+    PlutusV2 => "plutus:v2",
+    PlutusV3 => "plutus:v3",
+});
 
 /// Datum (inline or hash reference).
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -104,18 +199,35 @@ pub enum Datum {
 /// Redeemer for script execution.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Redeemer {
-    /// Script purpose.
-    pub purpose: RedeemerPurpose,
-    /// Redeemer data.
-    pub datum: Datum,
+    /// Script the redeemer is passed to.
+    pub validator: ValidatorIndex,
+    /// Redeemer data (CBOR, hex-encoded).
+    #[serde(rename = "redeemer")]
+    pub data: Datum,
     /// Execution budget.
     pub execution_units: ExUnits,
 }
 
+impl Redeemer {
+    /// Redeemer data.
+    ///
+    /// The former `purpose` has no replacement accessor: Ogmios v6 only sends
+    /// the purpose and index in [`validator`](Self::validator).
+    #[deprecated(note = "use `data`")]
+    pub fn datum(&self) -> &Datum {
+        &self.data
+    }
+}
+
 /// Redeemer purpose (what the script is validating).
+///
+/// Purposes not known to this version are kept as
+/// [`Other`](RedeemerPurpose::Other), payload included. A known purpose with
+/// a malformed payload is an error.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(tag = "purpose", rename_all = "camelCase")]
+#[serde(remote = "Self", tag = "purpose", rename_all = "camelCase")]
 pub enum RedeemerPurpose {
     /// Spending a UTXO.
     Spend {
@@ -145,11 +257,38 @@ pub enum RedeemerPurpose {
     Vote {
         voter: serde_json::Value,
     },
+    /// Purpose not known to this version.
+    #[serde(skip)]
+    Other(serde_json::Value),
+}
+
+impl Serialize for RedeemerPurpose {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RedeemerPurpose::Other(value) => value.serialize(serializer),
+            purpose => RedeemerPurpose::serialize(purpose, serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RedeemerPurpose {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let value = serde_json::Value::deserialize(deserializer)?;
+        let purpose = value.get("purpose").and_then(serde_json::Value::as_str);
+        let purpose = ScriptPurpose::from(purpose.unwrap_or_default().to_string());
+        if let ScriptPurpose::Other(_) = purpose {
+            return Ok(RedeemerPurpose::Other(value));
+        }
+        RedeemerPurpose::deserialize(value).map_err(D::Error::custom)
+    }
 }
 
 /// Output reference for redeemer.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct OutputReference {
     /// Transaction ID.
     #[serde(rename = "transaction")]
@@ -161,6 +300,7 @@ pub struct OutputReference {
 /// Script reference in a UTXO.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ScriptReference {
     /// Script hash.
     pub hash: ScriptHash,
@@ -170,3 +310,142 @@ pub struct ScriptReference {
     #[serde(default)]
     pub cbor: Option<String>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+    const BOB: &str = "5a3b2fd1f7c26e1d8e3b1c1a7e9d2c4b6a8f0e1d3c5b7a9f1e2d3c4b";
+    const CAROL: &str = "70e60f3b5ea7153e0acc7a803e4401d44b8ed1bae1c7baaad1a62a72";
+
+    fn sig(key: &str) -> NativeScript {
+        NativeScript::Signature {
+            from: key.parse().unwrap(),
+        }
+    }
+
+    fn signers(keys: &[&str]) -> HashSet<DigestBlake2b224> {
+        keys.iter().map(|key| key.parse().unwrap()).collect()
+    }
+
+    /// Alice, plus two of Alice, Bob and Carol, or Bob and Carol alone.
+    fn nested() -> NativeScript {
+        NativeScript::Any {
+            from: vec![
+                NativeScript::All {
+                    from: vec![
+                        sig(ALICE),
+                        NativeScript::Some {
+                            at_least: 2,
+                            from: vec![sig(ALICE), sig(BOB), sig(CAROL)],
+                        },
+                    ],
+                },
+                NativeScript::Some {
+                    at_least: 2,
+                    from: vec![sig(BOB), sig(CAROL)],
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn test_evaluate_nested_some_of() {
+        let script = nested();
+        let always = (None, None);
+        assert!(script.evaluate(&signers(&[ALICE, BOB]), always));
+        assert!(script.evaluate(&signers(&[ALICE, CAROL]), always));
+        assert!(script.evaluate(&signers(&[BOB, CAROL]), always));
+        assert!(!script.evaluate(&signers(&[ALICE]), always));
+        assert!(!script.evaluate(&signers(&[CAROL]), always));
+
+        let none_needed = NativeScript::Some {
+            at_least: 0,
+            from: vec![sig(ALICE)],
+        };
+        assert!(none_needed.evaluate(&signers(&[]), always));
+        assert!(!NativeScript::Any { from: vec![] }.evaluate(&signers(&[ALICE]), always));
+        assert!(NativeScript::All { from: vec![] }.evaluate(&signers(&[]), always));
+    }
+
+    #[test]
+    fn test_evaluate_timelock_boundaries() {
+        let after = NativeScript::After { slot: 100 };
+        assert!(after.evaluate(&signers(&[]), (Some(100), None)));
+        assert!(after.evaluate(&signers(&[]), (Some(101), Some(200))));
+        assert!(!after.evaluate(&signers(&[]), (Some(99), None)));
+        assert!(!after.evaluate(&signers(&[]), (None, Some(200))));
+
+        let before = NativeScript::Before { slot: 200 };
+        assert!(before.evaluate(&signers(&[]), (None, Some(200))));
+        assert!(before.evaluate(&signers(&[]), (Some(100), Some(199))));
+        assert!(!before.evaluate(&signers(&[]), (None, Some(201))));
+        assert!(!before.evaluate(&signers(&[]), (Some(100), None)));
+    }
+
+    #[test]
+    fn test_required_signers() {
+        assert_eq!(nested().required_signers(), signers(&[]));
+
+        let multisig = NativeScript::All {
+            from: vec![
+                sig(ALICE),
+                NativeScript::Some {
+                    at_least: 2,
+                    from: vec![sig(BOB), sig(CAROL), NativeScript::After { slot: 10 }],
+                },
+            ],
+        };
+        assert_eq!(multisig.required_signers(), signers(&[ALICE]));
+
+        let all_of_some = NativeScript::Some {
+            at_least: 2,
+            from: vec![sig(BOB), sig(CAROL)],
+        };
+        assert_eq!(all_of_some.required_signers(), signers(&[BOB, CAROL]));
+
+        let shared = NativeScript::Any {
+            from: vec![
+                NativeScript::All { from: vec![sig(ALICE), sig(BOB)] },
+                NativeScript::All { from: vec![sig(ALICE), sig(CAROL)] },
+            ],
+        };
+        assert_eq!(shared.required_signers(), signers(&[ALICE]));
+    }
+
+    #[test]
+    fn test_unknown_redeemer_purpose_round_trip() {
+        let purpose = serde_json::json!({ "purpose": "guard", "guardIndex": 2 });
+        let parsed: RedeemerPurpose = serde_json::from_value(purpose.clone()).unwrap();
+        assert_eq!(parsed, RedeemerPurpose::Other(purpose.clone()));
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), purpose);
+
+        let mint: RedeemerPurpose = serde_json::from_value(serde_json::json!({
+            "purpose": "mint",
+            "policy": "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7"
+        }))
+        .unwrap();
+        assert!(matches!(mint, RedeemerPurpose::Mint { .. }));
+        assert_eq!(serde_json::to_value(&mint).unwrap()["purpose"], "mint");
+    }
+
+    #[test]
+    fn test_malformed_redeemer_purpose_is_an_error() {
+        let malformed = serde_json::json!({ "purpose": "mint", "policy": 42 });
+        assert!(serde_json::from_value::<RedeemerPurpose>(malformed).is_err());
+
+        let untagged = serde_json::json!({ "guardIndex": 2 });
+        let parsed: RedeemerPurpose = serde_json::from_value(untagged.clone()).unwrap();
+        assert_eq!(parsed, RedeemerPurpose::Other(untagged));
+    }
+
+    #[test]
+    fn test_language_names() {
+        let v3: Language = serde_json::from_value(serde_json::json!("plutus:v3")).unwrap();
+        assert_eq!(v3, Language::PlutusV3);
+        let v4: Language = serde_json::from_value(serde_json::json!("plutus:v4")).unwrap();
+        assert_eq!(v4.to_string(), "plutus:v4");
+        assert_eq!(serde_json::to_value(&v4).unwrap(), "plutus:v4");
+    }
+}
@@ -0,0 +1,400 @@
+//! Golden Ogmios payloads and round-trip checks for schema tests.
+//!
+//! [`FIXTURES`] holds complete JSON-RPC responses, one file per method and
+//! case under `fixtures/` at the crate root. Downstream crates can run their
+//! own types or schema extensions against them, or check responses of their
+//! node with [`roundtrip`] and [`roundtrip_diff`].
+//!
+//! Requires the `test-fixtures` feature.
+//!
+//! # Example
+//!
+//! ```rust
+//! use ogmios_client::schema::testing::{fixture, roundtrip};
+//! use ogmios_client::schema::TreasuryAndReserves;
+//!
+//! let pots: TreasuryAndReserves =
+//!     roundtrip(&fixture("ledger-state/treasuryAndReserves").result());
+//! assert!(pots.reserves.lovelace() > pots.treasury.lovelace());
+//! ```
+//!
+//! # Regenerating
+//!
+//! Fixtures are the responses of a node, with hashes and keys swapped for
+//! stable values; `fixtures/README.md` lists the requests that produce them.
+//! A new file must also be added to [`FIXTURES`].
+
+use super::jsonrpc::JsonRpcError;
+use super::lossless::{assert_lossless_roundtrip, lossless_diff};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+/// A recorded Ogmios response.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Fixture {
+    /// Path of the file under `fixtures/`, without the `.json` extension,
+    /// e.g. `chain-sync/nextBlock-conway`.
+    pub name: &'static str,
+    source: &'static str,
+}
+
+impl Fixture {
+    /// The whole document, a JSON-RPC response except for `health/health`.
+    pub fn json(&self) -> Value {
+        serde_json::from_str(self.source)
+            .unwrap_or_else(|error| panic!("invalid fixture {}: {error}", self.name))
+    }
+
+    /// The `result` of the response.
+    ///
+    /// # Panics
+    ///
+    /// If the fixture is not a successful JSON-RPC response.
+    pub fn result(&self) -> Value {
+        match self.json() {
+            Value::Object(mut response) if response.contains_key("result") => {
+                response.remove("result").expect("checked above")
+            }
+            _ => panic!("fixture {} has no result", self.name),
+        }
+    }
+
+    /// The `error` of the response.
+    ///
+    /// # Panics
+    ///
+    /// If the fixture is not a JSON-RPC error response.
+    pub fn error(&self) -> JsonRpcError {
+        let error = self.json().get("error").cloned();
+        match error.map(serde_json::from_value) {
+            Some(Ok(error)) => error,
+            _ => panic!("fixture {} has no error", self.name),
+        }
+    }
+}
+
+macro_rules! fixtures {
+    ($($name:literal),+ $(,)?) => {
+        /// All fixtures, sorted by name.
+        pub static FIXTURES: &[Fixture] = &[
+            $(Fixture {
+                name: $name,
+                source: include_str!(concat!("../../fixtures/", $name, ".json")),
+            },)+
+        ];
+    };
+}
+
+fixtures![
+    "chain-sync/findIntersection",
+    "chain-sync/nextBlock-allegra",
+    "chain-sync/nextBlock-alonzo",
+    "chain-sync/nextBlock-babbage",
+    "chain-sync/nextBlock-backward",
+    "chain-sync/nextBlock-byron-bft",
+    "chain-sync/nextBlock-byron-ebb",
+    "chain-sync/nextBlock-conway",
+    "chain-sync/nextBlock-mary",
+    "chain-sync/nextBlock-shelley",
+    "evaluation/evaluateTransaction",
+    "evaluation/evaluateTransaction-3000",
+    "evaluation/evaluateTransaction-3010",
+    "health/health",
+    "ledger-state/constitution",
+    "ledger-state/constitutionalCommittee",
+    "ledger-state/delegateRepresentatives",
+    "ledger-state/epoch",
+    "ledger-state/eraStart",
+    "ledger-state/eraSummaries",
+    "ledger-state/governanceProposals",
+    "ledger-state/liveStakeDistribution",
+    "ledger-state/projectedRewards",
+    "ledger-state/protocolParameters",
+    "ledger-state/rewardAccountSummaries",
+    "ledger-state/stakePools",
+    "ledger-state/tip",
+    "ledger-state/treasuryAndReserves",
+    "ledger-state/utxo",
+    "network/blockHeight",
+    "network/genesisConfiguration-alonzo",
+    "network/genesisConfiguration-byron",
+    "network/genesisConfiguration-conway",
+    "network/genesisConfiguration-shelley",
+    "network/startTime",
+    "network/tip",
+    "submission/submitTransaction",
+    "submission/submitTransaction-3005",
+    "submission/submitTransaction-3117",
+    "submission/submitTransaction-3122",
+];
+
+/// Look a fixture up by name.
+///
+/// # Panics
+///
+/// If there is no fixture of that name.
+pub fn fixture(name: &str) -> &'static Fixture {
+    FIXTURES
+        .iter()
+        .find(|fixture| fixture.name == name)
+        .unwrap_or_else(|| panic!("no fixture named {name}"))
+}
+
+/// List the parts of `json` that do not survive a round trip through `T`,
+/// as [`lossless_diff`] does.
+///
+/// # Panics
+///
+/// If `json` does not deserialize, or if serializing `T` is not stable: the
+/// serialized form must deserialize again and serialize to the same JSON.
+#[track_caller]
+pub fn roundtrip_diff<T>(json: &Value) -> Vec<String>
+where
+    T: DeserializeOwned + Serialize,
+{
+    let differences = lossless_diff::<T>(json).unwrap_or_else(|error| {
+        panic!("failed to deserialize {}: {error}", std::any::type_name::<T>())
+    });
+    assert_stable::<T>(json);
+    differences
+}
+
+/// Deserialize `json` as `T`, panicking if anything is lost on the way back
+/// or if serialization is not stable.
+///
+/// # Panics
+///
+/// If [`assert_lossless_roundtrip`] or [`roundtrip_diff`] panics.
+#[track_caller]
+pub fn roundtrip<T>(json: &Value) -> T
+where
+    T: DeserializeOwned + Serialize,
+{
+    let value = assert_lossless_roundtrip::<T>(json);
+    assert_stable::<T>(json);
+    value
+}
+
+/// Check that the serialized form of `json` as `T` round-trips unchanged.
+#[track_caller]
+fn assert_stable<T>(json: &Value)
+where
+    T: DeserializeOwned + Serialize,
+{
+    let name = std::any::type_name::<T>();
+    let value: T = serde_json::from_value(json.clone()).expect("deserialized by the caller");
+    let serialized = serde_json::to_value(value).expect("schema types serialize to JSON");
+    match lossless_diff::<T>(&serialized) {
+        Err(error) => panic!("{name} does not deserialize its own output: {error}"),
+        Ok(unstable) => assert!(
+            unstable.is_empty(),
+            "{name} serializes differently after a round trip:\n  {}",
+            unstable.join("\n  ")
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::{EvaluateTransactionError, ScriptError, SubmitTransactionError};
+    use crate::schema::responses::{
+        FindIntersectionResponse, NextBlockResponse, SubmitTransactionResponse,
+    };
+    use crate::schema::*;
+    use std::collections::HashMap;
+
+    /// Round-trip the payload of a fixture losslessly.
+    #[track_caller]
+    fn check<T: DeserializeOwned + Serialize>(name: &str) -> T {
+        let fixture = fixture(name);
+        let json = match name {
+            "health/health" => fixture.json(),
+            _ => fixture.result(),
+        };
+        roundtrip::<T>(&json)
+    }
+
+    #[test]
+    fn test_every_fixture_is_registered() {
+        fn visit(directory: &std::path::Path, root: &std::path::Path, names: &mut Vec<String>) {
+            for entry in std::fs::read_dir(directory).unwrap() {
+                let path = entry.unwrap().path();
+                if path.is_dir() {
+                    visit(&path, root, names);
+                } else if path.extension().is_some_and(|extension| extension == "json") {
+                    let name = path.strip_prefix(root).unwrap().with_extension("");
+                    names.push(name.to_string_lossy().replace('\\', "/"));
+                }
+            }
+        }
+
+        let root = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures");
+        let mut files = Vec::new();
+        visit(&root, &root, &mut files);
+        files.sort();
+        let registered: Vec<_> = FIXTURES.iter().map(|fixture| fixture.name).collect();
+        assert_eq!(files, registered);
+        for fixture in FIXTURES {
+            let json = fixture.json();
+            if fixture.name != "health/health" {
+                assert_eq!(json["jsonrpc"], "2.0", "{}", fixture.name);
+            }
+        }
+    }
+
+    #[test]
+    fn test_chain_sync_fixtures() {
+        let mut eras = Vec::new();
+        for fixture in FIXTURES.iter().filter(|f| f.name.starts_with("chain-sync/nextBlock-")) {
+            match check::<NextBlockResponse>(fixture.name) {
+                NextBlockResponse::Forward { block, .. } => eras.push(block.era().to_string()),
+                NextBlockResponse::Backward { point, .. } => assert!(!point.is_origin()),
+            }
+        }
+        eras.sort();
+        eras.dedup();
+        assert_eq!(eras.len(), 7, "{eras:?}");
+        let intersection = check::<FindIntersectionResponse>("chain-sync/findIntersection");
+        assert!(intersection.intersection.is_some());
+    }
+
+    #[test]
+    fn test_ledger_state_fixtures() {
+        check::<Constitution>("ledger-state/constitution");
+        let committee = check::<ConstitutionalCommittee>("ledger-state/constitutionalCommittee");
+        assert!(matches!(
+            committee.members[0].delegate,
+            Some(ConstitutionalCommitteeDelegate::Authorized { from: CredentialOrigin::Script, .. })
+        ));
+        assert_eq!(committee.members[1].delegate, Some(ConstitutionalCommitteeDelegate::Resigned));
+        assert_eq!(
+            committee.members[1].next_status,
+            Some(ConstitutionalCommitteeMemberStatus::Expired)
+        );
+        let dreps = check::<Vec<DelegateRepresentativeSummary>>(
+            "ledger-state/delegateRepresentatives",
+        );
+        assert_eq!(dreps.len(), 3);
+        assert_eq!(check::<Epoch>("ledger-state/epoch"), 556);
+        let start = check::<EraStart>("ledger-state/eraStart");
+        assert_eq!(start.time, 236_260_800.0);
+        let summaries = check::<Vec<EraSummary>>("ledger-state/eraSummaries");
+        assert_eq!(summaries[0].parameters.slot_length, 20.0);
+        let proposals =
+            check::<Vec<GovernanceProposalState>>("ledger-state/governanceProposals");
+        assert_eq!(proposals[0].votes.len(), 2);
+        check::<HashMap<StakePoolId, LiveStakeDistributionEntry>>(
+            "ledger-state/liveStakeDistribution",
+        );
+        check::<ProjectedRewards>("ledger-state/projectedRewards");
+        let parameters = check::<ProtocolParameters>("ledger-state/protocolParameters");
+        assert_eq!(parameters.min_fee_constant.lovelace, 155_381);
+        check::<HashMap<RewardAccount, RewardAccountSummary>>(
+            "ledger-state/rewardAccountSummaries",
+        );
+        check::<HashMap<StakePoolId, StakePoolView>>("ledger-state/stakePools");
+        check::<Point>("ledger-state/tip");
+        check::<TreasuryAndReserves>("ledger-state/treasuryAndReserves");
+        let utxo = check::<Vec<Utxo>>("ledger-state/utxo");
+        assert_eq!(utxo[1].transaction.index, 2);
+    }
+
+    #[test]
+    fn test_network_fixtures() {
+        check::<BlockHeight>("network/blockHeight");
+        check::<UtcTime>("network/startTime");
+        // A point, without the height that `network_tip` still expects.
+        let tip = check::<Point>("network/tip");
+        assert_eq!(tip, check::<Point>("ledger-state/tip"));
+        for era in ["alonzo", "byron", "conway", "shelley"] {
+            check::<GenesisConfiguration>(&format!("network/genesisConfiguration-{era}"));
+        }
+        let GenesisConfiguration::Conway(conway) =
+            check::<GenesisConfiguration>("network/genesisConfiguration-conway")
+        else {
+            panic!("expected a Conway genesis");
+        };
+        assert_eq!(conway.constitutional_committee.unwrap().members[0].mandate.unwrap().epoch, 580);
+        assert_eq!(conway.constitutional_committee_min_size, Some(7));
+    }
+
+    #[test]
+    fn test_submission_fixtures() {
+        check::<SubmitTransactionResponse>("submission/submitTransaction");
+        let error = |name| SubmitTransactionError::from(fixture(name).error());
+        assert!(matches!(
+            error("submission/submitTransaction-3005"),
+            SubmitTransactionError::EraMismatch(_)
+        ));
+        assert!(matches!(
+            error("submission/submitTransaction-3117"),
+            SubmitTransactionError::UnknownOutputReferences(data)
+                if data.unknown_output_references.len() == 2
+        ));
+        assert!(matches!(
+            error("submission/submitTransaction-3122"),
+            SubmitTransactionError::TransactionFeeTooSmall(_)
+        ));
+    }
+
+    #[test]
+    fn test_evaluation_fixtures() {
+        let budgets = check::<Vec<EvaluationResult>>("evaluation/evaluateTransaction");
+        assert_eq!(budgets.len(), 2);
+        let error = |name| EvaluateTransactionError::from(fixture(name).error());
+        assert!(matches!(
+            error("evaluation/evaluateTransaction-3000"),
+            EvaluateTransactionError::IncompatibleEra(_)
+        ));
+        let EvaluateTransactionError::ScriptExecutionFailure(failures) =
+            error("evaluation/evaluateTransaction-3010")
+        else {
+            panic!("expected a script execution failure");
+        };
+        assert!(matches!(failures[0].error, ScriptError::ValidationFailure(_)));
+        assert_eq!(failures[0].traces(), ["Deadline not reached", "PT5"]);
+    }
+
+    #[test]
+    fn test_health_fixture() {
+        let health = check::<ServerHealth>("health/health");
+        assert!(health.is_node_connected());
+    }
+
+    #[test]
+    fn test_fixture_accessors() {
+        let tip = fixture("network/blockHeight");
+        assert_eq!(tip.result(), 11_292_014);
+        assert_eq!(fixture("submission/submitTransaction-3122").error().code, 3122);
+    }
+
+    #[test]
+    #[should_panic(expected = "has no error")]
+    fn test_error_of_a_result_panics() {
+        fixture("network/blockHeight").error();
+    }
+
+    #[test]
+    fn test_roundtrip_diff_reports_lost_fields() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Size {
+            bytes: u64,
+        }
+        let size = serde_json::json!({ "bytes": 1, "kilobytes": 0 });
+        assert_eq!(roundtrip_diff::<Size>(&size), ["dropped /kilobytes"]);
+        assert_eq!(roundtrip::<Size>(&serde_json::json!({ "bytes": 1 })).bytes, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "does not deserialize its own output")]
+    fn test_roundtrip_diff_rejects_unstable_serialization() {
+        #[derive(Serialize, serde::Deserialize)]
+        struct Renamed {
+            #[serde(rename(serialize = "after", deserialize = "before"))]
+            value: u64,
+        }
+        roundtrip_diff::<Renamed>(&serde_json::json!({ "before": 1 }));
+    }
+}
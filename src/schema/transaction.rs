@@ -1,6 +1,13 @@
 //! Transaction types for Cardano.
 
+use super::address::Address;
 use super::certificates::Certificate;
+use super::digest::{
+    DatumHash, DigestBlake2b224, DigestBlake2b256, InvalidDigest, InvalidIndexedId, ScriptHash, TransactionId, parse_indexed_id,
+};
+use super::errors::ValidityInterval;
+use super::governance::{GovernanceProposal, GovernanceVote};
+use super::metadatum::{MetadataLabels, Metadatum, deserialize_labels};
 use super::primitives::*;
 use super::scripts::{Datum, Redeemer, Script};
 use serde::{Deserialize, Serialize};
@@ -9,12 +16,17 @@ use std::collections::HashMap;
 /// A Cardano transaction.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Transaction {
     /// Transaction ID.
     pub id: TransactionId,
     /// Whether the transaction is valid.
     #[serde(default = "default_true")]
     pub valid: bool,
+    /// Which inputs the transaction consumed: its collaterals if phase-2
+    /// validation failed, its inputs otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spends: Option<InputSource>,
     /// Transaction inputs.
     #[serde(default)]
     pub inputs: Vec<TransactionInput>,
@@ -28,32 +40,29 @@ pub struct Transaction {
     #[serde(default)]
     pub collateral_return: Option<TransactionOutput>,
     /// Total collateral amount.
-    #[serde(default)]
+    #[serde(default, with = "ada_amount")]
     pub total_collateral: Option<Lovelace>,
     /// Reference inputs.
     #[serde(default)]
     pub references: Vec<TransactionInput>,
     /// Transaction fee.
-    #[serde(default)]
+    #[serde(default, with = "ada_amount")]
     pub fee: Option<Lovelace>,
-    /// Validity interval start (slot).
-    #[serde(default)]
-    pub valid_from: Option<Slot>,
-    /// Validity interval end (slot).
-    #[serde(default)]
-    pub valid_until: Option<Slot>,
+    /// Slots the transaction is valid in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub validity_interval: Option<ValidityInterval>,
     /// Certificates included in the transaction.
     #[serde(default)]
     pub certificates: Vec<Certificate>,
     /// Withdrawals from reward accounts.
-    #[serde(default)]
+    #[serde(default, with = "ada_amounts")]
     pub withdrawals: HashMap<RewardAccount, Lovelace>,
     /// Minted/burned assets.
-    #[serde(default)]
+    #[serde(default, deserialize_with = "super::primitives::deserialize_assets")]
     pub mint: Assets,
-    /// Required signers (for Plutus).
-    #[serde(default)]
-    pub required_extra_signers: Vec<DigestBlake2b224>,
+    /// Key hashes that must sign the transaction (for Plutus).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub required_extra_signatories: Vec<DigestBlake2b224>,
     /// Required scripts.
     #[serde(default)]
     pub required_extra_scripts: Vec<ScriptHash>,
@@ -63,9 +72,18 @@ pub struct Transaction {
     /// Script integrity hash.
     #[serde(default)]
     pub script_integrity_hash: Option<DigestBlake2b256>,
-    /// Witness set.
-    #[serde(default)]
-    pub witnesses: Option<Witnesses>,
+    /// Key and bootstrap witnesses.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub signatories: Vec<Signatory>,
+    /// Scripts witnessing the transaction, by hash.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub scripts: HashMap<ScriptHash, Script>,
+    /// Datums witnessing the transaction, by hash.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub datums: HashMap<DatumHash, Datum>,
+    /// Redeemers of the scripts the transaction runs.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub redeemers: Vec<Redeemer>,
     /// Metadata.
     #[serde(default)]
     pub metadata: Option<Metadata>,
@@ -74,27 +92,257 @@ pub struct Transaction {
     pub cbor: Option<String>,
     /// Proposals (Conway era).
     #[serde(default)]
-    pub proposals: Vec<serde_json::Value>,
+    pub proposals: Vec<GovernanceProposal>,
     /// Votes (Conway era).
     #[serde(default)]
-    pub votes: Vec<serde_json::Value>,
+    pub votes: Vec<GovernanceVote>,
+    /// Treasury value and donation (Conway era).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub treasury: Option<TreasuryTransfer>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// An amount sent as an ada-only value, or as a plain number of lovelace.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum AdaAmountRepr {
+    Lovelace(Lovelace),
+    Ada(AdaValue),
+}
+
+impl From<AdaAmountRepr> for Lovelace {
+    fn from(repr: AdaAmountRepr) -> Self {
+        match repr {
+            AdaAmountRepr::Lovelace(lovelace) => lovelace,
+            AdaAmountRepr::Ada(ada) => ada.lovelace,
+        }
+    }
+}
+
+/// Optional amounts, serialized as ada-only values.
+mod ada_amount {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        amount: &Option<Lovelace>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        amount.map(|lovelace| AdaValue { lovelace }).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<Lovelace>, D::Error> {
+        Ok(Option::<AdaAmountRepr>::deserialize(deserializer)?.map(Lovelace::from))
+    }
+}
+
+/// Amounts by reward account, serialized as ada-only values.
+mod ada_amounts {
+    use super::*;
+    use serde::{Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        amounts: &HashMap<RewardAccount, Lovelace>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(
+            amounts
+                .iter()
+                .map(|(account, lovelace)| (account, AdaValue { lovelace: *lovelace })),
+        )
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<RewardAccount, Lovelace>, D::Error> {
+        let amounts = HashMap::<RewardAccount, AdaAmountRepr>::deserialize(deserializer)?;
+        Ok(amounts
+            .into_iter()
+            .map(|(account, amount)| (account, amount.into()))
+            .collect())
+    }
+}
+
+impl Transaction {
+    /// Which inputs the transaction consumed.
+    ///
+    /// Falls back to `valid` when `spends` is missing.
+    pub fn input_source(&self) -> InputSource {
+        self.spends.unwrap_or(if self.valid {
+            InputSource::Inputs
+        } else {
+            InputSource::Collaterals
+        })
+    }
+
+    /// Inputs the transaction actually consumed.
+    pub fn consumed_inputs(&self) -> &[TransactionInput] {
+        match self.input_source() {
+            InputSource::Inputs => &self.inputs,
+            InputSource::Collaterals => &self.collaterals,
+        }
+    }
+
+    /// Outputs the transaction actually created, with their references.
+    ///
+    /// When phase-2 validation failed, only the collateral return output is
+    /// created; its index follows the regular outputs.
+    pub fn produced_outputs(
+        &self,
+    ) -> impl Iterator<Item = (TransactionOutputReference, &TransactionOutput)> {
+        let (first_index, outputs) = match self.input_source() {
+            InputSource::Inputs => (0, self.outputs.as_slice()),
+            InputSource::Collaterals => (self.outputs.len(), self.collateral_return.as_slice()),
+        };
+        outputs.iter().enumerate().map(move |(index, output)| {
+            let reference = TransactionOutputReference {
+                id: self.id.clone(),
+                index: (first_index + index) as u32,
+            };
+            (reference, output)
+        })
+    }
+
+    /// Validity interval start (slot).
+    #[deprecated(note = "use `validity_interval`, as sent by Ogmios v6")]
+    pub fn valid_from(&self) -> Option<Slot> {
+        self.validity_interval.and_then(|interval| interval.invalid_before)
+    }
+
+    /// Validity interval end (slot).
+    #[deprecated(note = "use `validity_interval`, as sent by Ogmios v6")]
+    pub fn valid_until(&self) -> Option<Slot> {
+        self.validity_interval.and_then(|interval| interval.invalid_after)
+    }
+
+    /// Required signers (for Plutus).
+    #[deprecated(note = "use `required_extra_signatories`, as sent by Ogmios v6")]
+    pub fn required_extra_signers(&self) -> &[DigestBlake2b224] {
+        &self.required_extra_signatories
+    }
+
+    /// Witness set, rebuilt from the fields Ogmios v6 sends in its place.
+    #[deprecated(note = "use `signatories`, `scripts`, `datums` and `redeemers`")]
+    #[allow(deprecated)]
+    pub fn witnesses(&self) -> Witnesses {
+        let (bootstrap, keys): (Vec<_>, Vec<_>) =
+            self.signatories.iter().partition(|signatory| signatory.is_bootstrap());
+        Witnesses {
+            keys: keys
+                .into_iter()
+                .map(|signatory| KeyWitness {
+                    key: signatory.key.clone(),
+                    signature: signatory.signature.clone(),
+                })
+                .collect(),
+            scripts: self.scripts.clone(),
+            bootstrap: bootstrap
+                .into_iter()
+                .map(|signatory| BootstrapWitness {
+                    key: signatory.key.clone(),
+                    chain_code: signatory.chain_code.clone().unwrap_or_default(),
+                    address_attributes: signatory.address_attributes.clone().unwrap_or_default(),
+                    signature: signatory.signature.clone(),
+                })
+                .collect(),
+            datums: self.datums.clone(),
+            redeemers: self.redeemers.clone(),
+        }
+    }
+}
+
 /// A transaction input.
+///
+/// On the wire, an input is `{ "transaction": { "id": .. }, "index": .. }`;
+/// the form with the index inside `transaction` is accepted as well.
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(try_from = "OutputReferenceRepr", into = "OutputReferenceRepr")]
 pub struct TransactionInput {
     /// Transaction ID containing the output.
     pub transaction: TransactionOutputReference,
 }
 
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct OutputReferenceRepr {
+    transaction: TransactionRepr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<u32>,
+}
+
+#[derive(Serialize, Deserialize)]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+struct TransactionRepr {
+    id: TransactionId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<u32>,
+}
+
+impl TryFrom<OutputReferenceRepr> for TransactionOutputReference {
+    type Error = &'static str;
+
+    fn try_from(repr: OutputReferenceRepr) -> Result<Self, Self::Error> {
+        let index = repr
+            .index
+            .or(repr.transaction.index)
+            .ok_or("missing field `index`")?;
+        Ok(Self {
+            id: repr.transaction.id,
+            index,
+        })
+    }
+}
+
+impl From<TransactionOutputReference> for OutputReferenceRepr {
+    fn from(reference: TransactionOutputReference) -> Self {
+        Self {
+            transaction: TransactionRepr {
+                id: reference.id,
+                index: None,
+            },
+            index: Some(reference.index),
+        }
+    }
+}
+
+impl TryFrom<OutputReferenceRepr> for TransactionInput {
+    type Error = &'static str;
+
+    fn try_from(repr: OutputReferenceRepr) -> Result<Self, Self::Error> {
+        Ok(Self {
+            transaction: repr.try_into()?,
+        })
+    }
+}
+
+impl From<TransactionInput> for OutputReferenceRepr {
+    fn from(input: TransactionInput) -> Self {
+        input.transaction.into()
+    }
+}
+
 /// Reference to a transaction output.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+///
+/// Formats and parses as `<transaction id>#<index>`, the convention of
+/// command-line tools and explorers.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::TransactionOutputReference;
+///
+/// let reference: TransactionOutputReference =
+///     "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7#1".parse().unwrap();
+/// assert_eq!(reference.index, 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct TransactionOutputReference {
     /// Transaction ID.
     pub id: TransactionId,
@@ -103,11 +351,73 @@ pub struct TransactionOutputReference {
 }
 
 impl TransactionOutputReference {
-    pub fn new(id: impl Into<String>, index: u32) -> Self {
-        Self {
-            id: id.into(),
-            index,
-        }
+    pub fn new(id: TransactionId, index: u32) -> Self {
+        Self { id, index }
+    }
+}
+
+/// A string that is not an output reference.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum InvalidOutputReference {
+    /// The `#` separator is missing.
+    #[error("invalid output reference {value:?}: expected <transaction id>#<index>")]
+    Format {
+        /// Rejected value.
+        value: String,
+    },
+    /// The transaction ID half is malformed.
+    #[error("invalid transaction ID in output reference {value:?}: {source}")]
+    TransactionId {
+        /// Rejected value.
+        value: String,
+        /// Why the transaction ID was rejected.
+        source: InvalidDigest,
+    },
+    /// The index half is malformed.
+    #[error("invalid index in output reference {value:?}: {source}")]
+    Index {
+        /// Rejected value.
+        value: String,
+        /// Why the index was rejected.
+        source: std::num::ParseIntError,
+    },
+}
+
+impl std::fmt::Display for TransactionOutputReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}#{}", self.id, self.index)
+    }
+}
+
+impl std::str::FromStr for TransactionOutputReference {
+    type Err = InvalidOutputReference;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = s.to_string();
+        let (id, index) = parse_indexed_id(s).map_err(|error| match error {
+            InvalidIndexedId::Format => InvalidOutputReference::Format { value },
+            InvalidIndexedId::TransactionId(source) => {
+                InvalidOutputReference::TransactionId { value, source }
+            }
+            InvalidIndexedId::Index(source) => InvalidOutputReference::Index { value, source },
+        })?;
+        Ok(Self { id, index })
+    }
+}
+
+impl TryFrom<&str> for TransactionOutputReference {
+    type Error = InvalidOutputReference;
+
+    fn try_from(s: &str) -> Result<Self, Self::Error> {
+        s.parse()
+    }
+}
+
+impl TryFrom<String> for TransactionOutputReference {
+    type Error = InvalidOutputReference;
+
+    fn try_from(s: String) -> Result<Self, Self::Error> {
+        s.parse()
     }
 }
 
@@ -131,19 +441,89 @@ pub struct TransactionOutput {
 }
 
 /// UTXO - a transaction output with its reference.
+///
+/// Sent as an output with the `transaction` and `index` of its reference,
+/// like a [`TransactionInput`].
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+#[serde(try_from = "UtxoRepr", into = "UtxoRepr")]
 pub struct Utxo {
     /// Transaction reference.
     pub transaction: TransactionOutputReference,
     /// The output.
-    #[serde(flatten)]
     pub output: TransactionOutput,
 }
 
+#[derive(Serialize, Deserialize)]
+struct UtxoRepr {
+    transaction: TransactionRepr,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    index: Option<u32>,
+    #[serde(flatten)]
+    output: TransactionOutput,
+}
+
+impl TryFrom<UtxoRepr> for Utxo {
+    type Error = &'static str;
+
+    fn try_from(repr: UtxoRepr) -> Result<Self, Self::Error> {
+        let reference = OutputReferenceRepr {
+            transaction: repr.transaction,
+            index: repr.index,
+        };
+        Ok(Self {
+            transaction: reference.try_into()?,
+            output: repr.output,
+        })
+    }
+}
+
+impl From<Utxo> for UtxoRepr {
+    fn from(utxo: Utxo) -> Self {
+        let OutputReferenceRepr { transaction, index } = utxo.transaction.into();
+        Self {
+            transaction,
+            index,
+            output: utxo.output,
+        }
+    }
+}
+
+/// A signature of the transaction: a key witness, or a bootstrap witness
+/// of a Byron address.
+///
+/// Bootstrap witnesses carry the chain code and the attributes of the
+/// address, except for redeem addresses, which have no chain code.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct Signatory {
+    /// Verification key.
+    pub key: VerificationKey,
+    /// Signature.
+    pub signature: Signature,
+    /// Chain code (bootstrap witnesses).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub chain_code: Option<String>,
+    /// Address attributes (bootstrap witnesses).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address_attributes: Option<String>,
+}
+
+impl Signatory {
+    /// Whether this is the witness of a Byron address.
+    pub fn is_bootstrap(&self) -> bool {
+        self.chain_code.is_some() || self.address_attributes.is_some()
+    }
+}
+
 /// Witness set for a transaction.
+///
+/// Ogmios v6 sends no witness set; see [`Transaction::witnesses`].
+#[deprecated(note = "use the `signatories`, `scripts`, `datums` and `redeemers` of `Transaction`")]
+#[allow(deprecated)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Witnesses {
     /// Key witnesses.
     #[serde(default)]
@@ -163,8 +543,10 @@ pub struct Witnesses {
 }
 
 /// A key witness (signature).
+#[deprecated(note = "use `Signatory`")]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct KeyWitness {
     /// Verification key.
     pub key: VerificationKey,
@@ -173,31 +555,67 @@ pub struct KeyWitness {
 }
 
 /// Bootstrap witness for Byron-era transactions.
+///
+/// Witnesses of redeem addresses have no chain code, and addresses without
+/// attributes have none either; missing values are empty.
+#[deprecated(note = "use `Signatory`")]
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct BootstrapWitness {
     /// Verification key.
     pub key: VerificationKey,
     /// Chain code.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub chain_code: String,
     /// Address attributes.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub address_attributes: String,
     /// Signature.
     pub signature: Signature,
 }
 
+/// Treasury fields of a Conway transaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
+pub struct TreasuryTransfer {
+    /// Value of the treasury the transaction expects.
+    #[serde(default, with = "ada_amount", skip_serializing_if = "Option::is_none")]
+    pub value: Option<Lovelace>,
+    /// Amount donated to the treasury.
+    #[serde(default, with = "ada_amount", skip_serializing_if = "Option::is_none")]
+    pub donation: Option<Lovelace>,
+}
+
 /// Transaction metadata.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct Metadata {
     /// Metadata labels and values.
-    #[serde(default)]
+    #[serde(default, deserialize_with = "deserialize_labels")]
     pub labels: MetadataLabels,
     /// Hash of the metadata.
     #[serde(default)]
     pub hash: Option<DigestBlake2b256>,
 }
 
+impl Metadata {
+    /// Get the value under a metadata label, e.g. `674` for CIP-20 messages.
+    pub fn label(&self, label: u64) -> Option<&Metadatum> {
+        self.labels.get(&label)
+    }
+
+    /// Iterate over the labels in a range, in ascending order.
+    pub fn labels_in(
+        &self,
+        range: impl std::ops::RangeBounds<u64>,
+    ) -> impl Iterator<Item = (u64, &Metadatum)> {
+        self.labels.range(range).map(|(label, value)| (*label, value))
+    }
+}
+
 /// Input source type.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -209,8 +627,9 @@ pub enum InputSource {
 }
 
 /// Transaction evaluation result.
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct EvaluationResult {
     /// Validator index.
     pub validator: ValidatorIndex,
@@ -218,9 +637,35 @@ pub struct EvaluationResult {
     pub budget: ExUnits,
 }
 
+impl EvaluationResult {
+    /// Collect evaluation results into budgets keyed by validator.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use ogmios_client::schema::{EvaluationResult, ExUnits, ScriptPurpose, ValidatorIndex};
+    ///
+    /// let spend = ValidatorIndex { purpose: ScriptPurpose::Spend, index: 0 };
+    /// let budgets = EvaluationResult::budgets(vec![EvaluationResult {
+    ///     validator: spend.clone(),
+    ///     budget: ExUnits::new(1_700, 476_468),
+    /// }]);
+    /// assert_eq!(budgets[&spend].memory, 1_700);
+    /// ```
+    pub fn budgets(
+        results: impl IntoIterator<Item = EvaluationResult>,
+    ) -> HashMap<ValidatorIndex, ExUnits> {
+        results
+            .into_iter()
+            .map(|result| (result.validator, result.budget))
+            .collect()
+    }
+}
+
 /// Validator index in a transaction.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ValidatorIndex {
     /// Purpose of the script.
     pub purpose: ScriptPurpose,
@@ -229,8 +674,10 @@ pub struct ValidatorIndex {
 }
 
 /// Purpose of a script execution.
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
+///
+/// Purposes added by later eras are kept as [`Other`](ScriptPurpose::Other).
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum ScriptPurpose {
     /// Spending a UTXO.
     Spend,
@@ -244,11 +691,23 @@ pub enum ScriptPurpose {
     Propose,
     /// Voting (Conway).
     Vote,
+    /// Purpose not known to this version.
+    Other(String),
 }
 
+named_enum!(ScriptPurpose {
+    Spend => "spend",
+    Mint => "mint",
+    Publish => "publish",
+    Withdraw => "withdraw",
+    Propose => "propose",
+    Vote => "vote",
+});
+
 /// Execution units (memory and CPU steps).
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
+#[cfg_attr(feature = "strict-schema", serde(deny_unknown_fields))]
 pub struct ExUnits {
     /// Memory units.
     pub memory: u64,
@@ -261,3 +720,133 @@ impl ExUnits {
         Self { memory, cpu }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TX_ID: &str = "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7";
+
+    /// The script transaction of the captured Babbage block, which carries
+    /// collaterals and a collateral return.
+    fn script_transaction() -> serde_json::Value {
+        let response = crate::schema::testing::fixture("chain-sync/nextBlock-babbage");
+        response.result()["block"]["transactions"][0].clone()
+    }
+
+    #[test]
+    fn test_script_transaction_spends_inputs() {
+        let transaction: Transaction = serde_json::from_value(script_transaction()).unwrap();
+        assert_eq!(transaction.spends, Some(InputSource::Inputs));
+        assert_eq!(transaction.consumed_inputs(), transaction.inputs.as_slice());
+        let indices: Vec<_> = transaction.produced_outputs().map(|(r, _)| r.index).collect();
+        assert_eq!(indices, [0, 1]);
+        assert_eq!(transaction.total_collateral, Some(582_378));
+    }
+
+    /// Synthetic: the captured script transaction with `spends` flipped, since
+    /// no transaction that failed phase-2 validation is captured yet (see
+    /// `fixtures/README.md`).
+    #[test]
+    fn test_failed_script_transaction_spends_collaterals() {
+        let mut fixture = script_transaction();
+        fixture["spends"] = serde_json::json!("collaterals");
+        let transaction: Transaction = serde_json::from_value(fixture).unwrap();
+        assert_eq!(transaction.spends, Some(InputSource::Collaterals));
+        assert_eq!(transaction.consumed_inputs(), transaction.collaterals.as_slice());
+
+        let produced: Vec<_> = transaction.produced_outputs().collect();
+        assert_eq!(produced.len(), 1);
+        let id = transaction.id.as_str();
+        assert_eq!(produced[0].0, TransactionOutputReference::new(id.parse().unwrap(), 2));
+        assert_eq!(Some(produced[0].1), transaction.collateral_return.as_ref());
+
+        let serialized = serde_json::to_value(&transaction).unwrap();
+        assert_eq!(serialized["spends"], "collaterals");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_accessors_read_the_v6_fields() {
+        let transaction: Transaction = serde_json::from_value(script_transaction()).unwrap();
+        assert_eq!(transaction.valid_from(), Some(87_246_000));
+        assert_eq!(transaction.valid_until(), Some(87_253_471));
+        assert_eq!(
+            transaction.required_extra_signers(),
+            ["840b16037767b83f7dd82e1132e61dc63f86099ea3ce8079fae25d89"]
+        );
+        let witnesses = transaction.witnesses();
+        assert_eq!(witnesses.keys.len(), 1);
+        assert_eq!(witnesses.keys[0].key, transaction.signatories[0].key);
+        assert!(witnesses.bootstrap.is_empty());
+        assert_eq!(witnesses.redeemers, transaction.redeemers);
+        assert_eq!(witnesses.redeemers[0].datum(), &transaction.redeemers[0].data);
+
+        let response = crate::schema::testing::fixture("chain-sync/nextBlock-byron-bft");
+        let byron: Transaction =
+            serde_json::from_value(response.result()["block"]["transactions"][0].clone()).unwrap();
+        let witnesses = byron.witnesses();
+        assert!(witnesses.keys.is_empty());
+        assert_eq!(witnesses.bootstrap[0].address_attributes, "a0");
+    }
+
+    #[test]
+    fn test_spends_defaults_from_validity() {
+        let transaction: Transaction = serde_json::from_value(serde_json::json!({ "id": TX_ID }))
+            .unwrap();
+        assert_eq!(transaction.spends, None);
+        assert_eq!(transaction.input_source(), InputSource::Inputs);
+
+        let invalid: Transaction =
+            serde_json::from_value(serde_json::json!({ "id": TX_ID, "valid": false })).unwrap();
+        assert_eq!(invalid.input_source(), InputSource::Collaterals);
+    }
+
+    #[test]
+    fn test_output_reference_string_form() {
+        let reference: TransactionOutputReference = format!("{TX_ID}#7").parse().unwrap();
+        assert_eq!(reference, TransactionOutputReference::new(TX_ID.parse().unwrap(), 7));
+        assert_eq!(reference.to_string(), format!("{TX_ID}#7"));
+
+        let upper = format!("{}#7", TX_ID.to_uppercase());
+        assert_eq!(TransactionOutputReference::try_from(upper.as_str()).unwrap(), reference);
+
+        let spent = std::collections::HashSet::from([reference.clone()]);
+        assert!(spent.contains(&reference));
+    }
+
+    #[test]
+    fn test_output_reference_errors_name_the_bad_half() {
+        assert!(matches!(
+            TX_ID.parse::<TransactionOutputReference>(),
+            Err(InvalidOutputReference::Format { .. })
+        ));
+        assert!(matches!(
+            format!("{}#0", &TX_ID[1..]).parse::<TransactionOutputReference>(),
+            Err(InvalidOutputReference::TransactionId { .. })
+        ));
+        for index in ["", "-1", "4294967296", "one"] {
+            let error = format!("{TX_ID}#{index}")
+                .parse::<TransactionOutputReference>()
+                .unwrap_err();
+            assert!(matches!(error, InvalidOutputReference::Index { .. }), "{index}");
+            assert!(error.to_string().starts_with("invalid index"), "{error}");
+        }
+    }
+
+    #[test]
+    fn test_unknown_script_purpose_round_trip() {
+        let results: Vec<EvaluationResult> = serde_json::from_value(serde_json::json!([
+            { "validator": { "purpose": "spend", "index": 0 },
+              "budget": { "memory": 1700, "cpu": 476468 } },
+            { "validator": { "purpose": "guard", "index": 1 },
+              "budget": { "memory": 0, "cpu": 0 } }
+        ]))
+        .unwrap();
+        assert_eq!(results[0].validator.purpose, ScriptPurpose::Spend);
+        let guard = &results[1].validator;
+        assert_eq!(guard.purpose, ScriptPurpose::Other("guard".to_string()));
+        assert_eq!(serde_json::to_value(guard).unwrap()["purpose"], "guard");
+        assert!(EvaluationResult::budgets(results.clone()).contains_key(guard));
+    }
+}
@@ -3,14 +3,383 @@
 //! This module provides functions to check the health of an Ogmios server
 //! and verify it's ready to accept connections.
 
-use crate::connection::{create_connection_object, Connection, ConnectionConfig};
-use crate::error::{OgmiosError, Result};
-use crate::schema::ServerHealth;
+use crate::connection::{Connection, ConnectionConfig, shared_http_client};
+use crate::error::{NotReadyReason, OgmiosError, Result};
+use crate::prometheus::PrometheusMetrics;
+use crate::schema::{ServerHealth, Tip};
+use std::hash::{BuildHasher, RandomState};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
 use tracing::debug;
 
 /// Default minimum synchronization required (99.99%).
 pub const DEFAULT_MIN_SYNCHRONIZATION: f64 = 0.999;
 
+/// Default timeout of a health request.
+pub const DEFAULT_HEALTH_CHECK_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Options for health requests.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthCheckOptions {
+    /// Fail a health request that takes longer than this.
+    pub timeout: Duration,
+    /// Extra headers sent with each health request, after the connection headers.
+    pub headers: Vec<(String, String)>,
+    /// `User-Agent` header sent with each health request.
+    pub user_agent: Option<String>,
+}
+
+impl Default for HealthCheckOptions {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_HEALTH_CHECK_TIMEOUT,
+            headers: Vec::new(),
+            user_agent: None,
+        }
+    }
+}
+
+/// Health checker for one Ogmios server.
+///
+/// Holds a [`reqwest::Client`] that is reused across checks, so polling keeps
+/// a pooled connection alive. By default the client is shared with the free
+/// functions of this module; inject a custom one with
+/// [`with_http_client`](Self::with_http_client) to configure proxies, TLS or
+/// connection pool settings.
+///
+/// Health requests carry the [`headers`](ConnectionConfig::headers) of the
+/// connection configuration, the same ones sent with the WebSocket handshake,
+/// followed by those of the [`HealthCheckOptions`].
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::server_health::HealthClient;
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let http = reqwest::Client::builder()
+///     .proxy(reqwest::Proxy::http("http://proxy.local:3128")?)
+///     .build()?;
+/// let client = HealthClient::new(None)?
+///     .with_http_client(http)
+///     .with_timeout(Duration::from_secs(5));
+/// let health = client.wait_for_ready(0.99, Duration::from_secs(1), Duration::from_secs(60)).await?;
+/// println!("Server is ready at {:.2}% sync", health.network_synchronization * 100.0);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HealthClient {
+    http: reqwest::Client,
+    connection: Connection,
+    connection_headers: Vec<(String, String)>,
+    options: HealthCheckOptions,
+}
+
+impl HealthClient {
+    /// Create a health checker for a connection configuration.
+    ///
+    /// Uses the shared HTTP client, unless the configuration carries custom
+    /// [`TlsConfig`](crate::connection::TlsConfig) settings, which get a
+    /// dedicated client.
+    ///
+    /// # Errors
+    ///
+    /// Fails if the TLS settings are invalid.
+    pub fn new(connection: Option<ConnectionConfig>) -> Result<Self> {
+        let config = connection.unwrap_or_default();
+        let mut client = Self::from_connection(Connection::from_config(&config));
+        client.connection_headers = config.headers;
+        if !config.tls_config.is_default() {
+            client.http = config.tls_config.http_client()?;
+        }
+        Ok(client)
+    }
+
+    /// Create a health checker for a connection object, using the shared HTTP client.
+    pub fn from_connection(connection: Connection) -> Self {
+        Self {
+            http: shared_http_client().clone(),
+            connection,
+            connection_headers: Vec::new(),
+            options: HealthCheckOptions::default(),
+        }
+    }
+
+    /// Use the given HTTP client instead of the shared one.
+    pub fn with_http_client(mut self, http: reqwest::Client) -> Self {
+        self.http = http;
+        self
+    }
+
+    /// Use the given health request options.
+    pub fn with_options(mut self, options: HealthCheckOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Fail each health request that takes longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.options.timeout = timeout;
+        self
+    }
+
+    /// Get the server health.
+    ///
+    /// Fails with the [`Http`](OgmiosError::Http) error of the status if the
+    /// server answers with an error status and no health document, e.g. a
+    /// proxy rejecting the credentials.
+    pub async fn health(&self) -> Result<ServerHealth> {
+        let response = self.get("health").await?;
+        let status = response.error_for_status_ref().err();
+        let body = response.bytes().await?;
+
+        match serde_json::from_slice(&body) {
+            Ok(health) => Ok(health),
+            Err(e) => Err(status.map_or_else(|| e.into(), OgmiosError::from)),
+        }
+    }
+
+    /// Get the server metrics from the Prometheus endpoint.
+    ///
+    /// See [`get_server_metrics`].
+    pub async fn metrics(&self) -> Result<PrometheusMetrics> {
+        let text = self.get("metrics").await?.error_for_status()?.text().await?;
+        PrometheusMetrics::parse(&text)
+    }
+
+    async fn get(&self, path: &str) -> Result<reqwest::Response> {
+        let url = format!("{}/{}", self.connection.address.http, path);
+        debug!("Fetching {}", url);
+
+        let mut request = self.http.get(&url).timeout(self.options.timeout);
+        for (name, value) in self.connection_headers.iter().chain(&self.options.headers) {
+            request = request.header(name.as_str(), value.as_str());
+        }
+        if let Some(user_agent) = &self.options.user_agent {
+            request = request.header(reqwest::header::USER_AGENT, user_agent.as_str());
+        }
+
+        Ok(request.send().await?)
+    }
+
+    /// Get the server health, failing if the synchronization is below `min_synchronization`.
+    ///
+    /// See [`ensure_server_health`].
+    pub async fn ensure_health(&self, min_synchronization: f64) -> Result<ServerHealth> {
+        let health = self.health().await?;
+
+        if !health.is_node_connected() {
+            return Err(OgmiosError::NodeDisconnected);
+        }
+        if health.network_synchronization < min_synchronization {
+            return Err(OgmiosError::ServerNotReady(Box::new(ServerNotReady {
+                synchronization: health.network_synchronization,
+                minimum: min_synchronization,
+                health,
+            })));
+        }
+
+        Ok(health)
+    }
+
+    /// Poll the server health until the synchronization reaches `min_synchronization`.
+    ///
+    /// See [`wait_for_server_ready`].
+    pub async fn wait_for_ready(
+        &self,
+        min_synchronization: f64,
+        poll_interval: Duration,
+        timeout: Duration,
+    ) -> Result<ServerHealth> {
+        self.poll_until_ready(&WaitForServerReadyOptions {
+            min_synchronization,
+            poll_interval,
+            timeout,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Poll with the schedule of `options`, ignoring its connection and
+    /// health check settings, which this client already carries.
+    async fn poll_until_ready(&self, options: &WaitForServerReadyOptions) -> Result<ServerHealth> {
+        let deadline = tokio::time::Instant::now() + options.timeout;
+        let mut interval = options.poll_interval;
+        let mut last_health = None;
+
+        loop {
+            let reason = match self.health().await {
+                Ok(health) => {
+                    if let Some(on_progress) = &options.on_progress {
+                        on_progress(&health);
+                    }
+                    let reason = if !health.is_node_connected() {
+                        debug!("Server disconnected from its node, waiting");
+                        NotReadyReason::NodeDisconnected
+                    } else if health.network_synchronization >= options.min_synchronization {
+                        return Ok(health);
+                    } else {
+                        debug!(
+                            "Server sync at {:.2}%, waiting for {:.2}%",
+                            health.network_synchronization * 100.0,
+                            options.min_synchronization * 100.0
+                        );
+                        NotReadyReason::Syncing {
+                            synchronization: health.network_synchronization,
+                            minimum: options.min_synchronization,
+                        }
+                    };
+                    last_health = Some(Box::new(health));
+                    reason
+                }
+                Err(e) if !e.is_retryable() => return Err(e),
+                Err(e) => {
+                    debug!("Health check failed: {}, retrying...", e);
+                    NotReadyReason::Unreachable(e.to_string())
+                }
+            };
+
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(OgmiosError::ServerReadyTimeout {
+                    timeout_ms: options.timeout.as_millis() as u64,
+                    reason,
+                    last_health,
+                });
+            }
+
+            tokio::time::sleep(options.backoff.delay(interval).min(deadline - now)).await;
+            interval = options.backoff.next_interval(interval);
+        }
+    }
+
+    /// Poll the server health every `interval`, publishing each result.
+    ///
+    /// See [`watch_server_health`].
+    pub async fn watch(
+        &self,
+        interval: Duration,
+    ) -> (watch::Receiver<HealthUpdate>, WatchHandle) {
+        let last_success = Arc::new(Mutex::new(None));
+        let first = self.poll(&last_success).await;
+        let (tx, rx) = watch::channel(first);
+
+        let client = self.clone();
+        let task_last_success = last_success.clone();
+        let task = tokio::spawn(async move {
+            let mut ticks =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                ticks.tick().await;
+                let update = client.poll(&task_last_success).await;
+                if tx.send(update).is_err() {
+                    debug!("All health receivers dropped, stopping watcher");
+                    return;
+                }
+            }
+        });
+
+        (rx, WatchHandle { task, last_success })
+    }
+
+    async fn poll(&self, last_success: &Mutex<Option<Instant>>) -> HealthUpdate {
+        let update = self.health().await.map_err(HealthError::from);
+        if update.is_ok() {
+            *last_success.lock().unwrap() = Some(Instant::now());
+        }
+        update
+    }
+}
+
+/// Failed health poll published by [`watch_server_health`].
+///
+/// Wraps the underlying [`OgmiosError`] so that it can be shared by every
+/// receiver of the watch channel.
+#[derive(Debug, Clone, thiserror::Error)]
+#[error(transparent)]
+pub struct HealthError(Arc<OgmiosError>);
+
+impl HealthError {
+    /// Get the underlying error.
+    pub fn error(&self) -> &OgmiosError {
+        &self.0
+    }
+}
+
+impl From<OgmiosError> for HealthError {
+    fn from(error: OgmiosError) -> Self {
+        Self(Arc::new(error))
+    }
+}
+
+/// Result of one health poll published by [`watch_server_health`].
+pub type HealthUpdate = std::result::Result<ServerHealth, HealthError>;
+
+/// Handle to a health watcher started by [`watch_server_health`].
+///
+/// Dropping the handle stops the watcher.
+#[derive(Debug)]
+pub struct WatchHandle {
+    task: JoinHandle<()>,
+    last_success: Arc<Mutex<Option<Instant>>>,
+}
+
+impl WatchHandle {
+    /// When the last successful health poll completed, if any did.
+    pub fn last_success(&self) -> Option<Instant> {
+        *self.last_success.lock().unwrap()
+    }
+
+    /// Stop the watcher.
+    pub fn stop(self) {}
+}
+
+impl Drop for WatchHandle {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Shortcuts to the latest health on a [`watch_server_health`] receiver.
+pub trait HealthReceiverExt {
+    /// Network synchronization of the latest poll, or `None` if it failed.
+    fn network_synchronization(&self) -> Option<f64>;
+
+    /// Last known tip of the latest poll, or `None` if it failed.
+    fn last_known_tip(&self) -> Option<Tip>;
+
+    /// Whether the latest poll succeeded with at least `min_synchronization`
+    /// and Ogmios connected to its node.
+    fn is_ready(&self, min_synchronization: f64) -> bool;
+}
+
+impl HealthReceiverExt for watch::Receiver<HealthUpdate> {
+    fn network_synchronization(&self) -> Option<f64> {
+        self.borrow()
+            .as_ref()
+            .ok()
+            .map(|health| health.network_synchronization)
+    }
+
+    fn last_known_tip(&self) -> Option<Tip> {
+        self.borrow()
+            .as_ref()
+            .ok()
+            .map(|health| health.last_known_tip.clone())
+    }
+
+    fn is_ready(&self, min_synchronization: f64) -> bool {
+        self.borrow().as_ref().is_ok_and(|health| {
+            health.is_node_connected() && health.network_synchronization >= min_synchronization
+        })
+    }
+}
+
 /// Get the server health.
 ///
 /// This can be safely polled at regular intervals for monitoring.
@@ -36,19 +405,65 @@ pub const DEFAULT_MIN_SYNCHRONIZATION: f64 = 0.999;
 /// # }
 /// ```
 pub async fn get_server_health(connection: Option<ConnectionConfig>) -> Result<ServerHealth> {
-    let conn = create_connection_object(connection);
-    get_server_health_from_connection(&conn).await
+    get_server_health_with_options(connection, HealthCheckOptions::default()).await
 }
 
-/// Get the server health from a connection object.
-pub async fn get_server_health_from_connection(connection: &Connection) -> Result<ServerHealth> {
-    let url = format!("{}/health", connection.address.http);
-    debug!("Fetching server health from {}", url);
+/// Get the server health with the given request options.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::connection::ConnectionConfig;
+/// use ogmios_client::server_health::{HealthCheckOptions, get_server_health_with_options};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let connection = ConnectionConfig::new("ogmios.example.com", 443)
+///     .with_tls()
+///     .with_header("dmtr-api-key", "secret");
+/// let options = HealthCheckOptions {
+///     timeout: Duration::from_secs(2),
+///     user_agent: Some("readiness-probe".into()),
+///     ..Default::default()
+/// };
+/// let health = get_server_health_with_options(Some(connection), options).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_server_health_with_options(
+    connection: Option<ConnectionConfig>,
+    options: HealthCheckOptions,
+) -> Result<ServerHealth> {
+    HealthClient::new(connection)?
+        .with_options(options)
+        .health()
+        .await
+}
 
-    let response = reqwest::get(&url).await?;
-    let health: ServerHealth = response.json().await?;
+/// Get the server metrics from the Prometheus `/metrics` endpoint.
+///
+/// Reports counters that are not part of the health document, such as runtime
+/// and garbage collection statistics.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::server_health::get_server_metrics;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let metrics = get_server_metrics(None).await?;
+/// println!("Connected clients: {:?}", metrics.active_connections());
+/// println!("GC CPU time: {:?}s", metrics.runtime_gc_cpu_time());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn get_server_metrics(connection: Option<ConnectionConfig>) -> Result<PrometheusMetrics> {
+    HealthClient::new(connection)?.metrics().await
+}
 
-    Ok(health)
+/// Get the server health from a connection object.
+pub async fn get_server_health_from_connection(connection: &Connection) -> Result<ServerHealth> {
+    HealthClient::from_connection(connection.clone()).health().await
 }
 
 /// Options for ensuring server health.
@@ -58,6 +473,8 @@ pub struct EnsureServerHealthOptions {
     pub connection: Option<ConnectionConfig>,
     /// Minimum network synchronization required (0.0 to 1.0).
     pub min_synchronization: f64,
+    /// Health request options.
+    pub health_check: HealthCheckOptions,
 }
 
 impl Default for EnsureServerHealthOptions {
@@ -65,6 +482,7 @@ impl Default for EnsureServerHealthOptions {
         Self {
             connection: None,
             min_synchronization: DEFAULT_MIN_SYNCHRONIZATION,
+            health_check: HealthCheckOptions::default(),
         }
     }
 }
@@ -84,7 +502,9 @@ impl Default for EnsureServerHealthOptions {
 ///
 /// # Errors
 ///
-/// Returns `OgmiosError::ServerNotReady` if the synchronization is below the minimum.
+/// Returns `OgmiosError::NodeDisconnected` if Ogmios reports that it cannot reach
+/// its node, whatever the synchronization, and `OgmiosError::ServerNotReady` if
+/// the synchronization is below the minimum.
 ///
 /// # Example
 ///
@@ -102,27 +522,21 @@ impl Default for EnsureServerHealthOptions {
 /// # }
 /// ```
 pub async fn ensure_server_health(options: EnsureServerHealthOptions) -> Result<ServerHealth> {
-    let health = get_server_health(options.connection).await?;
-
-    if health.network_synchronization < options.min_synchronization {
-        return Err(OgmiosError::ServerNotReady {
-            synchronization: health.network_synchronization * 100.0,
-            minimum: options.min_synchronization * 100.0,
-        });
-    }
-
-    Ok(health)
+    HealthClient::new(options.connection)?
+        .with_options(options.health_check)
+        .ensure_health(options.min_synchronization)
+        .await
 }
 
 /// Server not ready error with detailed information.
 ///
-/// This is a structured error type that provides more context about why
-/// the server is not ready.
+/// Carried by [`OgmiosError::ServerNotReady`], so that callers can report the
+/// tip, era or version of the server alongside the synchronization.
 #[derive(Debug, Clone)]
 pub struct ServerNotReady {
-    /// Current network synchronization.
+    /// Current network synchronization (0.0 to 1.0).
     pub synchronization: f64,
-    /// Required minimum synchronization.
+    /// Required minimum synchronization (0.0 to 1.0).
     pub minimum: f64,
     /// Server health information.
     pub health: ServerHealth,
@@ -141,6 +555,111 @@ impl std::fmt::Display for ServerNotReady {
 
 impl std::error::Error for ServerNotReady {}
 
+/// Backoff between the health checks of [`wait_for_server_ready_with_options`].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PollBackoff {
+    /// Factor applied to the interval after each health check (`1.0` keeps it fixed).
+    pub multiplier: f64,
+    /// Upper bound of the interval.
+    pub max_interval: Duration,
+    /// Wait a random duration between half and all of each interval, so that
+    /// many clients started together do not poll in lockstep.
+    pub jitter: bool,
+}
+
+impl PollBackoff {
+    /// Poll at a fixed interval.
+    pub fn fixed() -> Self {
+        Self {
+            multiplier: 1.0,
+            max_interval: Duration::MAX,
+            jitter: false,
+        }
+    }
+
+    /// Double the interval after each health check, up to `max_interval`, with jitter.
+    pub fn exponential(max_interval: Duration) -> Self {
+        Self {
+            multiplier: 2.0,
+            max_interval,
+            jitter: true,
+        }
+    }
+
+    fn next_interval(&self, interval: Duration) -> Duration {
+        Duration::try_from_secs_f64(interval.as_secs_f64() * self.multiplier)
+            .unwrap_or(self.max_interval)
+            .min(self.max_interval)
+    }
+
+    fn delay(&self, interval: Duration) -> Duration {
+        let interval = interval.min(self.max_interval);
+        if self.jitter {
+            // Uniform in [0.5, 1.0) from the 53 high bits of a random hash.
+            let random = RandomState::new().hash_one(Instant::now()) >> 11;
+            interval.mul_f64(0.5 + random as f64 / (1u64 << 54) as f64)
+        } else {
+            interval
+        }
+    }
+}
+
+impl Default for PollBackoff {
+    fn default() -> Self {
+        Self::fixed()
+    }
+}
+
+/// Callback reporting each successful health check while waiting for the server.
+pub type ProgressCallback = Box<dyn Fn(&ServerHealth) + Send + Sync>;
+
+/// Options for [`wait_for_server_ready_with_options`].
+pub struct WaitForServerReadyOptions {
+    /// Connection configuration.
+    pub connection: Option<ConnectionConfig>,
+    /// Health request options.
+    pub health_check: HealthCheckOptions,
+    /// Minimum network synchronization required (0.0 to 1.0).
+    pub min_synchronization: f64,
+    /// Interval before the second health check.
+    pub poll_interval: Duration,
+    /// How the interval evolves between health checks.
+    pub backoff: PollBackoff,
+    /// Maximum time to wait for the server to be ready.
+    pub timeout: Duration,
+    /// Called with the health of each successful health check, e.g. to report
+    /// the synchronization progress.
+    pub on_progress: Option<ProgressCallback>,
+}
+
+impl Default for WaitForServerReadyOptions {
+    fn default() -> Self {
+        Self {
+            connection: None,
+            health_check: HealthCheckOptions::default(),
+            min_synchronization: DEFAULT_MIN_SYNCHRONIZATION,
+            poll_interval: Duration::from_secs(1),
+            backoff: PollBackoff::fixed(),
+            timeout: Duration::from_secs(60),
+            on_progress: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for WaitForServerReadyOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitForServerReadyOptions")
+            .field("connection", &self.connection)
+            .field("health_check", &self.health_check)
+            .field("min_synchronization", &self.min_synchronization)
+            .field("poll_interval", &self.poll_interval)
+            .field("backoff", &self.backoff)
+            .field("timeout", &self.timeout)
+            .field("on_progress", &self.on_progress.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
 /// Wait for the server to be ready.
 ///
 /// This function polls the server health at regular intervals until the server
@@ -159,50 +678,608 @@ impl std::error::Error for ServerNotReady {}
 ///
 /// # Errors
 ///
-/// Returns `OgmiosError::Timeout` if the server doesn't become ready within the timeout.
+/// Returns `OgmiosError::ServerReadyTimeout` if the server doesn't become ready
+/// within the timeout, telling whether it was unreachable or still syncing,
+/// with the last health document observed. Fails right away with the error
+/// of a health check that is not [retryable](OgmiosError::is_retryable),
+/// such as a rejected API key.
+///
+/// Every poll goes through the same HTTP client, reusing its connection. See
+/// [`wait_for_server_ready_with_options`] for backoff, progress reporting and
+/// health request options.
 pub async fn wait_for_server_ready(
     connection: Option<ConnectionConfig>,
     min_synchronization: f64,
-    poll_interval: std::time::Duration,
-    timeout: std::time::Duration,
+    poll_interval: Duration,
+    timeout: Duration,
+) -> Result<ServerHealth> {
+    wait_for_server_ready_with_options(WaitForServerReadyOptions {
+        connection,
+        min_synchronization,
+        poll_interval,
+        timeout,
+        ..Default::default()
+    })
+    .await
+}
+
+/// Wait for the server to be ready, with backoff and progress reporting.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::server_health::{
+///     PollBackoff, WaitForServerReadyOptions, wait_for_server_ready_with_options,
+/// };
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let health = wait_for_server_ready_with_options(WaitForServerReadyOptions {
+///     backoff: PollBackoff::exponential(Duration::from_secs(60)),
+///     timeout: Duration::from_secs(6 * 60 * 60),
+///     on_progress: Some(Box::new(|health| {
+///         println!("Syncing: {:.2}%", health.network_synchronization * 100.0);
+///     })),
+///     ..Default::default()
+/// })
+/// .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub async fn wait_for_server_ready_with_options(
+    options: WaitForServerReadyOptions,
 ) -> Result<ServerHealth> {
-    let deadline = tokio::time::Instant::now() + timeout;
+    HealthClient::new(options.connection.clone())?
+        .with_options(options.health_check.clone())
+        .poll_until_ready(&options)
+        .await
+}
+
+/// Watch the server health continuously.
+///
+/// Polls the server health right away and then every `interval`, publishing
+/// each result on the returned channel. The watcher runs until the
+/// [`WatchHandle`] is dropped or every receiver is gone.
+///
+/// # Errors
+///
+/// Fails only if the TLS settings of the connection are invalid; failed
+/// health checks are published on the channel.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use ogmios_client::server_health::{HealthReceiverExt, watch_server_health};
+/// use std::time::Duration;
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let (health, _handle) = watch_server_health(None, Duration::from_secs(5)).await?;
+///
+/// // In a readiness probe handler:
+/// let ready = health.is_ready(0.999);
+/// println!("ready: {ready}, tip: {:?}", health.last_known_tip());
+/// # Ok(())
+/// # }
+/// ```
+pub async fn watch_server_health(
+    connection: Option<ConnectionConfig>,
+    interval: Duration,
+) -> Result<(watch::Receiver<HealthUpdate>, WatchHandle)> {
+    Ok(HealthClient::new(connection)?.watch(interval).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::connection::TlsConfig;
+    use crate::schema::ConnectionStatus;
+    use serde_json::json;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn health(synchronization: f64) -> serde_json::Value {
+        health_with_status(synchronization, "connected")
+    }
+
+    /// A health document as served by Ogmios v6.
+    fn health_with_status(synchronization: f64, status: &str) -> serde_json::Value {
+        json!({
+            "startTime": "2024-06-25T08:12:01.019421797Z",
+            "lastKnownTip": {
+                "slot": 100,
+                "id": "8d4f9c3e2b8e1d0d3f6a41a5bb1c3a2e9e2b3cfb8c7f2d1a0e9f8b7c6d5e4f3a",
+                "height": 10
+            },
+            "lastTipUpdate": "2024-06-25T10:47:42.713582433Z",
+            "networkSynchronization": synchronization,
+            "currentEra": "conway",
+            "metrics": {
+                "activeConnections": 0,
+                "runtimeStats": {
+                    "cpuTime": 1056326712,
+                    "currentHeapSize": 1217,
+                    "gcCpuTime": 734481000,
+                    "maxHeapSize": 5301
+                },
+                "sessionDurations": { "max": 0, "mean": 0, "min": 0 },
+                "totalConnections": 1,
+                "totalMessages": 1,
+                "totalUnrouted": 0
+            },
+            "connectionStatus": status,
+            "currentEpoch": 152,
+            "slotInEpoch": 270000,
+            "version": "v6.4.0 (a2a3ab5d)",
+            "network": "preprod"
+        })
+    }
+
+    /// Serve `/health` with keep-alive, answering the `n`-th request on any
+    /// connection with `bodies[n]` (the last body once exhausted), and count
+    /// accepted TCP connections.
+    async fn health_server(bodies: Vec<serde_json::Value>) -> (ConnectionConfig, Arc<AtomicUsize>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let accepts = Arc::new(AtomicUsize::new(0));
+        let served = Arc::new(AtomicUsize::new(0));
+        let bodies = Arc::new(bodies);
+
+        let counter = accepts.clone();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                counter.fetch_add(1, Ordering::SeqCst);
+                let served = served.clone();
+                let bodies = bodies.clone();
+                tokio::spawn(async move {
+                    let mut buffer = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    loop {
+                        while !buffer.windows(4).any(|w| w == b"\r\n\r\n") {
+                            match socket.read(&mut chunk).await {
+                                Ok(0) | Err(_) => return,
+                                Ok(n) => buffer.extend_from_slice(&chunk[..n]),
+                            }
+                        }
+                        buffer.clear();
+                        let n = served.fetch_add(1, Ordering::SeqCst);
+                        let body = bodies[n.min(bodies.len() - 1)].to_string();
+                        let response = format!(
+                            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{}",
+                            body.len(),
+                            body
+                        );
+                        if socket.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        (ConnectionConfig::new("127.0.0.1", port), accepts)
+    }
+
+    #[tokio::test]
+    async fn test_health_client_reuses_connection() {
+        let (config, accepts) = health_server(vec![health(1.0)]).await;
+        let client = HealthClient::new(Some(config))
+            .unwrap()
+            .with_http_client(reqwest::Client::new());
 
-    loop {
-        match get_server_health(connection.clone()).await {
-            Ok(health) => {
-                if health.network_synchronization >= min_synchronization {
-                    return Ok(health);
+        for _ in 0..3 {
+            let health = client.health().await.unwrap();
+            assert_eq!(health.network_synchronization, 1.0);
+        }
+        assert!(client.ensure_health(0.99).await.is_ok());
+        assert_eq!(accepts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_ready_polls_over_one_connection() {
+        let (config, accepts) =
+            health_server(vec![health(0.5), health(0.9), health(1.0)]).await;
+
+        let health = wait_for_server_ready(
+            Some(config),
+            0.99,
+            Duration::from_millis(10),
+            Duration::from_secs(5),
+        )
+        .await
+        .unwrap();
+        assert_eq!(health.network_synchronization, 1.0);
+        assert_eq!(accepts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_watch_publishes_updates() {
+        let (config, _) = health_server(vec![health(0.5), health(1.0)]).await;
+        let (mut rx, handle) = watch_server_health(Some(config), Duration::from_millis(10))
+            .await
+            .unwrap();
+
+        assert_eq!(rx.network_synchronization(), Some(0.5));
+        assert!(!rx.is_ready(0.99));
+        assert_eq!(rx.last_known_tip().and_then(|tip| tip.slot()), Some(100));
+        assert!(handle.last_success().is_some());
+
+        rx.changed().await.unwrap();
+        assert!(rx.is_ready(0.99));
+
+        drop(handle);
+        tokio::time::timeout(Duration::from_secs(1), async {
+            while rx.changed().await.is_ok() {}
+        })
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_watch_publishes_errors() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+
+        let (rx, handle) = watch_server_health(
+            Some(ConnectionConfig::new("127.0.0.1", port)),
+            Duration::from_secs(60),
+        )
+        .await
+        .unwrap();
+        assert!(rx.borrow().is_err());
+        assert_eq!(rx.network_synchronization(), None);
+        assert!(handle.last_success().is_none());
+    }
+
+    /// Accept one connection, returning its request head, and never respond.
+    async fn silent_server() -> (ConnectionConfig, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let request = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut chunk).await.unwrap();
+                request.extend_from_slice(&chunk[..n]);
+            }
+            // Hold the connection open until the client gives up.
+            let _ = socket.read(&mut chunk).await;
+            String::from_utf8(request).unwrap()
+        });
+        (ConnectionConfig::new("127.0.0.1", port), request)
+    }
+
+    #[tokio::test]
+    async fn test_health_request_options() {
+        let (config, request) = silent_server().await;
+        let options = HealthCheckOptions {
+            timeout: Duration::from_millis(100),
+            headers: vec![("x-request-id".into(), "probe".into())],
+            user_agent: Some("readiness-probe".into()),
+        };
+
+        let started = Instant::now();
+        let connection = config.with_header("dmtr-api-key", "secret");
+        let result = get_server_health_with_options(Some(connection), options).await;
+        assert!(result.is_err());
+        assert!(started.elapsed() < Duration::from_secs(2));
+
+        let request = request.await.unwrap().to_lowercase();
+        assert!(request.contains("dmtr-api-key: secret\r\n"));
+        assert!(request.contains("x-request-id: probe\r\n"));
+        assert!(request.contains("user-agent: readiness-probe\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_get_server_metrics() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut chunk = [0u8; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = socket.read(&mut chunk).await.unwrap();
+                request.extend_from_slice(&chunk[..n]);
+            }
+            assert!(request.starts_with(b"GET /metrics "));
+            let body = "# TYPE ogmios_active_connections gauge\nogmios_active_connections  4\n";
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/plain\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            socket.write_all(response.as_bytes()).await.unwrap();
+        });
+
+        let metrics = get_server_metrics(Some(ConnectionConfig::new("127.0.0.1", port)))
+            .await
+            .unwrap();
+        assert_eq!(metrics.active_connections(), Some(4));
+    }
+
+    #[tokio::test]
+    async fn test_wait_reports_progress() {
+        let (config, _) = health_server(vec![health(0.5), health(0.9), health(1.0)]).await;
+        let progress = Arc::new(Mutex::new(Vec::new()));
+        let reported = progress.clone();
+
+        wait_for_server_ready_with_options(WaitForServerReadyOptions {
+            connection: Some(config),
+            min_synchronization: 0.99,
+            poll_interval: Duration::from_millis(5),
+            backoff: PollBackoff::exponential(Duration::from_millis(20)),
+            on_progress: Some(Box::new(move |health| {
+                reported.lock().unwrap().push(health.network_synchronization)
+            })),
+            ..Default::default()
+        })
+        .await
+        .unwrap();
+        assert_eq!(*progress.lock().unwrap(), [0.5, 0.9, 1.0]);
+    }
+
+    #[tokio::test]
+    async fn test_wait_timeout_tells_syncing_from_unreachable() {
+        let (config, _) = health_server(vec![health(0.5)]).await;
+        let error = wait_for_server_ready(
+            Some(config),
+            0.99,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::ServerReadyTimeout {
+                timeout_ms: 50,
+                reason: NotReadyReason::Syncing { synchronization: 0.5, minimum: 0.99 },
+                last_health: Some(ref health),
+            } if health.network_synchronization == 0.5
+        ));
+        assert!(
+            error
+                .to_string()
+                .contains("network synchronization is 50.00%, minimum required is 99.00%")
+        );
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        let error = wait_for_server_ready(
+            Some(ConnectionConfig::new("127.0.0.1", port)),
+            0.99,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::ServerReadyTimeout {
+                reason: NotReadyReason::Unreachable(_),
+                last_health: None,
+                ..
+            }
+        ));
+    }
+
+    /// Answer every request with `status` and an HTML body, as a proxy would.
+    async fn status_server(status: &'static str) -> ConnectionConfig {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = Vec::new();
+                let mut chunk = [0u8; 1024];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match socket.read(&mut chunk).await {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&chunk[..n]),
+                    }
                 }
-                debug!(
-                    "Server sync at {:.2}%, waiting for {:.2}%",
-                    health.network_synchronization * 100.0,
-                    min_synchronization * 100.0
+                let body = "<html>rejected</html>";
+                let response = format!(
+                    "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                    body.len()
                 );
+                let _ = socket.write_all(response.as_bytes()).await;
             }
-            Err(e) => {
-                debug!("Health check failed: {}, retrying...", e);
-            }
-        }
+        });
+        ConnectionConfig::new("127.0.0.1", port)
+    }
+
+    #[tokio::test]
+    async fn test_wait_fails_on_errors_not_retryable() {
+        let started = Instant::now();
+        let error = wait_for_server_ready(
+            Some(status_server("401 Unauthorized").await),
+            0.99,
+            Duration::from_millis(10),
+            Duration::from_secs(30),
+        )
+        .await
+        .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(5));
+        let OgmiosError::Http(ref e) = error else {
+            panic!("expected an HTTP error, got {error:?}");
+        };
+        assert_eq!(e.status(), Some(reqwest::StatusCode::UNAUTHORIZED));
+
+        let error = wait_for_server_ready(
+            Some(status_server("503 Service Unavailable").await),
+            0.99,
+            Duration::from_millis(10),
+            Duration::from_millis(50),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(
+            error,
+            OgmiosError::ServerReadyTimeout { reason: NotReadyReason::Unreachable(_), .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_wait_sends_health_check_options() {
+        let (config, request) = silent_server().await;
+        let options = HealthCheckOptions {
+            timeout: Duration::from_millis(50),
+            headers: vec![("x-request-id".into(), "probe".into())],
+            user_agent: None,
+        };
 
-        if tokio::time::Instant::now() >= deadline {
-            return Err(OgmiosError::Timeout {
-                timeout_ms: timeout.as_millis() as u64,
-            });
+        let error = wait_for_server_ready_with_options(WaitForServerReadyOptions {
+            connection: Some(config),
+            health_check: options,
+            min_synchronization: 0.99,
+            poll_interval: Duration::from_millis(10),
+            timeout: Duration::from_millis(200),
+            ..Default::default()
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(error, OgmiosError::ServerReadyTimeout { .. }));
+        let request = request.await.unwrap().to_lowercase();
+        assert!(request.contains("x-request-id: probe\r\n"));
+    }
+
+    #[test]
+    fn test_poll_backoff() {
+        let fixed = PollBackoff::fixed();
+        assert_eq!(fixed.next_interval(Duration::from_secs(1)), Duration::from_secs(1));
+        assert_eq!(fixed.delay(Duration::from_secs(1)), Duration::from_secs(1));
+
+        let backoff = PollBackoff::exponential(Duration::from_secs(5));
+        let intervals: Vec<_> = std::iter::successors(Some(Duration::from_secs(1)), |interval| {
+            Some(backoff.next_interval(*interval))
+        })
+        .take(5)
+        .collect();
+        assert_eq!(intervals, [1, 2, 4, 5, 5].map(Duration::from_secs));
+
+        for _ in 0..100 {
+            let delay = backoff.delay(Duration::from_secs(4));
+            assert!(delay >= Duration::from_secs(2) && delay < Duration::from_secs(4));
         }
+        assert!(backoff.delay(Duration::from_secs(60)) < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_ensure_health_disconnected() {
+        let (config, _) = health_server(vec![health_with_status(1.0, "disconnected")]).await;
+        let client = HealthClient::new(Some(config)).unwrap();
 
-        tokio::time::sleep(poll_interval).await;
+        let health = client.health().await.unwrap();
+        assert_eq!(health.connection_status, Some(ConnectionStatus::Disconnected));
+        assert_eq!(health.current_epoch, Some(152));
+        assert_eq!(health.slot_in_epoch, Some(270000));
+        assert!(matches!(
+            client.ensure_health(0.99).await,
+            Err(OgmiosError::NodeDisconnected)
+        ));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Serve `/health` over TLS with a self-signed certificate for
+    /// `localhost`, returning the port and the certificate PEM.
+    async fn tls_health_server() -> (u16, String) {
+        use tokio_rustls::rustls::pki_types::{CertificateDer, PrivatePkcs8KeyDer};
+
+        let certificate =
+            rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let config = tokio_rustls::rustls::ServerConfig::builder()
+            .with_no_client_auth()
+            .with_single_cert(
+                vec![CertificateDer::from(certificate.serialize_der().unwrap())],
+                PrivatePkcs8KeyDer::from(certificate.serialize_private_key_der()).into(),
+            )
+            .unwrap();
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        tokio::spawn(async move {
+            while let Ok((socket, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut stream) = acceptor.accept(socket).await else {
+                        return;
+                    };
+                    let mut request = Vec::new();
+                    let mut chunk = [0u8; 1024];
+                    while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                        match stream.read(&mut chunk).await {
+                            Ok(0) | Err(_) => return,
+                            Ok(n) => request.extend_from_slice(&chunk[..n]),
+                        }
+                    }
+                    let body = health(1.0).to_string();
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        (port, certificate.serialize_pem().unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_health_over_tls_with_custom_root() {
+        let (port, ca) = tls_health_server().await;
+        let config = ConnectionConfig::new("localhost", port).with_tls();
+
+        assert!(get_server_health(Some(config.clone())).await.is_err());
+
+        let trusted = config
+            .clone()
+            .with_tls_config(TlsConfig::default().with_root_certificate(ca));
+        let health = get_server_health(Some(trusted)).await.unwrap();
+        assert_eq!(health.network_synchronization, 1.0);
+
+        let insecure = config.with_tls_config(TlsConfig::default().danger_accept_invalid_certs());
+        assert!(get_server_health(Some(insecure)).await.is_ok());
+    }
+
+    #[test]
+    fn test_invalid_tls_config() {
+        let config = ConnectionConfig::new("localhost", 1337)
+            .with_tls_config(TlsConfig::default().with_identity("not a pem"));
+        assert!(HealthClient::new(Some(config)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_ensure_health_below_minimum() {
+        let (config, _) = health_server(vec![health(0.5)]).await;
+        let error = HealthClient::new(Some(config))
+            .unwrap()
+            .ensure_health(0.99)
+            .await
+            .unwrap_err();
+        let OgmiosError::ServerNotReady(not_ready) = error else {
+            panic!("expected ServerNotReady, got {error:?}");
+        };
+        assert_eq!(not_ready.synchronization, 0.5);
+        assert_eq!(not_ready.minimum, 0.99);
+        assert_eq!(not_ready.health.last_known_tip.slot(), Some(100));
+        assert_eq!(
+            not_ready.to_string(),
+            "Server not ready: network synchronization is 50.00%, minimum required is 99.00%"
+        );
+    }
 
     #[test]
     fn test_default_options() {
         let options = EnsureServerHealthOptions::default();
         assert!(options.connection.is_none());
         assert_eq!(options.min_synchronization, DEFAULT_MIN_SYNCHRONIZATION);
+        assert_eq!(options.health_check.timeout, DEFAULT_HEALTH_CHECK_TIMEOUT);
     }
 }
@@ -5,7 +5,8 @@ use crate::connection::{
     InteractionType,
 };
 use crate::error::Result;
-use crate::schema::{EvaluationResult, TransactionId, Utxo};
+use crate::schema::{ExUnits, TransactionId, Utxo, ValidatorIndex};
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::{evaluate_transaction, submit_transaction};
@@ -50,6 +51,14 @@ impl TransactionSubmissionClient {
         }
     }
 
+    /// Create a transaction submission client on a context shared with other
+    /// clients.
+    ///
+    /// [`shutdown`](Self::shutdown) closes the connection for all users.
+    pub fn with_shared(context: Arc<InteractionContext>) -> Self {
+        Self { context }
+    }
+
     /// Connect to Ogmios and create a new transaction submission client.
     ///
     /// # Arguments
@@ -80,12 +89,16 @@ impl TransactionSubmissionClient {
     ///
     /// # Returns
     ///
-    /// A list of evaluation results for each script in the transaction.
+    /// The execution budget of each script in the transaction, keyed by
+    /// validator.
+    ///
+    /// Migration: this used to return a `Vec<EvaluationResult>`; see
+    /// [`evaluate_transaction`](super::evaluate_transaction).
     pub async fn evaluate_transaction(
         &self,
         cbor: &str,
         additional_utxo: Option<Vec<Utxo>>,
-    ) -> Result<Vec<EvaluationResult>> {
+    ) -> Result<HashMap<ValidatorIndex, ExUnits>> {
         evaluate_transaction(&self.context, cbor, additional_utxo).await
     }
 
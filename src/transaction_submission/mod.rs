@@ -8,9 +8,12 @@ mod client;
 pub use client::*;
 
 use crate::connection::InteractionContext;
-use crate::error::{OgmiosError, Result};
-use crate::schema::{EvaluationResult, TransactionId, Utxo};
+use crate::error::{EvaluateTransactionError, OgmiosError, Result, SubmitTransactionError};
+use crate::schema::{
+    EvaluationResult, ExUnits, JsonRpcError, TransactionId, Utxo, ValidatorIndex,
+};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 /// Submit a transaction to the network.
 ///
@@ -23,6 +26,12 @@ use serde::{Deserialize, Serialize};
 ///
 /// The transaction ID if successful.
 ///
+/// # Errors
+///
+/// A transaction rejected by the ledger is reported as
+/// [`OgmiosError::TransactionSubmission`], with the data Ogmios sent about the failure,
+/// e.g. the unknown inputs or the minimum fee.
+///
 /// # Example
 ///
 /// ```rust,no_run
@@ -62,13 +71,14 @@ pub async fn submit_transaction(
     }
 
     let response: Response = context
-        .request(
+        .request_rpc(
             "submitTransaction",
             Some(Params {
                 transaction: Transaction { cbor },
             }),
         )
-        .await?;
+        .await?
+        .map_err(|error| OgmiosError::from(SubmitTransactionError::from(error)))?;
 
     Ok(response.transaction.id)
 }
@@ -86,7 +96,18 @@ pub async fn submit_transaction(
 ///
 /// # Returns
 ///
-/// A list of evaluation results for each script in the transaction.
+/// The execution budget of each script in the transaction, keyed by the
+/// validator (purpose and index) of its redeemer.
+///
+/// Migration: this used to return a `Vec<EvaluationResult>`. Iterate over the
+/// map's `(validator, budget)` pairs instead, or rebuild the results with
+/// `EvaluationResult { validator, budget }`.
+///
+/// # Errors
+///
+/// A transaction that cannot be evaluated is reported as
+/// [`OgmiosError::TransactionEvaluation`]. When scripts fail, it lists the failure of
+/// each, with the traces of those that failed to validate.
 ///
 /// # Example
 ///
@@ -97,13 +118,9 @@ pub async fn submit_transaction(
 /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
 /// # let context = create_interaction_context(InteractionContextOptions::default()).await?;
 /// let tx_cbor = "84a400..."; // Your transaction CBOR
-/// let results = evaluate_transaction(&context, tx_cbor, None).await?;
-/// for result in results {
-///     println!("Validator {:?}: {} mem, {} cpu",
-///         result.validator,
-///         result.budget.memory,
-///         result.budget.cpu
-///     );
+/// let budgets = evaluate_transaction(&context, tx_cbor, None).await?;
+/// for (validator, budget) in &budgets {
+///     println!("Validator {:?}: {} mem, {} cpu", validator, budget.memory, budget.cpu);
 /// }
 /// # Ok(())
 /// # }
@@ -112,7 +129,7 @@ pub async fn evaluate_transaction(
     context: &InteractionContext,
     cbor: &str,
     additional_utxo: Option<Vec<Utxo>>,
-) -> Result<Vec<EvaluationResult>> {
+) -> Result<HashMap<ValidatorIndex, ExUnits>> {
     #[derive(Serialize)]
     #[serde(rename_all = "camelCase")]
     struct Params<'a> {
@@ -127,31 +144,28 @@ pub async fn evaluate_transaction(
     }
 
     let response: serde_json::Value = context
-        .request(
+        .request_rpc(
             "evaluateTransaction",
             Some(Params {
                 transaction: Transaction { cbor },
                 additional_utxo,
             }),
         )
-        .await?;
+        .await?
+        .map_err(|error| OgmiosError::from(EvaluateTransactionError::from(error)))?;
 
     // The response can be either a list of results or an error
-    if let Some(arr) = response.as_array() {
-        let results: Vec<EvaluationResult> = arr
-            .iter()
-            .filter_map(|v| serde_json::from_value(v.clone()).ok())
-            .collect();
-        Ok(results)
+    if response.is_array() {
+        let results: Vec<EvaluationResult> = serde_json::from_value(response)?;
+        Ok(EvaluationResult::budgets(results))
     } else if let Some(obj) = response.as_object() {
-        if obj.contains_key("error") {
-            return Err(OgmiosError::EvaluationError(
-                serde_json::to_string(&response).unwrap_or_default(),
-            ));
+        if let Some(error) = obj.get("error") {
+            let error: JsonRpcError = serde_json::from_value(error.clone())?;
+            return Err(EvaluateTransactionError::from(error).into());
         }
         // Single result
         let result: EvaluationResult = serde_json::from_value(response)?;
-        Ok(vec![result])
+        Ok(EvaluationResult::budgets([result]))
     } else {
         Err(OgmiosError::InvalidResponse {
             message: "Unexpected evaluation response format".to_string(),
@@ -161,8 +175,124 @@ pub async fn evaluate_transaction(
 
 #[cfg(test)]
 mod tests {
+    use super::*;
+    use crate::mock_server::{MockReply, MockServer};
+    use serde_json::json;
+
     #[test]
     fn test_module_compiles() {
         // Basic compilation test
     }
+
+    #[tokio::test]
+    async fn test_submit_reports_typed_failure() {
+        let server = MockServer::start(|_, _| MockReply::Error {
+            code: 3117,
+            message: "The transaction contains unknown UTxO references as inputs.".to_string(),
+            data: Some(json!({
+                "unknownOutputReferences": [{
+                    "transaction": {
+                        "id": "3c7a93a2dd1c1a1c54bb70ec39bcd0c1f9ca3c0c5f5d54b4bb6dcce74fc3a5b7"
+                    },
+                    "index": 0
+                }]
+            })),
+        })
+        .await;
+        let context = server.context().await;
+
+        let error = submit_transaction(&context, "84a400").await.unwrap_err();
+        let OgmiosError::TransactionSubmission(failure) = error else {
+            panic!("{error:?}");
+        };
+        let SubmitTransactionError::UnknownOutputReferences(data) = failure else {
+            panic!("{failure:?}");
+        };
+        assert_eq!(data.unknown_output_references[0].index, 0);
+    }
+
+    #[tokio::test]
+    async fn test_submit_failure_matches_in_place() {
+        let server = MockServer::start(|_, _| {
+            let fixture = crate::schema::testing::fixture("submission/submitTransaction-3122");
+            let error = fixture.error();
+            MockReply::Error {
+                code: error.code,
+                message: error.message,
+                data: error.data,
+            }
+        })
+        .await;
+        let context = server.context().await;
+
+        let shortfall = match submit_transaction(&context, "84a400").await {
+            Err(OgmiosError::TransactionSubmission(
+                SubmitTransactionError::TransactionFeeTooSmall(fee),
+            )) => fee.minimum_required_fee.lovelace - fee.provided_fee.lovelace,
+            other => panic!("{other:?}"),
+        };
+        assert_eq!(shortfall, 1045);
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_returns_budgets_by_validator() {
+        let server = MockServer::start(|_, _| {
+            let fixture = crate::schema::testing::fixture("evaluation/evaluateTransaction");
+            MockReply::result(fixture.result())
+        })
+        .await;
+        let context = server.context().await;
+
+        let budgets = evaluate_transaction(&context, "84a400", None).await.unwrap();
+        assert_eq!(budgets.len(), 2);
+        let spend = &budgets[&ValidatorIndex {
+            purpose: crate::schema::ScriptPurpose::Spend,
+            index: 0,
+        }];
+        assert_eq!((spend.memory, spend.cpu), (1765011, 503871230));
+        let mint = &budgets[&ValidatorIndex {
+            purpose: crate::schema::ScriptPurpose::Mint,
+            index: 0,
+        }];
+        assert_eq!((mint.memory, mint.cpu), (12210, 3956811));
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_rejects_malformed_results() {
+        let server = MockServer::start(|_, _| {
+            MockReply::result(json!([{ "validator": { "purpose": "spend", "index": 0 } }]))
+        })
+        .await;
+        let context = server.context().await;
+
+        let error = evaluate_transaction(&context, "84a400", None).await.unwrap_err();
+        assert!(matches!(error, OgmiosError::Json(_)), "{error:?}");
+    }
+
+    #[tokio::test]
+    async fn test_evaluate_reports_typed_failure() {
+        let server = MockServer::start(|_, _| MockReply::Error {
+            code: 3010,
+            message: "Some scripts of the transactions terminated with error(s).".to_string(),
+            data: Some(json!([{
+                "validator": { "purpose": "spend", "index": 0 },
+                "error": {
+                    "code": 3012,
+                    "message": "Some of the scripts failed to evaluate to a positive outcome.",
+                    "data": { "validationError": "error", "traces": ["a", "b"] }
+                }
+            }])),
+        })
+        .await;
+        let context = server.context().await;
+
+        let error = evaluate_transaction(&context, "84a400", None).await.unwrap_err();
+        let OgmiosError::TransactionEvaluation(failure) = error else {
+            panic!("{error:?}");
+        };
+        let EvaluateTransactionError::ScriptExecutionFailure(failures) = failure else {
+            panic!("{failure:?}");
+        };
+        assert_eq!(failures[0].traces(), ["a", "b"]);
+    }
 }
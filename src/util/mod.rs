@@ -0,0 +1,2448 @@
+//! Utility functions for the Ogmios client.
+//!
+//! This module provides various helper functions for working with Cardano data types.
+
+use crate::error::OgmiosError;
+use crate::ledger_state_query::UtcTimeInput;
+use crate::ledger_state_query::time::{relative_millis_to_slot, slot_to_relative_millis};
+#[cfg(feature = "bech32")]
+use crate::schema::{DRepId, DigestBlake2b224, ScriptHash};
+#[cfg(feature = "address")]
+use crate::schema::{AddressInfo, InvalidAddress};
+use crate::schema::{
+    Address, Block, Datum, DigestBlake2b256, EraSummary, ExUnits, InvalidDigest, Lovelace,
+    MinFeeReferenceScripts, NativeScript, Origin, Point, ProtocolParameters, RelativeTime, Script, Slot, TransactionOutput, Value,
+};
+#[cfg(feature = "bech32")]
+use bech32::{Bech32, primitives::decode::CheckedHrpstring};
+
+#[cfg(feature = "bech32")]
+pub use bech32::Hrp;
+
+mod nft;
+pub use nft::*;
+
+/// Constant output serialization overhead (160 bytes).
+///
+/// This is an approximation of the memory overhead for output serialization.
+pub const CONSTANT_OUTPUT_SERIALIZATION_OVERHEAD: u64 = 160;
+
+/// Check if a block is an Epoch Boundary Block (EBB).
+///
+/// EBB blocks are special blocks in the Byron era that mark epoch boundaries.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::is_block_ebb;
+/// use ogmios_client::schema::Block;
+///
+/// fn check_block(block: &Block) {
+///     if is_block_ebb(block) {
+///         println!("This is an EBB block");
+///     }
+/// }
+/// ```
+pub fn is_block_ebb(block: &Block) -> bool {
+    matches!(block, Block::EBB(_))
+}
+
+/// Check if a block is a BFT block (Byron era).
+///
+/// BFT blocks use the Byzantine Fault Tolerant consensus mechanism
+/// from the Byron era.
+pub fn is_block_bft(block: &Block) -> bool {
+    matches!(block, Block::BFT(_))
+}
+
+/// Check if a block is a Praos block (Shelley+ era).
+///
+/// Praos blocks use the Ouroboros Praos consensus mechanism
+/// from Shelley era onwards.
+pub fn is_block_praos(block: &Block) -> bool {
+    matches!(block, Block::Praos(_))
+}
+
+/// Check if a value is an object.
+pub fn is_object<T>(_value: &T) -> bool {
+    // In Rust, this is always true for struct types
+    true
+}
+
+/// Calculate the size of a UTXO for minimum lovelace calculations.
+///
+/// This is the size the ledger charges for: the
+/// [`CONSTANT_OUTPUT_SERIALIZATION_OVERHEAD`] plus the length of the output
+/// serialized in CBOR. Outputs without inline datum or script reference use
+/// the legacy array format, others the map format of the Babbage era, with
+/// the datum and script wrapped in CBOR tag 24.
+///
+/// Addresses are measured by decoding them. With the `address` feature they
+/// are decoded by [`Address::to_bytes`]; otherwise from their bech32 or
+/// base58 text, without checksum verification. Inline datums given as JSON
+/// rather than CBOR cannot be measured and count as 64 bytes.
+///
+/// # Arguments
+///
+/// * `output` - The transaction output to calculate the size for.
+///
+/// # Returns
+///
+/// The size in bytes.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::utxo_size;
+/// use ogmios_client::schema::TransactionOutput;
+///
+/// fn calculate_min_ada(output: &TransactionOutput) {
+///     let size = utxo_size(output);
+///     println!("UTXO size: {} bytes", size);
+/// }
+/// ```
+pub fn utxo_size(output: &TransactionOutput) -> u64 {
+    let address_size = size_of_bytes_def(size_of_address(&output.address));
+    let value_size = size_of_value(&output.value);
+
+    let size = if output.datum.is_none() && output.script.is_none() {
+        // Legacy format: [address, value, ? datum_hash]
+        match output.datum_hash {
+            Some(_) => size_of_array_def(3) + address_size + value_size + size_of_datum_hash(),
+            None => size_of_array_def(2) + address_size + value_size,
+        }
+    } else {
+        // Map format: { 0: address, 1: value, ? 2: datum_option, ? 3: script_ref }
+        let mut entries = 2;
+        let mut size = 2 + address_size + value_size;
+        if let Some(ref datum) = output.datum {
+            entries += 1;
+            size += 1 + size_of_inline_datum(datum);
+        } else if output.datum_hash.is_some() {
+            entries += 1;
+            // [0, datum_hash]
+            size += 1 + size_of_array_def(2) + 1 + size_of_datum_hash();
+        }
+        if let Some(ref script) = output.script {
+            entries += 1;
+            size += 1 + size_of_script(script);
+        }
+        size_of_array_def(entries) + size
+    };
+
+    CONSTANT_OUTPUT_SERIALIZATION_OVERHEAD + size
+}
+
+/// A protocol parameter needed for a calculation is not set, e.g. because
+/// the parameters are from an era before it was introduced.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("protocol parameter {parameter} is not set")]
+pub struct MissingProtocolParameter {
+    /// Name of the parameter, as sent by Ogmios.
+    pub parameter: &'static str,
+}
+
+/// Calculate the minimum amount of lovelace an output must hold.
+///
+/// Implements the rule of the Babbage era onwards:
+/// `minUtxoDepositConstant + minUtxoDepositCoefficient × utxo_size(output)`.
+/// The size depends on the amount held, so the minimum is computed for the
+/// output holding exactly that minimum, as `cardano-cli transaction
+/// calculate-min-required-utxo` does.
+///
+/// # Errors
+///
+/// If `minUtxoDepositCoefficient` is not set, before the Alonzo era.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::{ProtocolParameters, TransactionOutput};
+/// use ogmios_client::util::min_ada_required;
+///
+/// fn check(output: &TransactionOutput, params: &ProtocolParameters) {
+///     match min_ada_required(output, params) {
+///         Ok(minimum) if output.value.lovelace() < minimum => println!("add more ada"),
+///         Ok(_) => {}
+///         Err(error) => println!("{error}"),
+///     }
+/// }
+/// ```
+pub fn min_ada_required(
+    output: &TransactionOutput,
+    params: &ProtocolParameters,
+) -> Result<Lovelace, MissingProtocolParameter> {
+    let coefficient = params
+        .min_utxo_deposit_coefficient
+        .ok_or(MissingProtocolParameter {
+            parameter: "minUtxoDepositCoefficient",
+        })?;
+    let constant = params
+        .min_utxo_deposit_constant
+        .as_ref()
+        .map_or(0, |constant| constant.lovelace);
+
+    let mut output = output.clone();
+    loop {
+        let minimum = constant + coefficient * utxo_size(&output);
+        let lovelace = match &mut output.value {
+            Value::AdaOnly { ada } | Value::WithAssets { ada, .. } => &mut ada.lovelace,
+        };
+        if *lovelace == minimum {
+            return Ok(minimum);
+        }
+        *lovelace = minimum;
+    }
+}
+
+/// Calculate the minimum fee of a transaction.
+///
+/// The sum of:
+///
+/// - the size fee, `minFeeCoefficient × tx_size_bytes + minFeeConstant`;
+/// - the script fee, the execution units of all redeemers priced by
+///   `scriptExecutionPrices`, rounded up;
+/// - the fee of the reference scripts the transaction uses, priced per byte
+///   by `minFeeReferenceScripts`, the price growing by `multiplier` every
+///   `range` bytes, rounded down.
+///
+/// Prices are computed with exact integer arithmetic, as the ledger does.
+///
+/// # Errors
+///
+/// If `ex_units` are given without `scriptExecutionPrices`, or reference
+/// scripts without `minFeeReferenceScripts`.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::{ExUnits, ProtocolParameters};
+/// use ogmios_client::util::min_fee;
+///
+/// fn fee(params: &ProtocolParameters) {
+///     let budget = ExUnits::new(1_765_011, 503_871_230);
+///     match min_fee(4_213, Some(budget), 0, params) {
+///         Ok(fee) => println!("minimum fee: {fee} lovelace"),
+///         Err(error) => println!("{error}"),
+///     }
+/// }
+/// ```
+pub fn min_fee(
+    tx_size_bytes: u64,
+    ex_units: Option<ExUnits>,
+    ref_script_bytes: u64,
+    params: &ProtocolParameters,
+) -> Result<Lovelace, MissingProtocolParameter> {
+    let mut fee = params.min_fee_coefficient as u128 * tx_size_bytes as u128
+        + params.min_fee_constant.lovelace as u128;
+
+    if let Some(ex_units) = ex_units {
+        let prices = params
+            .script_execution_prices
+            .as_ref()
+            .ok_or(MissingProtocolParameter {
+                parameter: "scriptExecutionPrices",
+            })?;
+        let (memory, cpu) = (&prices.memory, &prices.cpu);
+        let denominator = memory.denominator as u128 * cpu.denominator as u128;
+        let numerator = (memory.numerator as u128 * ex_units.memory as u128)
+            .saturating_mul(cpu.denominator as u128)
+            .saturating_add(
+                (cpu.numerator as u128 * ex_units.cpu as u128)
+                    .saturating_mul(memory.denominator as u128),
+            );
+        fee += numerator.div_ceil(denominator.max(1));
+    }
+
+    if ref_script_bytes > 0 {
+        let prices = params
+            .min_fee_reference_scripts
+            .as_ref()
+            .ok_or(MissingProtocolParameter {
+                parameter: "minFeeReferenceScripts",
+            })?;
+        fee += reference_scripts_fee(ref_script_bytes, prices) as u128;
+    }
+
+    Ok(fee.min(Lovelace::MAX as u128) as Lovelace)
+}
+
+/// Fee of the reference scripts of a transaction (Conway).
+///
+/// The first `range` bytes cost `base` lovelace per byte, and each next
+/// `range` bytes cost `multiplier` times more per byte than the previous ones.
+/// As in the ledger, the tiers are summed exactly and the total is rounded
+/// down once: `base` and `multiplier` are read as the decimals they are
+/// written as, with no floating-point arithmetic.
+///
+/// Saturates at the largest amount of lovelace. [`min_fee`] includes this fee.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::MinFeeReferenceScripts;
+/// use ogmios_client::util::reference_scripts_fee;
+///
+/// // Mainnet parameters since the Chang hard fork.
+/// let params = MinFeeReferenceScripts {
+///     base: 15.0,
+///     range: 25_600,
+///     multiplier: 1.2,
+///     extra: Default::default(),
+/// };
+/// // 25600 bytes at 15 lovelace, then 4400 at 18.
+/// assert_eq!(reference_scripts_fee(30_000, &params), 384_000 + 79_200);
+/// ```
+pub fn reference_scripts_fee(
+    total_ref_script_bytes: u64,
+    params: &MinFeeReferenceScripts,
+) -> Lovelace {
+    let (base, base_denominator) = decimal_fraction(params.base);
+    let (multiplier, multiplier_denominator) = decimal_fraction(params.multiplier);
+    let range = u128::from(params.range.max(1));
+
+    let fee = || {
+        // The fee so far and the price of the current tier, over a common
+        // denominator.
+        let (mut fee, mut price, mut denominator) = (0u128, base, base_denominator);
+        let mut remaining = u128::from(total_ref_script_bytes);
+        while remaining >= range {
+            fee = fee.checked_add(range.checked_mul(price)?)?;
+            fee = fee.checked_mul(multiplier_denominator)?;
+            price = price.checked_mul(multiplier)?;
+            denominator = denominator.checked_mul(multiplier_denominator)?;
+            let divisor = gcd(gcd(fee, price), denominator);
+            (fee, price, denominator) = (fee / divisor, price / divisor, denominator / divisor);
+            remaining -= range;
+        }
+        let fee = fee.checked_add(remaining.checked_mul(price)?)? / denominator;
+        Lovelace::try_from(fee).ok()
+    };
+    fee().unwrap_or(Lovelace::MAX)
+}
+
+/// The exact fraction written as the shortest decimal of `value`, e.g.
+/// `6 / 5` for `1.2`.
+fn decimal_fraction(value: f64) -> (u128, u128) {
+    let decimal = value.to_string();
+    let (whole, fraction) = decimal.split_once('.').unwrap_or((&decimal, ""));
+    let denominator = 10u128.checked_pow(fraction.len() as u32);
+    match (format!("{whole}{fraction}").parse::<u128>(), denominator) {
+        (Ok(numerator), Some(denominator)) => {
+            let divisor = gcd(numerator, denominator);
+            (numerator / divisor, denominator / divisor)
+        }
+        _ => (0, 1),
+    }
+}
+
+fn gcd(mut a: u128, mut b: u128) -> u128 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Calculate the size of a CBOR variable-length integer.
+fn size_of_integer(value: u64) -> u64 {
+    if value < 24 {
+        1
+    } else if value < 256 {
+        2
+    } else if value < 65536 {
+        3
+    } else if value < 4294967296 {
+        5
+    } else {
+        9
+    }
+}
+
+/// Calculate the size of a CBOR integer, negative ones included.
+fn size_of_signed_integer(value: i128) -> u64 {
+    let magnitude = if value < 0 { -1 - value } else { value };
+    u64::try_from(magnitude).map_or(9, size_of_integer)
+}
+
+/// Calculate the size of definite-length bytes.
+fn size_of_bytes_def(len: u64) -> u64 {
+    size_of_integer(len) + len
+}
+
+/// Calculate the size of definite-length array/map overhead.
+fn size_of_array_def(len: u64) -> u64 {
+    size_of_integer(len)
+}
+
+/// Calculate the size of a hex string once decoded.
+fn size_of_hex(hex: &str) -> u64 {
+    hex.len() as u64 / 2
+}
+
+/// Calculate the length of an address in bytes.
+fn size_of_address(address: &Address) -> u64 {
+    #[cfg(feature = "address")]
+    if let Ok(bytes) = address.to_bytes() {
+        return bytes.len() as u64;
+    }
+
+    let address = address.as_str();
+    match address.rsplit_once('1') {
+        // Bech32: 5 bits per character, after the separator and before the
+        // 6-character checksum
+        Some((_, data)) if address.starts_with("addr") => {
+            data.len().saturating_sub(6) as u64 * 5 / 8
+        }
+        // Base58 (Byron)
+        _ => size_of_base58(address),
+    }
+}
+
+/// Calculate the length of base58-encoded bytes.
+fn size_of_base58(text: &str) -> u64 {
+    const ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+    // Digits of the number in base 256, least significant first
+    let mut digits: Vec<u8> = Vec::new();
+    for character in text.bytes() {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|digit| *digit == character)
+            .unwrap_or(0) as u32;
+        for digit in &mut digits {
+            carry += u32::from(*digit) * 58;
+            *digit = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            digits.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+    // Each leading '1' is a zero byte
+    let zeros = text.bytes().take_while(|character| *character == b'1').count();
+    (zeros + digits.len()) as u64
+}
+
+/// Calculate the size of a value.
+fn size_of_value(value: &Value) -> u64 {
+    match value {
+        Value::AdaOnly { ada } => {
+            // Just the lovelace amount
+            size_of_integer(ada.lovelace)
+        }
+        Value::WithAssets { ada, assets } => {
+            // [lovelace, { policy_id => { asset_name => quantity } }]
+            let mut size = size_of_array_def(2);
+            size += size_of_integer(ada.lovelace);
+
+            size += size_of_array_def(assets.len() as u64);
+            for asset_map in assets.values() {
+                // Policy ID is 28 bytes (224 bits)
+                size += size_of_bytes_def(28);
+                size += size_of_array_def(asset_map.len() as u64);
+                for (asset_name, quantity) in asset_map {
+                    size += size_of_bytes_def(size_of_hex(asset_name));
+                    size += size_of_signed_integer(*quantity);
+                }
+            }
+
+            size
+        }
+    }
+}
+
+/// Calculate the size of an inline datum option, `[1, #6.24(bytes)]`.
+fn size_of_inline_datum(datum: &Datum) -> u64 {
+    let datum_bytes = match datum {
+        Datum::Cbor(cbor) => size_of_hex(cbor),
+        Datum::Value(_) => 64,
+    };
+    size_of_array_def(2) + 1 + size_of_tag_24(datum_bytes)
+}
+
+/// Calculate the size of a datum hash reference.
+fn size_of_datum_hash() -> u64 {
+    // Datum hash is 32 bytes (256 bits)
+    size_of_bytes_def(32)
+}
+
+/// Calculate the size of a script reference, `#6.24(bytes .cbor [language, script])`.
+fn size_of_script(script: &Script) -> u64 {
+    let script_bytes = match script {
+        Script::Native { cbor: Some(cbor), .. } => size_of_hex(cbor),
+        Script::Native { script, cbor: None } => size_of_native_script(script),
+        // The script bytes, in a byte string
+        Script::PlutusV1 { cbor } | Script::PlutusV2 { cbor } | Script::PlutusV3 { cbor } => {
+            size_of_bytes_def(size_of_hex(cbor))
+        }
+    };
+    size_of_tag_24(size_of_array_def(2) + 1 + script_bytes)
+}
+
+/// Calculate the size of a native script encoded in CBOR.
+fn size_of_native_script(script: &NativeScript) -> u64 {
+    let all = |scripts: &[NativeScript]| {
+        let size: u64 = scripts.iter().map(size_of_native_script).sum();
+        size_of_array_def(scripts.len() as u64) + size
+    };
+    match script {
+        NativeScript::Signature { from } => 2 + size_of_bytes_def(size_of_hex(from.as_str())),
+        NativeScript::All { from } | NativeScript::Any { from } => 2 + all(from),
+        NativeScript::Some { at_least, from } => {
+            2 + size_of_integer(u64::from(*at_least)) + all(from)
+        }
+        NativeScript::After { slot } | NativeScript::Before { slot } => 2 + size_of_integer(*slot),
+    }
+}
+
+/// Calculate the size of bytes embedded as CBOR, tag 24 and a byte string.
+fn size_of_tag_24(len: u64) -> u64 {
+    2 + size_of_bytes_def(len)
+}
+
+/// Time at which a slot starts, in seconds since the network start.
+///
+/// Offline counterpart of
+/// [`SlotTimeInterpreter`](crate::ledger_state_query::SlotTimeInterpreter), for era
+/// summaries fetched earlier or hard-coded. Slots past the end of the last
+/// summary fail with [`OgmiosError::PastHorizon`].
+pub fn slot_to_relative_time(
+    summaries: &[EraSummary],
+    slot: Slot,
+) -> Result<RelativeTime, OgmiosError> {
+    Ok(slot_to_relative_millis(summaries, slot)? as f64 / 1000.0)
+}
+
+/// Time at which a slot starts, in milliseconds since the Unix epoch.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::EraSummary;
+/// use ogmios_client::util::slot_to_unix_time;
+///
+/// fn print_slot_time(summaries: &[EraSummary], slot: u64) {
+///     match slot_to_unix_time(summaries, "2017-09-23T21:44:51Z", slot) {
+///         Ok(millis) => println!("slot {slot} starts at {millis} ms"),
+///         Err(error) => println!("{error}"),
+///     }
+/// }
+/// ```
+pub fn slot_to_unix_time(
+    summaries: &[EraSummary],
+    system_start: impl UtcTimeInput,
+    slot: Slot,
+) -> Result<i64, OgmiosError> {
+    Ok(system_start.to_utc_millis()? + slot_to_relative_millis(summaries, slot)?)
+}
+
+/// Slot in progress at a time, in seconds since the network start.
+pub fn time_to_slot(summaries: &[EraSummary], time: RelativeTime) -> Result<Slot, OgmiosError> {
+    if time.is_nan() || time < 0.0 {
+        return Err(OgmiosError::InvalidTime(format!(
+            "{time}s is before the network start time"
+        )));
+    }
+    let millis = (time * 1000.0).floor() as i64;
+    relative_millis_to_slot(summaries, millis, || format!("time {time}s"))
+}
+
+/// Slot in progress at a wall-clock time, usually the current time.
+pub fn current_slot(
+    summaries: &[EraSummary],
+    system_start: impl UtcTimeInput,
+    now: impl UtcTimeInput,
+) -> Result<Slot, OgmiosError> {
+    let relative = now.to_utc_millis()? - system_start.to_utc_millis()?;
+    if relative < 0 {
+        return Err(OgmiosError::InvalidTime(format!(
+            "{now} is before the network start time"
+        )));
+    }
+    relative_millis_to_slot(summaries, relative, || format!("time {now}"))
+}
+
+/// KES period containing a slot, given the `slotsPerKESPeriod` of the
+/// Shelley genesis, or `None` if that is 0.
+///
+/// KES periods count from slot 0, whatever the era, so this holds on every
+/// network. Operational certificates must start at or before the current
+/// period.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::kes_period_of_slot;
+///
+/// assert_eq!(kes_period_of_slot(140_000_000, 129_600), Some(1080));
+/// assert_eq!(kes_period_of_slot(140_000_000, 0), None);
+/// ```
+pub fn kes_period_of_slot(slot: Slot, slots_per_kes_period: u64) -> Option<u64> {
+    slot.checked_div(slots_per_kes_period)
+}
+
+/// A value that is not a point.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid point {value}: {reason}")]
+pub struct InvalidPoint {
+    /// Rejected value, as JSON or as given.
+    pub value: String,
+    /// Why the value was rejected.
+    pub reason: String,
+}
+
+/// Parse a point from a string or structured format.
+///
+/// Accepts:
+/// - `"origin"`;
+/// - objects with a `slot` and an `id`, such as points and tips, where the
+///   slot may be a number or a string of digits;
+/// - the compact strings `"<slot>.<id>"` and `"<id>@<slot>"`.
+///
+/// Block IDs must be 64 hex characters; they are returned in lowercase.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::Point;
+/// use ogmios_client::util::parse_point;
+/// use serde_json::json;
+///
+/// let id = "9f3fd0e5be05a3f6f2da3ce4c0d4b1aa0a2e1ba9b3b2d7c4ae47f7b3c9e8e0a1";
+/// let point = Point::at(4492800, id.parse().unwrap());
+/// assert_eq!(parse_point(&json!(format!("4492800.{id}"))).unwrap(), point);
+/// assert_eq!(parse_point(&json!({ "slot": "4492800", "id": id, "height": 1 })).unwrap(), point);
+/// assert!(parse_point(&json!("4492800.abc")).is_err());
+/// ```
+pub fn parse_point(value: &serde_json::Value) -> Result<Point, InvalidPoint> {
+    let invalid = |reason: String| InvalidPoint {
+        value: value.to_string(),
+        reason,
+    };
+    match value {
+        serde_json::Value::String(s) => parse_compact_point(s),
+        serde_json::Value::Object(object) => {
+            let slot = match object.get("slot") {
+                Some(serde_json::Value::Number(slot)) => slot
+                    .as_u64()
+                    .ok_or_else(|| invalid(format!("slot {slot} is not a natural number")))?,
+                Some(serde_json::Value::String(slot)) => parse_slot(slot).map_err(invalid)?,
+                Some(slot) => return Err(invalid(format!("slot {slot} is not a number"))),
+                None => return Err(invalid("missing slot".to_string())),
+            };
+            let id = match object.get("id") {
+                Some(serde_json::Value::String(id)) => parse_block_id(id).map_err(invalid)?,
+                Some(id) => return Err(invalid(format!("id {id} is not a string"))),
+                None => return Err(invalid("missing id".to_string())),
+            };
+            Ok(Point::at(slot, id))
+        }
+        _ => Err(invalid("expected a string or an object".to_string())),
+    }
+}
+
+/// Parse a comma-separated list of compact points, e.g. from an environment
+/// variable.
+///
+/// Each entry is parsed as by [`parse_point`]; blanks around entries are
+/// ignored, and an empty or blank string is an empty list.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::{format_point, parse_points};
+///
+/// let id = "9f3fd0e5be05a3f6f2da3ce4c0d4b1aa0a2e1ba9b3b2d7c4ae47f7b3c9e8e0a1";
+/// let points = parse_points(&format!("origin, 4492800.{id}")).unwrap();
+/// assert_eq!(format_point(&points[1]), format!("4492800.{id}"));
+/// ```
+pub fn parse_points(s: &str) -> Result<Vec<Point>, InvalidPoint> {
+    if s.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    s.split(',').map(|point| parse_compact_point(point.trim())).collect()
+}
+
+/// Format a point in the compact form `<slot>.<id>`, or `origin`.
+pub fn format_point(point: &Point) -> String {
+    match point {
+        Point::Origin(_) => Origin::ORIGIN.to_string(),
+        Point::Point { slot, id } => format!("{slot}.{id}"),
+    }
+}
+
+fn parse_compact_point(s: &str) -> Result<Point, InvalidPoint> {
+    let invalid = |reason: String| InvalidPoint {
+        value: format!("{s:?}"),
+        reason,
+    };
+    if s == Origin::ORIGIN {
+        return Ok(Point::origin());
+    }
+    let (slot, id) = if let Some((slot, id)) = s.split_once('.') {
+        (slot, id)
+    } else if let Some((id, slot)) = s.split_once('@') {
+        (slot, id)
+    } else {
+        return Err(invalid(
+            "expected \"origin\", \"<slot>.<id>\" or \"<id>@<slot>\"".to_string(),
+        ));
+    };
+    Ok(Point::at(
+        parse_slot(slot).map_err(invalid)?,
+        parse_block_id(id).map_err(invalid)?,
+    ))
+}
+
+fn parse_slot(slot: &str) -> Result<Slot, String> {
+    if slot.is_empty() || !slot.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("slot {slot:?} is not a natural number"));
+    }
+    slot.parse().map_err(|_| format!("slot {slot} is too large"))
+}
+
+fn parse_block_id(id: &str) -> Result<DigestBlake2b256, String> {
+    if id.len() != 64 {
+        return Err(format!("block ID must be 64 hex characters, got {}", id.len()));
+    }
+    if let Some(c) = id.chars().find(|c| !c.is_ascii_hexdigit()) {
+        return Err(format!("block ID contains {c:?}, which is not a hex digit"));
+    }
+    id.parse().map_err(|error: InvalidDigest| error.to_string())
+}
+
+/// Number of lovelace in one ADA.
+pub const LOVELACE_PER_ADA: Lovelace = 1_000_000;
+
+/// Unit appended by [`format_lovelace`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AdaSuffix {
+    /// The ada sign, `₳`.
+    Symbol,
+    /// The ticker, `ADA`.
+    Ticker,
+}
+
+/// Options for [`format_lovelace`].
+///
+/// The default formats an amount as `1234.500000`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FormatOptions {
+    /// Group the whole ADA by thousands with this separator, e.g. `,`.
+    pub thousands_separator: Option<char>,
+    /// Drop trailing zero decimals, and the decimal point if none are left.
+    pub trim_trailing_zeros: bool,
+    /// Unit appended after a space.
+    pub suffix: Option<AdaSuffix>,
+}
+
+/// Format a lovelace amount as ADA.
+///
+/// Formatting uses integer arithmetic, so it is exact for every amount.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::{AdaSuffix, FormatOptions, format_lovelace};
+///
+/// let options = FormatOptions {
+///     thousands_separator: Some(','),
+///     trim_trailing_zeros: true,
+///     suffix: Some(AdaSuffix::Ticker),
+/// };
+/// assert_eq!(format_lovelace(1_234_567_890, options), "1,234.56789 ADA");
+/// ```
+pub fn format_lovelace(lovelace: Lovelace, options: FormatOptions) -> String {
+    let whole = (lovelace / LOVELACE_PER_ADA).to_string();
+    let mut ada = String::with_capacity(whole.len() * 4 / 3 + 12);
+    for (i, digit) in whole.chars().enumerate() {
+        if let Some(separator) = options.thousands_separator
+            && i > 0
+            && (whole.len() - i).is_multiple_of(3)
+        {
+            ada.push(separator);
+        }
+        ada.push(digit);
+    }
+
+    let fraction = format!("{:06}", lovelace % LOVELACE_PER_ADA);
+    let fraction = if options.trim_trailing_zeros {
+        fraction.trim_end_matches('0')
+    } else {
+        &fraction
+    };
+    if !fraction.is_empty() {
+        ada.push('.');
+        ada.push_str(fraction);
+    }
+
+    match options.suffix {
+        Some(AdaSuffix::Symbol) => ada.push_str(" ₳"),
+        Some(AdaSuffix::Ticker) => ada.push_str(" ADA"),
+        None => {}
+    }
+    ada
+}
+
+/// A string is not an amount of ADA.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid ADA amount {input:?}: {reason}")]
+pub struct ParseAdaError {
+    /// The string that was parsed.
+    pub input: String,
+    /// What is wrong with it.
+    pub reason: &'static str,
+}
+
+/// Parse an amount of ADA into lovelace.
+///
+/// Accepts a decimal number with at most 6 significant decimals, optionally
+/// grouped by thousands with `,`, `_` or spaces, and optionally preceded or
+/// followed by `₳` or `ADA`. Parsing uses integer arithmetic, so it is exact
+/// and reads back every output of [`format_lovelace`].
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::parse_ada;
+///
+/// assert_eq!(parse_ada("1.5").unwrap(), 1_500_000);
+/// assert_eq!(parse_ada("1,234.567890 ADA").unwrap(), 1_234_567_890);
+/// assert_eq!(parse_ada("₳ 0.000001").unwrap(), 1);
+/// assert!(parse_ada("0.0000001").is_err());
+/// ```
+pub fn parse_ada(input: &str) -> Result<Lovelace, ParseAdaError> {
+    let error = |reason| ParseAdaError {
+        input: input.to_string(),
+        reason,
+    };
+
+    let mut amount = input.trim();
+    if let Some(rest) = amount.strip_prefix('₳') {
+        amount = rest.trim_start();
+    } else if let Some(rest) = amount.strip_suffix('₳') {
+        amount = rest.trim_end();
+    } else if amount.len() >= 3 && amount.is_char_boundary(amount.len() - 3) {
+        let (rest, unit) = amount.split_at(amount.len() - 3);
+        if unit.eq_ignore_ascii_case("ada") {
+            amount = rest.trim_end();
+        }
+    }
+
+    let (whole, fraction) = match amount.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (amount, None),
+    };
+
+    let separator = whole.chars().find(|c| matches!(c, ',' | '_' | ' '));
+    let groups: Vec<&str> = match separator {
+        Some(separator) => whole.split(separator).collect(),
+        None => vec![whole],
+    };
+    let grouped = groups.len() > 1;
+    for (i, group) in groups.iter().enumerate() {
+        if group.is_empty() || !group.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(error(if amount.is_empty() {
+                "no digits"
+            } else {
+                "expected digits"
+            }));
+        }
+        if grouped && (group.len() > 3 || (i > 0 && group.len() != 3)) {
+            return Err(error("misplaced thousands separator"));
+        }
+    }
+
+    let mut lovelace: Lovelace = 0;
+    for digit in groups.iter().flat_map(|group| group.bytes()) {
+        lovelace = lovelace
+            .checked_mul(10)
+            .and_then(|lovelace| lovelace.checked_add(Lovelace::from(digit - b'0')))
+            .ok_or_else(|| error("too large"))?;
+    }
+    lovelace = lovelace
+        .checked_mul(LOVELACE_PER_ADA)
+        .ok_or_else(|| error("too large"))?;
+
+    if let Some(fraction) = fraction {
+        if fraction.is_empty() || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(error("expected decimals"));
+        }
+        let significant = fraction.trim_end_matches('0');
+        if significant.len() > 6 {
+            return Err(error("more than 6 decimals"));
+        }
+        let decimals = format!("{significant:0<6}")
+            .parse::<Lovelace>()
+            .expect("six ASCII digits");
+        lovelace = lovelace
+            .checked_add(decimals)
+            .ok_or_else(|| error("too large"))?;
+    }
+
+    Ok(lovelace)
+}
+
+/// Format a lovelace amount as ADA with decimals.
+///
+/// Same as [`format_lovelace`] with the default options.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::lovelace_to_ada;
+///
+/// let ada = lovelace_to_ada(1_500_000);
+/// assert_eq!(ada, "1.500000");
+/// ```
+pub fn lovelace_to_ada(lovelace: Lovelace) -> String {
+    format_lovelace(lovelace, FormatOptions::default())
+}
+
+/// Convert ADA to lovelace.
+///
+/// # Example
+///
+/// ```rust
+/// # #![allow(deprecated)]
+/// use ogmios_client::util::ada_to_lovelace;
+///
+/// let lovelace = ada_to_lovelace(1.5);
+/// assert_eq!(lovelace, 1_500_000);
+/// ```
+#[deprecated(note = "loses precision above 2^53 lovelace; use `parse_ada`, which is exact")]
+pub fn ada_to_lovelace(ada: f64) -> Lovelace {
+    (ada * 1_000_000.0) as Lovelace
+}
+
+/// Size in bytes of a serialized transaction, given in hex.
+///
+/// This is the size the `minFeeCoefficient` applies to.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::tx_size;
+///
+/// assert_eq!(tx_size("84a0a0f5f6").unwrap(), 5);
+/// assert!(tx_size("84a0a0f5f").is_err());
+/// ```
+pub fn tx_size(cbor_hex: &str) -> Result<u64, HexError> {
+    Ok(hex_decode(cbor_hex)?.len() as u64)
+}
+
+/// A transaction that is not valid CBOR or not shaped like a transaction.
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid transaction CBOR: {reason}")]
+pub struct InvalidTransactionCbor {
+    /// Why the transaction was rejected.
+    pub reason: String,
+}
+
+#[cfg(feature = "cbor")]
+impl From<HexError> for InvalidTransactionCbor {
+    fn from(error: HexError) -> Self {
+        Self {
+            reason: error.to_string(),
+        }
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl From<minicbor::decode::Error> for InvalidTransactionCbor {
+    fn from(error: minicbor::decode::Error) -> Self {
+        Self {
+            reason: error.to_string(),
+        }
+    }
+}
+
+/// Facts about a serialized transaction, read by [`inspect_tx`].
+#[cfg(feature = "cbor")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxInspection {
+    /// Size in bytes.
+    pub size: u64,
+    /// Number of spent inputs.
+    pub inputs: usize,
+    /// Number of reference inputs.
+    pub reference_inputs: usize,
+    /// Number of collateral inputs.
+    pub collaterals: usize,
+    /// Number of outputs.
+    pub outputs: usize,
+    /// Declared fee.
+    pub fee: Lovelace,
+    /// Slot from which the transaction is valid, if bounded.
+    pub invalid_before: Option<Slot>,
+    /// Slot from which the transaction is no longer valid (TTL), if bounded.
+    pub invalid_hereafter: Option<Slot>,
+    /// Number of verification key witnesses.
+    pub vkey_witnesses: usize,
+    /// Whether the witness set holds native or Plutus scripts.
+    pub has_script_witness: bool,
+    /// Whether the transaction is flagged valid; always true before Alonzo.
+    pub is_valid: bool,
+    /// Whether the transaction carries auxiliary data, e.g. metadata.
+    pub has_auxiliary_data: bool,
+}
+
+/// Read the basic facts of a serialized transaction, given in hex.
+///
+/// This is a shallow walk of the CBOR: fields are counted or skipped, not
+/// decoded, so the transaction is not validated beyond its overall shape.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::inspect_tx;
+///
+/// let inspection = inspect_tx("84a200800200a0f5f6").unwrap();
+/// assert_eq!(inspection.size, 9);
+/// assert_eq!(inspection.fee, 0);
+/// assert!(!inspection.has_script_witness);
+/// ```
+#[cfg(feature = "cbor")]
+pub fn inspect_tx(cbor_hex: &str) -> Result<TxInspection, InvalidTransactionCbor> {
+    use minicbor::Decoder;
+    use minicbor::data::Type;
+
+    let bytes = hex_decode(cbor_hex)?;
+    let invalid = |reason: &str| InvalidTransactionCbor {
+        reason: reason.to_string(),
+    };
+    let mut decoder = Decoder::new(&bytes);
+    let length = decoder.array()?;
+    if matches!(length, Some(length) if !(3..=4).contains(&length)) {
+        return Err(invalid("expected a transaction body, witnesses and auxiliary data"));
+    }
+
+    let mut inspection = TxInspection {
+        size: bytes.len() as u64,
+        inputs: 0,
+        reference_inputs: 0,
+        collaterals: 0,
+        outputs: 0,
+        fee: 0,
+        invalid_before: None,
+        invalid_hereafter: None,
+        vkey_witnesses: 0,
+        has_script_witness: false,
+        is_valid: true,
+        has_auxiliary_data: false,
+    };
+
+    let mut has_fee = false;
+    for _ in 0..map_length(&mut decoder)? {
+        match decoder.u64()? {
+            0 => inspection.inputs = count_items(&mut decoder)?,
+            1 => inspection.outputs = count_items(&mut decoder)?,
+            2 => {
+                inspection.fee = decoder.u64()?;
+                has_fee = true;
+            }
+            3 => inspection.invalid_hereafter = Some(decoder.u64()?),
+            8 => inspection.invalid_before = Some(decoder.u64()?),
+            13 => inspection.collaterals = count_items(&mut decoder)?,
+            18 => inspection.reference_inputs = count_items(&mut decoder)?,
+            _ => decoder.skip()?,
+        }
+    }
+    if !has_fee {
+        return Err(invalid("the transaction body has no fee"));
+    }
+
+    for _ in 0..map_length(&mut decoder)? {
+        match decoder.u64()? {
+            0 => inspection.vkey_witnesses = count_items(&mut decoder)?,
+            // Native, Plutus V1, V2 and V3 scripts.
+            1 | 3 | 6 | 7 => inspection.has_script_witness |= count_items(&mut decoder)? > 0,
+            _ => decoder.skip()?,
+        }
+    }
+
+    if decoder.datatype()? == Type::Bool {
+        inspection.is_valid = decoder.bool()?;
+    }
+    inspection.has_auxiliary_data = decoder.datatype()? != Type::Null;
+    decoder.skip()?;
+    if length.is_none() {
+        if decoder.datatype()? != Type::Break {
+            return Err(invalid("unexpected items after the auxiliary data"));
+        }
+        decoder.set_position(decoder.position() + 1);
+    }
+    if decoder.position() != bytes.len() {
+        return Err(invalid("trailing bytes after the transaction"));
+    }
+    Ok(inspection)
+}
+
+/// Read the length of a definite map, such as a transaction body.
+#[cfg(feature = "cbor")]
+fn map_length(decoder: &mut minicbor::Decoder<'_>) -> Result<u64, InvalidTransactionCbor> {
+    decoder.map()?.ok_or_else(|| InvalidTransactionCbor {
+        reason: "expected a map of definite length".to_string(),
+    })
+}
+
+/// Count and skip the items of an array or a set (an array tagged 258).
+#[cfg(feature = "cbor")]
+fn count_items(decoder: &mut minicbor::Decoder<'_>) -> Result<usize, InvalidTransactionCbor> {
+    use minicbor::data::Type;
+
+    if decoder.datatype()? == Type::Tag {
+        decoder.tag()?;
+    }
+    let length = decoder.array()?;
+    let mut count = 0;
+    loop {
+        match length {
+            Some(length) if count as u64 == length => return Ok(count),
+            None if decoder.datatype()? == Type::Break => {
+                decoder.set_position(decoder.position() + 1);
+                return Ok(count);
+            }
+            _ => {
+                decoder.skip()?;
+                count += 1;
+            }
+        }
+    }
+}
+
+/// Hex encode bytes.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// A string is not valid hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("invalid hex at byte {position}: {kind}")]
+pub struct HexError {
+    /// Byte offset in the string, counting the `0x` prefix if any.
+    pub position: usize,
+    /// What is wrong at that position.
+    pub kind: HexErrorKind,
+}
+
+/// Reason a string is not valid hex.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+pub enum HexErrorKind {
+    /// A character is not a hex digit.
+    #[error("{0:?} is not a hex digit")]
+    InvalidCharacter(char),
+    /// The last byte is missing a digit; the position is the end of the string.
+    #[error("odd number of digits")]
+    OddLength,
+}
+
+/// Hex decode a string.
+///
+/// Accepts upper and lower case digits and an optional `0x` prefix.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::{HexErrorKind, hex_decode};
+///
+/// assert_eq!(hex_decode("0xDEADbeef").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+/// assert_eq!(hex_decode("abc").unwrap_err().kind, HexErrorKind::OddLength);
+/// ```
+pub fn hex_decode(s: &str) -> Result<Vec<u8>, HexError> {
+    let mut bytes = Vec::new();
+    hex_decode_into(s, &mut bytes)?;
+    Ok(bytes)
+}
+
+/// Hex decode a string, appending the bytes to a buffer.
+///
+/// Same as [`hex_decode`], but reuses the allocation of `bytes`. The buffer is
+/// left unchanged if the string is not valid hex.
+pub fn hex_decode_into(s: &str, bytes: &mut Vec<u8>) -> Result<(), HexError> {
+    let (offset, digits) = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+        Some(digits) => (2, digits),
+        None => (0, s),
+    };
+
+    if let Some((i, c)) = digits.char_indices().find(|(_, c)| !c.is_ascii_hexdigit()) {
+        return Err(HexError {
+            position: offset + i,
+            kind: HexErrorKind::InvalidCharacter(c),
+        });
+    }
+    if !digits.len().is_multiple_of(2) {
+        return Err(HexError {
+            position: s.len(),
+            kind: HexErrorKind::OddLength,
+        });
+    }
+
+    fn value(digit: u8) -> u8 {
+        match digit {
+            b'0'..=b'9' => digit - b'0',
+            b'a'..=b'f' => digit - b'a' + 10,
+            _ => digit - b'A' + 10,
+        }
+    }
+    bytes.reserve(digits.len() / 2);
+    bytes.extend(
+        digits
+            .as_bytes()
+            .chunks_exact(2)
+            .map(|pair| value(pair[0]) << 4 | value(pair[1])),
+    );
+    Ok(())
+}
+
+/// A string that is not the expected Bech32 encoding.
+#[cfg(feature = "bech32")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid bech32 {value:?}: {kind}")]
+pub struct Bech32Error {
+    /// Rejected value.
+    pub value: String,
+    /// What is wrong with it.
+    pub kind: Bech32ErrorKind,
+}
+
+/// Reason a string is not the expected Bech32 encoding.
+#[cfg(feature = "bech32")]
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum Bech32ErrorKind {
+    /// A character is not in the Bech32 charset, the case is mixed, or the
+    /// separator is missing.
+    #[error("{0}")]
+    Charset(String),
+    /// The human-readable prefix is malformed.
+    #[error("invalid prefix: {0}")]
+    Hrp(String),
+    /// The checksum does not match, or the string is too long to be checked.
+    #[error("invalid checksum: {0}")]
+    Checksum(String),
+    /// The prefix is well formed but not the expected one.
+    #[error("expected prefix {expected:?}, got {found:?}")]
+    UnexpectedHrp {
+        /// Expected prefix.
+        expected: &'static str,
+        /// Actual prefix.
+        found: String,
+    },
+    /// The data is not what the prefix calls for, e.g. of the wrong length.
+    #[error("{0}")]
+    Payload(String),
+    /// A hex input is malformed.
+    #[error(transparent)]
+    Hex(#[from] HexError),
+}
+
+/// Decode a Bech32 string into its prefix and data.
+///
+/// Strings are not limited to the 90 characters of BIP-173, since Cardano
+/// addresses are longer.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::bech32_decode;
+///
+/// let (hrp, data) =
+///     bech32_decode("pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy").unwrap();
+/// assert_eq!(hrp.as_str(), "pool");
+/// assert_eq!(data.len(), 28);
+/// ```
+#[cfg(feature = "bech32")]
+pub fn bech32_decode(s: &str) -> Result<(Hrp, Vec<u8>), Bech32Error> {
+    use bech32::primitives::decode::{CheckedHrpstringError, UncheckedHrpstringError};
+
+    let checked = CheckedHrpstring::new::<Bech32>(s).map_err(|error| Bech32Error {
+        value: s.to_string(),
+        kind: match error {
+            CheckedHrpstringError::Parse(UncheckedHrpstringError::Char(error)) => {
+                Bech32ErrorKind::Charset(error.to_string())
+            }
+            CheckedHrpstringError::Parse(UncheckedHrpstringError::Hrp(error)) => {
+                Bech32ErrorKind::Hrp(error.to_string())
+            }
+            CheckedHrpstringError::Checksum(error) => Bech32ErrorKind::Checksum(error.to_string()),
+            error => Bech32ErrorKind::Charset(error.to_string()),
+        },
+    })?;
+    Ok((checked.hrp(), checked.byte_iter().collect()))
+}
+
+/// Encode data as a lowercase Bech32 string with a prefix.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::bech32_encode;
+///
+/// assert_eq!(bech32_encode("a", &[]).unwrap(), "a12uel5l");
+/// ```
+#[cfg(feature = "bech32")]
+pub fn bech32_encode(hrp: &str, data: &[u8]) -> Result<String, Bech32Error> {
+    let error = |kind| Bech32Error {
+        value: hrp.to_string(),
+        kind,
+    };
+    let parsed = Hrp::parse(hrp).map_err(|e| error(Bech32ErrorKind::Hrp(e.to_string())))?;
+    bech32::encode::<Bech32>(parsed, data)
+        .map_err(|e| error(Bech32ErrorKind::Payload(e.to_string())))
+}
+
+/// Decode a Bech32 string with an expected prefix and data length.
+#[cfg(feature = "bech32")]
+fn bech32_decode_as<const N: usize>(s: &str, prefix: &'static str) -> Result<[u8; N], Bech32Error> {
+    let (hrp, data) = bech32_decode(s)?;
+    let error = |kind| Bech32Error {
+        value: s.to_string(),
+        kind,
+    };
+    if hrp.to_lowercase() != prefix {
+        return Err(error(Bech32ErrorKind::UnexpectedHrp {
+            expected: prefix,
+            found: hrp.to_lowercase(),
+        }));
+    }
+    data.try_into().map_err(|data: Vec<u8>| {
+        error(Bech32ErrorKind::Payload(format!(
+            "expected {N} bytes, got {}",
+            data.len()
+        )))
+    })
+}
+
+/// Convert a Bech32 pool ID (`pool1…`) to the hex hash used by Ogmios.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::{hex_to_pool_id, pool_id_to_hex};
+///
+/// let hex = "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735";
+/// let pool_id = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+/// assert_eq!(pool_id_to_hex(pool_id).unwrap(), hex);
+/// assert_eq!(hex_to_pool_id(hex).unwrap(), pool_id);
+/// ```
+#[cfg(feature = "bech32")]
+pub fn pool_id_to_hex(pool_id: &str) -> Result<String, Bech32Error> {
+    Ok(hex_encode(&bech32_decode_as::<28>(pool_id, "pool")?))
+}
+
+/// Convert a hex pool hash to a Bech32 pool ID (`pool1…`).
+#[cfg(feature = "bech32")]
+pub fn hex_to_pool_id(hex: &str) -> Result<String, Bech32Error> {
+    let error = |kind| Bech32Error {
+        value: hex.to_string(),
+        kind,
+    };
+    let bytes = hex_decode(hex).map_err(|e| error(e.into()))?;
+    if bytes.len() != 28 {
+        return Err(error(Bech32ErrorKind::Payload(format!(
+            "expected 28 bytes, got {}",
+            bytes.len()
+        ))));
+    }
+    bech32_encode("pool", &bytes)
+}
+
+/// CIP-129 header of a DRep key hash credential.
+#[cfg(feature = "bech32")]
+const CIP129_DREP_KEY: u8 = 0x22;
+
+/// CIP-129 header of a DRep script hash credential.
+#[cfg(feature = "bech32")]
+const CIP129_DREP_SCRIPT: u8 = 0x23;
+
+/// Encode a registered DRep as a CIP-129 DRep ID (`drep1…`).
+///
+/// The ID carries a header byte telling key and script credentials apart.
+/// The pre-defined voting options have no ID and fail with
+/// [`Bech32ErrorKind::Payload`].
+#[cfg(feature = "bech32")]
+pub fn drep_id_to_bech32(drep: &DRepId) -> Result<String, Bech32Error> {
+    let (header, hash) = match drep {
+        DRepId::Key(hash) => (CIP129_DREP_KEY, hash.as_str()),
+        DRepId::Script(hash) => (CIP129_DREP_SCRIPT, hash.as_str()),
+        DRepId::AlwaysAbstain | DRepId::AlwaysNoConfidence => {
+            return Err(Bech32Error {
+                value: format!("{drep:?}"),
+                kind: Bech32ErrorKind::Payload("a pre-defined DRep has no ID".to_string()),
+            });
+        }
+    };
+    let error = |kind| Bech32Error {
+        value: hash.to_string(),
+        kind,
+    };
+    let hash = hex_decode(hash).map_err(|e| error(e.into()))?;
+    if hash.len() != 28 {
+        return Err(error(Bech32ErrorKind::Payload(format!(
+            "expected 28 bytes, got {}",
+            hash.len()
+        ))));
+    }
+
+    let mut bytes = Vec::with_capacity(29);
+    bytes.push(header);
+    bytes.extend_from_slice(&hash);
+    bech32_encode("drep", &bytes)
+}
+
+/// Decode a Bech32 DRep ID.
+///
+/// Accepts CIP-129 IDs (`drep1…` with a header byte) and the older CIP-105
+/// IDs (`drep1…` for key hashes, `drep_script1…` for script hashes).
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::DRepId;
+/// use ogmios_client::util::drep_id_from_bech32;
+///
+/// let drep = drep_id_from_bech32("drep15k6929drl7xt0spvudgcxndryn4kmlzpk4meed0xhqe25nle07s");
+/// assert_eq!(
+///     drep.unwrap(),
+///     DRepId::Key("a5b45515a3ff8cb7c02ce351834da324eb6dfc41b5779cb5e6b832aa".parse().unwrap())
+/// );
+/// ```
+#[cfg(feature = "bech32")]
+pub fn drep_id_from_bech32(s: &str) -> Result<DRepId, Bech32Error> {
+    let (hrp, data) = bech32_decode(s)?;
+    let error = |reason: String| Bech32Error {
+        value: s.to_string(),
+        kind: Bech32ErrorKind::Payload(reason),
+    };
+    match (hrp.to_lowercase().as_str(), data.len()) {
+        ("drep", 28) => Ok(DRepId::Key(DigestBlake2b224::from_bytes(
+            data.try_into().expect("28 bytes"),
+        ))),
+        ("drep_script", 28) => Ok(DRepId::Script(ScriptHash::from_bytes(
+            data.try_into().expect("28 bytes"),
+        ))),
+        ("drep", 29) => match data[0] {
+            CIP129_DREP_KEY => Ok(DRepId::Key(DigestBlake2b224::from_bytes(
+                data[1..].try_into().expect("28 bytes"),
+            ))),
+            CIP129_DREP_SCRIPT => Ok(DRepId::Script(ScriptHash::from_bytes(
+                data[1..].try_into().expect("28 bytes"),
+            ))),
+            header => Err(error(format!("{header:#04x} is not a DRep header"))),
+        },
+        ("drep" | "drep_script", len) => Err(error(format!("expected 28 or 29 bytes, got {len}"))),
+        (found, _) => Err(Bech32Error {
+            value: s.to_string(),
+            kind: Bech32ErrorKind::UnexpectedHrp {
+                expected: "drep",
+                found: found.to_string(),
+            },
+        }),
+    }
+}
+
+/// Compute the CIP-14 fingerprint (`asset1…`) of a native asset.
+///
+/// The fingerprint is the Bech32 encoding of the Blake2b-160 digest of the
+/// policy ID followed by the asset name, both given in hex.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::util::asset_fingerprint;
+///
+/// let fingerprint = asset_fingerprint(
+///     "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373",
+///     "504154415445",
+/// );
+/// assert_eq!(fingerprint.unwrap(), "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92");
+/// ```
+#[cfg(feature = "asset-fingerprint")]
+pub fn asset_fingerprint(policy_id: &str, asset_name: &str) -> Result<String, Bech32Error> {
+    use blake2::digest::consts::U20;
+    use blake2::{Blake2b, Digest};
+
+    let invalid = |value: &str, kind| Bech32Error {
+        value: value.to_string(),
+        kind,
+    };
+    let mut bytes = Vec::with_capacity(60);
+    hex_decode_into(policy_id, &mut bytes).map_err(|e| invalid(policy_id, e.into()))?;
+    if bytes.len() != 28 {
+        let reason = "a policy ID is 28 bytes".to_string();
+        return Err(invalid(policy_id, Bech32ErrorKind::Payload(reason)));
+    }
+    hex_decode_into(asset_name, &mut bytes).map_err(|e| invalid(asset_name, e.into()))?;
+    if bytes.len() > 28 + 32 {
+        let reason = "an asset name is at most 32 bytes".to_string();
+        return Err(invalid(asset_name, Bech32ErrorKind::Payload(reason)));
+    }
+    bech32_encode("asset", &Blake2b::<U20>::digest(&bytes))
+}
+
+/// Decode the type, network ID and credentials of an address (CIP-19).
+///
+/// Same as [`Address::info`], for addresses held as strings.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::AddressKind;
+/// use ogmios_client::util::inspect_address;
+///
+/// let info = inspect_address("stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw");
+/// let info = info.unwrap();
+/// assert_eq!(info.kind, AddressKind::Reward);
+/// assert_eq!(info.payment_credential, None);
+/// assert!(info.stake_credential.is_some());
+/// ```
+#[cfg(feature = "address")]
+pub fn inspect_address(address: &str) -> Result<AddressInfo, InvalidAddress> {
+    Address::from(address).info()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_lovelace_to_ada() {
+        assert_eq!(lovelace_to_ada(1_000_000), "1.000000");
+        assert_eq!(lovelace_to_ada(1_500_000), "1.500000");
+        assert_eq!(lovelace_to_ada(500_000), "0.500000");
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_ada_to_lovelace() {
+        assert_eq!(ada_to_lovelace(1.0), 1_000_000);
+        assert_eq!(ada_to_lovelace(1.5), 1_500_000);
+        assert_eq!(ada_to_lovelace(0.5), 500_000);
+    }
+
+    #[test]
+    fn test_format_lovelace() {
+        let grouped = FormatOptions {
+            thousands_separator: Some(','),
+            ..FormatOptions::default()
+        };
+        let trimmed = FormatOptions {
+            trim_trailing_zeros: true,
+            ..FormatOptions::default()
+        };
+        assert_eq!(format_lovelace(0, FormatOptions::default()), "0.000000");
+        assert_eq!(format_lovelace(0, trimmed), "0");
+        assert_eq!(format_lovelace(1_000_000, trimmed), "1");
+        assert_eq!(format_lovelace(1_500_000, trimmed), "1.5");
+        assert_eq!(format_lovelace(999_999_000_000, grouped), "999,999.000000");
+        assert_eq!(
+            format_lovelace(Lovelace::MAX, grouped),
+            "18,446,744,073,709.551615"
+        );
+        assert_eq!(
+            format_lovelace(
+                1_234_500_000,
+                FormatOptions {
+                    suffix: Some(AdaSuffix::Symbol),
+                    ..trimmed
+                }
+            ),
+            "1234.5 ₳"
+        );
+        // Exact where f64 rounds.
+        assert_eq!(lovelace_to_ada(9_007_199_254_740_993), "9007199254.740993");
+    }
+
+    #[test]
+    fn test_parse_ada() {
+        assert_eq!(parse_ada("1.5"), Ok(1_500_000));
+        assert_eq!(parse_ada("1,234.567890 ADA"), Ok(1_234_567_890));
+        assert_eq!(parse_ada(" 42 ada "), Ok(42_000_000));
+        assert_eq!(parse_ada("₳1"), Ok(1_000_000));
+        assert_eq!(parse_ada("1_000 ₳"), Ok(1_000_000_000));
+        assert_eq!(parse_ada("0.1000000000"), Ok(100_000));
+        assert_eq!(parse_ada("9007199254.740993"), Ok(9_007_199_254_740_993));
+        assert_eq!(parse_ada("18446744073709.551615"), Ok(Lovelace::MAX));
+
+        for (input, reason) in [
+            ("", "no digits"),
+            ("ADA", "no digits"),
+            ("-1", "expected digits"),
+            ("1e6", "expected digits"),
+            (".5", "expected digits"),
+            ("1.", "expected decimals"),
+            ("1.2.3", "expected decimals"),
+            ("0.0000001", "more than 6 decimals"),
+            ("1,23", "misplaced thousands separator"),
+            ("1234,567", "misplaced thousands separator"),
+            ("1,234_567", "expected digits"),
+            ("18446744073709.551616", "too large"),
+            ("18446744073710", "too large"),
+        ] {
+            assert_eq!(
+                parse_ada(input),
+                Err(ParseAdaError {
+                    input: input.to_string(),
+                    reason
+                }),
+                "{input}"
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_parse_ada_reads_formatted_amounts(
+            lovelace in any::<Lovelace>(),
+            separator in prop::option::of(prop::sample::select(vec![',', '_', ' '])),
+            trim_trailing_zeros in any::<bool>(),
+            suffix in prop::option::of(
+                prop::sample::select(vec![AdaSuffix::Symbol, AdaSuffix::Ticker])
+            ),
+        ) {
+            let options = FormatOptions {
+                thousands_separator: separator,
+                trim_trailing_zeros,
+                suffix,
+            };
+            let formatted = format_lovelace(lovelace, options);
+            prop_assert_eq!(parse_ada(&formatted), Ok(lovelace), "{}", formatted);
+        }
+    }
+
+    #[test]
+    fn test_size_of_integer() {
+        assert_eq!(size_of_integer(0), 1);
+        assert_eq!(size_of_integer(23), 1);
+        assert_eq!(size_of_integer(24), 2);
+        assert_eq!(size_of_integer(255), 2);
+        assert_eq!(size_of_integer(256), 3);
+    }
+
+    /// Outputs and their CBOR encoding, as the ledger serializes them.
+    fn serialized_outputs() -> Vec<(serde_json::Value, &'static str)> {
+        const BASE: &str = "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x";
+        const SCRIPT: &str = "addr1w8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcyjy7wx";
+        const POLICY: &str = "f0ff48bbb7bbe9d59a40f1ce90e9e9d0ff5002ec48f232b49ca0fb9a";
+        const KEY: &str = "e09d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a";
+        vec![
+            (
+                serde_json::json!({ "address": BASE, "value": { "ada": { "lovelace": 1000000 } } }),
+                "825839019493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e337b62cfff6403a06a\
+                 3acbc34f8c46003c69fe79a3628cefa9c472511a000f4240",
+            ),
+            (
+                serde_json::json!({
+                    "address": "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+                    "value": { "ada": { "lovelace": 1000000 } }
+                }),
+                "82581d619493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e1a000f4240",
+            ),
+            (
+                serde_json::json!({
+                    "address": "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi",
+                    "value": { "ada": { "lovelace": 1000000 } }
+                }),
+                "82582b82d818582183581cba970ad36654d8dd8f74274b733452ddeab9a62a397746be3c42ccdda000\
+                 1a9026da5b1a000f4240",
+            ),
+            (
+                serde_json::json!({
+                    "address": "addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k",
+                    "value": { "ada": { "lovelace": 1000000 } }
+                }),
+                "825823419493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e8198bd431b031a000f\
+                 4240",
+            ),
+            (
+                serde_json::json!({
+                    "address": BASE,
+                    "value": {
+                        "ada": { "lovelace": 1444443 },
+                        POLICY: { "6164612e68616e646c65": 1, "": 1_000_000_000_000u64 },
+                        "000102030405060708090a0b0c0d0e0f101112131415161718191a1b": {
+                            "0101010101010101010101010101010101010101010101010101010101010101": 42
+                        }
+                    }
+                }),
+                "825839019493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e337b62cfff6403a06a\
+                 3acbc34f8c46003c69fe79a3628cefa9c47251821a00160a5ba2581cf0ff48bbb7bbe9d59a40f1ce90\
+                 e9e9d0ff5002ec48f232b49ca0fb9aa24a6164612e68616e646c6501401b000000e8d4a51000581c00\
+                 0102030405060708090a0b0c0d0e0f101112131415161718191a1ba158200101010101010101010101\
+                 010101010101010101010101010101010101010101182a",
+            ),
+            (
+                serde_json::json!({
+                    "address": SCRIPT,
+                    "value": { "ada": { "lovelace": 2000000 } },
+                    "datumHash": "923918e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec"
+                }),
+                "83581d71c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f1a001e848058209239\
+                 18e403bf43c34b4ef6b48eb2ee04babed17320d8d1b9ff9ad086e86f44ec",
+            ),
+            (
+                serde_json::json!({
+                    "address": SCRIPT,
+                    "value": { "ada": { "lovelace": 2000000 } },
+                    "datum": concat!(
+                        "d8799f581c5a3b2fd1f7c26e1d8e3b1c1a7e9d2c4b6a8f0e1d3c5b7a9f1e2d3c4b",
+                        "1a05f5e100ff"
+                    ),
+                    "script": { "language": "plutus:v2", "cbor": "4d01000033222220051200120011" }
+                }),
+                "a400581d71c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f011a001e84800282\
+                 01d8185827d8799f581c5a3b2fd1f7c26e1d8e3b1c1a7e9d2c4b6a8f0e1d3c5b7a9f1e2d3c4b1a05f5e\
+                 100ff03d8185182024e4d01000033222220051200120011",
+            ),
+            (
+                serde_json::json!({
+                    "address": BASE,
+                    "value": { "ada": { "lovelace": 5000000 } },
+                    "script": {
+                        "language": "native",
+                        "json": {
+                            "clause": "all",
+                            "from": [
+                                { "clause": "signature", "from": KEY },
+                                { "clause": "before", "slot": 100000000 }
+                            ]
+                        }
+                    }
+                }),
+                "a3005839019493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e337b62cfff6403a0\
+                 6a3acbc34f8c46003c69fe79a3628cefa9c47251011a004c4b4003d818582c82008201828200581ce0\
+                 9d36c79dec9bd1b3d9e152247701cd0bb860b5ebfd1de8abb6735a82051a05f5e100",
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_utxo_size_matches_serialized_outputs() {
+        for (output, cbor) in serialized_outputs() {
+            let output: TransactionOutput = serde_json::from_value(output).unwrap();
+            assert_eq!(
+                utxo_size(&output),
+                CONSTANT_OUTPUT_SERIALIZATION_OVERHEAD + cbor.len() as u64 / 2,
+                "{output:?}"
+            );
+        }
+
+        // The well-known minimum of an ada-only output to a base address, at
+        // 4310 lovelace per byte.
+        let (output, _) = &serialized_outputs()[0];
+        let output: TransactionOutput = serde_json::from_value(output.clone()).unwrap();
+        assert_eq!(utxo_size(&output) * 4310, 969_750);
+    }
+
+    fn protocol_parameters() -> ProtocolParameters {
+        let parameters = crate::schema::testing::fixture("ledger-state/protocolParameters");
+        serde_json::from_value(parameters.result()).unwrap()
+    }
+
+    fn era_summaries() -> Vec<EraSummary> {
+        let summaries = crate::schema::testing::fixture("ledger-state/eraSummaries");
+        serde_json::from_value(summaries.result()).unwrap()
+    }
+
+    #[test]
+    fn test_slot_time_conversions() {
+        const MAINNET_START: &str = "2017-09-23T21:44:51Z";
+        let summaries = era_summaries();
+
+        // Last Byron slot and the Shelley hard fork.
+        assert_eq!(slot_to_relative_time(&summaries, 4492799).unwrap(), 89_855_980.0);
+        assert_eq!(slot_to_relative_time(&summaries, 4492800).unwrap(), 89_856_000.0);
+        assert_eq!(
+            slot_to_unix_time(&summaries, MAINNET_START, 4492800).unwrap(),
+            1_596_059_091_000
+        );
+        assert_eq!(time_to_slot(&summaries, 89_855_999.9).unwrap(), 4492799);
+        assert_eq!(time_to_slot(&summaries, 89_856_000.0).unwrap(), 4492800);
+        assert_eq!(
+            current_slot(&summaries, MAINNET_START, "2020-07-29T21:44:51Z").unwrap(),
+            4492800
+        );
+
+        // Conway.
+        assert_eq!(slot_to_relative_time(&summaries, 134_660_800).unwrap(), 237_260_800.0);
+        assert_eq!(
+            slot_to_unix_time(&summaries, MAINNET_START, 134_660_800).unwrap(),
+            1_743_463_891_000
+        );
+        assert_eq!(time_to_slot(&summaries, 237_260_800.5).unwrap(), 134_660_800);
+        assert_eq!(
+            current_slot(&summaries, MAINNET_START, "2025-03-31T23:31:32.5Z").unwrap(),
+            134_660_801
+        );
+
+        let horizon = 141_004_800;
+        assert!(matches!(
+            slot_to_relative_time(&summaries, horizon),
+            Err(OgmiosError::PastHorizon { horizon: 141_004_800, .. })
+        ));
+        assert!(matches!(
+            current_slot(&summaries, MAINNET_START, "2030-01-01T00:00:00Z"),
+            Err(OgmiosError::PastHorizon { .. })
+        ));
+        assert!(matches!(
+            current_slot(&summaries, MAINNET_START, "2017-01-01T00:00:00Z"),
+            Err(OgmiosError::InvalidTime(_))
+        ));
+        assert!(matches!(time_to_slot(&summaries, -1.0), Err(OgmiosError::InvalidTime(_))));
+    }
+
+    #[test]
+    fn test_min_ada_required() {
+        let params = protocol_parameters();
+        let minimums: Vec<Lovelace> = serialized_outputs()
+            .into_iter()
+            .map(|(output, _)| serde_json::from_value(output).unwrap())
+            .map(|output| min_ada_required(&output, &params).unwrap())
+            .collect();
+        // Ada only, to a base, enterprise, Byron and pointer address; with
+        // assets; with a datum hash; with an inline datum and a Plutus
+        // reference script; with a native reference script.
+        assert_eq!(
+            minimums,
+            [969_750, 849_070, 909_410, 874_930, 1_495_570, 995_610, 1_146_460, 1_189_560]
+        );
+
+        // The minimum holds for an output holding it: one holding 0 lovelace
+        // is smaller than one holding the minimum.
+        let (output, _) = serialized_outputs().swap_remove(0);
+        let mut output: TransactionOutput = serde_json::from_value(output).unwrap();
+        output.value = Value::ada_only(0);
+        assert_eq!(utxo_size(&output) * 4310, 952_510);
+        assert_eq!(min_ada_required(&output, &params), Ok(969_750));
+
+        let shelley = ProtocolParameters {
+            min_utxo_deposit_coefficient: None,
+            ..params
+        };
+        assert_eq!(
+            min_ada_required(&output, &shelley).unwrap_err().to_string(),
+            "protocol parameter minUtxoDepositCoefficient is not set"
+        );
+    }
+
+    #[test]
+    fn test_min_fee() {
+        let params = protocol_parameters();
+        // 44 lovelace per byte plus 155381
+        assert_eq!(min_fee(16_384, None, 0, &params), Ok(876_277));
+
+        // 1765011 × 577/10000 + 503871230 × 721/10000000 = 138170.25, rounded up
+        let budget = ExUnits::new(1_765_011, 503_871_230);
+        assert_eq!(min_fee(4_213, Some(budget), 0, &params), Ok(340_753 + 138_171));
+
+        // 25600 bytes at 15 lovelace, 25600 at 18, 8800 at 21.6
+        assert_eq!(min_fee(300, None, 60_000, &params), Ok(168_581 + 1_034_880));
+        for (size, fee) in [(1, 15), (25_599, 383_985), (25_600, 384_000), (25_601, 384_018)] {
+            assert_eq!(min_fee(0, None, size, &params), Ok(155_381 + fee), "{size}");
+        }
+
+        let babbage = ProtocolParameters {
+            script_execution_prices: None,
+            min_fee_reference_scripts: None,
+            ..params
+        };
+        assert_eq!(min_fee(300, None, 0, &babbage), Ok(168_581));
+        assert_eq!(
+            min_fee(300, Some(budget), 0, &babbage).unwrap_err().parameter,
+            "scriptExecutionPrices"
+        );
+        assert_eq!(
+            min_fee(300, None, 1, &babbage).unwrap_err().parameter,
+            "minFeeReferenceScripts"
+        );
+    }
+
+    /// `tierRefScriptFee` of the Conway ledger (`Cardano.Ledger.Conway.Tx`),
+    /// written as it is there: each full tier adds `increment × price` to an
+    /// exact rational, the price grows by `multiplier`, and the total is
+    /// floored once. Fractions are `(numerator, denominator)`.
+    fn tier_ref_script_fee(
+        multiplier: (u128, u128),
+        increment: u64,
+        base: (u128, u128),
+        size: u64,
+    ) -> Lovelace {
+        fn add((a, b): (u128, u128), (c, d): (u128, u128)) -> (u128, u128) {
+            let (n, d) = (a * d + c * b, b * d);
+            let divisor = gcd(n, d);
+            (n / divisor, d / divisor)
+        }
+        fn mul((a, b): (u128, u128), (c, d): (u128, u128)) -> (u128, u128) {
+            let (n, d) = (a * c, b * d);
+            let divisor = gcd(n, d);
+            (n / divisor, d / divisor)
+        }
+
+        let (mut acc, mut price, mut n) = ((0, 1), base, size);
+        loop {
+            if n < increment {
+                let (numerator, denominator) = add(acc, mul((n.into(), 1), price));
+                return (numerator / denominator) as Lovelace;
+            }
+            acc = add(acc, mul((increment.into(), 1), price));
+            price = mul(multiplier, price);
+            n -= increment;
+        }
+    }
+
+    #[test]
+    fn test_reference_scripts_fee() {
+        let params = MinFeeReferenceScripts {
+            base: 15.0,
+            range: 25_600,
+            multiplier: 1.2,
+            extra: Default::default(),
+        };
+        // Every size, around each tier boundary, agrees with the ledger's
+        // `tierRefScriptFee`.
+        for tier in 0..=8u64 {
+            for size in [tier * 25_600, tier * 25_600 + 1, tier * 25_600 + 12_345] {
+                let expected = tier_ref_script_fee((6, 5), 25_600, (15, 1), size);
+                assert_eq!(reference_scripts_fee(size, &params), expected, "{size}");
+            }
+        }
+
+        // Tiers of 25600 bytes at 15, 18, 21.6, 25.92, 31.104, 37.3248,
+        // 44.78976 and 53.747712 lovelace per byte.
+        for (size, fee) in [
+            (0, 0),
+            (1, 15),
+            (25_600, 384_000),
+            (25_601, 384_018),
+            (51_200, 844_800),
+            (51_201, 844_821),
+            (76_800, 1_397_760),
+            (100_000, 1_999_104),
+            (102_400, 2_061_312),
+            // The largest total size of reference scripts, 200 KiB:
+            // 25600 × 247.486272 = 6335648.1632, rounded down.
+            (204_800, 6_335_648),
+        ] {
+            assert_eq!(reference_scripts_fee(size, &params), fee, "{size}");
+        }
+
+        // Fractional base prices are summed exactly before rounding down.
+        let fractional = MinFeeReferenceScripts {
+            base: 0.5,
+            range: 3,
+            multiplier: 1.5,
+            ..params.clone()
+        };
+        // 3 × 0.5 + 3 × 0.75 + 1 × 1.125 = 4.875
+        assert_eq!(reference_scripts_fee(7, &fractional), 4);
+
+        let huge = MinFeeReferenceScripts {
+            range: 1,
+            multiplier: 10.0,
+            ..params
+        };
+        assert_eq!(reference_scripts_fee(u64::MAX, &huge), Lovelace::MAX);
+    }
+
+    #[test]
+    fn test_decimal_fraction() {
+        assert_eq!(decimal_fraction(1.2), (6, 5));
+        assert_eq!(decimal_fraction(15.0), (15, 1));
+        assert_eq!(decimal_fraction(0.0000721), (721, 10_000_000));
+        assert_eq!(decimal_fraction(f64::NAN), (0, 1));
+    }
+
+    #[test]
+    fn test_size_of_base58() {
+        let byron = "Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi";
+        assert_eq!(size_of_base58(byron), 43);
+        assert_eq!(size_of_base58("11"), 2);
+        assert_eq!(size_of_base58(""), 0);
+    }
+
+    #[test]
+    fn test_hex_encode_decode() {
+        let bytes = vec![0xde, 0xad, 0xbe, 0xef];
+        let encoded = hex_encode(&bytes);
+        assert_eq!(encoded, "deadbeef");
+
+        let decoded = hex_decode(&encoded).unwrap();
+        assert_eq!(decoded, bytes);
+
+        assert!(hex_decode("").unwrap().is_empty());
+        assert!(hex_decode("0x").unwrap().is_empty());
+        assert_eq!(hex_decode("0XDEADBEEF").unwrap(), bytes);
+
+        let mut buffer = vec![0x01];
+        hex_decode_into("0203", &mut buffer).unwrap();
+        assert_eq!(buffer, [0x01, 0x02, 0x03]);
+        assert!(hex_decode_into("04z", &mut buffer).is_err());
+        assert_eq!(buffer, [0x01, 0x02, 0x03]);
+
+        for (input, position, kind) in [
+            ("abc", 3, HexErrorKind::OddLength),
+            ("0xabc", 5, HexErrorKind::OddLength),
+            ("abzd", 2, HexErrorKind::InvalidCharacter('z')),
+            ("0x0x", 3, HexErrorKind::InvalidCharacter('x')),
+            ("ab é", 2, HexErrorKind::InvalidCharacter(' ')),
+            ("aé", 1, HexErrorKind::InvalidCharacter('é')),
+            ("+1", 0, HexErrorKind::InvalidCharacter('+')),
+        ] {
+            assert_eq!(hex_decode(input), Err(HexError { position, kind }), "{input}");
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn test_hex_decode_never_panics(
+            s in prop_oneof![any::<String>(), "(0[xX])?[0-9a-fA-Fxé ]{0,9}"]
+        ) {
+            if let Err(error) = hex_decode(&s) {
+                prop_assert!(error.position <= s.len());
+                prop_assert!(s.is_char_boundary(error.position));
+            }
+        }
+
+        #[test]
+        fn test_hex_round_trip(bytes in prop::collection::vec(any::<u8>(), 0..64)) {
+            let hex = hex_encode(&bytes);
+            prop_assert_eq!(hex_decode(&hex).unwrap(), bytes.clone());
+            prop_assert_eq!(hex_decode(&format!("0x{}", hex.to_uppercase())).unwrap(), bytes);
+        }
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_bech32() {
+        // BIP-173 test vectors.
+        for valid in [
+            "A12UEL5L",
+            "a12uel5l",
+            "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            "?1ezyfcl",
+        ] {
+            let (hrp, data) = bech32_decode(valid).unwrap();
+            let reencoded = bech32_encode(hrp.as_str(), &data).unwrap();
+            assert_eq!(reencoded, valid.to_lowercase());
+        }
+        let (hrp, data) =
+            bech32_decode("abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw").unwrap();
+        assert_eq!(hrp.as_str(), "abcdef");
+        assert_eq!(hex_encode(&data), "00443214c74254b635cf84653a56d7c675be77df");
+
+        let kind = |s: &str| bech32_decode(s).unwrap_err().kind;
+        assert!(matches!(kind("x1b4n0q5v"), Bech32ErrorKind::Charset(_)));
+        assert!(matches!(kind("pzry9x0s0muk"), Bech32ErrorKind::Charset(_)));
+        assert!(matches!(kind("A12uEL5L"), Bech32ErrorKind::Charset(_)));
+        assert!(matches!(kind("1pzry9x0s0muk"), Bech32ErrorKind::Hrp(_)));
+        assert!(matches!(kind("\u{80}1eym55h"), Bech32ErrorKind::Hrp(_)));
+        assert!(matches!(kind("A1G7SGD8"), Bech32ErrorKind::Checksum(_)));
+        assert!(matches!(kind("li1dgmt3"), Bech32ErrorKind::Checksum(_)));
+        assert!(matches!(kind("a12uel5m"), Bech32ErrorKind::Checksum(_)));
+        assert!(matches!(
+            bech32_encode("", &[]).unwrap_err().kind,
+            Bech32ErrorKind::Hrp(_)
+        ));
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_pool_id() {
+        const HEX: &str = "0f292fcaa02b8b2f9b3c8f9fd8e0bb21abedb692a6d5058df3ef2735";
+        const POOL_ID: &str = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+        assert_eq!(pool_id_to_hex(POOL_ID).unwrap(), HEX);
+        assert_eq!(pool_id_to_hex(&POOL_ID.to_uppercase()).unwrap(), HEX);
+        assert_eq!(hex_to_pool_id(HEX).unwrap(), POOL_ID);
+        assert_eq!(hex_to_pool_id(&HEX.to_uppercase()).unwrap(), POOL_ID);
+
+        let stake = "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw";
+        assert_eq!(
+            pool_id_to_hex(stake).unwrap_err().kind,
+            Bech32ErrorKind::UnexpectedHrp {
+                expected: "pool",
+                found: "stake".to_string()
+            }
+        );
+        let short = bech32_encode("pool", &[0; 27]).unwrap();
+        assert_eq!(
+            pool_id_to_hex(&short).unwrap_err().kind,
+            Bech32ErrorKind::Payload("expected 28 bytes, got 27".to_string())
+        );
+        assert!(matches!(
+            hex_to_pool_id("0f29").unwrap_err().kind,
+            Bech32ErrorKind::Payload(_)
+        ));
+        assert!(matches!(hex_to_pool_id("0g").unwrap_err().kind, Bech32ErrorKind::Hex(_)));
+    }
+
+    #[cfg(feature = "bech32")]
+    #[test]
+    fn test_drep_id() {
+        const KEY: &str = "a5b45515a3ff8cb7c02ce351834da324eb6dfc41b5779cb5e6b832aa";
+        const SCRIPT: &str = "d04d39c4e4b42e9edddbb741bb6636683b6822ae2c68df704b65ce4a";
+        let key = DRepId::Key(KEY.parse().unwrap());
+        let script = DRepId::Script(SCRIPT.parse().unwrap());
+
+        // CIP-105 IDs.
+        let legacy_key = "drep15k6929drl7xt0spvudgcxndryn4kmlzpk4meed0xhqe25nle07s";
+        assert_eq!(drep_id_from_bech32(legacy_key).unwrap(), key);
+        let legacy_script = bech32_encode("drep_script", &hex_decode(SCRIPT).unwrap()).unwrap();
+        assert_eq!(drep_id_from_bech32(&legacy_script).unwrap(), script);
+
+        // CIP-129 IDs carry the credential kind in a header byte.
+        for drep in [&key, &script] {
+            let id = drep_id_to_bech32(drep).unwrap();
+            assert!(id.starts_with("drep1"));
+            assert_eq!(&drep_id_from_bech32(&id).unwrap(), drep);
+        }
+        let (_, data) = bech32_decode(&drep_id_to_bech32(&script).unwrap()).unwrap();
+        assert_eq!(data[0], 0x23);
+        assert_eq!(hex_encode(&data[1..]), SCRIPT);
+
+        // CIP-129 test vectors.
+        let zero = "00".repeat(28);
+        for (drep, id) in [
+            (
+                DRepId::Key(zero.parse().unwrap()),
+                "drep1ygqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq7vlc9n",
+            ),
+            (
+                DRepId::Script(zero.parse().unwrap()),
+                "drep1yvqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqq770f95",
+            ),
+        ] {
+            assert_eq!(drep_id_to_bech32(&drep).unwrap(), id);
+            assert_eq!(drep_id_from_bech32(id).unwrap(), drep);
+        }
+
+        assert!(matches!(
+            drep_id_to_bech32(&DRepId::AlwaysAbstain).unwrap_err().kind,
+            Bech32ErrorKind::Payload(_)
+        ));
+        let unknown_header = bech32_encode("drep", &[0x12; 29]).unwrap();
+        assert_eq!(
+            drep_id_from_bech32(&unknown_header).unwrap_err().kind,
+            Bech32ErrorKind::Payload("0x12 is not a DRep header".to_string())
+        );
+        let pool = "pool1pu5jlj4q9w9jlxeu370a3c9myx47md5j5m2str0naunn2q3lkdy";
+        assert!(matches!(
+            drep_id_from_bech32(pool).unwrap_err().kind,
+            Bech32ErrorKind::UnexpectedHrp { .. }
+        ));
+    }
+
+    #[cfg(feature = "asset-fingerprint")]
+    #[test]
+    fn test_asset_fingerprint() {
+        const POLICY_A: &str = "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc373";
+        const POLICY_B: &str = "7eae28af2208be856f7a119668ae52a49b73725e326dc16579dcc37e";
+        const POLICY_C: &str = "1e349c9bdea19fd6c147626a5260bc44b71635f398b67c59881df209";
+        // CIP-14 test vectors.
+        for (policy, name, fingerprint) in [
+            (POLICY_A, "", "asset1rjklcrnsdzqp65wjgrg55sy9723kw09mlgvlc3"),
+            (POLICY_B, "", "asset1nl0puwxmhas8fawxp8nx4e2q3wekg969n2auw3"),
+            (POLICY_C, "", "asset1uyuxku60yqe57nusqzjx38aan3f2wq6s93f6ea"),
+            (POLICY_A, "504154415445", "asset13n25uv0yaf5kus35fm2k86cqy60z58d9xmde92"),
+            (POLICY_C, "504154415445", "asset1hv4p5tv2a837mzqrst04d0dcptdjmluqvdx9k3"),
+            (POLICY_C, POLICY_A, "asset1aqrdypg669jgazruv5ah07nuyqe0wxjhe2el6f"),
+            (POLICY_A, POLICY_C, "asset17jd78wukhtrnmjh3fngzasxm8rck0l2r4hhyyt"),
+            (
+                POLICY_A,
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "asset1pkpwyknlvul7az0xx8czhl60pyel45rpje4z8w",
+            ),
+        ] {
+            assert_eq!(asset_fingerprint(policy, name).unwrap(), fingerprint, "{name}");
+        }
+
+        assert!(matches!(
+            asset_fingerprint(&POLICY_A[2..], "").unwrap_err().kind,
+            Bech32ErrorKind::Payload(_)
+        ));
+        assert!(matches!(
+            asset_fingerprint(POLICY_A, &"00".repeat(33)).unwrap_err().kind,
+            Bech32ErrorKind::Payload(_)
+        ));
+        assert!(matches!(
+            asset_fingerprint(POLICY_A, "token").unwrap_err().kind,
+            Bech32ErrorKind::Hex(_)
+        ));
+    }
+
+    /// A Conway transaction with two inputs, a reference input, two outputs,
+    /// a validity interval, a key witness and a native script witness.
+    fn conway_tx() -> String {
+        let input = |index: &str| format!("825820{}{index}", "11".repeat(32));
+        let output = format!("82581d61{}1a000f4240", "22".repeat(28));
+        let body = [
+            "a6".to_string(),
+            format!("00d9010282{}{}", input("00"), input("01")),
+            format!("0182{output}{output}"),
+            "021a0002a2a1".to_string(),
+            "031a0a4cb2f0".to_string(),
+            "081a0a4b0000".to_string(),
+            format!("1281{}", input("02")),
+        ];
+        let witnesses = format!(
+            "a2008182582033{}5840{}01818200581c{}",
+            "33".repeat(31),
+            "44".repeat(64),
+            "55".repeat(28)
+        );
+        format!("84{}{witnesses}f5f6", body.concat())
+    }
+
+    #[test]
+    fn test_tx_size() {
+        assert_eq!(tx_size(&conway_tx()).unwrap(), 351);
+        assert_eq!(tx_size("").unwrap(), 0);
+        assert_eq!(tx_size("84a0a0f5zz").unwrap_err().position, 8);
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn test_inspect_tx() {
+        assert_eq!(
+            inspect_tx(&conway_tx()).unwrap(),
+            TxInspection {
+                size: 351,
+                inputs: 2,
+                reference_inputs: 1,
+                collaterals: 0,
+                outputs: 2,
+                fee: 172_705,
+                invalid_before: Some(172_687_360),
+                invalid_hereafter: Some(172_798_704),
+                vkey_witnesses: 1,
+                has_script_witness: true,
+                is_valid: true,
+                has_auxiliary_data: false,
+            }
+        );
+
+        // Shelley transactions have no validity flag, and may use
+        // indefinite-length arrays.
+        let shelley = format!(
+            "9fa3009f825820{}00ff0180021a0002a2a1a0a100a0ff",
+            "11".repeat(32)
+        );
+        let inspection = inspect_tx(&shelley).unwrap();
+        assert_eq!((inspection.inputs, inspection.outputs), (1, 0));
+        assert_eq!(inspection.invalid_hereafter, None);
+        assert!(inspection.is_valid);
+        assert!(inspection.has_auxiliary_data);
+        assert!(!inspection.has_script_witness);
+
+        let tx = conway_tx();
+        assert!(inspect_tx(&tx[..tx.len() - 2]).is_err());
+        assert!(inspect_tx(&format!("{tx}00")).is_err());
+        assert!(inspect_tx(&tx[..tx.len() - 1]).is_err());
+        assert!(inspect_tx("82a0a0").is_err());
+        assert!(inspect_tx("84a0a0f5f6").is_err());
+        assert!(inspect_tx("").is_err());
+    }
+
+    #[test]
+    fn test_parse_point() {
+        const ID: &str = "e31d1a3a2a8d5ef35ba2e7e2ee4cf1eda2cbb8a5d2d2b6c1d6d4e64f7b1e6e0c";
+        let origin = serde_json::json!("origin");
+        assert_eq!(parse_point(&origin), Ok(Point::origin()));
+
+        let point = serde_json::json!({
+            "slot": 12345,
+            "id": ID
+        });
+        assert_eq!(parse_point(&point), Ok(Point::at(12345, ID.parse().unwrap())));
+
+        for value in [
+            serde_json::json!({ "slot": "12345", "id": ID }),
+            serde_json::json!({ "slot": 12345, "id": ID, "height": 42 }),
+            serde_json::json!({ "slot": 12345, "id": ID.to_uppercase() }),
+            serde_json::json!(format!("12345.{ID}")),
+            serde_json::json!(format!("{ID}@12345")),
+        ] {
+            assert_eq!(parse_point(&value), Ok(Point::at(12345, ID.parse().unwrap())), "{value}");
+        }
+
+        let reason = |value: serde_json::Value| parse_point(&value).unwrap_err().reason;
+        assert_eq!(
+            reason(serde_json::json!("12345.abc123")),
+            "block ID must be 64 hex characters, got 6"
+        );
+        assert_eq!(
+            reason(serde_json::json!(format!("12x45.{ID}"))),
+            "slot \"12x45\" is not a natural number"
+        );
+        assert_eq!(
+            reason(serde_json::json!(format!("-1.{ID}"))),
+            "slot \"-1\" is not a natural number"
+        );
+        assert_eq!(
+            reason(serde_json::json!(format!("99999999999999999999.{ID}"))),
+            "slot 99999999999999999999 is too large"
+        );
+        assert_eq!(
+            reason(serde_json::json!(format!("1.{}", ID.replace('e', "g")))),
+            "block ID contains 'g', which is not a hex digit"
+        );
+        assert_eq!(reason(serde_json::json!({ "id": ID })), "missing slot");
+        assert_eq!(
+            reason(serde_json::json!({ "slot": -5, "id": ID })),
+            "slot -5 is not a natural number"
+        );
+        assert_eq!(
+            reason(serde_json::json!({ "slot": 5, "id": 7 })),
+            "id 7 is not a string"
+        );
+        assert!(parse_point(&serde_json::json!("genesis")).is_err());
+        assert!(parse_point(&serde_json::json!(12345)).is_err());
+    }
+
+    #[test]
+    fn test_parse_points() {
+        const ID: &str = "e31d1a3a2a8d5ef35ba2e7e2ee4cf1eda2cbb8a5d2d2b6c1d6d4e64f7b1e6e0c";
+        let points = vec![
+            Point::at(4492800, ID.parse().unwrap()),
+            Point::at(5, ID.parse().unwrap()),
+            Point::origin(),
+        ];
+        assert_eq!(
+            parse_points(&format!("4492800.{ID}, {ID}@5 ,origin")),
+            Ok(points.clone())
+        );
+        let formatted: Vec<String> = points.iter().map(format_point).collect();
+        assert_eq!(parse_points(&formatted.join(",")), Ok(points));
+        assert_eq!(format_point(&Point::origin()), "origin");
+        assert_eq!(parse_points(" "), Ok(Vec::new()));
+
+        let error = parse_points(&format!("origin,,1.{ID}")).unwrap_err();
+        assert_eq!(error.value, "\"\"");
+        assert!(parse_points(&format!("1.{ID},2.{}", &ID[1..])).is_err());
+    }
+
+    #[cfg(feature = "address")]
+    #[test]
+    fn test_inspect_address() {
+        use crate::schema::{AddressKind, StakeCredential};
+
+        let key = StakeCredential::Key {
+            key: "9493315cd92eb5d8c4304e67b7e16ae36d61d34502694657811a2c8e".parse().unwrap(),
+        };
+        let stake_key = StakeCredential::Key {
+            key: "337b62cfff6403a06a3acbc34f8c46003c69fe79a3628cefa9c47251".parse().unwrap(),
+        };
+        let script = StakeCredential::Script {
+            script: "c37b1b5dc0669f1d3c61a6fddb2e8fde96be87b881c60bce8e8d542f".parse().unwrap(),
+        };
+
+        // The CIP-19 test vectors.
+        let vectors = [
+            (
+                "addr1qx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgse35a3x",
+                AddressKind::Base,
+                Some(&key),
+                Some(&stake_key),
+            ),
+            (
+                "addr1z8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gten0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs9yc0hh",
+                AddressKind::Base,
+                Some(&script),
+                Some(&stake_key),
+            ),
+            (
+                "addr1yx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzerkr0vd4msrxnuwnccdxlhdjar77j6lg0wypcc9uar5d2shs2z78ve",
+                AddressKind::Base,
+                Some(&key),
+                Some(&script),
+            ),
+            (
+                "addr1x8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gt7r0vd4msrxnuwnccdxlhdjar77j6lg0wypcc9uar5d2shskhj42g",
+                AddressKind::Base,
+                Some(&script),
+                Some(&script),
+            ),
+            (
+                "addr1gx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrzqf96k",
+                AddressKind::Pointer,
+                Some(&key),
+                None,
+            ),
+            (
+                "addr128phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtupnz75xxcrtw79hu",
+                AddressKind::Pointer,
+                Some(&script),
+                None,
+            ),
+            (
+                "addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl8",
+                AddressKind::Enterprise,
+                Some(&key),
+                None,
+            ),
+            (
+                "addr1w8phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcyjy7wx",
+                AddressKind::Enterprise,
+                Some(&script),
+                None,
+            ),
+            (
+                "stake1uyehkck0lajq8gr28t9uxnuvgcqrc6070x3k9r8048z8y5gh6ffgw",
+                AddressKind::Reward,
+                None,
+                Some(&stake_key),
+            ),
+            (
+                "stake178phkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcccycj5",
+                AddressKind::Reward,
+                None,
+                Some(&script),
+            ),
+            (
+                "addr_test1qz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer3n0d3vllmyqwsx5wktcd8cc3sq835lu7drv2xwl2wywfgs68faae",
+                AddressKind::Base,
+                Some(&key),
+                Some(&stake_key),
+            ),
+            (
+                "addr_test1gz2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzer5pnz75xxcrdw5vky",
+                AddressKind::Pointer,
+                Some(&key),
+                None,
+            ),
+            (
+                "addr_test1wrphkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcl6szpr",
+                AddressKind::Enterprise,
+                Some(&script),
+                None,
+            ),
+            (
+                "stake_test17rphkx6acpnf78fuvxn0mkew3l0fd058hzquvz7w36x4gtcljw6kf",
+                AddressKind::Reward,
+                None,
+                Some(&script),
+            ),
+        ];
+        for (address, kind, payment, stake) in vectors {
+            let info = inspect_address(address).unwrap();
+            assert_eq!(info.kind, kind, "{address}");
+            let network_id = if address.contains("_test") { 0 } else { 1 };
+            assert_eq!(info.network_id, Some(network_id), "{address}");
+            assert_eq!(info.payment_credential.as_ref(), payment, "{address}");
+            assert_eq!(info.stake_credential.as_ref(), stake, "{address}");
+        }
+
+        let byron = inspect_address("Ae2tdPwUPEZFRbyhz3cpfC2CumGzNkFBN2L42rcUc2yjQpEkxDbkPodpMAi");
+        let byron = byron.unwrap();
+        assert_eq!(byron.kind, AddressKind::Byron);
+        assert_eq!(byron.network_id, None);
+        assert_eq!(byron.payment_credential, None);
+        assert_eq!(byron.stake_credential, None);
+
+        let error = inspect_address("addr1vx2fxv2umyhttkxyxp8x0dlpdt3k6cwng5pxj3jhsydzers66hrl9");
+        assert!(error.is_err());
+    }
+}
@@ -0,0 +1,519 @@
+//! NFT metadata of CIP-25 (transaction metadata) and CIP-68 (reference datums).
+
+use super::hex_encode;
+#[cfg(feature = "plutus-data")]
+use super::hex_decode;
+#[cfg(feature = "plutus-data")]
+use crate::schema::PlutusData;
+use crate::schema::{AssetName, Metadata, Metadatum, PolicyId};
+use serde_json::{Map, Value};
+
+/// Metadata label of CIP-25 NFT metadata.
+pub const CIP25_LABEL: u64 = 721;
+
+/// NFT metadata that does not follow CIP-25 or CIP-68.
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("invalid NFT metadata: {reason}")]
+pub struct InvalidNftMetadata {
+    /// Why the metadata was rejected.
+    pub reason: String,
+}
+
+impl InvalidNftMetadata {
+    fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+        }
+    }
+}
+
+/// A file of an NFT.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NftFile {
+    /// Name of the file.
+    pub name: Option<String>,
+    /// MIME type of the file.
+    pub media_type: Option<String>,
+    /// URI of the file.
+    pub src: String,
+    /// Other properties, as JSON.
+    pub extra: Map<String, Value>,
+}
+
+/// Properties of an NFT, as defined by CIP-25 and reused by CIP-68.
+///
+/// Strings split in chunks, as metadata strings are limited to 64 bytes, are
+/// joined.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NftProperties {
+    /// Name of the NFT.
+    pub name: Option<String>,
+    /// URI of the image.
+    pub image: Option<String>,
+    /// MIME type of the image.
+    pub media_type: Option<String>,
+    /// Description.
+    pub description: Option<String>,
+    /// Files of the NFT.
+    pub files: Vec<NftFile>,
+    /// Other properties, as JSON.
+    pub extra: Map<String, Value>,
+}
+
+/// CIP-25 metadata of one asset.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NftMetadata {
+    /// Minting policy of the asset.
+    pub policy_id: PolicyId,
+    /// Hex-encoded asset name.
+    pub asset_name: AssetName,
+    /// Version of the metadata: 1, with text keys, or 2, with byte keys.
+    pub version: u64,
+    /// Properties of the asset.
+    pub properties: NftProperties,
+}
+
+/// Metadata of a CIP-68 reference NFT.
+#[cfg(feature = "plutus-data")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cip68Metadata {
+    /// Version of the datum.
+    pub version: u64,
+    /// Properties of the asset.
+    pub properties: NftProperties,
+    /// Custom data of the datum, if any.
+    pub extra: Option<PlutusData>,
+}
+
+/// Read the CIP-25 NFT metadata of a transaction.
+///
+/// Returns one entry per asset under label 721, in metadata order, and no
+/// entries if the label is absent. Policy IDs and asset names may be text, as
+/// in version 1, or bytes, as in version 2.
+///
+/// # Example
+///
+/// ```rust
+/// use ogmios_client::schema::{Metadata, Metadatum};
+/// use ogmios_client::util::extract_cip25;
+///
+/// let nft = Metadatum::try_from_json(&serde_json::json!({
+///     "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7": {
+///         "NFT": { "name": "My NFT", "image": ["ipfs://Qm", "Yx"] }
+///     }
+/// }))
+/// .unwrap();
+/// let metadata = Metadata {
+///     labels: [(721, nft)].into(),
+///     hash: None,
+/// };
+/// let nfts = extract_cip25(&metadata).unwrap();
+/// assert_eq!(nfts[0].asset_name, "4e4654");
+/// assert_eq!(nfts[0].properties.image.as_deref(), Some("ipfs://QmYx"));
+/// ```
+pub fn extract_cip25(metadata: &Metadata) -> Result<Vec<NftMetadata>, InvalidNftMetadata> {
+    let Some(label) = metadata.label(CIP25_LABEL) else {
+        return Ok(Vec::new());
+    };
+    let Metadatum::Map(policies) = label else {
+        return Err(InvalidNftMetadata::new("label 721 is not a map"));
+    };
+
+    let mut version = 1;
+    for entry in policies {
+        if matches!(&entry.k, Metadatum::String(key) if key == "version") {
+            version = match &entry.v {
+                Metadatum::Int(version @ 1..=2) => *version as u64,
+                Metadatum::String(version) if version == "1.0" || version == "1" => 1,
+                Metadatum::String(version) if version == "2.0" || version == "2" => 2,
+                other => {
+                    let reason = format!("unsupported version {}", other.to_json());
+                    return Err(InvalidNftMetadata::new(reason));
+                }
+            };
+        }
+    }
+
+    let mut nfts = Vec::new();
+    for entry in policies {
+        let policy_id = match &entry.k {
+            Metadatum::String(key) if key == "version" => continue,
+            Metadatum::String(hex) | Metadatum::Bytes(hex) => {
+                hex.parse::<PolicyId>().map_err(|error| InvalidNftMetadata::new(error.to_string()))?
+            }
+            other => {
+                let reason = format!("policy ID {} is not a string", other.to_json());
+                return Err(InvalidNftMetadata::new(reason));
+            }
+        };
+        let Metadatum::Map(assets) = &entry.v else {
+            return Err(InvalidNftMetadata::new(format!("assets of {policy_id} are not a map")));
+        };
+
+        for asset in assets {
+            let asset_name = match &asset.k {
+                Metadatum::String(name) => hex_encode(name.as_bytes()),
+                Metadatum::Bytes(hex) => hex.to_ascii_lowercase(),
+                other => {
+                    let reason = format!("asset name {} is not a string", other.to_json());
+                    return Err(InvalidNftMetadata::new(reason));
+                }
+            };
+            let Value::Object(properties) = asset.v.to_json() else {
+                let reason = format!("properties of {policy_id}.{asset_name} are not a map");
+                return Err(InvalidNftMetadata::new(reason));
+            };
+            nfts.push(NftMetadata {
+                policy_id: policy_id.clone(),
+                asset_name,
+                version,
+                properties: parse_properties(properties)?,
+            });
+        }
+    }
+    Ok(nfts)
+}
+
+/// Read the metadata of a CIP-68 reference NFT from its inline datum.
+///
+/// The datum is `Constr 0 [metadata, version, extra]`, where `metadata` maps
+/// UTF-8 property names to values. Byte strings that are valid UTF-8 are read
+/// as text, and others as `0x`-prefixed hex.
+#[cfg(feature = "plutus-data")]
+pub fn extract_cip68(datum: &PlutusData) -> Result<Cip68Metadata, InvalidNftMetadata> {
+    let PlutusData::Constr {
+        constructor: 0,
+        fields,
+    } = datum
+    else {
+        return Err(InvalidNftMetadata::new("the datum is not constructor 0"));
+    };
+    let [metadata, PlutusData::Int(version), extra @ ..] = fields.as_slice() else {
+        return Err(InvalidNftMetadata::new("expected metadata and a version"));
+    };
+    let version =
+        u64::try_from(*version).map_err(|_| InvalidNftMetadata::new("negative version"))?;
+    if extra.len() > 1 {
+        return Err(InvalidNftMetadata::new("too many fields in the datum"));
+    }
+    let PlutusData::Map(_) = metadata else {
+        return Err(InvalidNftMetadata::new("the metadata is not a map"));
+    };
+    let Value::Object(properties) = plutus_data_to_json(metadata) else {
+        unreachable!("a map converts to an object");
+    };
+
+    Ok(Cip68Metadata {
+        version,
+        properties: parse_properties(properties)?,
+        extra: extra.first().cloned(),
+    })
+}
+
+/// Convert Plutus data to JSON, reading byte strings as text where possible.
+#[cfg(feature = "plutus-data")]
+fn plutus_data_to_json(data: &PlutusData) -> Value {
+    match data {
+        PlutusData::Bytes(hex) => {
+            let bytes = hex_decode(hex).unwrap_or_default();
+            match String::from_utf8(bytes) {
+                Ok(text) => Value::String(text),
+                Err(_) => Value::String(format!("0x{hex}")),
+            }
+        }
+        PlutusData::Int(int) => match i64::try_from(*int) {
+            Ok(int) => Value::from(int),
+            Err(_) => Value::String(int.to_string()),
+        },
+        PlutusData::BigInt { negative, bytes } => {
+            Value::String(big_int_to_decimal(*negative, &hex_decode(bytes).unwrap_or_default()))
+        }
+        PlutusData::List(items) => Value::Array(items.iter().map(plutus_data_to_json).collect()),
+        PlutusData::Map(entries) => Value::Object(
+            entries
+                .iter()
+                .map(|(key, value)| {
+                    let key = match plutus_data_to_json(key) {
+                        Value::String(key) => key,
+                        key => key.to_string(),
+                    };
+                    (key, plutus_data_to_json(value))
+                })
+                .collect(),
+        ),
+        PlutusData::Constr {
+            constructor,
+            fields,
+        } => serde_json::json!({
+            "constructor": constructor,
+            "fields": fields.iter().map(plutus_data_to_json).collect::<Vec<_>>(),
+        }),
+    }
+}
+
+/// Decimal form of the bignum `n`, or of `-1 - n` if `negative`.
+#[cfg(feature = "plutus-data")]
+fn big_int_to_decimal(negative: bool, bytes: &[u8]) -> String {
+    // -1 - n is -(n + 1).
+    let mut magnitude = bytes.to_vec();
+    if negative {
+        let carry = magnitude.iter_mut().rev().all(|byte| {
+            let (sum, overflow) = byte.overflowing_add(1);
+            *byte = sum;
+            overflow
+        });
+        if carry {
+            magnitude.insert(0, 1);
+        }
+    }
+
+    // Divide by 10 until nothing is left, collecting the remainders.
+    let mut digits = Vec::new();
+    while magnitude.iter().any(|byte| *byte != 0) {
+        let mut remainder = 0u16;
+        for byte in &mut magnitude {
+            let value = (remainder << 8) | u16::from(*byte);
+            *byte = (value / 10) as u8;
+            remainder = value % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+    }
+    if digits.is_empty() {
+        digits.push(b'0');
+    }
+    if negative {
+        digits.push(b'-');
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+fn parse_properties(
+    mut properties: Map<String, Value>,
+) -> Result<NftProperties, InvalidNftMetadata> {
+    let files = match properties.remove("files") {
+        None => Vec::new(),
+        Some(Value::Array(files)) => files.into_iter().map(parse_file).collect::<Result<_, _>>()?,
+        Some(_) => return Err(InvalidNftMetadata::new("files is not a list")),
+    };
+    Ok(NftProperties {
+        name: take_string(&mut properties, "name")?,
+        image: take_string(&mut properties, "image")?,
+        media_type: take_string(&mut properties, "mediaType")?,
+        description: take_string(&mut properties, "description")?,
+        files,
+        extra: properties,
+    })
+}
+
+fn parse_file(file: Value) -> Result<NftFile, InvalidNftMetadata> {
+    let Value::Object(mut file) = file else {
+        return Err(InvalidNftMetadata::new("a file is not a map"));
+    };
+    let src = take_string(&mut file, "src")?
+        .ok_or_else(|| InvalidNftMetadata::new("a file has no src"))?;
+    Ok(NftFile {
+        name: take_string(&mut file, "name")?,
+        media_type: take_string(&mut file, "mediaType")?,
+        src,
+        extra: file,
+    })
+}
+
+/// Remove a string property, joining it if it is split in chunks.
+fn take_string(
+    properties: &mut Map<String, Value>,
+    key: &str,
+) -> Result<Option<String>, InvalidNftMetadata> {
+    let invalid = || InvalidNftMetadata::new(format!("{key} is not a string or a list of strings"));
+    match properties.remove(key) {
+        None => Ok(None),
+        Some(Value::String(text)) => Ok(Some(text)),
+        Some(Value::Array(chunks)) => chunks
+            .iter()
+            .map(|chunk| chunk.as_str().ok_or_else(invalid))
+            .collect::<Result<String, _>>()
+            .map(Some),
+        Some(_) => Err(invalid()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    // Laid out as in the CIP-25 and CIP-68 specifications; no mint of either
+    // kind is captured under `fixtures/` yet (see its README).
+    const POLICY: &str = "b0d07d45fe9514f80213f4020e5a61241458be626841cde717cb38a7";
+
+    fn metadata(label: Metadatum) -> Metadata {
+        Metadata {
+            labels: [(CIP25_LABEL, label)].into(),
+            hash: None,
+        }
+    }
+
+    fn from_json(json: Value) -> Metadata {
+        metadata(Metadatum::try_from_json(&json).unwrap())
+    }
+
+    #[test]
+    fn test_cip25_version_1() {
+        let metadata = from_json(json!({
+                POLICY: {
+                    "SpaceBud3411": {
+                        "name": "SpaceBud #3411",
+                        "image": [
+                            "ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fb",
+                            "zdi"
+                        ],
+                        "mediaType": "image/png",
+                        "traits": ["Star Suit", "Chestplate"],
+                        "files": [{
+                            "name": "SpaceBud #3411",
+                            "mediaType": "video/mp4",
+                            "src": ["ipfs://Qm", "Zx"],
+                            "duration": 12
+                        }]
+                    },
+                    "SpaceBud3412": { "name": "SpaceBud #3412" }
+                },
+                "version": "1.0"
+        }));
+        let nfts = extract_cip25(&metadata).unwrap();
+        assert_eq!(nfts.len(), 2);
+        let nft = &nfts[0];
+        assert_eq!(nft.policy_id, POLICY);
+        assert_eq!(nft.asset_name, "537061636542756433343131");
+        assert_eq!(nft.version, 1);
+        assert_eq!(nft.properties.name.as_deref(), Some("SpaceBud #3411"));
+        assert_eq!(
+            nft.properties.image.as_deref(),
+            Some("ipfs://bafybeigdyrzt5sfp7udm7hu76uh7y26nf3efuylqabf3oclgtqy55fbzdi")
+        );
+        assert_eq!(nft.properties.media_type.as_deref(), Some("image/png"));
+        assert_eq!(nft.properties.description, None);
+        assert_eq!(nft.properties.extra["traits"], json!(["Star Suit", "Chestplate"]));
+        assert_eq!(
+            nft.properties.files,
+            [NftFile {
+                name: Some("SpaceBud #3411".to_string()),
+                media_type: Some("video/mp4".to_string()),
+                src: "ipfs://QmZx".to_string(),
+                extra: Map::from_iter([("duration".to_string(), json!(12))]),
+            }]
+        );
+        assert_eq!(nfts[1].asset_name, "537061636542756433343132");
+    }
+
+    #[test]
+    fn test_cip25_version_2() {
+        let metadata = from_json(json!({
+            (format!("0x{POLICY}")): {
+                "0x000de1404e4654": {
+                    "name": "NFT",
+                    "description": ["Two ", "chunks"]
+                }
+            },
+            "version": 2
+        }));
+        let nfts = extract_cip25(&metadata).unwrap();
+        assert_eq!(nfts.len(), 1);
+        assert_eq!(nfts[0].asset_name, "000de1404e4654");
+        assert_eq!(nfts[0].version, 2);
+        assert_eq!(nfts[0].properties.name.as_deref(), Some("NFT"));
+        assert_eq!(nfts[0].properties.description.as_deref(), Some("Two chunks"));
+    }
+
+    #[test]
+    fn test_cip25_errors() {
+        let no_nft = Metadata {
+            labels: Default::default(),
+            hash: None,
+        };
+        assert_eq!(extract_cip25(&no_nft).unwrap(), []);
+
+        for (label, reason) in [
+            (json!(1), "label 721 is not a map"),
+            (json!({ "version": "3.0" }), "unsupported version \"3.0\""),
+            (json!({ POLICY: [] }), "assets of"),
+            (json!({ POLICY: { "A": { "name": 1 } } }), "name is not a string"),
+            (json!({ POLICY: { "A": { "files": [{}] } } }), "a file has no src"),
+            (json!({ "abcd": {} }), "invalid PolicyId"),
+        ] {
+            let error = extract_cip25(&from_json(label)).unwrap_err();
+            assert!(error.reason.starts_with(reason), "{error}");
+        }
+    }
+
+    #[cfg(feature = "plutus-data")]
+    #[test]
+    fn test_cip68() {
+        let text = |s: &str| PlutusData::Bytes(hex_encode(s.as_bytes()));
+        let datum = PlutusData::Constr {
+            constructor: 0,
+            fields: vec![
+                PlutusData::Map(vec![
+                    (text("name"), text("Hosky #42")),
+                    (text("image"), text("ipfs://QmHosky")),
+                    (text("mediaType"), text("image/png")),
+                    (text("rarity"), PlutusData::Int(7)),
+                    (text("hash"), PlutusData::Bytes("ff00".to_string())),
+                    (
+                        text("supply"),
+                        PlutusData::BigInt {
+                            negative: false,
+                            bytes: format!("01{}", "00".repeat(16)),
+                        },
+                    ),
+                    (
+                        text("debt"),
+                        PlutusData::BigInt {
+                            negative: true,
+                            bytes: "ff".repeat(17),
+                        },
+                    ),
+                    (
+                        text("files"),
+                        PlutusData::List(vec![PlutusData::Map(vec![
+                            (text("src"), text("ipfs://QmFile")),
+                            (text("mediaType"), text("text/html")),
+                        ])]),
+                    ),
+                ]),
+                PlutusData::Int(1),
+                PlutusData::Constr {
+                    constructor: 1,
+                    fields: vec![],
+                },
+            ],
+        };
+        let metadata = extract_cip68(&datum).unwrap();
+        assert_eq!(metadata.version, 1);
+        assert_eq!(metadata.properties.name.as_deref(), Some("Hosky #42"));
+        assert_eq!(metadata.properties.image.as_deref(), Some("ipfs://QmHosky"));
+        assert_eq!(metadata.properties.media_type.as_deref(), Some("image/png"));
+        assert_eq!(metadata.properties.files[0].src, "ipfs://QmFile");
+        assert_eq!(metadata.properties.extra["rarity"], json!(7));
+        assert_eq!(metadata.properties.extra["hash"], json!("0xff00"));
+        // 2^128, and -1 - (2^136 - 1).
+        assert_eq!(
+            metadata.properties.extra["supply"],
+            json!("340282366920938463463374607431768211456")
+        );
+        assert_eq!(
+            metadata.properties.extra["debt"],
+            json!("-87112285931760246646623899502532662132736")
+        );
+        assert!(matches!(metadata.extra, Some(PlutusData::Constr { constructor: 1, .. })));
+
+        let not_constr = PlutusData::Int(0);
+        assert!(extract_cip68(&not_constr).is_err());
+        let no_version = PlutusData::Constr {
+            constructor: 0,
+            fields: vec![PlutusData::Map(vec![])],
+        };
+        assert!(extract_cip68(&no_version).is_err());
+    }
+}